@@ -0,0 +1,485 @@
+# Deutsche Texte. Was hier fehlt, erscheint auf Englisch.
+
+language = 🌐 Sprache
+language-system = Systemsprache
+
+colors = 🎨 Farben
+appearance = Darstellung
+backgrounds = Hintergründe: ein Bild oder ein Verlauf zwischen zwei Farben
+backgrounds-hint = Wird für jede Art von Phase hinter dem Timer und hinter dem Pausenbildschirm gezeigt. Ein Bild hat Vorrang vor einem Verlauf.
+background-gradient-error = Verläufe als zwei Farben angeben, etwa #ff6b6b, #4ecdc4
+background-dim = Hintergründe abdunkeln um
+timer-face = Zeitanzeige
+timer-face-digits = Ziffern
+timer-face-dial = Küchenwecker
+ui-scale = 🔍 Oberflächengröße (%, 80-200)
+countdown-font = 🔤 Schrift des Countdowns
+countdown-font-placeholder = Schriftname, monospace oder eine .ttf/.otf-Datei (leer = Standard)
+palette-standard = Standard
+palette-high-contrast = Hoher Kontrast
+palette-deuteranopia = Für Deuteranopie (Rot-Grün)
+palette-protanopia = Für Protanopie (Rot-Grün)
+
+guest-session-banner = 👤 Gastsitzung · wird nicht gespeichert
+screen-lock-paused = 🔒 Pausiert, während der Bildschirm gesperrt war. Setze fort, wenn du bereit bist.
+durations-apply-next-period = ⏳ Neue Dauern gelten ab der nächsten Phase
+stop-alarm = ■ Stopp
+snooze-minutes = 💤 { $minutes } Min. schlummern
+intention-prompt = Woran arbeitest du?
+intention-placeholder = z. B. Release Notes entwerfen
+start-without-intention = Überspringen
+ask-intention = 🎯 Beim Start eines Pomodoros fragen, woran ich arbeite
+focus-check-prompt = Bist du konzentriert geblieben?
+focused-yes = ✓ Ja
+focused-no = ✕ Nein
+undo = ↶ Rückgängig
+undo-timer-reset = Timer zurückgesetzt
+undo-count-reset = Zähler zurückgesetzt
+settings = ⚙ Einstellungen
+work-duration-minutes = 🍅 Arbeitsdauer (Minuten oder mm:ss)
+short-break-minutes = ☕ Kurze Pause (Minuten oder mm:ss)
+long-break-minutes = ☕ Lange Pause (Minuten oder mm:ss)
+experiment-lengths = Experiment: tageweise mit einer zweiten Arbeitsdauer abwechseln
+experiment-hint = In der Statistik vergleichen, wie oft Pomodoros jeder Länge abgeschlossen werden.
+stats-experiment = 🧪 Experiment zur Arbeitsdauer
+experiment-result = { $length }: { $finished } von { $attempts } abgeschlossen ({ $completion } %), { $abandoned } abgebrochen ({ $abandonment } %)
+experiment-better = Pomodoros mit { $length } werden bisher öfter abgeschlossen.
+experiment-too-early = Probiere jede Länge mindestens { $count ->
+    [one] einmal
+   *[other] { $count }-mal
+}, um sie zu vergleichen.
+take-long-breaks = Lange Pausen machen
+long-break-every-pomodoros = 🔄 Lange Pause alle (Pomodoros)
+long-break-pattern-empty-hint = Leer lassen, um die Zahl oben zu verwenden.
+long-break-skip = ⏭️ Wenn eine lange Pause übersprungen wird
+long-break-skip-defer = In der nächsten Pause nachholen
+long-break-skip-forfeit = Verfallen lassen
+long-break-skip-shorten = Stattdessen eine kurze Pause machen
+adaptive-break-length = 🔋 Längere Pausen nach Pomodoros am Stück (Minuten)
+adaptive-break-hint = Jeder Pomodoro nach dem ersten, der ohne Pause dazwischen beendet wurde, verlängert die nächste Pause um so viel. 0 schaltet es aus.
+long-break-pattern-advanced = 🧩 Muster für lange Pausen (fortgeschritten)
+alarm-volume = 🔊 Alarmlautstärke (%)
+test-sound = ▶ Testen
+ticking-volume = ⏱ Ticken während der Arbeit (%, 0 = aus)
+ambient-sound = 🌧 Hintergrundgeräusch während der Arbeit
+close-to-tray-hint = Der Countdown läuft im Hintergrund weiter. Beenden über das Tray-Menü.
+screen-lock-action = 🔒 Wenn der Bildschirm während der Arbeit gesperrt wird
+alarm-pattern = 🎵 Alarmtöne
+alarm-tones = Töne (Hz)
+alarm-beeps = Pieptöne
+alarm-beep-length = Tonlänge (ms)
+alarm-gap = Pause zwischen Tönen (ms)
+alarm-length = Gesamtlänge (ms)
+alarm-pattern-hint = Die Pieptöne gehen der Reihe nach durch die Töne. Danach bleibt der Alarm bis zum Ende seiner Länge still, bevor er sich wiederholen kann.
+break-extension-length = ☕ Pausen verlängern um (Minuten)
+break-extension-hint = Fügt eine Schaltfläche hinzu, die eine laufende Pause verlängert. 0 schaltet sie aus. Die Arbeitsstatistik bleibt unberührt; wie lange jede Pause wirklich gedauert hat, wird beim Pomodoro davor gespeichert.
+snooze-length = 💤 Schlummerdauer (Minuten)
+snooze-hint = Schlummern am Ende eines Pomodoros verlängert die Arbeit um diese Dauer, bevor die Pause beginnt. Nach einer Pause schweigt der Alarm so lange.
+alarm-repeat = 🔁 Alarm wiederholen alle (Sekunden)
+alarm-repeat-hint = Wiederholt sich, bis du Start drückst oder ins Fenster klickst. Bei 0 ertönt er einmal.
+countdown-overlay = ⧉ Countdown-Overlay
+completion-criteria = ✅ Pomodoro zählen
+low-power-threshold = 🔋 Energiesparmodus unter Akkustand (%)
+low-power-hint = 0 schaltet ihn aus, 100 gilt immer im Akkubetrieb.
+focus-messages = 💬 Fokusnachrichten
+streak-day-start = 🔥 Serientag beginnt um (Stunde, 0-23)
+holidays = 🏖 Urlaubstage (JJJJ-MM-TT, mit ; trennen)
+calendar = 📅 Kalender
+export-calendar = Fokussitzungen exportieren (.ics)
+calendar-subscribe-hint = Bei aktiver HTTP-API können Kalender-Apps auch http://127.0.0.1:<port>/calendar.ics abonnieren.
+meetings-calendar = Terminkalender (iCal-Adresse, z. B. die geheime Adresse aus Google Kalender)
+import-from-app = 📥 Sitzungen aus einer anderen App importieren
+preview = Vorschau
+restore-backup = Wiederherstellen und beenden
+backup = 💾 Sicherung
+backup-now = Jetzt sichern
+check-backup = Sicherung prüfen
+weekly-report-folder = 📄 Ordner für Wochenberichte (leer = Downloads)
+api-port = 🌐 Port der lokalen HTTP-API (0 = aus)
+api-port-hint = Lauscht auf 127.0.0.1: GET /status und /events (live), POST /start, /pause, /skip, /reset, /toggle.
+mqtt-broker = 🏠 MQTT-Broker (Host[:Port], leer = aus)
+mqtt-hint = Veröffentlicht dauerhaft <Präfix>/state, /period, /remaining, /status (JSON) und /availability.
+push-notifications = 📱 Push-Benachrichtigungen
+push-hint = Wird gesendet, wenn eine Arbeitsphase oder Pause endet.
+telegram-bot = ✈ Telegram-Bot
+telegram-hint = Meldet Phasenwechsel im Chat und befolgt dort /status, /start, /pause, /skip, /reset und /toggle.
+worklog = 📝 Arbeitsprotokolle
+worklog-off = Aus
+api-token = API-Token
+jira-email = E-Mail des Kontos
+jira-issue-key = Vorgangsschlüssel, z. B. POM-42
+worklog-hint = Jeder abgeschlossene Pomodoro wird als Arbeitsprotokoll mit der aktiven Aufgabe oder dem Profil als Kommentar gebucht. Was nicht gebucht werden kann, z. B. offline oder mit falschem Token oder Vorgangsschlüssel, wartet und wird erneut versucht, seltener je länger es fehlschlägt. Nur Arbeitsprotokolle, die der Dienst für ungültig hält, werden verworfen.
+worklog-last-error = Letzter Fehler: { $error }
+worklogs-waiting = Arbeitsprotokolle, die auf das Buchen warten: { $count }
+toggl-track = ⏱ Toggl Track
+toggl-hint = Jeder abgeschlossene Pomodoro wird ein Zeiteintrag, beschrieben durch das aktive Profil oder den Text oben. Einträge, die nicht gesendet werden können, z. B. offline, werden nicht erneut versucht.
+todoist = ✅ Todoist
+github = 🐙 GitHub-Issues
+github-token = Zugriffstoken (für öffentliche Issues optional)
+github-every = Kommentar anbieten alle
+github-hint = Aufgaben können ein GitHub-Issue verknüpfen, im Titel oder in der Aufgabenliste. Das Token braucht das Recht, Issues zu kommentieren, und wird im Schlüsselbund des Systems gespeichert.
+todoist-every = Alle
+active-task-pomodoros-suffix = Pomodoros an der aktiven Aufgabe:
+todoist-hint = Wähle die aktive Aufgabe aus den heutigen Aufgaben im Timer. Das Token liegt im Schlüsselbund des Systems.
+notes-log = 📝 Notizprotokoll
+notes-log-hint = Eine Zeile pro Pomodoro. Platzhalter: {"{"}date{"}"}, {"{"}time{"}"}, {"{"}end{"}"}, {"{"}minutes{"}"}, {"{"}duration{"}"}, {"{"}tag{"}"} (aktive Aufgabe oder Profil).
+streaming-overlay = 📺 Streaming-Overlay
+remote-databases = 🔗 Datenbanken anderer Geräte (mit ; trennen)
+sync-folder = 📂 Sync-Ordner (Dropbox, Syncthing, ...)
+sync-folder-hint = Jedes Gerät führt dort sein eigenes Journal; die Wochenstatistik zählt alle zusammen.
+sync-server = 🔄 Sync-Server
+profiles = 👥 Profile
+save-as-profile = 💾 Als Profil speichern
+edit = ✎ Bearbeiten
+weekday-schedule = 📅 Wochenplan…
+edit-period-sequence = 🧩 Phasenfolge bearbeiten…
+durations-apply-after-period = ⏳ Eine Phase läuft. Geänderte Dauern gelten, wenn sie endet.
+save = ✓ Speichern
+cancel = ✕ Abbrechen
+tasks-title = 📋 Heutige Aufgaben
+tasks-loading = Aufgaben werden aus Todoist geladen…
+tasks-none-due = Heute ist nichts fällig.
+refresh = ⟳ Aktualisieren
+clear-task = Aufgabe abwählen
+back = ← Zurück
+stats = 📊 Statistik
+date-range-from = Von
+date-range-to = bis
+history = 🗂 Verlauf
+history-hint = Auf diesem Gerät gespeicherte Sitzungen, neueste zuerst. Korrigiere ein Tag oder eine Dauer oder lösche Testläufe.
+session-idle = untätig
+history-empty = Noch keine Sitzungen
+newer = ‹ Neuer
+older = Älter ›
+team-session = 🤝 Teamsitzung
+stop-hosting = Hosten beenden
+team-host = Hosten
+team-leave = Verlassen
+team-join = Beitreten
+team-searching = Suche nach Sitzungen in diesem Netzwerk…
+plan = 🗓 Plan
+plan-empty = Noch nichts geplant
+add-task = + Aufgabe hinzufügen
+weekday-schedule-title = 📅 Wochenplan
+schedule-no-profiles = Speichere zuerst ein Profil in den Einstellungen.
+period-sequence = 🧩 Phasenfolge
+standard-cycle = Standardzyklus
+add-step = + Schritt hinzufügen
+clear = Leeren
+sequence-applies-after-period = ⏳ Eine Phase läuft. Die neue Folge beginnt, wenn sie endet.
+weekly-review = 📋 Wochenrückblick
+save-report = 📄 Bericht speichern
+copy-report = 📋 Bericht kopieren
+review-reflection-prompt = Was lief gut? Was kam dazwischen?
+review-goal = 🎯 Pomodoro-Ziel für nächste Woche
+review-next = Weiter ›
+review-back = ‹ Zurück
+keep-screen-on = Bildschirm während der Arbeit nicht abschalten oder sperren
+do-not-disturb = „Nicht stören“ während der Arbeit einschalten
+do-not-disturb-hint = Funktioniert mit KDE und GNOME. Die vorherige Einstellung kehrt zurück, wenn der Pomodoro endet.
+focus-guard = Wenn eine gesperrte App während der Arbeit läuft
+focus-guard-off = Nichts tun
+focus-guard-nudge = Timer in den Vordergrund holen
+focus-guard-minimize = App minimieren
+focus-guard-hint = Apps werden am Prozessnamen erkannt, z. B. discord oder steam.exe. Nicht angehakte Apps bleiben gelistet, sind aber erlaubt.
+focus-guard-minimize-hint = Apps werden am Prozessnamen erkannt, z. B. discord oder steam.exe. Minimieren braucht unter Linux xdotool und geht nur unter X11.
+guarded-app-placeholder = Prozessname
+add-guarded-app = Hinzufügen
+blocked = Gesperrt
+distracting-apps-running = ⚠ Ablenkung: { $apps }
+track-window-titles = Ablenkende Fenster während der Arbeit erfassen
+track-window-titles-hint = Prüft alle 15 Sekunden den Titel des vorderen Fensters und zählt Titel mit einem dieser Wörter als abgelenkte Zeit. Titel verlassen diesen Computer nie. Braucht unter Linux xdotool und X11.
+editor-heartbeats = Editor-Aktivität folgen
+editor-heartbeats-hint = Editor-Plugins melden Tippen mit „roth-pomodoro heartbeat“ oder einem POST an /heartbeat der lokalen API. Die erste Meldung startet einen Pomodoro, und Pomodoros ohne Meldung werden im Verlauf als untätig markiert.
+eye-breaks = 👀 Alle 20 Minuten Arbeit daran erinnern, 20 Sekunden lang 6 Meter weit zu schauen (20-20-20)
+eye-break = 👀 20 Sekunden lang 6 Meter weit schauen
+eye-break-hint = Gönn deinen Augen etwas in der Ferne, dann geht es weiter.
+break-screen = 🖥 Nach einer Arbeitsphase ein Pausenfenster im Vollbild zeigen
+break-screen-all-monitors = Alle Bildschirme abdecken
+break-screen-primary-monitor = Den Hauptbildschirm abdecken
+break-screen-current-monitor = Den Bildschirm mit dem Timer abdecken
+break-screen-placement-hint = Während einer Pause an- oder abgesteckte Bildschirme werden nach wenigen Sekunden berücksichtigt. Unter Wayland wählt der Compositor den Bildschirm.
+announce-transitions = 🗣 Phasenwechsel per Desktop-Benachrichtigung ansagen, für Screenreader
+keyboard-shortcuts = Tastenkürzel
+keyboard-shortcuts-hint = Klicke auf eine Taste und drücke die neue. Die Tasten wirken, wenn kein Textfeld den Fokus hat.
+press-a-key = Taste drücken…
+reset-shortcuts = Auf Standard zurücksetzen
+shortcut-conflict = { $key } ist schon für „{ $shortcut }“ belegt
+shortcut-start-stop = Starten oder pausieren
+shortcut-reset = Zurücksetzen
+shortcut-skip = Überspringen
+shortcut-back = Alarm stoppen oder zurück
+shortcut-focus-next = Nächstes Feld
+shortcut-focus-previous = Vorheriges Feld
+always-on-top = 📌 Timerfenster über anderen Fenstern halten
+start-minimized = 🗕 Minimiert im Tray starten
+close-to-tray = ✕ Schließen blendet das Fenster in den Tray aus
+minimize-to-tray = 🗕 Minimieren blendet das Fenster in den Tray aus
+strict-mode = 🔒 Strenger Modus: laufende Pomodoros nicht pausieren oder zurücksetzen
+report-html = Auch eine HTML-Kopie speichern
+reset-count = Zähler zurücksetzen
+settings-tooltip = Einstellungen
+stats-tooltip = Statistik
+plan-tooltip = Tag planen
+quick-timer = ⏲ Kurzzeitwecker
+quick-timers = ⏲ Kurzzeitwecker
+quick-timer-up = Abgelaufen
+quick-timer-tooltip = Kurzzeitwecker für alles andere
+quick-timer-hint = Ein einmaliger Countdown für Tee oder ein Meeting. Er klingelt wie der Timer, zählt aber nicht zu deinen Statistiken.
+quick-timer-label = Wofür (optional)
+quick-timer-preset = { $minutes } Min.
+quick-timer-done = ⏲ { $label } ist abgelaufen
+team-session-tooltip = Teamsitzung
+weekly-review-tooltip = Wochenrückblick
+strict-mode-no-pause = Strenger Modus: ein Pomodoro kann nicht pausiert werden
+profile = Profil
+working-on = Arbeite an…
+ambient-file-placeholder = Loopfähige .ogg-, .mp3-, .flac- oder .wav-Datei
+focus-header-placeholder = Überschrift, z. B. Deep Work – Handy weg
+focus-message-placeholder = Nachricht, z. B. Streck dich mal
+image-path-optional = Bildpfad (optional)
+import-path-placeholder = Pfad zur exportierten Datei
+topic-prefix = Topic-Präfix
+username = Benutzername
+password = Passwort
+ntfy-topic = Topic auf ntfy.sh oder https://dein-server/topic
+user-key = User Key
+application-token = Anwendungstoken
+bot-token = Bot-Token
+chat-id = Chat-ID
+api-token-empty-off = API-Token (leer = aus)
+workspace-id = Workspace-ID
+project-id-optional = Projekt-ID (optional)
+description = Beschreibung
+notes-log-path = Datei zum Anhängen, z. B. ~/notizen/{"{"}date{"}"}.md (leer = aus)
+obs-folder-placeholder = Ordner für OBS-Textdateien (leer = aus)
+access-token = Zugriffstoken
+profile-name-placeholder = Profilname, z. B. Deep Work
+tag-placeholder = Tag
+team-pomodoro = Team-Pomodoro
+task-placeholder = Aufgabe
+review-reflection-placeholder = Gedanken zur Woche
+break-screen-title = ☕ Zeit für eine Pause
+break-screen-message = Geh weg vom Bildschirm.
+start-break = ▶ Pause starten
+skip-break = ⏭ Pause überspringen
+notify-start-next = ▶ Nächsten Pomodoro starten
+
+tray-show-timer = Timer anzeigen
+tray-start-pause = Start / Pause
+tray-tooltip-running = { $period } · noch { $minutes } Min.
+tray-tooltip-paused = { $period } · pausiert, noch { $minutes } Min.
+tray-tooltip-finished = { $period } beendet
+tray-tooltip-idle = { $period } · bereit
+tray-tooltip-today = Heute erledigt: { $count }
+tray-tooltip-task = Aufgabe: { $task }
+tray-skip = Überspringen
+tray-quit = Beenden
+window-overlay = Pomodoro-Overlay
+window-break = Pomodoro-Pause
+window-guest = Pomodoro-Timer (Gast)
+window-timer = Pomodoro-Timer
+stats-date-error = Verwende Datumsangaben im Format JJJJ-MM-TT, das erste am oder vor dem zweiten.
+push-work-finished = 🍅 Arbeitsphase beendet
+push-break-over = ☕ { $period } vorbei
+push-next-up = Als Nächstes: { $period } ({ $minutes } Min.)
+progress-sequence = Schritt { $step }/{ $steps } deiner Folge
+progress-no-long-breaks = Pomodoro { $pomodoro }, keine langen Pausen
+progress-cycle = Pomodoro { $pomodoro }/{ $every } bis zur langen Pause
+progress-pattern = Pomodoro { $pomodoro }, lange Pause nach Nr. { $long-break }
+adaptive-break-note = +{ $minutes } Min. für { $pomodoros ->
+    [one] { $pomodoros } Pomodoro
+   *[other] { $pomodoros } Pomodoros
+} am Stück
+progress-break = Pausenzeit – entspann dich!
+strict-mode-no-reset = Strenger Modus: ein Pomodoro kann nicht zurückgesetzt werden
+reset-tooltip = Zurücksetzen
+unpin-from-top = Nicht mehr im Vordergrund
+keep-on-top = Im Vordergrund halten
+unmute = Ton an
+mute = Stumm
+hide-overlay = Overlay ausblenden
+show-overlay = Overlay einblenden
+streak-tooltip = Tagesserie: Tage in Folge mit mindestens einem Pomodoro. Tage beginnen um { $hour }:00, Urlaubstage unterbrechen sie nicht.
+free-focus = ⏱ Freier Fokus
+stopwatch-tooltip = Freier Fokus (zählt hoch, ohne feste Länge)
+back-to-pomodoros = Zurück zu den Pomodoros
+finish-session = ✔ Beenden
+stopwatch-pomodoros = Bisher { $count ->
+    [one] { $count } Pomodoro
+   *[other] { $count } Pomodoros
+} wert
+meeting-mode-tooltip = Besprechungsmodus (keine Alarme oder Benachrichtigungen)
+end-meeting-mode = Besprechungsmodus beenden
+meeting-mode-on = 🤫 Besprechungsmodus: Alarme und Benachrichtigungen sind aus
+quiet-hours-on = 🤫 Ruhezeit: Alarme und Benachrichtigungen sind aus
+end-guest-session = Gastsitzung beenden
+guest-session-tooltip = Gastsitzung (nichts wird gespeichert)
+low-power-percent = 🔋 Energiesparen · { $percent } %
+low-power = 🔋 Energiesparen
+pick-a-task = 📋 Aufgabe wählen
+next-meeting = 📅 Nächster Termin in { $minutes } Min.
+meeting-collides = { $meeting } · bevor dieser Pomodoro endet
+planned-progress = 🗓 { $done }/{ $planned } geplant
+team-following = Folgt { $host }
+team-hosting = Hostet { $name } · { $followers } folgen
+completed = ✓ Erledigt: { $count }
+weekly-goal-progress = 🎯 Wochenziel: { $done }/{ $goal }
+pause-break = ⏸ Pause anhalten
+resume-break = ▶ Pause fortsetzen
+extend-break = ☕ +{ $minutes } Min.
+pause = ⏸ Pause
+resume = ▶ Fortsetzen
+start = ▶ Start
+period-snoozed = 💤 { $period } beendet – schlummert
+period-finished = ⏰ { $period } beendet!
+long-break-preview = Lange Pausen nach den Pomodoros { $positions }, …
+long-break-pattern-error = Verwende Pomodoro-Nummern von 1 bis { $max }, durch Kommas getrennt.
+import-sessions = { $count ->
+    [one] { $count } Sitzung
+   *[other] { $count } Sitzungen
+} importieren
+streaming-overlay-hint = Schreibt dort { $countdown } und { $period }. Bei aktiver HTTP-API stattdessen http://127.0.0.1:<port>/overlay als Browserquelle hinzufügen.
+tasks-hint = Pomodoros zählen für die aktive Aufgabe. Alle { $every }: { $action }.
+achievements = 🏆 Erfolge
+achievement-unlocked = 🏆 Erfolg freigeschaltet: { $title }
+achievement-earned-on = Erreicht am { $date }
+achievement-first-pomodoro = Erster Pomodoro
+achievement-first-pomodoro-hint = Schließe deinen ersten Pomodoro ab.
+achievement-hundred = Hundertschaft
+achievement-hundred-hint = Schließe insgesamt 100 Pomodoros ab.
+achievement-week-streak = Eine Woche am Stück
+achievement-week-streak-hint = Arbeite an 7 Tagen in Folge mindestens einen Pomodoro.
+achievement-ten-in-a-day = Zweistellig
+achievement-ten-in-a-day-hint = Schließe 10 Pomodoros an einem Tag ab.
+stats-window-titles = Fenstertitel: { $focused } Min. fokussiert, { $distracted } Min. abgelenkt ({ $percent } %)
+stats-all-tags = Alle Tags
+stats-untagged = Ohne Tag
+stats-7-days = 7 Tage
+stats-30-days = 30 Tage
+stats-90-days = 90 Tage
+stats-year = Jahr
+stats-totals = 🍅 { $pomodoros ->
+    [one] { $pomodoros } Pomodoro
+   *[other] { $pomodoros } Pomodoros
+} · ⏱ { $hours } Std. { $minutes } Min. · 📅 { $days ->
+    [one] { $days } aktiver Tag
+   *[other] { $days } aktive Tage
+}
+history-page = Seite { $page }/{ $pages }
+team-hint = Hoste eine Sitzung, dann können Teammitglieder im selben Netzwerk beitreten. Ihre Timer folgen deinem: Starten, Pausieren, Überspringen und Zurücksetzen.
+plan-hint = Schätze, wie viele Pomodoros jede Aufgabe braucht. Pomodoros zählen für die im Timer gewählte Aufgabe.
+plan-today = Heute
+plan-tomorrow = Morgen
+schedule-hint = Wähle für jeden Wochentag ein Profil. Es wird zu Tagesbeginn aktiviert; du kannst trotzdem von Hand wechseln.
+sequence-hint = Die Phasen laufen in dieser Reihenfolge und wiederholen sich. Leer lassen für den üblichen Wechsel aus Arbeit und Pause.
+review-step-stats = Schritt 1/3 · Letzte 7 Tage
+review-step-reflection = Schritt 2/3 · Rückblick
+review-step-goals = Schritt 3/3 · Nächste Woche
+review-pomodoros = 🍅 Pomodoros: { $count }
+review-focus-time = ⏱ Fokuszeit: { $hours } Std. { $minutes } Min.
+review-active-days = 📅 Aktive Tage: { $days }/7
+review-last-goal = Letztes Ziel: { $goal } Pomodoros ({ $done } erledigt seit { $since })
+review-estimates = 🎯 Schätzungen: { $accuracy } % genau über { $days ->
+    [one] { $days } geplanten Tag
+   *[other] { $days } geplante Tage
+}
+review-plan-day = { $day }: { $done } von { $planned } geplant · { $accuracy } %
+saved-file = { $path } gespeichert
+error-saving-file = Fehler beim Speichern von { $path }: { $error }
+error-reading-file = Fehler beim Lesen von { $path }: { $error }
+imported-sessions = { $count ->
+    [one] { $count } Sitzung
+   *[other] { $count } Sitzungen
+} importiert
+backup-intact = Die Sicherung ist intakt und enthält { $count ->
+    [one] { $count } Sitzung
+   *[other] { $count } Sitzungen
+}
+backup-dir-error = { $dir } konnte nicht angelegt werden: { $error }
+backup-failed = Die Datenbank konnte nicht gesichert werden
+backup-damaged = Die Sicherung ist beschädigt: { $error }
+backup-not-ours = Das ist keine roth-pomodoro-Datenbank
+backup-missing = { $path } existiert nicht
+restore-failed = { $path } konnte nicht wiederhergestellt werden
+restored-backup = { $path } wiederhergestellt. Die bisherigen Daten wurden in { $previous } gesichert.
+quit-after-restore = Beenden und Sicherung laden
+import-invalid-json = Die Datei ist kein gültiges JSON: { $error }
+import-no-rounds = Keine Liste von Runden gefunden
+import-empty-file = Die Datei ist leer
+import-no-time-columns = Keine Spalte für Start- oder Endzeit gefunden
+import-toggl-columns = Erwartet werden Toggls Spalten Start date, Start time und Duration
+telegram-not-running = Der Timer läuft nicht.
+telegram-status = { $period }: noch { $remaining } ({ $state }), { $count ->
+    [one] { $count } Pomodoro
+   *[other] { $count } Pomodoros
+} erledigt
+telegram-state-idle = nicht gestartet
+telegram-state-running = läuft
+telegram-state-paused = pausiert
+telegram-state-ringing = klingelt
+github-token-rejected = GitHub hat das Token abgelehnt
+todoist-token-rejected = Todoist hat das Token abgelehnt
+github-issue-not-found = GitHub hat das Issue nicht gefunden, oder das Token kann es nicht sehen
+github-http-error = GitHub antwortete mit HTTP { $code }
+copied-to-clipboard = In die Zwischenablage kopiert
+invalid-settings = Ungültige Einstellungen. Verwende positive Dauern in Minuten oder mm:ss, ein gültiges Muster für lange Pausen, 0-100 für Lautstärken, eine Audiodatei falls gewählt, Urlaubstage als JJJJ-MM-TT, einen Port bis 65535, eine Größe von 80-200 % und Alarmtöne, die in die Alarmlänge passen.
+profile-needs-name = Gib dem Profil einen Namen.
+profile-invalid-settings = Korrigiere die Einstellungen oben, bevor du sie als Profil speicherst.
+team-lost-connection = Verbindung zu { $host } verloren
+history-length-error = Die Dauer muss eine positive Zahl an Minuten sein.
+plan-error = Jede Aufgabe braucht einen Namen und eine Schätzung von mindestens einem Pomodoro.
+sequence-error = Ungültige Folge. Verwende positive Längen in Minuten oder mm:ss und mindestens eine Arbeitsphase.
+review-goal-error = Ungültiges Ziel. Verwende eine positive Zahl an Pomodoros.
+work-time = 🍅 Arbeitszeit
+long-break = ☕ Lange Pause
+short-break = ☕ Kurze Pause
+period-work = Arbeit
+period-short-break = Kurze Pause
+period-long-break = Lange Pause
+corner-top-left = Oben links
+corner-top-right = Oben rechts
+corner-bottom-left = Unten links
+corner-bottom-right = Unten rechts
+completion-elapsed = Wenn die Zeit um ist
+completion-acknowledged = Wenn der Alarm bestätigt ist
+completion-confirmed = Wenn ich bestätige, dass ich konzentriert war
+quiet-hours = 🤫 Ruhezeiten
+quiet-hours-hint = Tageszeiten ohne Alarme, Benachrichtigungen oder automatisches Starten, z. B. 12:00-13:00, 22:00-07:00. Nur Viertelstunden.
+quiet-hours-error = Verwende Bereiche wie 12:00-13:00 auf Viertelstunden, getrennt durch Kommas.
+lock-keep-running = Weiterlaufen
+lock-pause = Pausieren, beim Entsperren fragen
+lock-pause-and-resume = Pausieren, beim Entsperren fortsetzen
+off = Aus
+ambient-white-noise = Weißes Rauschen
+ambient-pink-noise = Rosa Rauschen (wie Regen)
+ambient-file = Audiodatei (Regen, Café…)
+offer-issue-comment = „{ $comment }“ an { $issue } posten?
+issue-comment-body = 🍅 { $pomodoros ->
+    [one] { $pomodoros } Pomodoro
+   *[other] { $pomodoros } Pomodoros
+} hierfür aufgewendet
+post = Posten
+not-now = Nicht jetzt
+posting-issue-comment = Wird an { $issue } gepostet …
+issue-comment-posted = Kommentar gepostet
+github-token-needed = Für Kommentare in den Einstellungen ein GitHub-Token eintragen
+show-issue-title = Titel von { $issue } anzeigen
+active-task-issue = GitHub-Issue
+active-task-issue-linked = Verknüpft mit { $issue }. Alle { $every } Pomodoros wird angeboten, dort zu kommentieren.
+active-task-issue-hint = Die Adresse des Issues einfügen, falls der Aufgabentitel keine enthält.
+todoist-comment = Einen Kommentar schreiben
+todoist-complete = Die Aufgabe abschließen
+monday = Montag
+tuesday = Dienstag
+wednesday = Mittwoch
+thursday = Donnerstag
+friday = Freitag
+saturday = Samstag
+sunday = Sonntag
+schedule-no-change = Keine Änderung
+import-summary = { $count ->
+    [one] { $count } neue Sitzung
+   *[other] { $count } neue Sitzungen
+}{ $range }. { $duplicates } bereits gespeichert, { $skipped } übersprungen.
+import-range = , { $first } bis { $last }
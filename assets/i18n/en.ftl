@@ -0,0 +1,487 @@
+# English messages, the fallback for every other language.
+# Syntax: https://projectfluent.org/fluent/guide/ (messages, variables,
+# string literals and selectors on a number only).
+
+language = 🌐 Language
+language-system = System default
+
+colors = 🎨 Colors
+appearance = Appearance
+backgrounds = Backgrounds: an image, or a gradient between two colors
+backgrounds-hint = Shown behind the timer for each kind of period, and behind the break screen. An image wins over a gradient.
+background-gradient-error = Write gradients as two colors, like #ff6b6b, #4ecdc4
+background-dim = Dim backgrounds by
+timer-face = Timer face
+timer-face-digits = Digits
+timer-face-dial = Kitchen timer dial
+ui-scale = 🔍 Interface Scale (%, 80-200)
+countdown-font = 🔤 Countdown Font
+countdown-font-placeholder = Family name, monospace, or a .ttf/.otf file (empty = default)
+palette-standard = Standard
+palette-high-contrast = High contrast
+palette-deuteranopia = Deuteranopia-safe (red-green)
+palette-protanopia = Protanopia-safe (red-green)
+
+guest-session-banner = 👤 Guest session · not recorded
+screen-lock-paused = 🔒 Paused while your screen was locked. Resume when ready.
+durations-apply-next-period = ⏳ New durations apply from the next period
+stop-alarm = ■ Stop
+snooze-minutes = 💤 Snooze { $minutes } min
+intention-prompt = What are you working on?
+intention-placeholder = e.g. Draft the release notes
+start-without-intention = Skip
+ask-intention = 🎯 Ask what I'm working on when a pomodoro starts
+focus-check-prompt = Did you stay focused?
+focused-yes = ✓ Yes
+focused-no = ✕ No
+undo = ↶ Undo
+undo-timer-reset = Timer reset
+undo-count-reset = Count reset
+settings = ⚙ Settings
+work-duration-minutes = 🍅 Work Duration (minutes or mm:ss)
+short-break-minutes = ☕ Short Break (minutes or mm:ss)
+long-break-minutes = ☕ Long Break (minutes or mm:ss)
+experiment-lengths = Experiment: alternate with a second work length day by day
+experiment-hint = Compare how often pomodoros of each length get finished on the stats screen.
+stats-experiment = 🧪 Work Length Experiment
+experiment-result = { $length }: { $finished } of { $attempts } finished ({ $completion }%), { $abandoned } abandoned ({ $abandonment }%)
+experiment-better = { $length } pomodoros get finished more often so far.
+experiment-too-early = Try each length at least { $count ->
+    [one] once
+   *[other] { $count } times
+} to compare them.
+take-long-breaks = Take long breaks
+long-break-every-pomodoros = 🔄 Long Break Every (pomodoros)
+long-break-pattern-empty-hint = Leave empty to use the number above.
+long-break-skip = ⏭️ When a Long Break Is Skipped
+long-break-skip-defer = Take it at the next break
+long-break-skip-forfeit = Let it go
+long-break-skip-shorten = Take a short break instead
+adaptive-break-length = 🔋 Longer Breaks After Back-to-Back Pomodoros (minutes)
+adaptive-break-hint = Each pomodoro after the first finished without a break in between adds this much to the next break. 0 turns it off.
+long-break-pattern-advanced = 🧩 Long Break Pattern (advanced)
+alarm-volume = 🔊 Alarm Volume (%)
+test-sound = ▶ Test
+ticking-volume = ⏱ Ticking Volume During Work (%, 0 = off)
+ambient-sound = 🌧 Ambient Sound During Work
+close-to-tray-hint = The countdown keeps running while hidden. Quit from the tray menu.
+screen-lock-action = 🔒 When the Screen Locks During Work
+alarm-pattern = 🎵 Alarm Tones
+alarm-tones = Tones (Hz)
+alarm-beeps = Beeps
+alarm-beep-length = Beep length (ms)
+alarm-gap = Gap between beeps (ms)
+alarm-length = Total length (ms)
+alarm-pattern-hint = Beeps cycle through the tones. The alarm stays quiet for the rest of its length before it can repeat.
+break-extension-length = ☕ Extend Breaks By (minutes)
+break-extension-hint = Adds a button that lengthens a running break. 0 turns it off. Work statistics aren't affected; how long each break really lasted is saved with the pomodoro before it.
+snooze-length = 💤 Snooze Length (minutes)
+snooze-hint = Snoozing when a pomodoro ends keeps working this long before the break starts. After a break it quiets the alarm for this long.
+alarm-repeat = 🔁 Repeat Alarm Every (seconds)
+alarm-repeat-hint = Repeats until you press Start or click the window. 0 plays it once.
+countdown-overlay = ⧉ Countdown Overlay
+completion-criteria = ✅ Count a Pomodoro
+low-power-threshold = 🔋 Low-Power Mode Below Battery (%)
+low-power-hint = 0 turns it off, 100 applies whenever unplugged.
+focus-messages = 💬 Focus Messages
+streak-day-start = 🔥 Streak Day Starts At (hour, 0-23)
+holidays = 🏖 Holidays (YYYY-MM-DD, separate with ;)
+calendar = 📅 Calendar
+export-calendar = Export Focus Sessions (.ics)
+calendar-subscribe-hint = With the HTTP API on, calendar apps can also subscribe to http://127.0.0.1:<port>/calendar.ics.
+meetings-calendar = Meetings calendar (iCal address, e.g. Google Calendar's secret address)
+import-from-app = 📥 Import Sessions From Another App
+preview = Preview
+restore-backup = Restore and Quit
+backup = 💾 Backup
+backup-now = Back Up Now
+check-backup = Check Backup
+weekly-report-folder = 📄 Weekly Report Folder (empty = Downloads)
+api-port = 🌐 Local HTTP API Port (0 = off)
+api-port-hint = Listens on 127.0.0.1: GET /status and /events (live), POST /start, /pause, /skip, /reset, /toggle.
+mqtt-broker = 🏠 MQTT Broker (host[:port], empty = off)
+mqtt-hint = Publishes retained <prefix>/state, /period, /remaining, /status (JSON) and /availability.
+push-notifications = 📱 Push Notifications
+push-hint = Sent when a work period or break ends.
+telegram-bot = ✈ Telegram Bot
+telegram-hint = Sends period changes to the chat and obeys /status, /start, /pause, /skip, /reset and /toggle from it.
+worklog = 📝 Worklogs
+worklog-off = Off
+api-token = API token
+jira-email = Account email
+jira-issue-key = Issue key, e.g. POM-42
+worklog-hint = Each completed pomodoro is posted as a worklog with the active task or profile as its comment. Worklogs that can't be posted, e.g. while offline or with a wrong token or issue key, wait and are retried, less often the longer they keep failing. Only worklogs the service finds invalid are dropped.
+worklog-last-error = Last error: { $error }
+worklogs-waiting = Worklogs waiting to be posted: { $count }
+toggl-track = ⏱ Toggl Track
+toggl-hint = Each completed pomodoro becomes a time entry, described by the active profile or the text above. Entries that can't be sent, e.g. while offline, are not retried.
+todoist = ✅ Todoist
+github = 🐙 GitHub issues
+github-token = Access token (optional for public issues)
+github-every = Offer a comment every
+github-hint = Tasks can link a GitHub issue, in their title or on the task list. The token needs permission to comment on issues and is kept in the system keychain.
+todoist-every = Every
+active-task-pomodoros-suffix = pomodoros on the active task:
+todoist-hint = Pick the active task from today's tasks on the timer screen. The token is kept in the system keychain.
+notes-log = 📝 Notes Log
+notes-log-hint = One line per pomodoro. Placeholders: {"{"}date{"}"}, {"{"}time{"}"}, {"{"}end{"}"}, {"{"}minutes{"}"}, {"{"}duration{"}"}, {"{"}tag{"}"} (the active task or profile).
+streaming-overlay = 📺 Streaming Overlay
+remote-databases = 🔗 Other Devices' Databases (separate with ;)
+sync-folder = 📂 Sync Folder (Dropbox, Syncthing, ...)
+sync-folder-hint = Each device keeps its own journal there; weekly stats add up all of them.
+sync-server = 🔄 Sync Server
+profiles = 👥 Profiles
+save-as-profile = 💾 Save as Profile
+edit = ✎ Edit
+weekday-schedule = 📅 Weekday Schedule…
+edit-period-sequence = 🧩 Edit Period Sequence…
+durations-apply-after-period = ⏳ A period is in progress. Duration changes will apply when it ends.
+save = ✓ Save
+cancel = ✕ Cancel
+tasks-title = 📋 Today's Tasks
+tasks-loading = Loading tasks from Todoist…
+tasks-none-due = Nothing due today.
+refresh = ⟳ Refresh
+clear-task = Clear Task
+back = ← Back
+stats = 📊 Stats
+date-range-from = From
+date-range-to = to
+history = 🗂 History
+history-hint = Sessions recorded on this device, newest first. Fix a tag or length, or delete test runs.
+session-idle = idle
+history-empty = No sessions yet
+newer = ‹ Newer
+older = Older ›
+team-session = 🤝 Team Session
+stop-hosting = Stop Hosting
+team-host = Host
+team-leave = Leave
+team-join = Join
+team-searching = Looking for sessions on this network…
+plan = 🗓 Plan
+plan-empty = Nothing planned yet
+add-task = + Add Task
+weekday-schedule-title = 📅 Weekday Schedule
+schedule-no-profiles = Save a profile in Settings first.
+period-sequence = 🧩 Period Sequence
+standard-cycle = Standard cycle
+add-step = + Add Step
+clear = Clear
+sequence-applies-after-period = ⏳ A period is in progress. The new sequence will start when it ends.
+weekly-review = 📋 Weekly Review
+save-report = 📄 Save Report
+copy-report = 📋 Copy Report
+review-reflection-prompt = What went well? What got in the way?
+review-goal = 🎯 Pomodoro goal for next week
+review-next = Next ›
+review-back = ‹ Back
+keep-screen-on = Keep the screen from blanking or locking during work
+do-not-disturb = Turn on Do Not Disturb during work
+do-not-disturb-hint = Works with KDE and GNOME. Whatever was set before comes back when the pomodoro ends.
+focus-guard = When a blocked app runs during work
+focus-guard-off = Do nothing
+focus-guard-nudge = Bring the timer forward
+focus-guard-minimize = Minimize the app
+focus-guard-hint = Apps are matched by process name, e.g. discord or steam.exe. Unchecked apps stay listed but are allowed.
+focus-guard-minimize-hint = Apps are matched by process name, e.g. discord or steam.exe. Minimizing needs xdotool on Linux and only works under X11.
+guarded-app-placeholder = Process name
+add-guarded-app = Add
+blocked = Blocked
+distracting-apps-running = ⚠ Distracting: { $apps }
+track-window-titles = Log distracting windows during work
+track-window-titles-hint = Looks at the front window's title every 15 seconds and counts titles containing any of these words as distracted time. Titles never leave this computer. Needs xdotool and X11 on Linux.
+editor-heartbeats = Follow editor activity
+editor-heartbeats-hint = Editor plugins report typing by running "roth-pomodoro heartbeat" or posting to /heartbeat on the local API. The first one starts a pomodoro, and pomodoros without any are marked idle in the history.
+eye-breaks = 👀 Remind me every 20 minutes of work to look 20 feet away for 20 seconds (20-20-20)
+eye-break = 👀 Look 20 feet away for 20 seconds
+eye-break-hint = Rest your eyes on something far away, then carry on.
+break-screen = 🖥 Show a full-screen break window when a work period ends
+break-screen-all-monitors = Cover all monitors
+break-screen-primary-monitor = Cover the primary monitor
+break-screen-current-monitor = Cover the monitor the timer is on
+break-screen-placement-hint = Monitors plugged in or out during a break are picked up within a few seconds. On Wayland the compositor chooses the monitor.
+announce-transitions = 🗣 Announce period changes with a desktop notification, for screen readers
+keyboard-shortcuts = Keyboard shortcuts
+keyboard-shortcuts-hint = Click a key and press the new one. The keys work when no text field has focus.
+press-a-key = Press a key…
+reset-shortcuts = Reset to defaults
+shortcut-conflict = { $key } is already used for “{ $shortcut }”
+shortcut-start-stop = Start or pause
+shortcut-reset = Reset
+shortcut-skip = Skip
+shortcut-back = Stop the alarm or go back
+shortcut-focus-next = Next field
+shortcut-focus-previous = Previous field
+always-on-top = 📌 Keep the timer window above other windows
+start-minimized = 🗕 Start minimized to the tray
+close-to-tray = ✕ Closing the window hides it to the tray
+minimize-to-tray = 🗕 Minimizing the window hides it to the tray
+strict-mode = 🔒 Strict mode: no pausing or resetting a running pomodoro
+report-html = Also save an HTML copy
+reset-count = Reset Count
+settings-tooltip = Settings
+stats-tooltip = Stats
+plan-tooltip = Plan the Day
+quick-timer = ⏲ Quick Timer
+quick-timers = ⏲ Quick Timers
+quick-timer-up = Time's up
+quick-timer-tooltip = Quick timer for anything else
+quick-timer-hint = A one-off countdown for tea or a meeting. It rings like the timer but doesn't count towards your statistics.
+quick-timer-label = What it's for (optional)
+quick-timer-preset = { $minutes } min
+quick-timer-done = ⏲ { $label } is up
+team-session-tooltip = Team Session
+weekly-review-tooltip = Weekly Review
+strict-mode-no-pause = Strict mode: a pomodoro can't be paused
+profile = Profile
+working-on = Working on…
+ambient-file-placeholder = Loopable .ogg, .mp3, .flac or .wav file
+focus-header-placeholder = Header, e.g. Deep work — phone away
+focus-message-placeholder = Message, e.g. Go stretch
+image-path-optional = Image path (optional)
+import-path-placeholder = Path to the exported file
+topic-prefix = Topic prefix
+username = Username
+password = Password
+ntfy-topic = Topic on ntfy.sh, or https://your-server/topic
+user-key = User key
+application-token = Application token
+bot-token = Bot token
+chat-id = Chat ID
+api-token-empty-off = API token (empty = off)
+workspace-id = Workspace ID
+project-id-optional = Project ID (optional)
+description = Description
+notes-log-path = File to append to, e.g. ~/notes/{"{"}date{"}"}.md (empty = off)
+obs-folder-placeholder = Folder for OBS text files (empty = off)
+access-token = Access token
+profile-name-placeholder = Profile name, e.g. Deep Work
+tag-placeholder = Tag
+team-pomodoro = Team pomodoro
+task-placeholder = Task
+review-reflection-placeholder = Reflections on the week
+break-screen-title = ☕ Time for a break
+break-screen-message = Step away from the screen.
+start-break = ▶ Start break
+skip-break = ⏭ Skip break
+notify-start-next = ▶ Start next pomodoro
+
+tray-show-timer = Show Timer
+tray-start-pause = Start / Pause
+tray-tooltip-running = { $period } · { $minutes } min left
+tray-tooltip-paused = { $period } · paused with { $minutes } min left
+tray-tooltip-finished = { $period } finished
+tray-tooltip-idle = { $period } · ready to start
+tray-tooltip-today = Completed today: { $count }
+tray-tooltip-task = Task: { $task }
+tray-skip = Skip
+tray-quit = Quit
+window-overlay = Pomodoro Overlay
+window-break = Pomodoro Break
+window-guest = Pomodoro Timer (Guest)
+window-timer = Pomodoro Timer
+stats-date-error = Use YYYY-MM-DD dates, with the first one on or before the second.
+push-work-finished = 🍅 Work period finished
+push-break-over = ☕ { $period } over
+push-next-up = Next up: { $period } ({ $minutes } min)
+progress-sequence = Step { $step }/{ $steps } of your sequence
+progress-no-long-breaks = Pomodoro { $pomodoro }, no long breaks
+progress-cycle = Pomodoro { $pomodoro }/{ $every } until long break
+progress-pattern = Pomodoro { $pomodoro }, long break after #{ $long-break }
+adaptive-break-note = +{ $minutes } min for { $pomodoros ->
+    [one] { $pomodoros } pomodoro
+   *[other] { $pomodoros } pomodoros
+} in a row
+progress-break = Break time - relax!
+strict-mode-no-reset = Strict mode: a pomodoro can't be reset
+reset-tooltip = Reset
+unpin-from-top = Unpin from Top
+keep-on-top = Keep on Top
+unmute = Unmute
+mute = Mute
+hide-overlay = Hide Overlay
+show-overlay = Show Overlay
+streak-tooltip = Daily streak: days in a row with at least one pomodoro. Days start at { $hour }:00 and holidays don't break it.
+free-focus = ⏱ Free Focus
+stopwatch-tooltip = Free focus (counts up, no fixed length)
+back-to-pomodoros = Back to pomodoros
+finish-session = ✔ Finish
+stopwatch-pomodoros = Worth { $count ->
+    [one] { $count } pomodoro
+   *[other] { $count } pomodoros
+} so far
+meeting-mode-tooltip = Meeting mode (no alarms or notifications)
+end-meeting-mode = End meeting mode
+meeting-mode-on = 🤫 Meeting mode: alarms and notifications are off
+quiet-hours-on = 🤫 Quiet hours: alarms and notifications are off
+end-guest-session = End Guest Session
+guest-session-tooltip = Guest Session (nothing is recorded)
+low-power-percent = 🔋 Low power · { $percent }%
+low-power = 🔋 Low power
+pick-a-task = 📋 Pick a task
+next-meeting = 📅 Next meeting in { $minutes } min
+meeting-collides = { $meeting } · before this pomodoro ends
+planned-progress = 🗓 { $done }/{ $planned } planned
+team-following = Following { $host }
+team-hosting = Hosting { $name } · { $followers } following
+completed = ✓ Completed: { $count }
+weekly-goal-progress = 🎯 Weekly goal: { $done }/{ $goal }
+pause-break = ⏸ Pause Break
+resume-break = ▶ Resume Break
+extend-break = ☕ +{ $minutes } min
+pause = ⏸ Pause
+resume = ▶ Resume
+start = ▶ Start
+period-snoozed = 💤 { $period } finished - snoozed
+period-finished = ⏰ { $period } finished!
+long-break-preview = Long breaks after pomodoros { $positions }, …
+long-break-pattern-error = Use pomodoro numbers from 1 to { $max }, separated by commas.
+import-sessions = Import { $count ->
+    [one] { $count } Session
+   *[other] { $count } Sessions
+}
+streaming-overlay-hint = Writes { $countdown } and { $period } there. With the HTTP API on, add http://127.0.0.1:<port>/overlay as a browser source instead.
+tasks-hint = Pomodoros count towards the active task. Every { $every } of them: { $action }.
+achievements = 🏆 Achievements
+achievement-unlocked = 🏆 Achievement unlocked: { $title }
+achievement-earned-on = Earned { $date }
+achievement-first-pomodoro = First pomodoro
+achievement-first-pomodoro-hint = Finish your first pomodoro.
+achievement-hundred = Centurion
+achievement-hundred-hint = Finish 100 pomodoros in total.
+achievement-week-streak = A week in a row
+achievement-week-streak-hint = Work at least one pomodoro on 7 days in a row.
+achievement-ten-in-a-day = Double digits
+achievement-ten-in-a-day-hint = Finish 10 pomodoros in one day.
+stats-window-titles = Window titles: { $focused } min focused, { $distracted } min distracted ({ $percent }%)
+stats-all-tags = All tags
+stats-untagged = Untagged
+stats-7-days = 7 days
+stats-30-days = 30 days
+stats-90-days = 90 days
+stats-year = Year
+stats-totals = 🍅 { $pomodoros ->
+    [one] { $pomodoros } pomodoro
+   *[other] { $pomodoros } pomodoros
+} · ⏱ { $hours }h { $minutes }m · 📅 { $days ->
+    [one] { $days } active day
+   *[other] { $days } active days
+}
+history-page = Page { $page }/{ $pages }
+team-hint = Host a session and teammates on the same network can join it. Their timers follow yours: starts, pauses, skips and resets.
+plan-hint = Estimate how many pomodoros each task needs. Pomodoros count towards the task picked on the timer screen.
+plan-today = Today
+plan-tomorrow = Tomorrow
+schedule-hint = Pick a profile for each weekday. It is selected when the day starts; you can still switch by hand.
+sequence-hint = Periods run in this order and then repeat. Leave it empty to use the standard work/break cycle.
+review-step-stats = Step 1/3 · Last 7 days
+review-step-reflection = Step 2/3 · Reflection
+review-step-goals = Step 3/3 · Next week
+review-pomodoros = 🍅 Pomodoros: { $count }
+review-focus-time = ⏱ Focus time: { $hours }h { $minutes }m
+review-active-days = 📅 Active days: { $days }/7
+review-last-goal = Last goal: { $goal } pomodoros ({ $done } done since { $since })
+review-estimates = 🎯 Estimates: { $accuracy }% accurate over { $days ->
+    [one] { $days } planned day
+   *[other] { $days } planned days
+}
+review-plan-day = { $day }: { $done } of { $planned } planned · { $accuracy }%
+saved-file = Saved { $path }
+error-saving-file = Error saving { $path }: { $error }
+error-reading-file = Error reading { $path }: { $error }
+imported-sessions = Imported { $count ->
+    [one] { $count } session
+   *[other] { $count } sessions
+}
+backup-intact = The backup is intact, with { $count ->
+    [one] { $count } session
+   *[other] { $count } sessions
+}
+backup-dir-error = Couldn't create { $dir }: { $error }
+backup-failed = Couldn't back up the database
+backup-damaged = The backup is damaged: { $error }
+backup-not-ours = That isn't a roth-pomodoro database
+backup-missing = { $path } doesn't exist
+restore-failed = Couldn't restore { $path }
+restored-backup = Restored { $path }. The previous data was saved to { $previous }.
+quit-after-restore = Quit to Load the Backup
+import-invalid-json = The file isn't valid JSON: { $error }
+import-no-rounds = No list of rounds found
+import-empty-file = The file is empty
+import-no-time-columns = No start or end time column found
+import-toggl-columns = Expected Toggl's Start date, Start time and Duration columns
+telegram-not-running = The timer is not running.
+telegram-status = { $period }: { $remaining } left ({ $state }), { $count ->
+    [one] { $count } pomodoro
+   *[other] { $count } pomodoros
+} done
+telegram-state-idle = not started
+telegram-state-running = running
+telegram-state-paused = paused
+telegram-state-ringing = ringing
+github-token-rejected = GitHub rejected the token
+todoist-token-rejected = Todoist rejected the token
+github-issue-not-found = GitHub didn't find the issue, or the token can't see it
+github-http-error = GitHub answered HTTP { $code }
+copied-to-clipboard = Copied to the clipboard
+invalid-settings = Invalid settings. Use positive durations in minutes or mm:ss, a valid long break pattern, 0-100 for volumes, an ambience file if chosen, YYYY-MM-DD holidays, a port up to 65535, a scale of 80-200% and alarm beeps that fit in the alarm's length.
+profile-needs-name = Give the profile a name.
+profile-invalid-settings = Fix the settings above before saving them as a profile.
+team-lost-connection = Lost the connection to { $host }
+history-length-error = The length must be a positive number of minutes.
+plan-error = Every task needs a name and an estimate of at least one pomodoro.
+sequence-error = Invalid sequence. Use positive lengths in minutes or mm:ss and include at least one work period.
+review-goal-error = Invalid goal. Use a positive number of pomodoros.
+work-time = 🍅 Work Time
+long-break = ☕ Long Break
+short-break = ☕ Short Break
+period-work = Work
+period-short-break = Short break
+period-long-break = Long break
+corner-top-left = Top left
+corner-top-right = Top right
+corner-bottom-left = Bottom left
+corner-bottom-right = Bottom right
+completion-elapsed = When the time is up
+completion-acknowledged = When the alarm is acknowledged
+completion-confirmed = When I confirm I focused
+quiet-hours = 🤫 Quiet Hours
+quiet-hours-hint = Times of day without alarms, notifications or automatic starts, e.g. 12:00-13:00, 22:00-07:00. Quarter hours only.
+quiet-hours-error = Use ranges like 12:00-13:00 on quarter hours, separated by commas.
+lock-keep-running = Keep running
+lock-pause = Pause, ask me on unlock
+lock-pause-and-resume = Pause, resume on unlock
+off = Off
+ambient-white-noise = White noise
+ambient-pink-noise = Pink noise (rain-like)
+ambient-file = Audio file (rain, café…)
+offer-issue-comment = Post "{ $comment }" to { $issue }?
+issue-comment-body = 🍅 Spent { $pomodoros ->
+    [one] { $pomodoros } pomodoro
+   *[other] { $pomodoros } pomodoros
+} on this
+post = Post
+not-now = Not now
+posting-issue-comment = Posting to { $issue }…
+issue-comment-posted = Comment posted
+github-token-needed = Add a GitHub token in settings to post comments
+show-issue-title = Show title of { $issue }
+active-task-issue = GitHub issue
+active-task-issue-linked = Linked to { $issue }. Every { $every } pomodoros you're offered to comment there.
+active-task-issue-hint = Paste the issue's address, unless the task's title already has one.
+todoist-comment = Post a comment
+todoist-complete = Complete the task
+monday = Monday
+tuesday = Tuesday
+wednesday = Wednesday
+thursday = Thursday
+friday = Friday
+saturday = Saturday
+sunday = Sunday
+schedule-no-change = No change
+import-summary = { $count ->
+    [one] { $count } new session
+   *[other] { $count } new sessions
+}{ $range }. { $duplicates } already recorded, { $skipped } skipped.
+import-range = , { $first } to { $last }
@@ -0,0 +1,156 @@
+use crate::i18n::t;
+use chrono::{Duration, NaiveDate};
+use std::collections::{HashMap, HashSet};
+
+// Badges earned from the session history. They are worked out from the
+// pomodoros per day each time, and only remembered so that each one is
+// announced once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstPomodoro,
+    Hundred,
+    WeekStreak,
+    TenInADay,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::FirstPomodoro,
+        Achievement::Hundred,
+        Achievement::WeekStreak,
+        Achievement::TenInADay,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Achievement::FirstPomodoro => "first_pomodoro",
+            Achievement::Hundred => "hundred",
+            Achievement::WeekStreak => "week_streak",
+            Achievement::TenInADay => "ten_in_a_day",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|achievement| achievement.key() == key)
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Achievement::FirstPomodoro => t("achievement-first-pomodoro"),
+            Achievement::Hundred => t("achievement-hundred"),
+            Achievement::WeekStreak => t("achievement-week-streak"),
+            Achievement::TenInADay => t("achievement-ten-in-a-day"),
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::FirstPomodoro => t("achievement-first-pomodoro-hint"),
+            Achievement::Hundred => t("achievement-hundred-hint"),
+            Achievement::WeekStreak => t("achievement-week-streak-hint"),
+            Achievement::TenInADay => t("achievement-ten-in-a-day-hint"),
+        }
+    }
+
+    // How far along the badge is, as (done, needed)
+    pub fn progress(self, record: &Record) -> (u32, u32) {
+        let (done, needed) = match self {
+            Achievement::FirstPomodoro => (record.total, 1),
+            Achievement::Hundred => (record.total, 100),
+            Achievement::WeekStreak => (record.longest_streak, 7),
+            Achievement::TenInADay => (record.best_day, 10),
+        };
+        (done.min(needed), needed)
+    }
+
+    pub fn is_earned(self, record: &Record) -> bool {
+        let (done, needed) = self.progress(record);
+        done >= needed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Record {
+    pub total: u32,
+    pub best_day: u32,
+    // Most consecutive days with a pomodoro, with holidays skipped as they
+    // are for the current streak
+    pub longest_streak: u32,
+}
+
+impl Record {
+    pub fn from_days(days: &HashMap<NaiveDate, u32>, holidays: &HashSet<NaiveDate>) -> Self {
+        let mut active: Vec<NaiveDate> = days
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(day, _)| *day)
+            .collect();
+        active.sort();
+
+        let mut longest_streak = 0;
+        let mut streak = 0;
+        let mut last: Option<NaiveDate> = None;
+        for day in active {
+            let continues = last.is_some_and(|last| {
+                let mut gap = last + Duration::days(1);
+                while gap < day && holidays.contains(&gap) {
+                    gap += Duration::days(1);
+                }
+                gap == day
+            });
+            streak = if continues { streak + 1 } else { 1 };
+            longest_streak = longest_streak.max(streak);
+            last = Some(day);
+        }
+
+        Self {
+            total: days.values().sum(),
+            best_day: days.values().copied().max().unwrap_or_default(),
+            longest_streak,
+        }
+    }
+}
+
+#[cfg(test)]
+mod achievements_tests {
+    use super::*;
+    use crate::streak::parse_day;
+
+    #[test]
+    fn badges_follow_the_days_worked() {
+        let days: HashMap<NaiveDate, u32> = [
+            ("2026-03-02", 4),
+            ("2026-03-03", 10),
+            ("2026-03-04", 2),
+            ("2026-03-06", 1),
+            ("2026-03-09", 3),
+        ]
+        .iter()
+        .map(|(day, count)| (parse_day(day).unwrap(), *count))
+        .collect();
+
+        let record = Record::from_days(&days, &HashSet::new());
+        assert_eq!(
+            record,
+            Record {
+                total: 20,
+                best_day: 10,
+                longest_streak: 3,
+            }
+        );
+        assert!(Achievement::FirstPomodoro.is_earned(&record));
+        assert!(Achievement::TenInADay.is_earned(&record));
+        assert!(!Achievement::WeekStreak.is_earned(&record));
+        assert_eq!(Achievement::Hundred.progress(&record), (20, 100));
+
+        // Holidays bridge the gaps, as they do for the current streak
+        let holidays = ["2026-03-05", "2026-03-07", "2026-03-08"]
+            .iter()
+            .filter_map(|day| parse_day(day))
+            .collect();
+        let record = Record::from_days(&days, &holidays);
+        assert_eq!(record.longest_streak, 5);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::instance::Command;
+use iced::futures::{SinkExt, Stream, channel::mpsc};
+use serde::Serialize;
+use std::{net::Ipv4Addr, sync::LazyLock, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{
+        TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::watch,
+};
+
+// Snapshot of the timer served by `GET /status` and streamed by
+// `GET /events`. The timer publishes one after every update while the
+// server is enabled; only actual changes reach listeners, which in practice
+// means once a second while running and on every transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Status {
+    pub state: &'static str,
+    pub period: &'static str,
+    pub remaining_seconds: u32,
+    pub completed_pomodoros: u32,
+    pub guest: bool,
+}
+
+static STATUS: LazyLock<watch::Sender<Option<Status>>> = LazyLock::new(|| watch::Sender::new(None));
+
+// Sent on an idle event stream so proxies and browsers keep it open.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+// A client that is slower than this, or sends more than this before the
+// body, is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+pub fn publish(status: Status) {
+    STATUS.send_if_modified(|current| {
+        if current.as_ref() == Some(&status) {
+            return false;
+        }
+        *current = Some(status);
+        true
+    });
+}
+
+pub fn subscribe() -> watch::Receiver<Option<Status>> {
+    STATUS.subscribe()
+}
+
+fn status_json() -> String {
+    serde_json::to_string(&*STATUS.borrow()).unwrap_or_else(|_| "null".to_string())
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: &'static str, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Self::json(status, serde_json::json!({ "error": message }).to_string())
+    }
+}
+
+enum Route {
+    Reply(Response, Option<Command>),
+    Events,
+    Overlay,
+    Calendar,
+}
+
+// A styled countdown for OBS browser sources, fed by `/events`.
+const OVERLAY_HTML: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/assets/obs-overlay.html"
+));
+
+// Browsers attach an Origin to cross-site requests, including plain form
+// posts that skip CORS preflight. Commands are only taken from scripts and
+// tools (no Origin) or browser extensions, never from ordinary web pages.
+fn trusted_origin(origin: Option<&str>) -> bool {
+    origin.is_none_or(|origin| {
+        [
+            "chrome-extension://",
+            "moz-extension://",
+            "safari-web-extension://",
+        ]
+        .iter()
+        .any(|scheme| origin.starts_with(scheme))
+    })
+}
+
+fn route(method: &str, path: &str, origin: Option<&str>) -> Route {
+    let path = path.split('?').next().unwrap_or_default();
+    let reply = |response| Route::Reply(response, None);
+    match (method, path) {
+        ("GET", "/status") => reply(Response::json("200 OK", status_json())),
+        ("GET", "/events") => Route::Events,
+        ("GET", "/overlay") => Route::Overlay,
+        ("GET", "/calendar.ics") => Route::Calendar,
+        (_, "/status" | "/events" | "/overlay" | "/calendar.ics") => {
+            reply(Response::error("405 Method Not Allowed", "use GET"))
+        }
+        _ => match Command::from_key(path.trim_start_matches('/')) {
+            Some(_) if method != "POST" => {
+                reply(Response::error("405 Method Not Allowed", "use POST"))
+            }
+            Some(_) if !trusted_origin(origin) => reply(Response::error(
+                "403 Forbidden",
+                "web pages can't control the timer",
+            )),
+            Some(command) => Route::Reply(
+                Response::json("200 OK", serde_json::json!({ "ok": true }).to_string()),
+                Some(command),
+            ),
+            None => reply(Response::error("404 Not Found", "unknown endpoint")),
+        },
+    }
+}
+
+// The event stream is read-only, so any page (e.g. an OBS browser source
+// loaded from a file) may subscribe to it.
+async fn stream_events(mut writer: OwnedWriteHalf) -> std::io::Result<()> {
+    writer
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+              Access-Control-Allow-Origin: *\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await?;
+
+    let mut updates = STATUS.subscribe();
+    updates.mark_changed();
+    loop {
+        match tokio::time::timeout(KEEP_ALIVE, updates.changed()).await {
+            Ok(Ok(())) => {
+                let event = format!("event: status\ndata: {}\n\n", status_json());
+                writer.write_all(event.as_bytes()).await?;
+            }
+            Ok(Err(_)) => return Ok(()),
+            Err(_) => writer.write_all(b": keep-alive\n\n").await?,
+        }
+    }
+}
+
+fn document(content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+struct Request {
+    method: String,
+    path: String,
+    origin: Option<String>,
+}
+
+// Reads the request line and headers, and skips any body; none of the
+// endpoints take input. None when the head is cut off or too long.
+async fn read_request(reader: OwnedReadHalf) -> Option<Request> {
+    let mut reader = BufReader::new(reader).take(MAX_HEAD_BYTES);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await.ok()?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default().to_string(),
+        parts.next().unwrap_or_default().to_string(),
+    );
+
+    let mut content_length = 0;
+    let mut origin = None;
+    let mut header = String::new();
+    loop {
+        if reader.read_line(&mut header).await.ok()? == 0 {
+            return None;
+        }
+        let line = header.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("origin") {
+                origin = Some(value.trim().to_string());
+            }
+        }
+        header.clear();
+    }
+    let mut body = vec![0; content_length.min(MAX_BODY_BYTES)];
+    let _ = reader.into_inner().read_exact(&mut body).await;
+
+    Some(Request {
+        method,
+        path,
+        origin,
+    })
+}
+
+async fn serve(stream: TcpStream, mut output: mpsc::Sender<Command>) {
+    let (reader, mut writer) = stream.into_split();
+    let Ok(Some(request)) = tokio::time::timeout(REQUEST_TIMEOUT, read_request(reader)).await
+    else {
+        return;
+    };
+
+    match route(&request.method, &request.path, request.origin.as_deref()) {
+        Route::Events => {
+            let _ = stream_events(writer).await;
+        }
+        Route::Overlay => {
+            let reply = document("text/html", OVERLAY_HTML);
+            let _ = writer.write_all(reply.as_bytes()).await;
+        }
+        Route::Calendar => {
+            let calendar = tokio::task::spawn_blocking(|| {
+                crate::ics::calendar(&crate::db::load_focus_sessions())
+            })
+            .await
+            .unwrap_or_default();
+            let reply = document("text/calendar", &calendar);
+            let _ = writer.write_all(reply.as_bytes()).await;
+        }
+        Route::Reply(response, command) => {
+            let reply = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.status,
+                response.body.len(),
+                response.body
+            );
+            let _ = writer.write_all(reply.as_bytes()).await;
+            if let Some(command) = command {
+                let _ = output.send(command).await;
+            }
+        }
+    }
+}
+
+// A deliberately small HTTP/1.1 server on localhost with one request per
+// connection. Each connection gets its own task so that event streams
+// don't hold up other requests.
+pub fn requests(port: &u32) -> impl Stream<Item = Command> + use<> {
+    let port = *port as u16;
+    iced::stream::channel(8, async move |output| {
+        let listener = match tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                println!("Error starting HTTP API on port {}: {}", port, err);
+                return;
+            }
+        };
+
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve(stream, output.clone()));
+        }
+    })
+}
+
+#[cfg(test)]
+mod api_tests {
+    use super::*;
+
+    #[test]
+    fn commands_need_post_from_a_trusted_origin() {
+        let reply = |method, path, origin| match route(method, path, origin) {
+            Route::Reply(response, command) => (response.status, command),
+            Route::Events => ("events", None),
+            Route::Overlay => ("overlay", None),
+            Route::Calendar => ("calendar", None),
+        };
+
+        assert_eq!(
+            reply("POST", "/skip", None),
+            ("200 OK", Some(Command::Skip))
+        );
+        assert_eq!(
+            reply("GET", "/start", None),
+            ("405 Method Not Allowed", None)
+        );
+        assert_eq!(
+            reply("POST", "/heartbeat", None),
+            ("200 OK", Some(Command::Heartbeat))
+        );
+        assert_eq!(reply("GET", "/status?verbose=1", None).0, "200 OK");
+        assert_eq!(reply("POST", "/status", None).0, "405 Method Not Allowed");
+        assert_eq!(reply("GET", "/events", None).0, "events");
+        assert_eq!(reply("GET", "/overlay", None).0, "overlay");
+        assert_eq!(reply("GET", "/calendar.ics", None).0, "calendar");
+        assert_eq!(reply("GET", "/missing", None).0, "404 Not Found");
+
+        // Cross-site form posts from web pages are refused
+        assert_eq!(
+            reply("POST", "/start", Some("https://example.com")),
+            ("403 Forbidden", None)
+        );
+        assert_eq!(
+            reply("POST", "/start", Some("chrome-extension://abc")).1,
+            Some(Command::Start)
+        );
+    }
+}
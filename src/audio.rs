@@ -1,18 +1,661 @@
-#[cfg(test)]
-mod audio_tests {
-    use std::{thread, time::Duration};
-
-    use rodio;
-
-    #[test]
-    fn play_sound() {
-        let (_stream, stream_handle) = rodio::OutputStream::try_default().unwrap();
-        let source = rodio::source::SineWave::new(440.0);
-        let result = stream_handle.play_raw(source);
-        match result {
-            Ok(_) => println!("Sound played successfully"),
-            Err(err) => println!("Error playing sound: {}", err),
-        }
-        thread::sleep(Duration::from_secs(5));
-    }
-}
+use rodio::{Decoder, OutputStream, Sink, Source};
+use std::{
+    fs::File,
+    io::BufReader,
+    path::PathBuf,
+    sync::mpsc::{self, Sender},
+    thread,
+    time::Duration,
+};
+
+const ALARM_AMPLITUDE: f32 = 0.20;
+const TICK_AMPLITUDE: f32 = 0.15;
+const AMBIENT_AMPLITUDE: f32 = 0.30;
+const NOISE_SAMPLE_RATE: u32 = 48_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ambience {
+    WhiteNoise,
+    PinkNoise,
+    File(PathBuf),
+}
+
+// The built-in tone alarm: `beeps` tones cycling through `tones`, each
+// `beep_ms` long with `gap_ms` of silence between them, and then quiet until
+// `length_ms` is up. Fixed-size so that Settings stays Copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlarmPattern {
+    tones: [u16; AlarmPattern::MAX_TONES],
+    tone_count: usize,
+    pub beeps: u32,
+    pub beep_ms: u32,
+    pub gap_ms: u32,
+    pub length_ms: u32,
+}
+
+impl AlarmPattern {
+    pub const MAX_TONES: usize = 8;
+    pub const MAX_BEEPS: u32 = 12;
+
+    pub fn new(
+        tones: &[u16],
+        beeps: u32,
+        beep_ms: u32,
+        gap_ms: u32,
+        length_ms: u32,
+    ) -> Option<Self> {
+        if tones.is_empty()
+            || tones.len() > Self::MAX_TONES
+            || tones.iter().any(|tone| !(20..=20_000).contains(tone))
+            || !(1..=Self::MAX_BEEPS).contains(&beeps)
+            || !(10..=5_000).contains(&beep_ms)
+            || gap_ms > 10_000
+            || length_ms > 60_000
+        {
+            return None;
+        }
+        let mut pattern = Self {
+            tones: [0; Self::MAX_TONES],
+            tone_count: tones.len(),
+            beeps,
+            beep_ms,
+            gap_ms,
+            length_ms,
+        };
+        pattern.tones[..tones.len()].copy_from_slice(tones);
+        (pattern.beeps_length_ms() <= length_ms).then_some(pattern)
+    }
+
+    pub fn tones(&self) -> &[u16] {
+        &self.tones[..self.tone_count]
+    }
+
+    // From the first beep starting to the last one ending
+    pub fn beeps_length_ms(&self) -> u32 {
+        self.beeps * self.beep_ms + (self.beeps - 1) * self.gap_ms
+    }
+
+    // Tones as typed in settings, e.g. "240, 340, 440"
+    pub fn parse_tones(input: &str) -> Option<Vec<u16>> {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|tone| !tone.is_empty())
+            .map(|tone| tone.parse().ok())
+            .collect()
+    }
+
+    // The stored form, as written by Display. Missing keys keep their
+    // defaults.
+    pub fn parse(input: &str) -> Option<Self> {
+        let default = Self::default();
+        let mut tones = default.tones().to_vec();
+        let (mut beeps, mut beep_ms, mut gap_ms, mut length_ms) = (
+            default.beeps,
+            default.beep_ms,
+            default.gap_ms,
+            default.length_ms,
+        );
+        for (key, value) in input
+            .split_whitespace()
+            .filter_map(|part| part.split_once('='))
+        {
+            match key {
+                "tones" => tones = Self::parse_tones(value)?,
+                "beeps" => beeps = value.parse().ok()?,
+                "beep" => beep_ms = value.parse().ok()?,
+                "gap" => gap_ms = value.parse().ok()?,
+                "length" => length_ms = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        Self::new(&tones, beeps, beep_ms, gap_ms, length_ms)
+    }
+
+    fn steps(&self, amplitude: f32) -> Vec<Step> {
+        let mut steps = Vec::new();
+        for beep in 0..self.beeps {
+            steps.push(Step::Tone(Tone {
+                frequency: self.tones()[beep as usize % self.tone_count] as f32,
+                duration: Duration::from_millis(self.beep_ms as u64),
+                amplitude,
+            }));
+            let gap = if beep + 1 < self.beeps {
+                self.gap_ms
+            } else {
+                self.length_ms - self.beeps_length_ms()
+            };
+            steps.push(Step::Gap(Duration::from_millis(gap as u64)));
+        }
+        steps
+    }
+}
+
+// Three rising beeps a second apart, then a pause
+impl Default for AlarmPattern {
+    fn default() -> Self {
+        Self::new(&[240, 340, 440], 3, 500, 500, 5_000).expect("default alarm pattern is valid")
+    }
+}
+
+impl std::fmt::Display for AlarmPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tones: Vec<String> = self.tones().iter().map(u16::to_string).collect();
+        write!(
+            f,
+            "tones={} beeps={} beep={} gap={} length={}",
+            tones.join(","),
+            self.beeps,
+            self.beep_ms,
+            self.gap_ms,
+            self.length_ms
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AudioCommand {
+    Alarm,
+    // Plays the alarm once at the given volume and pattern, even when
+    // muted, so that unsaved settings can be tried out.
+    Preview(f32, AlarmPattern),
+    Stop,
+    SetVolume(f32),
+    SetAlarmPattern(AlarmPattern),
+    SetMuted(bool),
+    SetTicking(bool),
+    SetTickVolume(f32),
+    SetAmbience(Option<Ambience>),
+    SetAmbientVolume(f32),
+    SetAmbientPlaying(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    pub frequency: f32,
+    pub duration: Duration,
+    pub amplitude: f32,
+}
+
+// Alarm tones and pauses are queued to play one after another, so handing
+// them over never blocks and `stop` cuts them off at once.
+pub trait AudioBackend {
+    fn play_tone(&mut self, tone: Tone);
+    fn pause(&mut self, duration: Duration);
+    fn stop(&mut self);
+    // Starts or stops the looped tick; `None` silences it.
+    fn set_ticking(&mut self, amplitude: Option<f32>);
+    // Loops the given ambience, replacing whatever was playing; `None`
+    // silences it.
+    fn set_ambience(&mut self, ambience: Option<&Ambience>, amplitude: f32);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Tone(Tone),
+    Gap(Duration),
+}
+
+pub struct AudioEngine<B: AudioBackend> {
+    backend: B,
+    volume: f32,
+    pattern: AlarmPattern,
+    muted: bool,
+    ticking: bool,
+    tick_volume: f32,
+    ambience: Option<Ambience>,
+    ambient_volume: f32,
+    ambient_playing: bool,
+}
+
+impl<B: AudioBackend> AudioEngine<B> {
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            volume: 1.0,
+            pattern: AlarmPattern::default(),
+            muted: false,
+            ticking: false,
+            tick_volume: 0.0,
+            ambience: None,
+            ambient_volume: 0.0,
+            ambient_playing: false,
+        }
+    }
+
+    pub fn handle(&mut self, command: AudioCommand) {
+        match command {
+            AudioCommand::Alarm => {
+                if self.muted {
+                    return;
+                }
+                self.play_alarm(self.volume, self.pattern);
+            }
+            AudioCommand::Preview(volume, pattern) => {
+                self.backend.stop();
+                self.play_alarm(volume.clamp(0.0, 1.0), pattern);
+            }
+            AudioCommand::Stop => {
+                self.backend.stop();
+            }
+            AudioCommand::SetVolume(volume) => {
+                self.volume = volume.clamp(0.0, 1.0);
+            }
+            AudioCommand::SetAlarmPattern(pattern) => {
+                self.pattern = pattern;
+            }
+            AudioCommand::SetMuted(muted) => {
+                self.muted = muted;
+                if muted {
+                    self.backend.stop();
+                }
+                self.update_ticking();
+                self.update_ambience();
+            }
+            AudioCommand::SetTicking(ticking) => {
+                self.ticking = ticking;
+                self.update_ticking();
+            }
+            AudioCommand::SetTickVolume(volume) => {
+                self.tick_volume = volume.clamp(0.0, 1.0);
+                self.update_ticking();
+            }
+            AudioCommand::SetAmbience(ambience) => {
+                self.ambience = ambience;
+                self.update_ambience();
+            }
+            AudioCommand::SetAmbientVolume(volume) => {
+                self.ambient_volume = volume.clamp(0.0, 1.0);
+                self.update_ambience();
+            }
+            AudioCommand::SetAmbientPlaying(playing) => {
+                self.ambient_playing = playing;
+                self.update_ambience();
+            }
+        }
+    }
+
+    fn update_ambience(&mut self) {
+        let audible = self.ambient_playing && !self.muted && self.ambient_volume > 0.0;
+        self.backend.set_ambience(
+            self.ambience.as_ref().filter(|_| audible),
+            AMBIENT_AMPLITUDE * self.ambient_volume,
+        );
+    }
+
+    fn play_alarm(&mut self, volume: f32, pattern: AlarmPattern) {
+        for step in pattern.steps(ALARM_AMPLITUDE * volume) {
+            match step {
+                Step::Tone(tone) => self.backend.play_tone(tone),
+                Step::Gap(duration) => self.backend.pause(duration),
+            }
+        }
+    }
+
+    // The tick has its own volume, independent of the alarm's.
+    fn update_ticking(&mut self) {
+        let audible = self.ticking && !self.muted && self.tick_volume > 0.0;
+        self.backend
+            .set_ticking(audible.then_some(TICK_AMPLITUDE * self.tick_volume));
+    }
+}
+
+pub struct RodioBackend {
+    _stream: OutputStream,
+    sink: Sink,
+    tick_sink: Sink,
+    ambient_sink: Sink,
+    ambience: Option<Ambience>,
+    // A file that couldn't be played, not retried until the ambience changes
+    failed: Option<Ambience>,
+}
+
+impl RodioBackend {
+    pub fn try_new() -> Option<Self> {
+        let (stream, stream_handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(err) => {
+                println!("Error initializing sound: {}", err);
+                return None;
+            }
+        };
+        let sink = Sink::try_new(&stream_handle).ok()?;
+        let tick_sink = Sink::try_new(&stream_handle).ok()?;
+        let ambient_sink = Sink::try_new(&stream_handle).ok()?;
+
+        Some(Self {
+            _stream: stream,
+            sink,
+            tick_sink,
+            ambient_sink,
+            ambience: None,
+            failed: None,
+        })
+    }
+}
+
+impl AudioBackend for RodioBackend {
+    fn play_tone(&mut self, tone: Tone) {
+        let source = rodio::source::SineWave::new(tone.frequency)
+            .take_duration(tone.duration)
+            .amplify(tone.amplitude);
+        self.sink.append(source);
+    }
+
+    fn pause(&mut self, duration: Duration) {
+        self.sink
+            .append(rodio::source::Zero::<f32>::new(1, NOISE_SAMPLE_RATE).take_duration(duration));
+    }
+
+    fn stop(&mut self) {
+        self.sink.stop();
+    }
+
+    fn set_ticking(&mut self, amplitude: Option<f32>) {
+        let Some(amplitude) = amplitude else {
+            self.tick_sink.stop();
+            return;
+        };
+        if self.tick_sink.empty() {
+            // A short click once a second, like a kitchen timer
+            let tick = rodio::source::SineWave::new(1200.0)
+                .take_duration(Duration::from_millis(12))
+                .delay(Duration::from_millis(988))
+                .repeat_infinite();
+            self.tick_sink.append(tick);
+        }
+        self.tick_sink.set_volume(amplitude);
+        self.tick_sink.play();
+    }
+
+    fn set_ambience(&mut self, ambience: Option<&Ambience>, amplitude: f32) {
+        let Some(ambience) = ambience else {
+            self.ambient_sink.stop();
+            self.ambience = None;
+            self.failed = None;
+            return;
+        };
+        if self.failed.as_ref() == Some(ambience) {
+            return;
+        }
+
+        if self.ambience.as_ref() != Some(ambience) || self.ambient_sink.empty() {
+            self.ambient_sink.stop();
+            self.ambience = None;
+            match ambience {
+                Ambience::WhiteNoise => {
+                    self.ambient_sink
+                        .append(rodio::source::white(rodio::cpal::SampleRate(
+                            NOISE_SAMPLE_RATE,
+                        )))
+                }
+                Ambience::PinkNoise => {
+                    self.ambient_sink
+                        .append(rodio::source::pink(rodio::cpal::SampleRate(
+                            NOISE_SAMPLE_RATE,
+                        )))
+                }
+                Ambience::File(path) => {
+                    let decoder =
+                        File::open(path)
+                            .map_err(|err| err.to_string())
+                            .and_then(|file| {
+                                Decoder::new_looped(BufReader::new(file))
+                                    .map_err(|err| err.to_string())
+                            });
+                    match decoder {
+                        Ok(decoder) => self.ambient_sink.append(decoder),
+                        Err(err) => {
+                            println!("Error playing {}: {}", path.display(), err);
+                            self.failed = Some(ambience.clone());
+                            return;
+                        }
+                    }
+                }
+            }
+            self.ambience = Some(ambience.clone());
+        }
+        self.ambient_sink.set_volume(amplitude);
+        self.ambient_sink.play();
+    }
+}
+
+pub fn spawn() -> Sender<AudioCommand> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let Some(backend) = RodioBackend::try_new() else {
+            // Keep draining so senders never see a closed channel.
+            for _ in receiver {}
+            return;
+        };
+        let mut engine = AudioEngine::new(backend);
+        for command in receiver {
+            engine.handle(command);
+        }
+    });
+
+    sender
+}
+
+#[cfg(test)]
+mod audio_tests {
+    use std::{thread, time::Duration};
+
+    use rodio;
+
+    use super::{AlarmPattern, Ambience, AudioBackend, AudioCommand, AudioEngine, Tone};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Play(Tone),
+        Pause(Duration),
+        Stop,
+        Ticking(Option<f32>),
+        Ambience(Option<Ambience>),
+    }
+
+    #[derive(Default)]
+    struct FakeBackend {
+        events: Vec<Event>,
+        // Tones and pauses queued and not yet played
+        pending: usize,
+    }
+
+    impl AudioBackend for FakeBackend {
+        fn play_tone(&mut self, tone: Tone) {
+            self.events.push(Event::Play(tone));
+            self.pending += 1;
+        }
+
+        fn pause(&mut self, duration: Duration) {
+            self.events.push(Event::Pause(duration));
+            self.pending += 1;
+        }
+
+        fn stop(&mut self) {
+            self.events.push(Event::Stop);
+            self.pending = 0;
+        }
+
+        fn set_ticking(&mut self, amplitude: Option<f32>) {
+            self.events.push(Event::Ticking(amplitude));
+        }
+
+        fn set_ambience(&mut self, ambience: Option<&Ambience>, _amplitude: f32) {
+            self.events.push(Event::Ambience(ambience.cloned()));
+        }
+    }
+
+    fn played(engine: &AudioEngine<FakeBackend>) -> Vec<Tone> {
+        engine
+            .backend
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Play(tone) => Some(*tone),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Muting also updates the ambience, so the tick isn't the last event
+    fn last_ticking(engine: &AudioEngine<FakeBackend>) -> Option<Option<f32>> {
+        engine
+            .backend
+            .events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                Event::Ticking(amplitude) => Some(*amplitude),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn play_sound() {
+        let (_stream, stream_handle) = rodio::OutputStream::try_default().unwrap();
+        let source = rodio::source::SineWave::new(440.0);
+        let result = stream_handle.play_raw(source);
+        match result {
+            Ok(_) => println!("Sound played successfully"),
+            Err(err) => println!("Error playing sound: {}", err),
+        }
+        thread::sleep(Duration::from_secs(5));
+    }
+
+    #[test]
+    fn alarm_plays_rising_tones_with_gaps() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::Alarm);
+
+        let frequencies: Vec<f32> = played(&engine).iter().map(|t| t.frequency).collect();
+        assert_eq!(frequencies, vec![240.0, 340.0, 440.0]);
+
+        let pauses: Vec<Duration> = engine
+            .backend
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Pause(duration) => Some(*duration),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            pauses,
+            vec![
+                Duration::from_millis(500),
+                Duration::from_millis(500),
+                Duration::from_millis(2500)
+            ]
+        );
+    }
+
+    #[test]
+    fn alarm_follows_a_custom_pattern() {
+        let pattern = AlarmPattern::parse("tones=880,660 beeps=3 beep=200 gap=100 length=2000");
+        assert_eq!(
+            pattern.map(|pattern| pattern.to_string()).as_deref(),
+            Some("tones=880,660 beeps=3 beep=200 gap=100 length=2000")
+        );
+
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetAlarmPattern(pattern.unwrap()));
+        engine.handle(AudioCommand::Alarm);
+        let frequencies: Vec<f32> = played(&engine).iter().map(|t| t.frequency).collect();
+        assert_eq!(frequencies, vec![880.0, 660.0, 880.0]);
+        assert_eq!(
+            engine.backend.events.last(),
+            Some(&Event::Pause(Duration::from_millis(1200)))
+        );
+
+        // Beeps that don't fit in the length
+        assert!(AlarmPattern::new(&[440], 5, 500, 500, 2000).is_none());
+    }
+
+    #[test]
+    fn stop_follows_the_queued_alarm_straight_away() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::Alarm);
+        assert_eq!(engine.backend.pending, 6);
+        engine.handle(AudioCommand::Stop);
+
+        assert_eq!(played(&engine).len(), 3);
+        assert_eq!(engine.backend.events.last(), Some(&Event::Stop));
+        assert_eq!(engine.backend.pending, 0);
+    }
+
+    #[test]
+    fn volume_scales_alarm_amplitude() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetVolume(0.5));
+        engine.handle(AudioCommand::Alarm);
+
+        assert!(
+            played(&engine)
+                .iter()
+                .all(|t| (t.amplitude - 0.10).abs() < f32::EPSILON)
+        );
+    }
+
+    #[test]
+    fn muted_alarm_is_silent() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetMuted(true));
+        engine.handle(AudioCommand::Alarm);
+        assert!(played(&engine).is_empty());
+
+        engine.handle(AudioCommand::SetMuted(false));
+        engine.handle(AudioCommand::Alarm);
+        assert_eq!(played(&engine).len(), 3);
+    }
+
+    #[test]
+    fn preview_plays_at_its_own_volume_even_when_muted() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetMuted(true));
+        engine.handle(AudioCommand::Preview(0.5, AlarmPattern::default()));
+
+        let tones = played(&engine);
+        assert_eq!(tones.len(), 3);
+        assert!(
+            tones
+                .iter()
+                .all(|t| (t.amplitude - 0.10).abs() < f32::EPSILON)
+        );
+    }
+
+    #[test]
+    fn ticking_has_its_own_volume_and_respects_mute() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetVolume(0.2));
+        engine.handle(AudioCommand::SetTickVolume(1.0));
+        engine.handle(AudioCommand::SetTicking(true));
+        assert_eq!(
+            engine.backend.events.last(),
+            Some(&Event::Ticking(Some(0.15)))
+        );
+
+        engine.handle(AudioCommand::SetMuted(true));
+        assert_eq!(last_ticking(&engine), Some(None));
+        // Turning the tick up while muted keeps it quiet
+        engine.handle(AudioCommand::SetTickVolume(0.5));
+        assert_eq!(engine.backend.events.last(), Some(&Event::Ticking(None)));
+    }
+
+    #[test]
+    fn ambience_loops_only_while_playing() {
+        let mut engine = AudioEngine::new(FakeBackend::default());
+        engine.handle(AudioCommand::SetAmbientVolume(0.5));
+        engine.handle(AudioCommand::SetAmbience(Some(Ambience::PinkNoise)));
+        assert_eq!(engine.backend.events.last(), Some(&Event::Ambience(None)));
+
+        engine.handle(AudioCommand::SetAmbientPlaying(true));
+        assert_eq!(
+            engine.backend.events.last(),
+            Some(&Event::Ambience(Some(Ambience::PinkNoise)))
+        );
+
+        engine.handle(AudioCommand::SetAmbientPlaying(false));
+        assert_eq!(engine.backend.events.last(), Some(&Event::Ambience(None)));
+    }
+}
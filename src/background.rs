@@ -0,0 +1,148 @@
+use crate::settings::PeriodKind;
+use iced::{
+    Color, ContentFit, Element, Length, Radians, gradient,
+    widget::{Space, container, image, stack},
+};
+use std::f32::consts::FRAC_PI_4;
+
+// What's painted behind the timer during a period: a picture from the
+// user's files, or failing that a gradient between two colors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Background {
+    pub image_path: String,
+    // Two colors, "#rrggbb, #rrggbb"
+    pub gradient: String,
+}
+
+impl Background {
+    pub fn gradient_colors(&self) -> Option<(Color, Color)> {
+        let (from, to) = self.gradient.split_once(',')?;
+        Some((parse_hex(from)?, parse_hex(to)?))
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.gradient.trim().is_empty() || self.gradient_colors().is_some()
+    }
+
+    fn has_image(&self) -> bool {
+        !self.image_path.is_empty() && std::path::Path::new(&self.image_path).exists()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Backgrounds {
+    pub work: Background,
+    pub short_break: Background,
+    pub long_break: Background,
+}
+
+impl Backgrounds {
+    pub fn trimmed(&self) -> Self {
+        let trim = |background: &Background| Background {
+            image_path: background.image_path.trim().to_string(),
+            gradient: background.gradient.trim().to_string(),
+        };
+
+        Self {
+            work: trim(&self.work),
+            short_break: trim(&self.short_break),
+            long_break: trim(&self.long_break),
+        }
+    }
+
+    pub fn get(&self, kind: PeriodKind) -> &Background {
+        match kind {
+            PeriodKind::Work => &self.work,
+            PeriodKind::ShortBreak => &self.short_break,
+            PeriodKind::LongBreak => &self.long_break,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: PeriodKind) -> &mut Background {
+        match kind {
+            PeriodKind::Work => &mut self.work,
+            PeriodKind::ShortBreak => &mut self.short_break,
+            PeriodKind::LongBreak => &mut self.long_break,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        PeriodKind::ALL
+            .into_iter()
+            .all(|kind| self.get(kind).is_valid())
+    }
+}
+
+fn parse_hex(text: &str) -> Option<Color> {
+    let hex = text.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |at: usize| u8::from_str_radix(hex.get(at..at + 2)?, 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+// Lays the background under the content, scaled to cover the window, with
+// a veil of `veil` color over it so the timer stays readable. `dim` is how
+// opaque the veil is, from 0 to 1.
+pub fn behind<'a, Message: 'a>(
+    content: Element<'a, Message>,
+    background: &Background,
+    dim: f32,
+    veil: Color,
+) -> Element<'a, Message> {
+    let layer: Element<'a, Message> = if background.has_image() {
+        image(background.image_path.clone())
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    } else if let Some((from, to)) = background.gradient_colors() {
+        container(Space::new())
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(move |_theme| {
+                container::Style::default().background(
+                    gradient::Linear::new(Radians(FRAC_PI_4))
+                        .add_stop(0.0, from)
+                        .add_stop(1.0, to),
+                )
+            })
+            .into()
+    } else {
+        return content;
+    };
+    let veil = container(Space::new())
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(move |_theme| {
+            container::Style::default().background(Color {
+                a: dim.clamp(0.0, 1.0),
+                ..veil
+            })
+        });
+    stack![layer, veil, content].into()
+}
+
+#[cfg(test)]
+mod background_tests {
+    use super::*;
+
+    #[test]
+    fn gradients_need_two_hex_colors() {
+        let mut background = Background {
+            gradient: "#ff0000, #0000FF".to_string(),
+            ..Background::default()
+        };
+        assert_eq!(
+            background.gradient_colors(),
+            Some((Color::from_rgb8(255, 0, 0), Color::from_rgb8(0, 0, 255)))
+        );
+        background.gradient = "#ff0000".to_string();
+        assert!(!background.is_valid());
+        background.gradient = "red, blue".to_string();
+        assert!(!background.is_valid());
+        background.gradient = String::new();
+        assert!(background.is_valid());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::i18n::t;
+use iced::{
+    Alignment::Center,
+    Background, Color, Element, Length, Point,
+    widget::{Column, button, container, row, text},
+    window,
+};
+
+// Without a position the system picks the monitor
+pub fn window_settings(position: Option<Point>) -> window::Settings {
+    window::Settings {
+        position: position.map_or(window::Position::Default, window::Position::Specific),
+        fullscreen: true,
+        decorations: false,
+        transparent: true,
+        level: window::Level::AlwaysOnTop,
+        ..Default::default()
+    }
+}
+
+pub fn view<'a, Message: Clone + 'a>(
+    time_left: u32,
+    color: Color,
+    note: Option<String>,
+    running: bool,
+    start: Message,
+    skip: Message,
+) -> Element<'a, Message> {
+    let content = Column::new()
+        .align_x(Center)
+        .spacing(30)
+        .push(text(t("break-screen-title")).size(48).color(color))
+        .push(text(t("break-screen-message")).size(20).color(Color::WHITE))
+        .push(
+            text(format!("{:02}:{:02}", time_left / 60, time_left % 60))
+                .size(120)
+                .color(color),
+        )
+        // How much longer back-to-back pomodoros made this break
+        .push(note.map(|note| text(note).size(20).color(color)))
+        .push(
+            row![
+                button(text(t("start-break")).size(18))
+                    .padding([12, 24])
+                    .on_press_maybe((!running).then_some(start)),
+                button(text(t("skip-break")).size(18))
+                    .padding([12, 24])
+                    .on_press(skip),
+            ]
+            .spacing(15),
+        );
+
+    container(content)
+        .center(Length::Fill)
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.85))),
+            ..Default::default()
+        })
+        .into()
+}
@@ -0,0 +1,29 @@
+use std::{sync::LazyLock, time::Duration};
+
+// The fast clock runs periods sixty times faster, so a second counts as a
+// minute. It is meant for demos and for trying out period transitions, and
+// is enabled with `--fast-clock` or POMODORO_FAST_CLOCK=1.
+const SPEEDUP: u32 = 60;
+
+static FAST: LazyLock<bool> = LazyLock::new(|| {
+    std::env::args().skip(1).any(|arg| arg == "--fast-clock")
+        || std::env::var("POMODORO_FAST_CLOCK").is_ok_and(|value| !matches!(value.trim(), "" | "0"))
+});
+
+pub fn is_fast() -> bool {
+    *FAST
+}
+
+// How long a stretch of timer time takes on the wall clock
+pub fn wall(timer: Duration) -> Duration {
+    if is_fast() { timer / SPEEDUP } else { timer }
+}
+
+// How much timer time passes in a stretch of wall-clock time
+pub fn timer(wall: Duration) -> Duration {
+    if is_fast() {
+        wall.saturating_mul(SPEEDUP)
+    } else {
+        wall
+    }
+}
@@ -0,0 +1,124 @@
+use crate::settings::Settings;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+fn config_path() -> PathBuf {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(config_home) if !config_home.is_empty() => PathBuf::from(config_home)
+            .join("roth-pomodoro")
+            .join("config.toml"),
+        _ => {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home)
+                .join(".config")
+                .join("roth-pomodoro")
+                .join("config.toml")
+        }
+    }
+}
+
+fn read_table() -> Option<Table> {
+    let contents = std::fs::read_to_string(config_path()).ok()?;
+    match contents.parse::<Table>() {
+        Ok(table) => Some(table),
+        Err(err) => {
+            println!("Error parsing config.toml: {}", err);
+            None
+        }
+    }
+}
+
+// Lengths are in minutes, with a fraction for durations that aren't whole
+// minutes.
+fn duration(table: &Table, key: &str) -> Option<u32> {
+    let minutes = match table.get(key)? {
+        Value::Float(minutes) => *minutes,
+        value => value.as_integer()? as f64,
+    };
+    let seconds = (minutes * 60.0).round();
+    (seconds >= 1.0 && seconds <= u32::MAX as f64).then_some(seconds as u32)
+}
+
+fn minutes_value(seconds: u32) -> Value {
+    if seconds.is_multiple_of(60) {
+        Value::Integer((seconds / 60).into())
+    } else {
+        Value::Float(seconds as f64 / 60.0)
+    }
+}
+
+pub fn apply_overrides(settings: Settings) -> Settings {
+    match read_table() {
+        Some(table) => apply(settings, &table),
+        None => settings,
+    }
+}
+
+// The keys in a config file, also used to share settings between devices.
+pub fn apply(mut settings: Settings, table: &Table) -> Settings {
+    if let Some(seconds) = duration(table, "work_minutes") {
+        settings.work_seconds = seconds;
+    }
+    if let Some(seconds) = duration(table, "short_break_minutes") {
+        settings.short_break_seconds = seconds;
+    }
+    if let Some(seconds) = duration(table, "long_break_minutes") {
+        settings.long_break_seconds = seconds;
+    }
+    // 0 means no long breaks
+    if let Some(every) = table
+        .get("long_break_every")
+        .and_then(Value::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+    {
+        settings.long_break_every = every;
+    }
+    if let Some(volume) = table
+        .get("alarm_volume")
+        .and_then(Value::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+        .filter(|v| *v <= 100)
+    {
+        settings.alarm_volume = volume;
+    }
+
+    settings
+}
+
+// The config file is opt-in: settings are only written back when the user
+// has created one, and unknown keys are left untouched.
+pub fn save_settings(settings: Settings) {
+    let Some(mut table) = read_table() else {
+        return;
+    };
+
+    insert(settings, &mut table);
+    if let Ok(contents) = toml::to_string(&table)
+        && let Err(err) = std::fs::write(config_path(), contents)
+    {
+        println!("Error writing config.toml: {}", err);
+    }
+}
+
+pub fn insert(settings: Settings, table: &mut Table) {
+    table.insert(
+        "work_minutes".to_string(),
+        minutes_value(settings.work_seconds),
+    );
+    table.insert(
+        "short_break_minutes".to_string(),
+        minutes_value(settings.short_break_seconds),
+    );
+    table.insert(
+        "long_break_minutes".to_string(),
+        minutes_value(settings.long_break_seconds),
+    );
+    table.insert(
+        "long_break_every".to_string(),
+        Value::Integer(settings.long_break_every.into()),
+    );
+    table.insert(
+        "alarm_volume".to_string(),
+        Value::Integer(settings.alarm_volume.into()),
+    );
+}
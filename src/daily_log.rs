@@ -0,0 +1,128 @@
+use chrono::{DateTime, Local};
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+pub const DEFAULT_FORMAT: &str = "- {time} 🍅 {minutes} min {tag}";
+
+// Appends a line per completed pomodoro to a notes file. Both the path and
+// the line are templates, so e.g. `~/notes/{date}.md` follows a daily note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyLog {
+    pub path: String,
+    pub format: String,
+}
+
+impl Default for DailyLog {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            format: DEFAULT_FORMAT.to_string(),
+        }
+    }
+}
+
+// Placeholders: {date} (2024-05-31), {time} (14:05, when the period
+// started), {end} (14:30), {minutes}, {duration} (25:00) and {tag}.
+fn render(template: &str, started: DateTime<Local>, duration_seconds: u32, tag: &str) -> String {
+    let ended = started + chrono::Duration::seconds(duration_seconds as i64);
+    template
+        .replace("{date}", &started.format("%Y-%m-%d").to_string())
+        .replace("{time}", &started.format("%H:%M").to_string())
+        .replace("{end}", &ended.format("%H:%M").to_string())
+        .replace("{minutes}", &(duration_seconds / 60).to_string())
+        .replace(
+            "{duration}",
+            &format!("{:02}:{:02}", duration_seconds / 60, duration_seconds % 60),
+        )
+        .replace("{tag}", tag)
+        .trim_end()
+        .to_string()
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    line: String,
+}
+
+static WRITER: OnceLock<Sender<Entry>> = OnceLock::new();
+
+fn spawn_writer() -> Sender<Entry> {
+    let (sender, receiver) = mpsc::channel::<Entry>();
+    thread::spawn(move || {
+        for entry in receiver {
+            let result = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&entry.path)
+                .and_then(|mut file| writeln!(file, "{}", entry.line));
+            if let Err(err) = result {
+                println!("Error appending to {}: {}", entry.path.display(), err);
+            }
+        }
+    });
+
+    sender
+}
+
+pub fn append(log: &DailyLog, ended_at: i64, duration_seconds: u32, tag: &str) {
+    if log.path.is_empty() {
+        return;
+    }
+    let Some(started) = DateTime::from_timestamp(ended_at - duration_seconds as i64, 0) else {
+        return;
+    };
+    let started = started.with_timezone(&Local);
+    let format = if log.format.is_empty() {
+        DEFAULT_FORMAT
+    } else {
+        &log.format
+    };
+    let _ = WRITER.get_or_init(spawn_writer).send(Entry {
+        path: expand_home(&render(&log.path, started, duration_seconds, tag)),
+        line: render(format, started, duration_seconds, tag),
+    });
+}
+
+#[cfg(test)]
+mod daily_log_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn templates_fill_in_the_session() {
+        let started = Local.with_ymd_and_hms(2024, 5, 31, 14, 5, 0).unwrap();
+
+        assert_eq!(
+            render(DEFAULT_FORMAT, started, 25 * 60, "Write report"),
+            "- 14:05 🍅 25 min Write report"
+        );
+        assert_eq!(
+            render(
+                "- [{date} {time}-{end}] {duration} {tag}",
+                started,
+                1500,
+                ""
+            ),
+            "- [2024-05-31 14:05-14:30] 25:00"
+        );
+        assert_eq!(
+            render("notes/{date}.md", started, 1500, ""),
+            "notes/2024-05-31.md"
+        );
+    }
+}
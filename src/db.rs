@@ -1,11 +1,86 @@
-use crate::settings::Settings;
-use rusqlite::{Connection, OptionalExtension};
-use std::path::PathBuf;
+use crate::achievements::Achievement;
+use crate::audio::AlarmPattern;
+use crate::background::Backgrounds;
+use crate::daily_log::DailyLog;
+use crate::experiment::LengthResult;
+use crate::focus_guard::GuardedApp;
+use crate::github::GithubConfig;
+use crate::history::{HistorySession, PAGE_SIZE};
+use crate::i18n::{Language, t, tf};
+use crate::ics::FocusSession;
+use crate::import::ImportedSession;
+use crate::mqtt::MqttConfig;
+use crate::palette::PaletteKind;
+use crate::plan::{DayPlan, PlanItem};
+use crate::profile::Profile;
+use crate::push::{PushConfig, PushService};
+use crate::report::{ReportConfig, ReportSession};
+use crate::review::{WeeklyReview, WeeklyStats};
+use crate::schedule::{DayProfile, Schedule};
+use crate::sequence::SequenceStep;
+use crate::settings::{
+    AmbientSound, BreakScreenPlacement, CadencePattern, CompletionCriteria, FocusGuard,
+    FocusMessages, LongBreakSkip, OverlayCorner, PeriodKind, QuietHours, ScreenLockAction,
+    Settings, TimerFace, WindowGeometry,
+};
+use crate::shortcuts::Shortcuts;
+use crate::stats::{DayTotals, TagFilter};
+use crate::sync::JournalEntry;
+use crate::sync_server::SyncServerConfig;
+use crate::telegram::TelegramConfig;
+use crate::todoist::{TaskAction, TodoistConfig, TodoistTask};
+use crate::toggl::TogglConfig;
+use crate::window_titles::TitleTally;
+use crate::worklog::{WorklogConfig, WorklogEntry, WorklogService, WorklogStatus};
+use chrono::NaiveDate;
+use directories::ProjectDirs;
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, OnceLock,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
 
 const APP_TABLE_SETTINGS: &str = "app_settings";
 const APP_TABLE_COUNTERS: &str = "app_counters";
+const APP_TABLE_SESSIONS: &str = "sessions";
+const APP_TABLE_WEEKLY_REVIEWS: &str = "weekly_reviews";
+const APP_TABLE_FOCUS_MESSAGES: &str = "focus_messages";
+const APP_TABLE_DEVICE: &str = "app_device";
+const APP_TABLE_REMOTE_SOURCES: &str = "remote_sources";
+const APP_TABLE_HOLIDAYS: &str = "holidays";
+const APP_TABLE_PERIOD_SEQUENCE: &str = "period_sequence";
+const APP_TABLE_PROFILES: &str = "profiles";
+const APP_TABLE_WEEKDAY_PROFILES: &str = "weekday_profiles";
+const APP_TABLE_PLAN_ITEMS: &str = "plan_items";
+const APP_TABLE_SERVER_SESSIONS: &str = "server_sessions";
+const APP_TABLE_ACHIEVEMENTS: &str = "achievements";
+const APP_TABLE_EXPERIMENT: &str = "experiment_periods";
+const APP_TABLE_BACKGROUNDS: &str = "period_backgrounds";
+const APP_TABLE_GUARDED_APPS: &str = "focus_guard_apps";
+const APP_TABLE_DISTRACTING_TITLES: &str = "distracting_titles";
+const APP_TABLE_WORKLOG_OUTBOX: &str = "worklog_outbox";
 
-fn db_path() -> PathBuf {
+// Offset of the local time zone from UTC right now, stored with each session
+// so that days are counted as they were experienced, even after travelling.
+pub fn local_offset_seconds() -> i32 {
+    chrono::Local::now().offset().local_minus_utc()
+}
+
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn legacy_db_path() -> PathBuf {
     match std::env::var("XDG_DATA_HOME") {
         Ok(data_home) if !data_home.is_empty() => PathBuf::from(data_home)
             .join("roth-pomodoro")
@@ -21,16 +96,160 @@ fn db_path() -> PathBuf {
     }
 }
 
+// Portable mode keeps the database next to the executable, e.g. on a USB
+// stick. It is enabled with `--portable` or a `portable.txt` beside the binary.
+fn portable_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let requested = std::env::args().skip(1).any(|arg| arg == "--portable");
+    (requested || exe_dir.join("portable.txt").exists()).then_some(exe_dir)
+}
+
+pub fn db_path() -> PathBuf {
+    if let Some(dir) = portable_dir() {
+        return dir.join("roth-pomodoro.sqlite");
+    }
+
+    match ProjectDirs::from("", "", "roth-pomodoro") {
+        Some(dirs) => dirs.data_dir().join("roth-pomodoro.sqlite"),
+        None => legacy_db_path(),
+    }
+}
+
+// Older builds always used the XDG layout, even on Windows and macOS. Move
+// such a database to the platform data directory the first time we see it.
+fn migrate_legacy_db(path: &Path) {
+    let legacy = legacy_db_path();
+    if legacy == path || path.exists() || !legacy.exists() {
+        return;
+    }
+
+    if std::fs::rename(&legacy, path).is_err()
+        && let Err(err) = std::fs::copy(&legacy, path)
+    {
+        println!(
+            "Error migrating database from {}: {}",
+            legacy.display(),
+            err
+        );
+    }
+}
+
 fn open() -> rusqlite::Result<Connection> {
     let path = db_path();
     if let Some(parent) = path.parent() {
         let _ = std::fs::create_dir_all(parent);
     }
+    if portable_dir().is_none() {
+        migrate_legacy_db(&path);
+    }
 
     Connection::open(path)
 }
 
-fn init(conn: &Connection) -> rusqlite::Result<()> {
+fn ensure_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> rusqlite::Result<()> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?1"
+        ))?
+        .exists((column,))?;
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"),
+            (),
+        )?;
+    }
+
+    Ok(())
+}
+
+// Each migration moves the schema up by one version, tracked in SQLite's
+// `user_version` pragma. Append new migrations at the end; never reorder or
+// edit ones that have shipped. The early steps are idempotent because
+// databases created before this runner existed report version 0.
+type Migration = fn(&Connection) -> rusqlite::Result<()>;
+
+const MIGRATIONS: &[Migration] = &[
+    migrate_base_tables,
+    migrate_history_tables,
+    migrate_focus_messages,
+    migrate_alarm_volume,
+    migrate_overlay_settings,
+    migrate_device_id,
+    migrate_remote_sources,
+    migrate_session_offsets,
+    migrate_long_break_pattern,
+    migrate_low_power_threshold,
+    migrate_alarm_repeat,
+    migrate_streak_settings,
+    migrate_completion_criteria,
+    migrate_tick_volume,
+    migrate_ambient_sound,
+    migrate_break_screen,
+    migrate_strict_mode,
+    migrate_period_sequence,
+    migrate_profiles,
+    migrate_weekday_profiles,
+    migrate_screen_lock_action,
+    migrate_window_geometry,
+    migrate_always_on_top,
+    migrate_tray_settings,
+    migrate_api_port,
+    migrate_obs_output_dir,
+    migrate_mqtt,
+    migrate_push,
+    migrate_telegram,
+    migrate_toggl,
+    migrate_todoist,
+    migrate_daily_log,
+    migrate_meeting_calendar,
+    migrate_plan_items,
+    migrate_session_tags,
+    migrate_report,
+    migrate_sync_dir,
+    migrate_sync_server,
+    migrate_language,
+    migrate_announce_transitions,
+    migrate_palette,
+    migrate_ui_scale,
+    migrate_countdown_font,
+    migrate_alarm_pattern,
+    migrate_snooze_minutes,
+    migrate_focus_headers,
+    migrate_eye_breaks,
+    migrate_intentions,
+    migrate_achievements,
+    migrate_experiment,
+    migrate_long_break_skip,
+    migrate_cycle_position,
+    migrate_break_lengths,
+    migrate_adaptive_breaks,
+    migrate_stopwatch,
+    migrate_quiet_hours,
+    migrate_shortcuts,
+    migrate_timer_face,
+    migrate_backgrounds,
+    migrate_break_screen_placement,
+    migrate_keep_screen_on,
+    migrate_do_not_disturb,
+    migrate_focus_guard,
+    migrate_window_titles,
+    migrate_editor_heartbeats,
+    migrate_worklog,
+    migrate_github,
+    migrate_telegram_token_to_keychain,
+    migrate_mqtt_password_to_keychain,
+    migrate_pushover_keys_to_keychain,
+    migrate_toggl_token_to_keychain,
+];
+
+fn migrate_base_tables(conn: &Connection) -> rusqlite::Result<()> {
+    let first_run = Settings::first_run();
+
     conn.execute(
         &format!(
             "CREATE TABLE IF NOT EXISTS {APP_TABLE_SETTINGS} (\
@@ -61,10 +280,10 @@ fn init(conn: &Connection) -> rusqlite::Result<()> {
              VALUES (1, ?1, ?2, ?3, ?4)"
         ),
         (
-            Settings::default().work_seconds,
-            Settings::default().short_break_seconds,
-            Settings::default().long_break_seconds,
-            Settings::default().long_break_every,
+            first_run.work_seconds,
+            first_run.short_break_seconds,
+            first_run.long_break_seconds,
+            first_run.long_break_every,
         ),
     )?;
 
@@ -78,90 +297,3779 @@ fn init(conn: &Connection) -> rusqlite::Result<()> {
     Ok(())
 }
 
-pub fn load_settings() -> Settings {
-    let Ok(conn) = open() else {
-        return Settings::default();
-    };
-    if init(&conn).is_err() {
-        return Settings::default();
+fn migrate_history_tables(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_SESSIONS} (\
+                id INTEGER PRIMARY KEY AUTOINCREMENT,\
+                ended_at INTEGER NOT NULL,\
+                duration_seconds INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_WEEKLY_REVIEWS} (\
+                id INTEGER PRIMARY KEY AUTOINCREMENT,\
+                created_at INTEGER NOT NULL,\
+                completed_pomodoros INTEGER NOT NULL,\
+                focus_seconds INTEGER NOT NULL,\
+                reflection TEXT NOT NULL,\
+                next_week_goal INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_focus_messages(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_FOCUS_MESSAGES} (\
+                period TEXT PRIMARY KEY,\
+                message TEXT NOT NULL,\
+                image_path TEXT NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_alarm_volume(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "alarm_volume",
+        &format!(
+            "INTEGER NOT NULL DEFAULT {}",
+            Settings::DEFAULT_ALARM_VOLUME
+        ),
+    )
+}
+
+fn migrate_overlay_settings(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "overlay_corner",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().overlay_corner.key()
+        ),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "overlay_font_size",
+        &format!(
+            "INTEGER NOT NULL DEFAULT {}",
+            Settings::DEFAULT_OVERLAY_FONT_SIZE
+        ),
+    )
+}
+
+fn migrate_device_id(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_DEVICE} (\
+                id INTEGER PRIMARY KEY CHECK (id = 1),\
+                device_id TEXT NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    conn.execute(
+        &format!("INSERT OR IGNORE INTO {APP_TABLE_DEVICE} (id, device_id) VALUES (1, ?1)"),
+        (uuid::Uuid::new_v4().to_string(),),
+    )?;
+
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "device_id",
+        "TEXT NOT NULL DEFAULT ''",
+    )?;
+    conn.execute(
+        &format!(
+            "UPDATE {APP_TABLE_SESSIONS} \
+             SET device_id = (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1) \
+             WHERE device_id = ''"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_remote_sources(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_REMOTE_SOURCES} (\
+                path TEXT PRIMARY KEY\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+// Sessions have always been stored as UTC unix timestamps; older rows get
+// today's offset as the best available guess.
+fn migrate_session_offsets(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "utc_offset_seconds",
+        &format!("INTEGER NOT NULL DEFAULT {}", local_offset_seconds()),
+    )
+}
+
+fn migrate_long_break_pattern(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "long_break_pattern",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_low_power_threshold(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "low_power_threshold",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_alarm_repeat(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "alarm_repeat_seconds",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_streak_settings(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "day_start_hour",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_HOLIDAYS} (\
+                day TEXT PRIMARY KEY\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_completion_criteria(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "completion_criteria",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().completion_criteria.key()
+        ),
+    )
+}
+
+fn migrate_tick_volume(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "tick_volume",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_ambient_sound(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "ambient_sound",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().ambient_sound.key()
+        ),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "ambient_volume",
+        &format!(
+            "INTEGER NOT NULL DEFAULT {}",
+            Settings::DEFAULT_AMBIENT_VOLUME
+        ),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "ambient_path",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_break_screen(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "break_screen",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_strict_mode(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "strict_mode",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_period_sequence(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_PERIOD_SEQUENCE} (\
+                position INTEGER PRIMARY KEY,\
+                kind TEXT NOT NULL,\
+                seconds INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_profiles(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_PROFILES} (\
+                name TEXT PRIMARY KEY,\
+                work_seconds INTEGER NOT NULL,\
+                short_break_seconds INTEGER NOT NULL,\
+                long_break_seconds INTEGER NOT NULL,\
+                long_break_every INTEGER NOT NULL,\
+                alarm_volume INTEGER NOT NULL,\
+                tick_volume INTEGER NOT NULL,\
+                ambient_sound TEXT NOT NULL,\
+                ambient_volume INTEGER NOT NULL,\
+                ambient_path TEXT NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "active_profile",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_weekday_profiles(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_WEEKDAY_PROFILES} (\
+                weekday INTEGER PRIMARY KEY,\
+                profile TEXT NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "schedule_day",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_screen_lock_action(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "screen_lock_action",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().screen_lock_action.key()
+        ),
+    )
+}
+
+fn migrate_window_geometry(conn: &Connection) -> rusqlite::Result<()> {
+    let size = WindowGeometry::DEFAULT_SIZE;
+    ensure_column(conn, APP_TABLE_SETTINGS, "window_x", "REAL")?;
+    ensure_column(conn, APP_TABLE_SETTINGS, "window_y", "REAL")?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "window_width",
+        &format!("REAL NOT NULL DEFAULT {}", size.width),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "window_height",
+        &format!("REAL NOT NULL DEFAULT {}", size.height),
+    )
+}
+
+fn migrate_always_on_top(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "always_on_top",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_tray_settings(conn: &Connection) -> rusqlite::Result<()> {
+    for column in ["start_minimized", "close_to_tray", "minimize_to_tray"] {
+        ensure_column(
+            conn,
+            APP_TABLE_SETTINGS,
+            column,
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
     }
 
-    let row = conn
-        .query_row(
-            &format!(
-                "SELECT work_seconds, short_break_seconds, long_break_seconds, long_break_every \
-                 FROM {APP_TABLE_SETTINGS} WHERE id = 1"
-            ),
+    Ok(())
+}
+
+fn migrate_api_port(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "api_port",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_obs_output_dir(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "obs_output_dir",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_mqtt(conn: &Connection) -> rusqlite::Result<()> {
+    for (column, definition) in [
+        ("mqtt_broker", "TEXT NOT NULL DEFAULT ''"),
+        ("mqtt_topic_prefix", "TEXT NOT NULL DEFAULT 'roth-pomodoro'"),
+        ("mqtt_username", "TEXT NOT NULL DEFAULT ''"),
+        ("mqtt_password", "TEXT NOT NULL DEFAULT ''"),
+    ] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, definition)?;
+    }
+    Ok(())
+}
+
+fn migrate_push(conn: &Connection) -> rusqlite::Result<()> {
+    for column in [
+        "push_service",
+        "ntfy_topic",
+        "pushover_user",
+        "pushover_token",
+    ] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, "TEXT NOT NULL DEFAULT ''")?;
+    }
+    Ok(())
+}
+
+fn migrate_telegram(conn: &Connection) -> rusqlite::Result<()> {
+    for column in ["telegram_token", "telegram_chat_id"] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, "TEXT NOT NULL DEFAULT ''")?;
+    }
+    Ok(())
+}
+
+// Moves a token saved in plain text before it went to the keychain. The
+// column is only cleared once the keychain has the token.
+fn move_to_keychain(conn: &Connection, column: &str, name: &str) -> rusqlite::Result<()> {
+    let secret: String = conn.query_row(
+        &format!("SELECT {column} FROM {APP_TABLE_SETTINGS} WHERE id = 1"),
+        (),
+        |r| r.get(0),
+    )?;
+    if !secret.is_empty() && crate::secrets::save(name, &secret) {
+        conn.execute(
+            &format!("UPDATE {APP_TABLE_SETTINGS} SET {column} = '' WHERE id = 1"),
             (),
-            |r| {
-                Ok(Settings {
-                    work_seconds: r.get::<_, i64>(0)? as u32,
-                    short_break_seconds: r.get::<_, i64>(1)? as u32,
-                    long_break_seconds: r.get::<_, i64>(2)? as u32,
-                    long_break_every: r.get::<_, i64>(3)? as u32,
-                })
-            },
-        )
-        .optional();
+        )?;
+    }
+    Ok(())
+}
 
-    match row {
-        Ok(Some(settings)) if settings.long_break_every > 0 => settings,
-        _ => Settings::default(),
+fn migrate_toggl(conn: &Connection) -> rusqlite::Result<()> {
+    for column in [
+        "toggl_api_token",
+        "toggl_workspace_id",
+        "toggl_project_id",
+        "toggl_description",
+    ] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, "TEXT NOT NULL DEFAULT ''")?;
     }
+    Ok(())
 }
 
-pub fn save_settings(settings: Settings) {
-    let Ok(conn) = open() else {
-        return;
-    };
-    if init(&conn).is_err() {
-        return;
+fn migrate_todoist(conn: &Connection) -> rusqlite::Result<()> {
+    for (column, definition) in [
+        ("todoist_action", "TEXT NOT NULL DEFAULT 'comment'"),
+        ("todoist_every", "INTEGER NOT NULL DEFAULT 1"),
+        ("todoist_task_id", "TEXT NOT NULL DEFAULT ''"),
+        ("todoist_task_content", "TEXT NOT NULL DEFAULT ''"),
+        ("todoist_task_pomodoros", "INTEGER NOT NULL DEFAULT 0"),
+    ] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, definition)?;
     }
+    Ok(())
+}
+
+fn migrate_daily_log(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "daily_log_path",
+        "TEXT NOT NULL DEFAULT ''",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "daily_log_format",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_meeting_calendar(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "meeting_calendar_url",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
 
-    let _ = conn.execute(
+fn migrate_plan_items(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
         &format!(
-            "UPDATE {APP_TABLE_SETTINGS} \
-             SET work_seconds = ?1, short_break_seconds = ?2, long_break_seconds = ?3, long_break_every = ?4 \
-             WHERE id = 1"
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_PLAN_ITEMS} (\
+                day TEXT NOT NULL,\
+                position INTEGER NOT NULL,\
+                title TEXT NOT NULL,\
+                estimate INTEGER NOT NULL,\
+                actual INTEGER NOT NULL DEFAULT 0,\
+                PRIMARY KEY (day, position)\
+            )"
         ),
-        (
-            settings.work_seconds,
-            settings.short_break_seconds,
-            settings.long_break_seconds,
-            settings.long_break_every,
+        (),
+    )?;
+
+    Ok(())
+}
+
+// What each pomodoro was worked on: the task, planned task or profile
+fn migrate_session_tags(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(conn, APP_TABLE_SESSIONS, "tag", "TEXT NOT NULL DEFAULT ''")
+}
+
+fn migrate_report(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "report_dir",
+        "TEXT NOT NULL DEFAULT ''",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "report_html",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_sync_dir(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "sync_dir",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+// The sync server's copy of every device's sessions, as last pulled
+fn migrate_sync_server(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_SERVER_SESSIONS} (\
+                device_id TEXT NOT NULL,\
+                ended_at INTEGER NOT NULL,\
+                utc_offset_seconds INTEGER NOT NULL,\
+                duration_seconds INTEGER NOT NULL,\
+                tag TEXT NOT NULL DEFAULT '',\
+                written_at INTEGER NOT NULL,\
+                deleted INTEGER NOT NULL DEFAULT 0,\
+                PRIMARY KEY (device_id, ended_at)\
+            )"
         ),
-    );
+        (),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "sync_server_url",
+        "TEXT NOT NULL DEFAULT ''",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "sync_server_cursor",
+        "TEXT NOT NULL DEFAULT ''",
+    )?;
+    // When the settings were last changed here, or by the server
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "settings_updated_at",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
 }
 
-pub fn load_completed_pomodoros() -> u32 {
-    let Ok(conn) = open() else {
-        return 0;
-    };
-    if init(&conn).is_err() {
-        return 0;
-    }
+fn migrate_language(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "language",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
 
-    let row: rusqlite::Result<Option<u32>> = conn
-        .query_row(
-            &format!("SELECT completed_pomodoros FROM {APP_TABLE_COUNTERS} WHERE id = 1"),
-            (),
-            |r| Ok(r.get::<_, i64>(0)? as u32),
-        )
-        .optional();
+fn migrate_announce_transitions(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "announce_transitions",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
 
-    row.ok().flatten().unwrap_or(0)
+fn migrate_palette(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "palette",
+        "TEXT NOT NULL DEFAULT 'standard'",
+    )
 }
 
-pub fn save_completed_pomodoros(completed: u32) {
-    let Ok(conn) = open() else {
-        return;
-    };
-    if init(&conn).is_err() {
-        return;
-    }
+fn migrate_ui_scale(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "ui_scale",
+        "INTEGER NOT NULL DEFAULT 100",
+    )
+}
+
+fn migrate_countdown_font(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "countdown_font",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_alarm_pattern(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "alarm_pattern",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
 
-    let _ = conn.execute(
-        &format!("UPDATE {APP_TABLE_COUNTERS} SET completed_pomodoros = ?1 WHERE id = 1"),
-        (completed,),
-    );
+fn migrate_snooze_minutes(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "snooze_minutes",
+        "INTEGER NOT NULL DEFAULT 5",
+    )
+}
+
+fn migrate_focus_headers(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_FOCUS_MESSAGES,
+        "header",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_eye_breaks(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "eye_breaks",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+// What each pomodoro was meant for, as typed when it started
+fn migrate_intentions(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "ask_intention",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "intention",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_achievements(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_ACHIEVEMENTS} (\
+                key TEXT PRIMARY KEY,\
+                unlocked_at INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn migrate_long_break_skip(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "long_break_skip",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().long_break_skip.key()
+        ),
+    )
+}
+
+// Where the timer is in the long break cycle, so a restart doesn't begin
+// the cycle again
+fn migrate_cycle_position(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_COUNTERS,
+        "work_periods",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_COUNTERS,
+        "long_break_owed",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_break_lengths(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "break_extension_minutes",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    // The break taken after a session's pomodoro, pauses and extensions
+    // included, 0 when none was
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "break_seconds",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_adaptive_breaks(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "adaptive_break_minutes",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+// Stopwatch sessions can be worth several pomodoros, or none
+fn migrate_stopwatch(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "pomodoros",
+        "INTEGER NOT NULL DEFAULT 1",
+    )
+}
+
+fn migrate_quiet_hours(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "quiet_hours",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_shortcuts(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "shortcuts",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_timer_face(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "timer_face",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().timer_face.key()
+        ),
+    )
+}
+
+fn migrate_backgrounds(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_BACKGROUNDS} (\
+                period TEXT PRIMARY KEY,\
+                image_path TEXT NOT NULL,\
+                gradient TEXT NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "background_dim",
+        &format!(
+            "INTEGER NOT NULL DEFAULT {}",
+            Settings::default().background_dim
+        ),
+    )
+}
+
+fn migrate_break_screen_placement(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "break_screen_placement",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().break_screen_placement.key()
+        ),
+    )
+}
+
+fn migrate_keep_screen_on(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "keep_screen_on",
+        "INTEGER NOT NULL DEFAULT 1",
+    )
+}
+
+fn migrate_do_not_disturb(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "do_not_disturb",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_focus_guard(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_GUARDED_APPS} (\
+                name TEXT PRIMARY KEY,\
+                blocked INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "focus_guard",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            Settings::default().focus_guard.key()
+        ),
+    )
+}
+
+fn migrate_window_titles(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_DISTRACTING_TITLES} (\
+                pattern TEXT PRIMARY KEY\
+            )"
+        ),
+        (),
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "track_window_titles",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    // Sampled work time by what the front window showed, both 0 when
+    // titles weren't tracked
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "focused_title_seconds",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "distracted_title_seconds",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_editor_heartbeats(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "editor_heartbeats",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(
+        conn,
+        APP_TABLE_SESSIONS,
+        "idle",
+        "INTEGER NOT NULL DEFAULT 0",
+    )
+}
+
+fn migrate_worklog(conn: &Connection) -> rusqlite::Result<()> {
+    for column in [
+        "worklog_jira_url",
+        "worklog_jira_email",
+        "worklog_clockify_workspace",
+        "worklog_task_key",
+    ] {
+        ensure_column(conn, APP_TABLE_SETTINGS, column, "TEXT NOT NULL DEFAULT ''")?;
+    }
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "worklog_service",
+        &format!(
+            "TEXT NOT NULL DEFAULT '{}'",
+            WorklogService::default().key()
+        ),
+    )?;
+    // Worklogs not yet accepted by the service, kept across restarts
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_WORKLOG_OUTBOX} (\
+                id INTEGER PRIMARY KEY AUTOINCREMENT,\
+                service TEXT NOT NULL,\
+                started_at INTEGER NOT NULL,\
+                duration_seconds INTEGER NOT NULL,\
+                task_key TEXT NOT NULL,\
+                comment TEXT NOT NULL,\
+                attempts INTEGER NOT NULL DEFAULT 0,\
+                last_error TEXT NOT NULL DEFAULT ''\
+            )"
+        ),
+        (),
+    )?;
+    Ok(())
+}
+
+fn migrate_github(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "github_every",
+        &format!("INTEGER NOT NULL DEFAULT {}", GithubConfig::default().every),
+    )?;
+    // The issue linked to the active task by hand, when its title has none
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "todoist_task_issue",
+        "TEXT NOT NULL DEFAULT ''",
+    )
+}
+
+fn migrate_telegram_token_to_keychain(conn: &Connection) -> rusqlite::Result<()> {
+    move_to_keychain(conn, "telegram_token", crate::telegram::TOKEN_NAME)
+}
+
+fn migrate_mqtt_password_to_keychain(conn: &Connection) -> rusqlite::Result<()> {
+    move_to_keychain(conn, "mqtt_password", crate::mqtt::PASSWORD_NAME)
+}
+
+fn migrate_pushover_keys_to_keychain(conn: &Connection) -> rusqlite::Result<()> {
+    move_to_keychain(conn, "pushover_user", crate::push::PUSHOVER_USER_NAME)?;
+    move_to_keychain(conn, "pushover_token", crate::push::PUSHOVER_TOKEN_NAME)
+}
+
+fn migrate_toggl_token_to_keychain(conn: &Connection) -> rusqlite::Result<()> {
+    move_to_keychain(conn, "toggl_api_token", crate::toggl::TOKEN_NAME)
+}
+
+fn migrate_experiment(conn: &Connection) -> rusqlite::Result<()> {
+    ensure_column(
+        conn,
+        APP_TABLE_SETTINGS,
+        "experiment_work_seconds",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {APP_TABLE_EXPERIMENT} (\
+                ended_at INTEGER NOT NULL,\
+                work_seconds INTEGER NOT NULL,\
+                finished INTEGER NOT NULL\
+            )"
+        ),
+        (),
+    )?;
+
+    Ok(())
+}
+
+fn schema_version(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.query_row("PRAGMA user_version", (), |r| r.get::<_, i64>(0))
+        .map(|version| version as usize)
+}
+
+fn init(conn: &Connection) -> rusqlite::Result<()> {
+    let version = schema_version(conn)?;
+    if version > MIGRATIONS.len() {
+        println!(
+            "Database schema version {} is newer than this build supports ({})",
+            version,
+            MIGRATIONS.len()
+        );
+        return Ok(());
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.unchecked_transaction()?;
+        migration(&tx)?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+// All access goes through one lazily opened connection in WAL mode. The
+// schema is migrated once when it is opened rather than on every call.
+static CONNECTION: OnceLock<Option<Mutex<Connection>>> = OnceLock::new();
+
+fn connect() -> rusqlite::Result<Connection> {
+    let conn = open()?;
+    conn.query_row("PRAGMA journal_mode = WAL", (), |_| Ok(()))?;
+    conn.busy_timeout(Duration::from_secs(5))?;
+    init(&conn)?;
+    Ok(conn)
+}
+
+fn with_connection<T>(f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Option<T> {
+    with_connection_mut(|conn| f(conn))
+}
+
+fn with_connection_mut<T>(f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Option<T> {
+    let conn = CONNECTION
+        .get_or_init(|| match connect() {
+            Ok(conn) => Some(Mutex::new(conn)),
+            Err(err) => {
+                println!("Error opening database: {}", err);
+                None
+            }
+        })
+        .as_ref()?;
+    let mut conn = conn.lock().ok()?;
+
+    match f(&mut conn) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            println!("Database error: {}", err);
+            None
+        }
+    }
+}
+
+// Writes are queued to a worker thread so that `update()` never waits on
+// the disk.
+enum WriteCommand {
+    Settings(Settings),
+    CompletedPomodoros(u32),
+    CyclePosition(u32, bool),
+    BreakLength(i64, u32),
+    Snooze(i64, u32),
+    TitleTally(i64, TitleTally),
+    IdleSession(i64),
+    Session {
+        ended_at: i64,
+        utc_offset_seconds: i32,
+        duration_seconds: u32,
+        tag: String,
+        intention: String,
+        pomodoros: u32,
+    },
+    WeeklyReview(WeeklyReview),
+    FocusMessages(FocusMessages),
+    Backgrounds(Backgrounds),
+    GuardedApps(Vec<GuardedApp>),
+    DistractingTitles(Vec<String>),
+    RemoteSources(Vec<String>),
+    Holidays(Vec<NaiveDate>),
+    Achievement(Achievement, i64),
+    ExperimentPeriod(u32, bool),
+    AmbientPath(String),
+    CountdownFont(String),
+    ObsOutputDir(String),
+    Mqtt(MqttConfig),
+    Push(PushConfig),
+    Telegram(TelegramConfig),
+    Toggl(TogglConfig),
+    Worklog(WorklogConfig),
+    QueueWorklog(WorklogEntry),
+    RemoveWorklog(i64),
+    WorklogFailed(i64, String),
+    Todoist(TodoistConfig),
+    Github(GithubConfig),
+    ActiveTaskIssue(String),
+    DailyLog(DailyLog),
+    MeetingCalendarUrl(String),
+    Report(ReportConfig),
+    SyncDir(String),
+    SyncServer(SyncServerConfig),
+    ServerSessions(Option<String>, Vec<JournalEntry>),
+    SettingsUpdatedAt(i64),
+    UpdateSession(HistorySession),
+    DeleteSession(i64),
+    ImportSessions(Vec<ImportedSession>),
+    Plan(NaiveDate, Vec<PlanItem>),
+    ActiveTask(Option<TodoistTask>, u32),
+    PeriodSequence(Vec<SequenceStep>),
+    Profile(Profile),
+    DeleteProfile(String),
+    ActiveProfile(String),
+    Schedule(Schedule),
+    WindowGeometry(WindowGeometry),
+    ScheduleDay(NaiveDate),
+    Flush(Sender<()>),
+}
+
+static WRITER: OnceLock<Sender<WriteCommand>> = OnceLock::new();
+// Set once a backup replaced the database. Writes from the state still on
+// screen would mix old data into the restored one, so they're dropped until
+// the app quits.
+static RESTORED: AtomicBool = AtomicBool::new(false);
+
+fn spawn_writer() -> Sender<WriteCommand> {
+    let (sender, receiver) = mpsc::channel::<WriteCommand>();
+    thread::spawn(move || {
+        for command in receiver {
+            match command {
+                WriteCommand::Settings(settings) => {
+                    with_connection(|conn| write_settings(conn, settings));
+                }
+                WriteCommand::CompletedPomodoros(completed) => {
+                    with_connection(|conn| write_completed_pomodoros(conn, completed));
+                }
+                WriteCommand::BreakLength(work_ended_at, break_seconds) => {
+                    with_connection(|conn| write_break_length(conn, work_ended_at, break_seconds));
+                }
+                WriteCommand::Snooze(work_ended_at, snoozed_seconds) => {
+                    with_connection(|conn| write_snooze(conn, work_ended_at, snoozed_seconds));
+                }
+                WriteCommand::TitleTally(work_ended_at, tally) => {
+                    with_connection(|conn| write_title_tally(conn, work_ended_at, &tally));
+                }
+                WriteCommand::IdleSession(work_ended_at) => {
+                    with_connection(|conn| write_idle_session(conn, work_ended_at));
+                }
+                WriteCommand::CyclePosition(work_periods, long_break_owed) => {
+                    with_connection(|conn| {
+                        write_cycle_position(conn, work_periods, long_break_owed)
+                    });
+                }
+                WriteCommand::Session {
+                    ended_at,
+                    utc_offset_seconds,
+                    duration_seconds,
+                    tag,
+                    intention,
+                    pomodoros,
+                } => {
+                    with_connection(|conn| {
+                        write_session(
+                            conn,
+                            ended_at,
+                            utc_offset_seconds,
+                            duration_seconds,
+                            &tag,
+                            &intention,
+                            pomodoros,
+                        )
+                    });
+                }
+                WriteCommand::WeeklyReview(review) => {
+                    with_connection(|conn| write_weekly_review(conn, &review));
+                }
+                WriteCommand::FocusMessages(messages) => {
+                    with_connection(|conn| write_focus_messages(conn, &messages));
+                }
+                WriteCommand::Backgrounds(backgrounds) => {
+                    with_connection(|conn| write_backgrounds(conn, &backgrounds));
+                }
+                WriteCommand::GuardedApps(apps) => {
+                    with_connection(|conn| write_guarded_apps(conn, &apps));
+                }
+                WriteCommand::DistractingTitles(patterns) => {
+                    with_connection(|conn| write_distracting_titles(conn, &patterns));
+                }
+                WriteCommand::RemoteSources(paths) => {
+                    with_connection(|conn| write_remote_sources(conn, &paths));
+                }
+                WriteCommand::Holidays(days) => {
+                    with_connection(|conn| write_holidays(conn, &days));
+                }
+                WriteCommand::ExperimentPeriod(work_seconds, finished) => {
+                    with_connection(|conn| write_experiment_period(conn, work_seconds, finished));
+                }
+                WriteCommand::Achievement(achievement, unlocked_at) => {
+                    with_connection(|conn| write_achievement(conn, achievement, unlocked_at));
+                }
+                WriteCommand::AmbientPath(path) => {
+                    with_connection(|conn| write_ambient_path(conn, &path));
+                }
+                WriteCommand::CountdownFont(font) => {
+                    with_connection(|conn| write_countdown_font(conn, &font));
+                }
+                WriteCommand::ObsOutputDir(dir) => {
+                    with_connection(|conn| write_obs_output_dir(conn, &dir));
+                }
+                WriteCommand::Mqtt(config) => {
+                    with_connection(|conn| write_mqtt_config(conn, &config));
+                    crate::secrets::save(crate::mqtt::PASSWORD_NAME, &config.password);
+                }
+                WriteCommand::Push(config) => {
+                    with_connection(|conn| write_push_config(conn, &config));
+                    crate::secrets::save(crate::push::PUSHOVER_USER_NAME, &config.pushover_user);
+                    crate::secrets::save(crate::push::PUSHOVER_TOKEN_NAME, &config.pushover_token);
+                }
+                WriteCommand::Telegram(config) => {
+                    with_connection(|conn| write_telegram_chat_id(conn, &config.chat_id));
+                    crate::secrets::save(crate::telegram::TOKEN_NAME, &config.token);
+                }
+                WriteCommand::Toggl(config) => {
+                    with_connection(|conn| write_toggl_config(conn, &config));
+                    crate::secrets::save(crate::toggl::TOKEN_NAME, &config.api_token);
+                }
+                WriteCommand::Worklog(config) => {
+                    with_connection(|conn| write_worklog_config(conn, &config));
+                    crate::secrets::save(crate::worklog::TOKEN_NAME, &config.token);
+                }
+                WriteCommand::QueueWorklog(entry) => {
+                    with_connection(|conn| write_queued_worklog(conn, &entry));
+                }
+                WriteCommand::RemoveWorklog(id) => {
+                    with_connection(|conn| {
+                        conn.execute(
+                            &format!("DELETE FROM {APP_TABLE_WORKLOG_OUTBOX} WHERE id = ?1"),
+                            (id,),
+                        )
+                    });
+                }
+                WriteCommand::WorklogFailed(id, error) => {
+                    with_connection(|conn| {
+                        conn.execute(
+                            &format!(
+                                "UPDATE {APP_TABLE_WORKLOG_OUTBOX} \
+                                 SET attempts = attempts + 1, last_error = ?2 WHERE id = ?1"
+                            ),
+                            (id, &error),
+                        )
+                    });
+                }
+                WriteCommand::Todoist(config) => {
+                    with_connection(|conn| write_todoist_config(conn, &config));
+                    crate::secrets::save(crate::todoist::TOKEN_NAME, &config.token);
+                }
+                WriteCommand::Github(config) => {
+                    with_connection(|conn| {
+                        conn.execute(
+                            &format!(
+                                "UPDATE {APP_TABLE_SETTINGS} SET github_every = ?1 WHERE id = 1"
+                            ),
+                            (config.every,),
+                        )
+                    });
+                    crate::secrets::save(crate::github::TOKEN_NAME, &config.token);
+                }
+                WriteCommand::ActiveTaskIssue(url) => {
+                    with_connection(|conn| {
+                        conn.execute(
+                            &format!(
+                                "UPDATE {APP_TABLE_SETTINGS} SET todoist_task_issue = ?1 WHERE id = 1"
+                            ),
+                            (&url,),
+                        )
+                    });
+                }
+                WriteCommand::DailyLog(log) => {
+                    with_connection(|conn| write_daily_log(conn, &log));
+                }
+                WriteCommand::Plan(day, items) => {
+                    with_connection(|conn| write_plan(conn, day, &items));
+                }
+                WriteCommand::UpdateSession(session) => {
+                    with_connection(|conn| write_session_correction(conn, &session));
+                }
+                WriteCommand::ImportSessions(sessions) => {
+                    with_connection(|conn| write_imported_sessions(conn, &sessions));
+                }
+                WriteCommand::DeleteSession(id) => {
+                    with_connection(|conn| delete_session_row(conn, id));
+                }
+                WriteCommand::SyncDir(dir) => {
+                    with_connection(|conn| write_sync_dir(conn, &dir));
+                }
+                WriteCommand::SyncServer(config) => {
+                    with_connection(|conn| write_sync_server_url(conn, &config.url));
+                    crate::secrets::save(crate::sync_server::TOKEN_NAME, &config.token);
+                }
+                WriteCommand::ServerSessions(cursor, entries) => {
+                    with_connection(|conn| {
+                        write_server_sessions(conn, cursor.as_deref(), &entries)
+                    });
+                }
+                WriteCommand::SettingsUpdatedAt(at) => {
+                    with_connection(|conn| write_settings_updated_at(conn, at));
+                }
+                WriteCommand::Report(config) => {
+                    with_connection(|conn| write_report_config(conn, &config));
+                }
+                WriteCommand::MeetingCalendarUrl(url) => {
+                    with_connection(|conn| write_meeting_calendar_url(conn, &url));
+                }
+                WriteCommand::ActiveTask(task, pomodoros) => {
+                    with_connection(|conn| write_active_task(conn, task.as_ref(), pomodoros));
+                }
+                WriteCommand::PeriodSequence(steps) => {
+                    with_connection(|conn| write_period_sequence(conn, &steps));
+                }
+                WriteCommand::Profile(profile) => {
+                    with_connection(|conn| write_profile(conn, &profile));
+                }
+                WriteCommand::DeleteProfile(name) => {
+                    with_connection(|conn| delete_profile_row(conn, &name));
+                }
+                WriteCommand::ActiveProfile(name) => {
+                    with_connection(|conn| write_active_profile(conn, &name));
+                }
+                WriteCommand::Schedule(schedule) => {
+                    with_connection(|conn| write_schedule(conn, &schedule));
+                }
+                WriteCommand::ScheduleDay(day) => {
+                    with_connection(|conn| write_schedule_day(conn, day));
+                }
+                WriteCommand::WindowGeometry(geometry) => {
+                    with_connection(|conn| write_window_geometry(conn, geometry));
+                }
+                WriteCommand::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    });
+
+    sender
+}
+
+fn send(command: WriteCommand) {
+    if RESTORED.load(Ordering::Relaxed) {
+        return;
+    }
+    let _ = WRITER.get_or_init(spawn_writer).send(command);
+}
+
+// Blocks until every queued write has been applied, e.g. before exiting.
+pub fn flush() {
+    if WRITER.get().is_none() || RESTORED.load(Ordering::Relaxed) {
+        return;
+    }
+    let (done, wait) = mpsc::channel();
+    send(WriteCommand::Flush(done));
+    let _ = wait.recv_timeout(Duration::from_secs(2));
+}
+
+pub fn backup_dir() -> PathBuf {
+    db_path().with_file_name("backups")
+}
+
+fn backup_to(conn: &Connection, path: &Path) -> rusqlite::Result<()> {
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])?;
+    Ok(())
+}
+
+// Copies the database, queued writes included, to a new timestamped file
+// in the backup folder.
+pub fn backup() -> Result<PathBuf, String> {
+    let dir = backup_dir();
+    std::fs::create_dir_all(&dir).map_err(|err| {
+        tf(
+            "backup-dir-error",
+            &[("dir", &dir.display()), ("error", &err)],
+        )
+    })?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    // A restore backs up first, possibly within the same second
+    let path = (1..)
+        .map(|n| match n {
+            1 => dir.join(format!("roth-pomodoro-{}.sqlite", stamp)),
+            n => dir.join(format!("roth-pomodoro-{}-{}.sqlite", stamp, n)),
+        })
+        .find(|path| !path.exists())
+        .unwrap_or_default();
+
+    flush();
+    with_connection(|conn| backup_to(conn, &path))
+        .map(|_| path)
+        .ok_or_else(|| t("backup-failed").to_string())
+}
+
+fn check_backup_file(path: &Path) -> rusqlite::Result<Result<u32, String>> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let integrity: String = conn.query_row("PRAGMA integrity_check", (), |row| row.get(0))?;
+    if integrity != "ok" {
+        return Ok(Err(tf("backup-damaged", &[("error", &integrity)])));
+    }
+    let has_settings: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        params![APP_TABLE_SETTINGS],
+        |row| row.get(0),
+    )?;
+    if !has_settings {
+        return Ok(Err(t("backup-not-ours").to_string()));
+    }
+    // Backups from before sessions were recorded have none to count
+    let sessions = conn
+        .query_row("SELECT COUNT(*) FROM sessions", (), |row| row.get(0))
+        .unwrap_or(0);
+    Ok(Ok(sessions))
+}
+
+// Returns the number of sessions in an intact backup.
+pub fn check_backup(path: &Path) -> Result<u32, String> {
+    if !path.is_file() {
+        return Err(tf("backup-missing", &[("path", &path.display())]));
+    }
+    check_backup_file(path).map_err(|err| {
+        tf(
+            "error-reading-file",
+            &[("path", &path.display()), ("error", &err)],
+        )
+    })?
+}
+
+fn restore_from(conn: &mut Connection, path: &Path) -> rusqlite::Result<()> {
+    conn.restore(rusqlite::DatabaseName::Main, path, None::<fn(_)>)?;
+    // Backups from older versions still need their migrations
+    init(conn)
+}
+
+// Replaces the database with a checked backup, keeping a copy of the
+// current data in the backup folder first. Returns that copy.
+pub fn restore(path: &Path) -> Result<PathBuf, String> {
+    check_backup(path)?;
+    // The copy of the current data includes writes still queued
+    flush();
+    let previous = backup()?;
+    with_connection_mut(|conn| restore_from(conn, path))
+        .map(|_| {
+            RESTORED.store(true, Ordering::Relaxed);
+            previous
+        })
+        .ok_or_else(|| tf("restore-failed", &[("path", &path.display())]))
+}
+
+pub fn load_settings() -> Settings {
+    let row = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT work_seconds, short_break_seconds, long_break_seconds, long_break_every, \
+                    alarm_volume, overlay_corner, overlay_font_size, long_break_pattern, \
+                    low_power_threshold, alarm_repeat_seconds, day_start_hour, \
+                    completion_criteria, tick_volume, ambient_sound, ambient_volume, \
+                    break_screen, strict_mode, screen_lock_action, always_on_top, \
+                    start_minimized, close_to_tray, minimize_to_tray, api_port, language, \
+                    announce_transitions, palette, ui_scale, alarm_pattern, \
+                    snooze_minutes, eye_breaks, ask_intention, experiment_work_seconds, \
+                    long_break_skip, break_extension_minutes, adaptive_break_minutes, \
+                    quiet_hours, shortcuts, timer_face, background_dim, \
+                    break_screen_placement, keep_screen_on, do_not_disturb, focus_guard, \
+                    track_window_titles, editor_heartbeats \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(Settings {
+                work_seconds: r.get::<_, i64>(0)? as u32,
+                short_break_seconds: r.get::<_, i64>(1)? as u32,
+                long_break_seconds: r.get::<_, i64>(2)? as u32,
+                long_break_every: r.get::<_, i64>(3)? as u32,
+                alarm_volume: r.get::<_, i64>(4)? as u32,
+                overlay_corner: OverlayCorner::from_key(&r.get::<_, String>(5)?)
+                    .unwrap_or(Settings::default().overlay_corner),
+                overlay_font_size: r.get::<_, i64>(6)? as u32,
+                long_break_pattern: CadencePattern::parse(&r.get::<_, String>(7)?)
+                    .unwrap_or_default(),
+                low_power_threshold: r.get::<_, i64>(8)? as u32,
+                alarm_repeat_seconds: r.get::<_, i64>(9)? as u32,
+                day_start_hour: r.get::<_, i64>(10)? as u32,
+                completion_criteria: CompletionCriteria::from_key(&r.get::<_, String>(11)?)
+                    .unwrap_or(Settings::default().completion_criteria),
+                tick_volume: r.get::<_, i64>(12)? as u32,
+                ambient_sound: AmbientSound::from_key(&r.get::<_, String>(13)?)
+                    .unwrap_or(Settings::default().ambient_sound),
+                ambient_volume: r.get::<_, i64>(14)? as u32,
+                break_screen: r.get::<_, bool>(15)?,
+                strict_mode: r.get::<_, bool>(16)?,
+                screen_lock_action: ScreenLockAction::from_key(&r.get::<_, String>(17)?)
+                    .unwrap_or(Settings::default().screen_lock_action),
+                always_on_top: r.get::<_, bool>(18)?,
+                start_minimized: r.get::<_, bool>(19)?,
+                close_to_tray: r.get::<_, bool>(20)?,
+                minimize_to_tray: r.get::<_, bool>(21)?,
+                api_port: r.get::<_, i64>(22)? as u32,
+                language: Language::from_key(&r.get::<_, String>(23)?),
+                announce_transitions: r.get::<_, bool>(24)?,
+                palette: PaletteKind::from_key(&r.get::<_, String>(25)?).unwrap_or_default(),
+                ui_scale: r.get::<_, i64>(26)? as u32,
+                alarm_pattern: AlarmPattern::parse(&r.get::<_, String>(27)?).unwrap_or_default(),
+                snooze_minutes: r.get::<_, i64>(28)? as u32,
+                eye_breaks: r.get::<_, bool>(29)?,
+                ask_intention: r.get::<_, bool>(30)?,
+                experiment_work_seconds: r.get::<_, i64>(31)? as u32,
+                long_break_skip: LongBreakSkip::from_key(&r.get::<_, String>(32)?)
+                    .unwrap_or(Settings::default().long_break_skip),
+                break_extension_minutes: r.get::<_, i64>(33)? as u32,
+                adaptive_break_minutes: r.get::<_, i64>(34)? as u32,
+                quiet_hours: QuietHours::parse(&r.get::<_, String>(35)?).unwrap_or_default(),
+                shortcuts: Shortcuts::parse(&r.get::<_, String>(36)?),
+                timer_face: TimerFace::from_key(&r.get::<_, String>(37)?)
+                    .unwrap_or(Settings::default().timer_face),
+                background_dim: r.get::<_, i64>(38)? as u32,
+                break_screen_placement: BreakScreenPlacement::from_key(&r.get::<_, String>(39)?)
+                    .unwrap_or(Settings::default().break_screen_placement),
+                keep_screen_on: r.get::<_, bool>(40)?,
+                do_not_disturb: r.get::<_, bool>(41)?,
+                focus_guard: FocusGuard::from_key(&r.get::<_, String>(42)?)
+                    .unwrap_or(Settings::default().focus_guard),
+                track_window_titles: r.get::<_, bool>(43)?,
+                editor_heartbeats: r.get::<_, bool>(44)?,
+            })
+        })
+        .optional()
+    });
+
+    match row {
+        Some(Some(settings)) if settings.work_seconds > 0 => settings,
+        _ => Settings::default(),
+    }
+}
+
+pub fn save_settings(settings: Settings) {
+    send(WriteCommand::Settings(settings));
+}
+
+fn write_settings(conn: &Connection, settings: Settings) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} \
+         SET work_seconds = ?1, short_break_seconds = ?2, long_break_seconds = ?3, long_break_every = ?4, \
+             alarm_volume = ?5, overlay_corner = ?6, overlay_font_size = ?7, long_break_pattern = ?8, \
+             low_power_threshold = ?9, alarm_repeat_seconds = ?10, \
+             day_start_hour = ?11, completion_criteria = ?12, \
+             tick_volume = ?13, ambient_sound = ?14, ambient_volume = ?15, \
+             break_screen = ?16, strict_mode = ?17, screen_lock_action = ?18, \
+             always_on_top = ?19, start_minimized = ?20, close_to_tray = ?21, \
+             minimize_to_tray = ?22, api_port = ?23, language = ?24, \
+             announce_transitions = ?25, palette = ?26, ui_scale = ?27, alarm_pattern = ?28, \
+             snooze_minutes = ?29, eye_breaks = ?30, ask_intention = ?31, \
+             experiment_work_seconds = ?32, long_break_skip = ?33, \
+             break_extension_minutes = ?34, adaptive_break_minutes = ?35, \
+             quiet_hours = ?36, shortcuts = ?37, timer_face = ?38, background_dim = ?39, \
+             break_screen_placement = ?40, keep_screen_on = ?41, \
+             do_not_disturb = ?42, focus_guard = ?43, track_window_titles = ?44, \
+             editor_heartbeats = ?45 \
+         WHERE id = 1"
+    ))?
+    .execute(params![
+        settings.work_seconds,
+        settings.short_break_seconds,
+        settings.long_break_seconds,
+        settings.long_break_every,
+        settings.alarm_volume,
+        settings.overlay_corner.key(),
+        settings.overlay_font_size,
+        settings.long_break_pattern.to_string(),
+        settings.low_power_threshold,
+        settings.alarm_repeat_seconds,
+        settings.day_start_hour,
+        settings.completion_criteria.key(),
+        settings.tick_volume,
+        settings.ambient_sound.key(),
+        settings.ambient_volume,
+        settings.break_screen,
+        settings.strict_mode,
+        settings.screen_lock_action.key(),
+        settings.always_on_top,
+        settings.start_minimized,
+        settings.close_to_tray,
+        settings.minimize_to_tray,
+        settings.api_port,
+        settings.language.key(),
+        settings.announce_transitions,
+        settings.palette.key(),
+        settings.ui_scale,
+        settings.alarm_pattern.to_string(),
+        settings.snooze_minutes,
+        settings.eye_breaks,
+        settings.ask_intention,
+        settings.experiment_work_seconds,
+        settings.long_break_skip.key(),
+        settings.break_extension_minutes,
+        settings.adaptive_break_minutes,
+        settings.quiet_hours.to_string(),
+        settings.shortcuts.to_string(),
+        settings.timer_face.key(),
+        settings.background_dim,
+        settings.break_screen_placement.key(),
+        settings.keep_screen_on,
+        settings.do_not_disturb,
+        settings.focus_guard.key(),
+        settings.track_window_titles,
+        settings.editor_heartbeats,
+    ])?;
+
+    Ok(())
+}
+
+// The ambience file path lives with the other settings but outside the
+// Copy `Settings` struct.
+pub fn load_ambient_path() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT ambient_path FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_ambient_path(path: String) {
+    send(WriteCommand::AmbientPath(path));
+}
+
+fn write_ambient_path(conn: &Connection, path: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET ambient_path = ?1 WHERE id = 1"
+    ))?
+    .execute((path,))?;
+
+    Ok(())
+}
+
+// A family name or font file for the countdown, empty for the default.
+pub fn load_countdown_font() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT countdown_font FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_countdown_font(font: String) {
+    send(WriteCommand::CountdownFont(font));
+}
+
+fn write_countdown_font(conn: &Connection, font: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET countdown_font = ?1 WHERE id = 1"
+    ))?
+    .execute((font,))?;
+
+    Ok(())
+}
+
+// Folder for the OBS text files; empty when the output is off.
+pub fn load_obs_output_dir() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT obs_output_dir FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_obs_output_dir(dir: String) {
+    send(WriteCommand::ObsOutputDir(dir));
+}
+
+fn write_obs_output_dir(conn: &Connection, dir: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET obs_output_dir = ?1 WHERE id = 1"
+    ))?
+    .execute((dir,))?;
+
+    Ok(())
+}
+
+// The password itself lives in the system keychain.
+pub fn load_mqtt_config() -> MqttConfig {
+    let config = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT mqtt_broker, mqtt_topic_prefix, mqtt_username \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(MqttConfig {
+                broker: r.get(0)?,
+                topic_prefix: r.get(1)?,
+                username: r.get(2)?,
+                password: String::new(),
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    MqttConfig {
+        password: crate::secrets::load(crate::mqtt::PASSWORD_NAME),
+        ..config
+    }
+}
+
+pub fn save_mqtt_config(config: MqttConfig) {
+    send(WriteCommand::Mqtt(config));
+}
+
+fn write_mqtt_config(conn: &Connection, config: &MqttConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET mqtt_broker = ?1, mqtt_topic_prefix = ?2, \
+         mqtt_username = ?3 WHERE id = 1"
+    ))?
+    .execute(params![config.broker, config.topic_prefix, config.username])?;
+
+    Ok(())
+}
+
+// The Pushover user key and app token live in the system keychain.
+pub fn load_push_config() -> PushConfig {
+    let config = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT push_service, ntfy_topic FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(PushConfig {
+                service: PushService::from_key(&r.get::<_, String>(0)?).unwrap_or_default(),
+                ntfy_topic: r.get(1)?,
+                ..PushConfig::default()
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    PushConfig {
+        pushover_user: crate::secrets::load(crate::push::PUSHOVER_USER_NAME),
+        pushover_token: crate::secrets::load(crate::push::PUSHOVER_TOKEN_NAME),
+        ..config
+    }
+}
+
+pub fn save_push_config(config: PushConfig) {
+    send(WriteCommand::Push(config));
+}
+
+fn write_push_config(conn: &Connection, config: &PushConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET push_service = ?1, ntfy_topic = ?2 WHERE id = 1"
+    ))?
+    .execute(params![config.service.key(), config.ntfy_topic])?;
+
+    Ok(())
+}
+
+// The bot token itself lives in the system keychain.
+pub fn load_telegram_config() -> TelegramConfig {
+    let chat_id = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT telegram_chat_id FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    TelegramConfig {
+        token: crate::secrets::load(crate::telegram::TOKEN_NAME),
+        chat_id,
+    }
+}
+
+pub fn save_telegram_config(config: TelegramConfig) {
+    send(WriteCommand::Telegram(config));
+}
+
+fn write_telegram_chat_id(conn: &Connection, chat_id: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET telegram_chat_id = ?1 WHERE id = 1"
+    ))?
+    .execute((chat_id,))?;
+
+    Ok(())
+}
+
+// The API token itself lives in the system keychain.
+pub fn load_toggl_config() -> TogglConfig {
+    let config = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT toggl_workspace_id, toggl_project_id, toggl_description \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(TogglConfig {
+                api_token: String::new(),
+                workspace_id: r.get(0)?,
+                project_id: r.get(1)?,
+                description: r.get(2)?,
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    TogglConfig {
+        api_token: crate::secrets::load(crate::toggl::TOKEN_NAME),
+        ..config
+    }
+}
+
+pub fn save_toggl_config(config: TogglConfig) {
+    send(WriteCommand::Toggl(config));
+}
+
+fn write_toggl_config(conn: &Connection, config: &TogglConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET toggl_workspace_id = ?1, \
+         toggl_project_id = ?2, toggl_description = ?3 WHERE id = 1"
+    ))?
+    .execute(params![
+        config.workspace_id,
+        config.project_id,
+        config.description
+    ])?;
+
+    Ok(())
+}
+
+// The API token itself lives in the system keychain.
+pub fn load_worklog_config() -> WorklogConfig {
+    let config = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT worklog_service, worklog_jira_url, worklog_jira_email, \
+                    worklog_clockify_workspace, worklog_task_key \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(WorklogConfig {
+                service: WorklogService::from_key(&r.get::<_, String>(0)?).unwrap_or_default(),
+                jira_url: r.get(1)?,
+                jira_email: r.get(2)?,
+                clockify_workspace_id: r.get(3)?,
+                task_key: r.get(4)?,
+                token: String::new(),
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    WorklogConfig {
+        token: crate::secrets::load(crate::worklog::TOKEN_NAME),
+        ..config
+    }
+}
+
+pub fn save_worklog_config(config: WorklogConfig) {
+    send(WriteCommand::Worklog(config));
+}
+
+fn write_worklog_config(conn: &Connection, config: &WorklogConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET worklog_service = ?1, worklog_jira_url = ?2, \
+         worklog_jira_email = ?3, worklog_clockify_workspace = ?4, worklog_task_key = ?5 \
+         WHERE id = 1"
+    ))?
+    .execute(params![
+        config.service.key(),
+        config.jira_url,
+        config.jira_email,
+        config.clockify_workspace_id,
+        config.task_key
+    ])?;
+
+    Ok(())
+}
+
+pub fn queue_worklog(entry: WorklogEntry) {
+    send(WriteCommand::QueueWorklog(entry));
+}
+
+fn write_queued_worklog(conn: &Connection, entry: &WorklogEntry) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT INTO {APP_TABLE_WORKLOG_OUTBOX} \
+            (service, started_at, duration_seconds, task_key, comment) \
+         VALUES (?1, ?2, ?3, ?4, ?5)"
+    ))?
+    .execute((
+        entry.service.key(),
+        entry.started_at,
+        entry.duration_seconds,
+        &entry.task_key,
+        &entry.comment,
+    ))?;
+
+    Ok(())
+}
+
+// Oldest first, with how many attempts to post each has failed
+pub fn load_worklog_outbox() -> Vec<(i64, WorklogEntry, u32)> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT id, service, started_at, duration_seconds, task_key, comment, attempts \
+             FROM {APP_TABLE_WORKLOG_OUTBOX} ORDER BY id"
+        ))?
+        .query_map((), |r| {
+            Ok((
+                r.get(0)?,
+                WorklogEntry {
+                    service: WorklogService::from_key(&r.get::<_, String>(1)?).unwrap_or_default(),
+                    started_at: r.get(2)?,
+                    duration_seconds: r.get(3)?,
+                    task_key: r.get(4)?,
+                    comment: r.get(5)?,
+                },
+                r.get(6)?,
+            ))
+        })?
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+// How many worklogs are waiting, and the last error of the oldest one that
+// failed
+pub fn load_worklog_status() -> WorklogStatus {
+    with_connection(|conn| {
+        let waiting = conn
+            .prepare_cached(&format!("SELECT COUNT(*) FROM {APP_TABLE_WORKLOG_OUTBOX}"))?
+            .query_row((), |r| r.get::<_, i64>(0))?;
+        let last_error = conn
+            .prepare_cached(&format!(
+                "SELECT last_error FROM {APP_TABLE_WORKLOG_OUTBOX} \
+                 WHERE attempts > 0 ORDER BY id LIMIT 1"
+            ))?
+            .query_row((), |r| r.get::<_, String>(0))
+            .optional()?
+            .unwrap_or_default();
+        Ok(WorklogStatus {
+            waiting: waiting as usize,
+            last_error,
+        })
+    })
+    .unwrap_or_default()
+}
+
+pub fn remove_worklog(id: i64) {
+    send(WriteCommand::RemoveWorklog(id));
+}
+
+pub fn worklog_failed(id: i64, error: &str) {
+    send(WriteCommand::WorklogFailed(id, error.to_string()));
+}
+
+// The API token itself lives in the system keychain.
+pub fn load_todoist_config() -> TodoistConfig {
+    let config = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT todoist_action, todoist_every FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(TodoistConfig {
+                action: TaskAction::from_key(&r.get::<_, String>(0)?).unwrap_or_default(),
+                every: r.get::<_, u32>(1)?.max(1),
+                ..TodoistConfig::default()
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    TodoistConfig {
+        token: crate::secrets::load(crate::todoist::TOKEN_NAME),
+        ..config
+    }
+}
+
+pub fn save_todoist_config(config: TodoistConfig) {
+    send(WriteCommand::Todoist(config));
+}
+
+fn write_todoist_config(conn: &Connection, config: &TodoistConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET todoist_action = ?1, todoist_every = ?2 WHERE id = 1"
+    ))?
+    .execute((config.action.key(), config.every))?;
+
+    Ok(())
+}
+
+// The access token itself lives in the system keychain.
+pub fn load_github_config() -> GithubConfig {
+    let every = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT github_every FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, u32>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or(GithubConfig::default().every);
+
+    GithubConfig {
+        token: crate::secrets::load(crate::github::TOKEN_NAME),
+        every: every.max(1),
+    }
+}
+
+pub fn save_github_config(config: GithubConfig) {
+    send(WriteCommand::Github(config));
+}
+
+pub fn load_active_task_issue() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT todoist_task_issue FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_active_task_issue(url: String) {
+    send(WriteCommand::ActiveTaskIssue(url));
+}
+
+pub fn load_daily_log() -> DailyLog {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT daily_log_path, daily_log_format FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            let format: String = r.get(1)?;
+            Ok(DailyLog {
+                path: r.get(0)?,
+                format: if format.is_empty() {
+                    crate::daily_log::DEFAULT_FORMAT.to_string()
+                } else {
+                    format
+                },
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_daily_log(log: DailyLog) {
+    send(WriteCommand::DailyLog(log));
+}
+
+fn write_daily_log(conn: &Connection, log: &DailyLog) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET daily_log_path = ?1, daily_log_format = ?2 WHERE id = 1"
+    ))?
+    .execute((&log.path, &log.format))?;
+
+    Ok(())
+}
+
+pub fn load_report_config() -> ReportConfig {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT report_dir, report_html FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok(ReportConfig {
+                dir: r.get(0)?,
+                html: r.get::<_, i64>(1)? != 0,
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_report_config(config: ReportConfig) {
+    send(WriteCommand::Report(config));
+}
+
+fn write_report_config(conn: &Connection, config: &ReportConfig) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET report_dir = ?1, report_html = ?2 WHERE id = 1"
+    ))?
+    .execute((&config.dir, config.html as i64))?;
+
+    Ok(())
+}
+
+fn query_sync_dir(conn: &Connection) -> rusqlite::Result<String> {
+    conn.prepare_cached(&format!(
+        "SELECT sync_dir FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+    ))?
+    .query_row((), |r| r.get::<_, String>(0))
+    .optional()
+    .map(Option::unwrap_or_default)
+}
+
+pub fn load_sync_dir() -> String {
+    with_connection(query_sync_dir).unwrap_or_default()
+}
+
+pub fn save_sync_dir(dir: String) {
+    send(WriteCommand::SyncDir(dir));
+}
+
+fn write_sync_dir(conn: &Connection, dir: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET sync_dir = ?1 WHERE id = 1"
+    ))?
+    .execute((dir,))?;
+
+    Ok(())
+}
+
+pub fn load_sync_server_config() -> SyncServerConfig {
+    let url = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT sync_server_url FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    SyncServerConfig {
+        url,
+        token: crate::secrets::load(crate::sync_server::TOKEN_NAME),
+    }
+}
+
+pub fn save_sync_server_config(config: SyncServerConfig) {
+    send(WriteCommand::SyncServer(config));
+}
+
+fn write_sync_server_url(conn: &Connection, url: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET sync_server_url = ?1 WHERE id = 1"
+    ))?
+    .execute((url,))?;
+
+    Ok(())
+}
+
+pub fn load_server_cursor() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT sync_server_cursor FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+// Every device's sessions as the server last had them, deletions included.
+pub fn load_server_sessions() -> Vec<JournalEntry> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT device_id, ended_at, utc_offset_seconds, duration_seconds, tag, \
+                    written_at, deleted \
+             FROM {APP_TABLE_SERVER_SESSIONS}"
+        ))?
+        .query_map((), |r| {
+            Ok(JournalEntry {
+                device_id: r.get(0)?,
+                ended_at: r.get(1)?,
+                utc_offset_seconds: r.get(2)?,
+                duration_seconds: r.get(3)?,
+                tag: r.get(4)?,
+                written_at: r.get(5)?,
+                deleted: r.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+// Keeps the newest version of each session; the cursor only moves on pulls.
+pub fn save_server_sessions(cursor: Option<String>, entries: Vec<JournalEntry>) {
+    send(WriteCommand::ServerSessions(cursor, entries));
+}
+
+fn write_server_sessions(
+    conn: &Connection,
+    cursor: Option<&str>,
+    entries: &[JournalEntry],
+) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut upsert = tx.prepare_cached(&format!(
+            "INSERT INTO {APP_TABLE_SERVER_SESSIONS} \
+                (device_id, ended_at, utc_offset_seconds, duration_seconds, tag, written_at, deleted) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) \
+             ON CONFLICT (device_id, ended_at) DO UPDATE SET \
+                utc_offset_seconds = excluded.utc_offset_seconds, \
+                duration_seconds = excluded.duration_seconds, \
+                tag = excluded.tag, \
+                written_at = excluded.written_at, \
+                deleted = excluded.deleted \
+             WHERE excluded.written_at >= written_at"
+        ))?;
+        for entry in entries {
+            upsert.execute((
+                &entry.device_id,
+                entry.ended_at,
+                entry.utc_offset_seconds,
+                entry.duration_seconds,
+                &entry.tag,
+                entry.written_at,
+                entry.deleted,
+            ))?;
+        }
+    }
+    if let Some(cursor) = cursor {
+        tx.execute(
+            &format!("UPDATE {APP_TABLE_SETTINGS} SET sync_server_cursor = ?1 WHERE id = 1"),
+            (cursor,),
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn load_settings_updated_at() -> i64 {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT settings_updated_at FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, i64>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_settings_updated_at(at: i64) {
+    send(WriteCommand::SettingsUpdatedAt(at));
+}
+
+fn write_settings_updated_at(conn: &Connection, at: i64) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET settings_updated_at = ?1 WHERE id = 1"
+    ))?
+    .execute((at,))?;
+
+    Ok(())
+}
+
+// This device's id and sessions, as they belong in its sync journal.
+pub fn load_journal_sessions() -> (String, Vec<JournalEntry>) {
+    with_connection(|conn| {
+        let device_id: String = conn.query_row(
+            &format!("SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1"),
+            (),
+            |r| r.get(0),
+        )?;
+        let sessions = conn
+            .prepare_cached(&format!(
+                "SELECT ended_at, utc_offset_seconds, duration_seconds, tag                  FROM {APP_TABLE_SESSIONS} WHERE device_id = ?1 ORDER BY ended_at"
+            ))?
+            .query_map((&device_id,), |r| {
+                Ok(JournalEntry {
+                    device_id: device_id.clone(),
+                    ended_at: r.get(0)?,
+                    utc_offset_seconds: r.get(1)?,
+                    duration_seconds: r.get(2)?,
+                    tag: r.get(3)?,
+                    written_at: 0,
+                    deleted: false,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok((device_id, sessions))
+    })
+    .unwrap_or_default()
+}
+
+pub fn load_meeting_calendar_url() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT meeting_calendar_url FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_meeting_calendar_url(url: String) {
+    send(WriteCommand::MeetingCalendarUrl(url));
+}
+
+fn write_meeting_calendar_url(conn: &Connection, url: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET meeting_calendar_url = ?1 WHERE id = 1"
+    ))?
+    .execute((url,))?;
+
+    Ok(())
+}
+
+// The task pomodoros are credited to, with how many it has had so far.
+pub fn load_active_task() -> (Option<TodoistTask>, u32) {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT todoist_task_id, todoist_task_content, todoist_task_pomodoros \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            let id: String = r.get(0)?;
+            let task = (!id.is_empty()).then(|| TodoistTask {
+                id,
+                content: r.get(1).unwrap_or_default(),
+            });
+            Ok((task, r.get(2)?))
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_active_task(task: Option<TodoistTask>, pomodoros: u32) {
+    send(WriteCommand::ActiveTask(task, pomodoros));
+}
+
+fn write_active_task(
+    conn: &Connection,
+    task: Option<&TodoistTask>,
+    pomodoros: u32,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET todoist_task_id = ?1, todoist_task_content = ?2, \
+         todoist_task_pomodoros = ?3 WHERE id = 1"
+    ))?
+    .execute(params![
+        task.map(|t| t.id.as_str()).unwrap_or_default(),
+        task.map(|t| t.content.as_str()).unwrap_or_default(),
+        pomodoros
+    ])?;
+
+    Ok(())
+}
+
+pub fn load_completed_pomodoros() -> u32 {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT completed_pomodoros FROM {APP_TABLE_COUNTERS} WHERE id = 1"
+        ))?
+        .query_row((), |r| Ok(r.get::<_, i64>(0)? as u32))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or(0)
+}
+
+pub fn save_completed_pomodoros(completed: u32) {
+    send(WriteCommand::CompletedPomodoros(completed));
+}
+
+fn write_completed_pomodoros(conn: &Connection, completed: u32) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_COUNTERS} SET completed_pomodoros = ?1 WHERE id = 1"
+    ))?
+    .execute((completed,))?;
+
+    Ok(())
+}
+
+// Work periods finished in the current cycle, and whether a skipped long
+// break is still owed
+pub fn load_cycle_position() -> (u32, bool) {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT work_periods, long_break_owed FROM {APP_TABLE_COUNTERS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            Ok((r.get::<_, i64>(0)? as u32, r.get::<_, bool>(1)?))
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_cycle_position(work_periods: u32, long_break_owed: bool) {
+    send(WriteCommand::CyclePosition(work_periods, long_break_owed));
+}
+
+fn write_cycle_position(
+    conn: &Connection,
+    work_periods: u32,
+    long_break_owed: bool,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_COUNTERS} SET work_periods = ?1, long_break_owed = ?2 WHERE id = 1"
+    ))?
+    .execute((work_periods, long_break_owed))?;
+
+    Ok(())
+}
+
+pub fn record_session(
+    ended_at: i64,
+    duration_seconds: u32,
+    tag: &str,
+    intention: &str,
+    pomodoros: u32,
+) {
+    send(WriteCommand::Session {
+        ended_at,
+        utc_offset_seconds: local_offset_seconds(),
+        duration_seconds,
+        tag: tag.to_string(),
+        intention: intention.to_string(),
+        pomodoros,
+    });
+}
+
+fn write_session(
+    conn: &Connection,
+    ended_at: i64,
+    utc_offset_seconds: i32,
+    duration_seconds: u32,
+    tag: &str,
+    intention: &str,
+    pomodoros: u32,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT INTO {APP_TABLE_SESSIONS} \
+            (ended_at, utc_offset_seconds, duration_seconds, device_id, tag, intention, \
+             pomodoros) \
+         VALUES (?1, ?2, ?3, (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1), ?4, ?5, \
+                 ?6)"
+    ))?
+    .execute((
+        ended_at,
+        utc_offset_seconds,
+        duration_seconds,
+        tag,
+        intention,
+        pomodoros,
+    ))?;
+
+    Ok(())
+}
+
+// Breaks are kept with the pomodoro before them, found by when it ended
+pub fn record_break(work_ended_at: i64, break_seconds: u32) {
+    send(WriteCommand::BreakLength(work_ended_at, break_seconds));
+}
+
+fn write_break_length(
+    conn: &Connection,
+    work_ended_at: i64,
+    break_seconds: u32,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SESSIONS} SET break_seconds = ?1 \
+         WHERE ended_at = ?2 \
+           AND device_id = (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1)"
+    ))?
+    .execute((break_seconds, work_ended_at))?;
+
+    Ok(())
+}
+
+// Time worked on by snoozing the alarm, added to the session it followed
+pub fn record_snooze(work_ended_at: i64, snoozed_seconds: u32) {
+    send(WriteCommand::Snooze(work_ended_at, snoozed_seconds));
+}
+
+fn write_snooze(
+    conn: &Connection,
+    work_ended_at: i64,
+    snoozed_seconds: u32,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SESSIONS} SET duration_seconds = duration_seconds + ?1 \
+         WHERE ended_at = ?2 \
+           AND device_id = (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1)"
+    ))?
+    .execute((snoozed_seconds, work_ended_at))?;
+
+    Ok(())
+}
+
+// Like breaks, the window title breakdown goes with the session it was
+// sampled in
+pub fn record_title_tally(work_ended_at: i64, tally: TitleTally) {
+    send(WriteCommand::TitleTally(work_ended_at, tally));
+}
+
+fn write_title_tally(
+    conn: &Connection,
+    work_ended_at: i64,
+    tally: &TitleTally,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SESSIONS} \
+         SET focused_title_seconds = ?1, distracted_title_seconds = ?2 \
+         WHERE ended_at = ?3 \
+           AND device_id = (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1)"
+    ))?
+    .execute((
+        tally.focused_seconds,
+        tally.distracted_seconds,
+        work_ended_at,
+    ))?;
+
+    Ok(())
+}
+
+pub fn record_idle_session(work_ended_at: i64) {
+    send(WriteCommand::IdleSession(work_ended_at));
+}
+
+fn write_idle_session(conn: &Connection, work_ended_at: i64) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SESSIONS} SET idle = 1 \
+         WHERE ended_at = ?1 \
+           AND device_id = (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1)"
+    ))?
+    .execute((work_ended_at,))?;
+
+    Ok(())
+}
+
+// Sessions recorded on other machines are read from copies of their
+// databases, the sync server's sessions and the journals in the sync
+// folder, and merged by device id, skipping our own device and duplicates from several copies of the
+// same one.
+fn load_remote_sessions(conn: &Connection, since: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        "CREATE TEMP TABLE IF NOT EXISTS remote_sessions (\
+            device_id TEXT NOT NULL,\
+            ended_at INTEGER NOT NULL,\
+            utc_offset_seconds INTEGER NOT NULL,\
+            duration_seconds INTEGER NOT NULL,\
+            PRIMARY KEY (device_id, ended_at)\
+        )",
+        (),
+    )?;
+    conn.execute("DELETE FROM temp.remote_sessions", ())?;
+
+    let mut insert = conn.prepare_cached(
+        "INSERT OR IGNORE INTO temp.remote_sessions \
+            (device_id, ended_at, utc_offset_seconds, duration_seconds) \
+         VALUES (?1, ?2, ?3, ?4)",
+    )?;
+
+    for path in load_remote_source_paths(conn)? {
+        let remote = match Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY) {
+            Ok(remote) => remote,
+            Err(err) => {
+                println!("Error opening {}: {}", path, err);
+                continue;
+            }
+        };
+
+        let rows = remote
+            .prepare(&format!(
+                "SELECT device_id, ended_at, utc_offset_seconds, duration_seconds \
+                 FROM {APP_TABLE_SESSIONS} WHERE ended_at >= ?1"
+            ))
+            .and_then(|mut statement| {
+                statement
+                    .query_map((since,), |r| {
+                        Ok((
+                            r.get::<_, String>(0)?,
+                            r.get::<_, i64>(1)?,
+                            r.get::<_, i64>(2)?,
+                            r.get::<_, i64>(3)?,
+                        ))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            });
+
+        match rows {
+            Ok(rows) => {
+                for row in rows {
+                    insert.execute(row)?;
+                }
+            }
+            Err(err) => println!("Error reading sessions from {}: {}", path, err),
+        }
+    }
+
+    conn.execute(
+        &format!(
+            "INSERT OR IGNORE INTO temp.remote_sessions \
+                (device_id, ended_at, utc_offset_seconds, duration_seconds) \
+             SELECT device_id, ended_at, utc_offset_seconds, duration_seconds \
+             FROM {APP_TABLE_SERVER_SESSIONS} WHERE deleted = 0 AND ended_at >= ?1"
+        ),
+        (since,),
+    )?;
+
+    let sync_dir = query_sync_dir(conn)?;
+    if !sync_dir.is_empty() {
+        for entry in crate::sync::read(&sync_dir) {
+            if entry.ended_at >= since {
+                insert.execute((
+                    &entry.device_id,
+                    entry.ended_at,
+                    entry.utc_offset_seconds,
+                    entry.duration_seconds,
+                ))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Every session recorded on this device, oldest first.
+pub fn load_focus_sessions() -> Vec<FocusSession> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT ended_at, duration_seconds, device_id FROM {APP_TABLE_SESSIONS} \
+             ORDER BY ended_at"
+        ))?
+        .query_map((), |r| {
+            Ok(FocusSession {
+                ended_at: r.get(0)?,
+                duration_seconds: r.get(1)?,
+                device_id: r.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+// End times of this device's sessions, sorted, for spotting duplicates.
+pub fn load_session_end_times() -> Vec<i64> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT ended_at FROM {APP_TABLE_SESSIONS} ORDER BY ended_at"
+        ))?
+        .query_map((), |r| r.get::<_, i64>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+pub fn import_sessions(sessions: Vec<ImportedSession>) {
+    send(WriteCommand::ImportSessions(sessions));
+}
+
+// Imported sessions get the local offset in effect when they ended.
+fn write_imported_sessions(
+    conn: &Connection,
+    sessions: &[ImportedSession],
+) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    for session in sessions {
+        let offset = chrono::DateTime::from_timestamp(session.ended_at, 0)
+            .map(|at| at.with_timezone(&chrono::Local).offset().local_minus_utc())
+            .unwrap_or_else(local_offset_seconds);
+        write_session(
+            &tx,
+            session.ended_at,
+            offset,
+            session.duration_seconds,
+            &session.tag,
+            "",
+            1,
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn count_sessions() -> usize {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!("SELECT COUNT(*) FROM {APP_TABLE_SESSIONS}"))?
+            .query_row((), |r| r.get::<_, i64>(0))
+    })
+    .unwrap_or_default() as usize
+}
+
+// A page of this device's sessions, newest first.
+pub fn load_history_page(page: usize) -> Vec<HistorySession> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT id, ended_at, duration_seconds, tag, intention, idle FROM {APP_TABLE_SESSIONS} \
+             ORDER BY ended_at DESC, id DESC LIMIT ?1 OFFSET ?2"
+        ))?
+        .query_map((PAGE_SIZE as i64, (page * PAGE_SIZE) as i64), |r| {
+            Ok(HistorySession {
+                id: r.get(0)?,
+                ended_at: r.get(1)?,
+                duration_seconds: r.get(2)?,
+                tag: r.get(3)?,
+                intention: r.get(4)?,
+                idle: r.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+pub fn update_session(session: HistorySession) {
+    send(WriteCommand::UpdateSession(session));
+}
+
+fn write_session_correction(conn: &Connection, session: &HistorySession) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SESSIONS} SET duration_seconds = ?1, tag = ?2 WHERE id = ?3"
+    ))?
+    .execute((session.duration_seconds, &session.tag, session.id))?;
+
+    Ok(())
+}
+
+pub fn delete_session(id: i64) {
+    send(WriteCommand::DeleteSession(id));
+}
+
+fn delete_session_row(conn: &Connection, id: i64) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!("DELETE FROM {APP_TABLE_SESSIONS} WHERE id = ?1"))?
+        .execute((id,))?;
+
+    Ok(())
+}
+
+// Sessions recorded on this device since `since`, for reports.
+pub fn load_report_sessions(since: i64) -> Vec<ReportSession> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT ended_at, utc_offset_seconds, duration_seconds, tag \
+             FROM {APP_TABLE_SESSIONS} WHERE ended_at >= ?1 ORDER BY ended_at"
+        ))?
+        .query_map((since,), |r| {
+            Ok(ReportSession {
+                ended_at: r.get(0)?,
+                utc_offset_seconds: r.get(1)?,
+                duration_seconds: r.get(2)?,
+                tag: r.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+// Narrows session queries to a range of days and a tag. Days are counted
+// the same way as for streaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionFilter {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub tag: TagFilter,
+    pub day_start_hour: u32,
+}
+
+impl SessionFilter {
+    // The local day expression and a WHERE clause, with parameters for both
+    fn query_parts(&self) -> (&'static str, String, Vec<rusqlite::types::Value>) {
+        use rusqlite::types::Value;
+
+        let day = "date(ended_at + utc_offset_seconds - ?1, 'unixepoch')";
+        let mut params = vec![
+            Value::Integer(self.day_start_hour as i64 * 3600),
+            Value::Text(self.from.format("%Y-%m-%d").to_string()),
+            Value::Text(self.to.format("%Y-%m-%d").to_string()),
+        ];
+        let mut conditions = format!("WHERE {day} BETWEEN ?2 AND ?3");
+        match &self.tag {
+            TagFilter::All => {}
+            TagFilter::Untagged => conditions.push_str(" AND tag = ''"),
+            TagFilter::Tag(tag) => {
+                conditions.push_str(" AND tag = ?4");
+                params.push(Value::Text(tag.clone()));
+            }
+        }
+        (day, conditions, params)
+    }
+}
+
+// Pomodoros and focus time per day for the sessions matching the filter.
+pub fn load_daily_totals(filter: &SessionFilter) -> Vec<DayTotals> {
+    let (day, conditions, params) = filter.query_parts();
+    with_connection(|conn| query_daily_totals(conn, day, &conditions, params)).unwrap_or_default()
+}
+
+fn query_daily_totals(
+    conn: &Connection,
+    day: &str,
+    conditions: &str,
+    params: Vec<rusqlite::types::Value>,
+) -> rusqlite::Result<Vec<DayTotals>> {
+    conn.prepare_cached(&format!(
+        "SELECT {day} AS day, SUM(pomodoros), SUM(duration_seconds) \
+         FROM {APP_TABLE_SESSIONS} {conditions} GROUP BY day ORDER BY day"
+    ))?
+    .query_map(rusqlite::params_from_iter(params), |r| {
+        Ok((
+            r.get::<_, String>(0)?,
+            r.get::<_, i64>(1)?,
+            r.get::<_, i64>(2)?,
+        ))
+    })?
+    .map(|row| {
+        row.map(|(day, pomodoros, seconds)| {
+            crate::streak::parse_day(&day).map(|day| DayTotals {
+                day,
+                pomodoros: pomodoros as u32,
+                focus_seconds: seconds as u32,
+            })
+        })
+    })
+    .filter_map(|row| row.transpose())
+    .collect()
+}
+
+pub fn load_title_tally(filter: &SessionFilter) -> TitleTally {
+    let (_, conditions, params) = filter.query_parts();
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT TOTAL(focused_title_seconds), TOTAL(distracted_title_seconds) \
+             FROM {APP_TABLE_SESSIONS} {conditions}"
+        ))?
+        .query_row(rusqlite::params_from_iter(params), |r| {
+            Ok(TitleTally {
+                focused_seconds: r.get::<_, f64>(0)? as u32,
+                distracted_seconds: r.get::<_, f64>(1)? as u32,
+            })
+        })
+    })
+    .unwrap_or_default()
+}
+
+pub fn load_session_tags() -> Vec<String> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT DISTINCT tag FROM {APP_TABLE_SESSIONS} WHERE tag != '' \
+             ORDER BY tag COLLATE NOCASE"
+        ))?
+        .query_map((), |r| r.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+pub fn load_stats_since(since: i64) -> WeeklyStats {
+    with_connection(|conn| {
+        load_remote_sessions(conn, since)?;
+        query_stats(conn, since)
+    })
+    .unwrap_or_default()
+}
+
+fn query_stats(conn: &Connection, since: i64) -> rusqlite::Result<WeeklyStats> {
+    conn.prepare_cached(&format!(
+        "SELECT COALESCE(SUM(pomodoros), 0), COALESCE(SUM(duration_seconds), 0), \
+                COUNT(DISTINCT date(ended_at + utc_offset_seconds, 'unixepoch')) \
+         FROM (\
+            SELECT ended_at, utc_offset_seconds, duration_seconds, pomodoros \
+            FROM {APP_TABLE_SESSIONS} WHERE ended_at >= ?1 \
+            UNION ALL \
+            SELECT ended_at, utc_offset_seconds, duration_seconds, 1 FROM temp.remote_sessions \
+            WHERE device_id != (SELECT device_id FROM {APP_TABLE_DEVICE} WHERE id = 1)\
+         )"
+    ))?
+    .query_row((since,), |r| {
+        Ok(WeeklyStats {
+            completed_pomodoros: r.get::<_, i64>(0)? as u32,
+            focus_seconds: r.get::<_, i64>(1)? as u32,
+            active_days: r.get::<_, i64>(2)? as u32,
+        })
+    })
+}
+
+pub fn save_weekly_review(review: &WeeklyReview) {
+    send(WriteCommand::WeeklyReview(review.clone()));
+}
+
+fn write_weekly_review(conn: &Connection, review: &WeeklyReview) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT INTO {APP_TABLE_WEEKLY_REVIEWS} \
+            (created_at, completed_pomodoros, focus_seconds, reflection, next_week_goal) \
+         VALUES (?1, ?2, ?3, ?4, ?5)"
+    ))?
+    .execute((
+        review.created_at,
+        review.stats.completed_pomodoros,
+        review.stats.focus_seconds,
+        &review.reflection,
+        review.next_week_goal,
+    ))?;
+
+    Ok(())
+}
+
+pub fn load_latest_review() -> Option<WeeklyReview> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT created_at, completed_pomodoros, focus_seconds, reflection, next_week_goal \
+             FROM {APP_TABLE_WEEKLY_REVIEWS} ORDER BY created_at DESC, id DESC LIMIT 1"
+        ))?
+        .query_row((), |r| {
+            Ok(WeeklyReview {
+                created_at: r.get(0)?,
+                stats: WeeklyStats {
+                    completed_pomodoros: r.get::<_, i64>(1)? as u32,
+                    focus_seconds: r.get::<_, i64>(2)? as u32,
+                    active_days: 0,
+                },
+                reflection: r.get(3)?,
+                next_week_goal: r.get::<_, i64>(4)? as u32,
+            })
+        })
+        .optional()
+    })
+    .flatten()
+}
+
+pub fn load_focus_messages() -> FocusMessages {
+    with_connection(|conn| {
+        let mut messages = FocusMessages::default();
+        let mut statement = conn.prepare_cached(&format!(
+            "SELECT header, message, image_path FROM {APP_TABLE_FOCUS_MESSAGES} WHERE period = ?1"
+        ))?;
+
+        for kind in PeriodKind::ALL {
+            let row: Option<(String, String, String)> = statement
+                .query_row((kind.key(),), |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+                .optional()?;
+            if let Some((header, text, image_path)) = row {
+                let message = messages.get_mut(kind);
+                message.header = header;
+                message.text = text;
+                message.image_path = image_path;
+            }
+        }
+
+        Ok(messages)
+    })
+    .unwrap_or_default()
+}
+
+pub fn save_focus_messages(messages: &FocusMessages) {
+    send(WriteCommand::FocusMessages(messages.clone()));
+}
+
+fn write_focus_messages(conn: &Connection, messages: &FocusMessages) -> rusqlite::Result<()> {
+    let mut statement = conn.prepare_cached(&format!(
+        "INSERT OR REPLACE INTO {APP_TABLE_FOCUS_MESSAGES} (period, header, message, image_path) \
+         VALUES (?1, ?2, ?3, ?4)"
+    ))?;
+
+    for kind in PeriodKind::ALL {
+        let message = messages.get(kind);
+        statement.execute((
+            kind.key(),
+            &message.header,
+            &message.text,
+            &message.image_path,
+        ))?;
+    }
+
+    Ok(())
+}
+
+pub fn load_backgrounds() -> Backgrounds {
+    with_connection(|conn| {
+        let mut backgrounds = Backgrounds::default();
+        let mut statement = conn.prepare_cached(&format!(
+            "SELECT image_path, gradient FROM {APP_TABLE_BACKGROUNDS} WHERE period = ?1"
+        ))?;
+
+        for kind in PeriodKind::ALL {
+            let row: Option<(String, String)> = statement
+                .query_row((kind.key(),), |r| Ok((r.get(0)?, r.get(1)?)))
+                .optional()?;
+            if let Some((image_path, gradient)) = row {
+                let background = backgrounds.get_mut(kind);
+                background.image_path = image_path;
+                background.gradient = gradient;
+            }
+        }
+
+        Ok(backgrounds)
+    })
+    .unwrap_or_default()
+}
+
+pub fn save_backgrounds(backgrounds: &Backgrounds) {
+    send(WriteCommand::Backgrounds(backgrounds.clone()));
+}
+
+fn write_backgrounds(conn: &Connection, backgrounds: &Backgrounds) -> rusqlite::Result<()> {
+    let mut statement = conn.prepare_cached(&format!(
+        "INSERT OR REPLACE INTO {APP_TABLE_BACKGROUNDS} (period, image_path, gradient) \
+         VALUES (?1, ?2, ?3)"
+    ))?;
+
+    for kind in PeriodKind::ALL {
+        let background = backgrounds.get(kind);
+        statement.execute((kind.key(), &background.image_path, &background.gradient))?;
+    }
+
+    Ok(())
+}
+
+pub fn load_guarded_apps() -> Vec<GuardedApp> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT name, blocked FROM {APP_TABLE_GUARDED_APPS} ORDER BY name COLLATE NOCASE"
+        ))?
+        .query_map((), |r| {
+            Ok(GuardedApp {
+                name: r.get(0)?,
+                blocked: r.get(1)?,
+            })
+        })?
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+pub fn save_guarded_apps(apps: Vec<GuardedApp>) {
+    send(WriteCommand::GuardedApps(apps));
+}
+
+fn write_guarded_apps(conn: &Connection, apps: &[GuardedApp]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_GUARDED_APPS}"), ())?;
+    for app in apps {
+        tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO {APP_TABLE_GUARDED_APPS} (name, blocked) VALUES (?1, ?2)"
+            ),
+            (&app.name, app.blocked),
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn load_distracting_titles() -> Vec<String> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT pattern FROM {APP_TABLE_DISTRACTING_TITLES} ORDER BY pattern COLLATE NOCASE"
+        ))?
+        .query_map((), |r| r.get(0))?
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+pub fn save_distracting_titles(patterns: Vec<String>) {
+    send(WriteCommand::DistractingTitles(patterns));
+}
+
+fn write_distracting_titles(conn: &Connection, patterns: &[String]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_DISTRACTING_TITLES}"), ())?;
+    for pattern in patterns {
+        tx.execute(
+            &format!("INSERT OR IGNORE INTO {APP_TABLE_DISTRACTING_TITLES} (pattern) VALUES (?1)"),
+            (pattern,),
+        )?;
+    }
+    tx.commit()
+}
+
+fn load_remote_source_paths(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    conn.prepare_cached(&format!(
+        "SELECT path FROM {APP_TABLE_REMOTE_SOURCES} ORDER BY path"
+    ))?
+    .query_map((), |r| r.get(0))?
+    .collect()
+}
+
+pub fn load_remote_sources() -> Vec<String> {
+    with_connection(load_remote_source_paths).unwrap_or_default()
+}
+
+pub fn save_remote_sources(paths: Vec<String>) {
+    send(WriteCommand::RemoteSources(paths));
+}
+
+fn write_remote_sources(conn: &Connection, paths: &[String]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_REMOTE_SOURCES}"), ())?;
+    for path in paths {
+        tx.execute(
+            &format!("INSERT OR IGNORE INTO {APP_TABLE_REMOTE_SOURCES} (path) VALUES (?1)"),
+            (path,),
+        )?;
+    }
+    tx.commit()
+}
+
+// Distinct days with a pomodoro, in the time zone each session was recorded
+// in and shifted so that days start at `day_start_hour`.
+pub fn load_active_days(day_start_hour: u32) -> HashSet<NaiveDate> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT DISTINCT date(ended_at + utc_offset_seconds - ?1, 'unixepoch') \
+             FROM {APP_TABLE_SESSIONS}"
+        ))?
+        .query_map((day_start_hour as i64 * 3600,), |r| r.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|day| crate::streak::parse_day(day))
+    .collect()
+}
+
+// Pomodoros per day, counted the same way as the active days
+pub fn load_pomodoros_by_day(day_start_hour: u32) -> HashMap<NaiveDate, u32> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT date(ended_at + utc_offset_seconds - ?1, 'unixepoch'), SUM(pomodoros) \
+             FROM {APP_TABLE_SESSIONS} GROUP BY 1"
+        ))?
+        .query_map((day_start_hour as i64 * 3600,), |r| {
+            Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|(day, count)| Some((crate::streak::parse_day(day)?, *count as u32)))
+    .collect()
+}
+
+pub fn load_achievements() -> Vec<(Achievement, i64)> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT key, unlocked_at FROM {APP_TABLE_ACHIEVEMENTS} ORDER BY unlocked_at"
+        ))?
+        .query_map((), |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|(key, at)| Some((Achievement::from_key(key)?, *at)))
+    .collect()
+}
+
+pub fn save_achievement(achievement: Achievement, unlocked_at: i64) {
+    send(WriteCommand::Achievement(achievement, unlocked_at));
+}
+
+fn write_achievement(
+    conn: &Connection,
+    achievement: Achievement,
+    unlocked_at: i64,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT OR IGNORE INTO {APP_TABLE_ACHIEVEMENTS} (key, unlocked_at) VALUES (?1, ?2)"
+    ))?
+    .execute((achievement.key(), unlocked_at))?;
+
+    Ok(())
+}
+
+pub fn record_experiment_period(work_seconds: u32, finished: bool) {
+    send(WriteCommand::ExperimentPeriod(work_seconds, finished));
+}
+
+fn write_experiment_period(
+    conn: &Connection,
+    work_seconds: u32,
+    finished: bool,
+) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT INTO {APP_TABLE_EXPERIMENT} (ended_at, work_seconds, finished) \
+         VALUES (?1, ?2, ?3)"
+    ))?
+    .execute((unix_now(), work_seconds, finished))?;
+
+    Ok(())
+}
+
+pub fn load_experiment_results() -> Vec<LengthResult> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT work_seconds, SUM(finished), SUM(1 - finished) \
+             FROM {APP_TABLE_EXPERIMENT} GROUP BY work_seconds ORDER BY work_seconds"
+        ))?
+        .query_map((), |r| {
+            Ok(LengthResult {
+                work_seconds: r.get::<_, i64>(0)? as u32,
+                finished: r.get::<_, i64>(1)? as u32,
+                abandoned: r.get::<_, i64>(2)? as u32,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+}
+
+pub fn load_holidays() -> Vec<NaiveDate> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT day FROM {APP_TABLE_HOLIDAYS} ORDER BY day"
+        ))?
+        .query_map((), |r| r.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|day| crate::streak::parse_day(day))
+    .collect()
+}
+
+pub fn save_holidays(days: Vec<NaiveDate>) {
+    send(WriteCommand::Holidays(days));
+}
+
+fn write_holidays(conn: &Connection, days: &[NaiveDate]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_HOLIDAYS}"), ())?;
+    for day in days {
+        tx.execute(
+            &format!("INSERT OR IGNORE INTO {APP_TABLE_HOLIDAYS} (day) VALUES (?1)"),
+            (day.format("%Y-%m-%d").to_string(),),
+        )?;
+    }
+    tx.commit()
+}
+
+// Plans for the days from `since` on, oldest first.
+pub fn load_plans_since(since: NaiveDate) -> Vec<DayPlan> {
+    let rows = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT day, title, estimate, actual FROM {APP_TABLE_PLAN_ITEMS} \
+             WHERE day >= ?1 ORDER BY day, position"
+        ))?
+        .query_map((since.format("%Y-%m-%d").to_string(),), |r| {
+            Ok((
+                r.get::<_, String>(0)?,
+                PlanItem {
+                    title: r.get(1)?,
+                    estimate: r.get::<_, i64>(2)? as u32,
+                    actual: r.get::<_, i64>(3)? as u32,
+                },
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default();
+
+    let mut plans: Vec<DayPlan> = Vec::new();
+    for (day, item) in rows {
+        let Some(day) = crate::streak::parse_day(&day) else {
+            continue;
+        };
+        match plans.last_mut() {
+            Some(plan) if plan.day == day => plan.items.push(item),
+            _ => plans.push(DayPlan {
+                day,
+                items: vec![item],
+            }),
+        }
+    }
+    plans
+}
+
+pub fn load_plan(day: NaiveDate) -> Vec<PlanItem> {
+    load_plans_since(day)
+        .into_iter()
+        .find(|plan| plan.day == day)
+        .map(|plan| plan.items)
+        .unwrap_or_default()
+}
+
+pub fn save_plan(day: NaiveDate, items: Vec<PlanItem>) {
+    send(WriteCommand::Plan(day, items));
+}
+
+fn write_plan(conn: &Connection, day: NaiveDate, items: &[PlanItem]) -> rusqlite::Result<()> {
+    let day = day.format("%Y-%m-%d").to_string();
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        &format!("DELETE FROM {APP_TABLE_PLAN_ITEMS} WHERE day = ?1"),
+        (&day,),
+    )?;
+    for (position, item) in items.iter().enumerate() {
+        tx.execute(
+            &format!(
+                "INSERT INTO {APP_TABLE_PLAN_ITEMS} (day, position, title, estimate, actual) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)"
+            ),
+            (
+                &day,
+                position as i64,
+                &item.title,
+                item.estimate,
+                item.actual,
+            ),
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn load_period_sequence() -> Vec<SequenceStep> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT kind, seconds FROM {APP_TABLE_PERIOD_SEQUENCE} ORDER BY position"
+        ))?
+        .query_map((), |r| Ok((r.get::<_, String>(0)?, r.get::<_, i64>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|(kind, seconds)| {
+        Some(SequenceStep {
+            kind: PeriodKind::from_key(&kind)?,
+            seconds: seconds as u32,
+        })
+    })
+    .collect()
+}
+
+pub fn save_period_sequence(steps: Vec<SequenceStep>) {
+    send(WriteCommand::PeriodSequence(steps));
+}
+
+fn write_period_sequence(conn: &Connection, steps: &[SequenceStep]) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_PERIOD_SEQUENCE}"), ())?;
+    for (position, step) in steps.iter().enumerate() {
+        tx.execute(
+            &format!(
+                "INSERT INTO {APP_TABLE_PERIOD_SEQUENCE} (position, kind, seconds) \
+                 VALUES (?1, ?2, ?3)"
+            ),
+            (position as i64, step.kind.key(), step.seconds),
+        )?;
+    }
+    tx.commit()
+}
+
+pub fn load_profiles() -> Vec<Profile> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT name, work_seconds, short_break_seconds, long_break_seconds, \
+                    long_break_every, alarm_volume, tick_volume, ambient_sound, \
+                    ambient_volume, ambient_path \
+             FROM {APP_TABLE_PROFILES} ORDER BY name COLLATE NOCASE"
+        ))?
+        .query_map((), |r| {
+            Ok(Profile {
+                name: r.get(0)?,
+                work_seconds: r.get::<_, i64>(1)? as u32,
+                short_break_seconds: r.get::<_, i64>(2)? as u32,
+                long_break_seconds: r.get::<_, i64>(3)? as u32,
+                long_break_every: r.get::<_, i64>(4)? as u32,
+                alarm_volume: r.get::<_, i64>(5)? as u32,
+                tick_volume: r.get::<_, i64>(6)? as u32,
+                ambient_sound: AmbientSound::from_key(&r.get::<_, String>(7)?)
+                    .unwrap_or(Settings::default().ambient_sound),
+                ambient_volume: r.get::<_, i64>(8)? as u32,
+                ambient_path: r.get(9)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|profile| profile.work_seconds > 0)
+    .collect()
+}
+
+pub fn save_profile(profile: Profile) {
+    send(WriteCommand::Profile(profile));
+}
+
+fn write_profile(conn: &Connection, profile: &Profile) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "INSERT OR REPLACE INTO {APP_TABLE_PROFILES} \
+            (name, work_seconds, short_break_seconds, long_break_seconds, long_break_every, \
+             alarm_volume, tick_volume, ambient_sound, ambient_volume, ambient_path) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+    ))?
+    .execute(params![
+        profile.name,
+        profile.work_seconds,
+        profile.short_break_seconds,
+        profile.long_break_seconds,
+        profile.long_break_every,
+        profile.alarm_volume,
+        profile.tick_volume,
+        profile.ambient_sound.key(),
+        profile.ambient_volume,
+        profile.ambient_path,
+    ])?;
+
+    Ok(())
+}
+
+pub fn delete_profile(name: String) {
+    send(WriteCommand::DeleteProfile(name));
+}
+
+fn delete_profile_row(conn: &Connection, name: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!("DELETE FROM {APP_TABLE_PROFILES} WHERE name = ?1"))?
+        .execute((name,))?;
+
+    Ok(())
+}
+
+// Name of the profile last picked on the timer screen; empty when the
+// settings have been edited by hand since.
+pub fn load_active_profile() -> String {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT active_profile FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default()
+}
+
+pub fn save_active_profile(name: String) {
+    send(WriteCommand::ActiveProfile(name));
+}
+
+fn write_active_profile(conn: &Connection, name: &str) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET active_profile = ?1 WHERE id = 1"
+    ))?
+    .execute((name,))?;
+
+    Ok(())
+}
+
+pub fn load_schedule() -> Schedule {
+    let rows = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT weekday, profile FROM {APP_TABLE_WEEKDAY_PROFILES}"
+        ))?
+        .query_map((), |r| Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()
+    })
+    .unwrap_or_default();
+
+    let mut schedule = Schedule::default();
+    for (weekday, profile) in rows {
+        if let Some(day) = schedule.days.get_mut(weekday as usize) {
+            *day = DayProfile::Profile(profile);
+        }
+    }
+    schedule
+}
+
+pub fn save_schedule(schedule: Schedule) {
+    send(WriteCommand::Schedule(schedule));
+}
+
+fn write_schedule(conn: &Connection, schedule: &Schedule) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&format!("DELETE FROM {APP_TABLE_WEEKDAY_PROFILES}"), ())?;
+    for (weekday, day) in schedule.days.iter().enumerate() {
+        if let DayProfile::Profile(name) = day {
+            tx.execute(
+                &format!(
+                    "INSERT INTO {APP_TABLE_WEEKDAY_PROFILES} (weekday, profile) VALUES (?1, ?2)"
+                ),
+                (weekday as i64, name),
+            )?;
+        }
+    }
+    tx.commit()
+}
+
+// The last day the schedule was applied, so that a restart during the day
+// doesn't override a profile picked by hand.
+pub fn load_schedule_day() -> Option<NaiveDate> {
+    with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT schedule_day FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| r.get::<_, String>(0))
+        .optional()
+    })
+    .flatten()
+    .and_then(|day| crate::streak::parse_day(&day))
+}
+
+pub fn save_schedule_day(day: NaiveDate) {
+    send(WriteCommand::ScheduleDay(day));
+}
+
+fn write_schedule_day(conn: &Connection, day: NaiveDate) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} SET schedule_day = ?1 WHERE id = 1"
+    ))?
+    .execute((day.format("%Y-%m-%d").to_string(),))?;
+
+    Ok(())
+}
+
+pub fn load_window_geometry() -> WindowGeometry {
+    let geometry = with_connection(|conn| {
+        conn.prepare_cached(&format!(
+            "SELECT window_x, window_y, window_width, window_height \
+             FROM {APP_TABLE_SETTINGS} WHERE id = 1"
+        ))?
+        .query_row((), |r| {
+            let x: Option<f64> = r.get(0)?;
+            let y: Option<f64> = r.get(1)?;
+            Ok(WindowGeometry {
+                position: x.zip(y).map(|(x, y)| iced::Point::new(x as f32, y as f32)),
+                size: iced::Size::new(r.get::<_, f64>(2)? as f32, r.get::<_, f64>(3)? as f32),
+            })
+        })
+        .optional()
+    })
+    .flatten()
+    .unwrap_or_default();
+
+    // Guard against a window that was shrunk to nothing or never reported
+    let min = WindowGeometry::MIN_SIZE;
+    if geometry.size.width < min.width || geometry.size.height < min.height {
+        return WindowGeometry {
+            size: WindowGeometry::DEFAULT_SIZE,
+            ..geometry
+        };
+    }
+    geometry
+}
+
+pub fn save_window_geometry(geometry: WindowGeometry) {
+    send(WriteCommand::WindowGeometry(geometry));
+}
+
+fn write_window_geometry(conn: &Connection, geometry: WindowGeometry) -> rusqlite::Result<()> {
+    conn.prepare_cached(&format!(
+        "UPDATE {APP_TABLE_SETTINGS} \
+         SET window_x = ?1, window_y = ?2, window_width = ?3, window_height = ?4 \
+         WHERE id = 1"
+    ))?
+    .execute((
+        geometry.position.map(|point| point.x as f64),
+        geometry.position.map(|point| point.y as f64),
+        geometry.size.width as f64,
+        geometry.size.height as f64,
+    ))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod db_tests {
+    use super::*;
+
+    #[test]
+    fn migrations_reach_latest_version() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+
+        // Running again is a no-op
+        init(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn migrations_upgrade_unversioned_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate_base_tables(&conn).unwrap();
+        conn.execute(
+            &format!("UPDATE {APP_TABLE_SETTINGS} SET work_seconds = 60 WHERE id = 1"),
+            (),
+        )
+        .unwrap();
+
+        init(&conn).unwrap();
+
+        let (work_seconds, alarm_volume): (i64, i64) = conn
+            .query_row(
+                &format!("SELECT work_seconds, alarm_volume FROM {APP_TABLE_SETTINGS}"),
+                (),
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(work_seconds, 60);
+        assert_eq!(alarm_volume, Settings::DEFAULT_ALARM_VOLUME as i64);
+    }
+
+    #[test]
+    fn remote_sessions_are_merged_by_device() {
+        let dir = std::env::temp_dir();
+        let remote_path = dir.join(format!("roth-pomodoro-{}.sqlite", uuid::Uuid::new_v4()));
+        let copy_path = dir.join(format!("roth-pomodoro-{}.sqlite", uuid::Uuid::new_v4()));
+
+        let remote = Connection::open(&remote_path).unwrap();
+        init(&remote).unwrap();
+        write_session(&remote, 1_000, 0, 1500, "", "", 1).unwrap();
+        write_session(&remote, 2_000, 0, 1500, "", "", 1).unwrap();
+        drop(remote);
+        // A second copy of the same device's database must not double count
+        std::fs::copy(&remote_path, &copy_path).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        write_session(&conn, 3_000, 0, 1500, "", "", 1).unwrap();
+        let paths = [&remote_path, &copy_path].map(|p| p.to_string_lossy().to_string());
+        write_remote_sources(&conn, &paths).unwrap();
+
+        load_remote_sessions(&conn, 1_500).unwrap();
+        let remote_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM temp.remote_sessions", (), |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(remote_count, 1);
+
+        let _ = std::fs::remove_file(remote_path);
+        let _ = std::fs::remove_file(copy_path);
+    }
+
+    #[test]
+    fn snoozed_time_is_added_to_the_session_it_followed() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        write_session(&conn, 1_000, 0, 1500, "", "", 1).unwrap();
+        write_session(&conn, 5_000, 0, 1500, "", "", 1).unwrap();
+
+        write_snooze(&conn, 5_000, 300).unwrap();
+        let durations: Vec<i64> = conn
+            .prepare(&format!(
+                "SELECT duration_seconds FROM {APP_TABLE_SESSIONS} ORDER BY ended_at"
+            ))
+            .unwrap()
+            .query_map((), |r| r.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(durations, vec![1500, 1800]);
+    }
+
+    #[test]
+    fn active_days_use_the_offset_recorded_with_each_session() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        // 23:30 UTC at home, then 00:30 UTC after flying an hour west: both
+        // happened on the same local day.
+        write_session(&conn, 86_400 - 1_800, 0, 1500, "", "", 1).unwrap();
+        write_session(&conn, 86_400 + 1_800, -3_600, 1500, "", "", 1).unwrap();
+
+        load_remote_sessions(&conn, 0).unwrap();
+        let stats = query_stats(&conn, 0).unwrap();
+        assert_eq!(stats.active_days, 1);
+    }
+
+    #[test]
+    fn session_filter_narrows_by_day_and_tag() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        let day = |d: u32| NaiveDate::from_ymd_opt(1970, 1, d).unwrap();
+        write_session(&conn, 3_600, 0, 1500, "Report", "", 1).unwrap();
+        write_session(&conn, 7_200, 0, 1500, "", "", 1).unwrap();
+        write_session(&conn, 86_400 + 3_600, 0, 1500, "Report", "", 1).unwrap();
+        write_session(&conn, 2 * 86_400 + 3_600, 0, 1500, "Report", "", 1).unwrap();
+
+        let totals = |filter: SessionFilter| {
+            let (day, conditions, params) = filter.query_parts();
+            query_daily_totals(&conn, day, &conditions, params)
+                .unwrap()
+                .into_iter()
+                .map(|totals| (totals.day, totals.pomodoros))
+                .collect::<Vec<_>>()
+        };
+        let filter = SessionFilter {
+            from: day(1),
+            to: day(2),
+            tag: TagFilter::All,
+            day_start_hour: 0,
+        };
+
+        assert_eq!(totals(filter.clone()), [(day(1), 2), (day(2), 1)]);
+        assert_eq!(
+            totals(SessionFilter {
+                tag: TagFilter::Tag("Report".to_string()),
+                ..filter.clone()
+            }),
+            [(day(1), 1), (day(2), 1)]
+        );
+        assert_eq!(
+            totals(SessionFilter {
+                tag: TagFilter::Untagged,
+                ..filter.clone()
+            }),
+            [(day(1), 1)]
+        );
+        // With days starting at 3am, the two sessions before 3am on
+        // 1 January count for 31 December
+        assert_eq!(
+            totals(SessionFilter {
+                day_start_hour: 3,
+                ..filter
+            }),
+            [(day(1), 1), (day(2), 1)]
+        );
+    }
+
+    #[test]
+    fn backups_are_checked_and_restored() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        write_session(&conn, 1_700_000_000, 0, 1500, "", "", 1).unwrap();
+        let path = std::env::temp_dir().join(format!("{}.sqlite", uuid::Uuid::new_v4()));
+        backup_to(&conn, &path).unwrap();
+
+        assert_eq!(check_backup(&path), Ok(1));
+        let mut restored = Connection::open_in_memory().unwrap();
+        init(&restored).unwrap();
+        restore_from(&mut restored, &path).unwrap();
+        let sessions: u32 = restored
+            .query_row("SELECT COUNT(*) FROM sessions", (), |row| row.get(0))
+            .unwrap();
+        assert_eq!(sessions, 1);
+
+        std::fs::write(&path, "not a database").unwrap();
+        assert!(check_backup(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn server_sessions_keep_the_newest_version() {
+        let conn = Connection::open_in_memory().unwrap();
+        init(&conn).unwrap();
+        let entry = |ended_at: i64, written_at: i64, deleted: bool| JournalEntry {
+            device_id: "laptop".to_string(),
+            ended_at,
+            utc_offset_seconds: 0,
+            duration_seconds: 1500,
+            tag: String::new(),
+            written_at,
+            deleted,
+        };
+        write_server_sessions(
+            &conn,
+            Some("7"),
+            &[entry(1_000, 20, false), entry(2_000, 20, true)],
+        )
+        .unwrap();
+        // A stale copy from before the deletion
+        write_server_sessions(&conn, None, &[entry(2_000, 10, false)]).unwrap();
+
+        load_remote_sessions(&conn, 0).unwrap();
+        let stats = query_stats(&conn, 0).unwrap();
+        assert_eq!(stats.completed_pomodoros, 1);
+        let cursor: String = conn
+            .query_row(
+                &format!("SELECT sync_server_cursor FROM {APP_TABLE_SETTINGS}"),
+                (),
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(cursor, "7");
+    }
 }
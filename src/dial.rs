@@ -0,0 +1,105 @@
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, path::Arc};
+use iced::{Color, Point, Radians, Rectangle, Renderer, Theme, mouse};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+// A kitchen timer face: the time left is a wedge that shrinks back towards
+// twelve o'clock, on a dial marked in minutes like the real thing.
+pub struct Dial {
+    // Fractional, so the wedge can sweep between whole seconds
+    pub seconds_left: f32,
+    pub period_seconds: u32,
+    pub color: Color,
+}
+
+// The dial goes round once an hour, like a kitchen timer, unless the
+// period is longer; then once for the period rounded up to whole hours
+pub fn dial_seconds(period_seconds: u32) -> u32 {
+    period_seconds.div_ceil(3600).max(1) * 3600
+}
+
+// The share of the dial the wedge covers
+pub fn wedge_fraction(seconds_left: f32, period_seconds: u32) -> f32 {
+    (seconds_left / dial_seconds(period_seconds) as f32).clamp(0.0, 1.0)
+}
+
+impl<Message> canvas::Program<Message> for Dial {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        let radius = frame.width().min(frame.height()) / 2.0 - 2.0;
+        let palette = theme.extended_palette();
+
+        frame.fill(&Path::circle(center, radius), palette.background.weak.color);
+
+        let fraction = wedge_fraction(self.seconds_left, self.period_seconds);
+        if fraction > 0.0 {
+            let wedge = Path::new(|builder| {
+                builder.move_to(center);
+                builder.arc(Arc {
+                    center,
+                    radius: radius * 0.82,
+                    start_angle: Radians(-FRAC_PI_2),
+                    end_angle: Radians(-FRAC_PI_2 + fraction * TAU),
+                });
+                builder.close();
+            });
+            frame.fill(&wedge, self.color);
+        }
+
+        // A mark for each minute, longer ones every five
+        let minutes = dial_seconds(self.period_seconds) / 60;
+        let step = (minutes / 60).max(1);
+        for minute in (0..minutes).step_by(step as usize) {
+            let angle = -FRAC_PI_2 + minute as f32 / minutes as f32 * TAU;
+            let major = (minute / step).is_multiple_of(5);
+            let inner = radius * if major { 0.86 } else { 0.91 };
+            let (sin, cos) = angle.sin_cos();
+            let mark = Path::line(
+                Point::new(center.x + inner * cos, center.y + inner * sin),
+                Point::new(
+                    center.x + radius * 0.97 * cos,
+                    center.y + radius * 0.97 * sin,
+                ),
+            );
+            frame.stroke(
+                &mark,
+                Stroke::default()
+                    .with_color(palette.background.base.text)
+                    .with_width(if major { 3.0 } else { 1.0 }),
+            );
+        }
+
+        frame.fill(
+            &Path::circle(center, radius * 0.08),
+            palette.background.strong.color,
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod dial_tests {
+    use super::*;
+
+    #[test]
+    fn the_wedge_shrinks_on_an_hour_dial() {
+        // A fresh pomodoro covers 25 of the 60 minutes
+        assert_eq!(wedge_fraction(1500.0, 1500), 1500.0 / 3600.0);
+        assert_eq!(wedge_fraction(0.0, 1500), 0.0);
+        // and shrinks between seconds too
+        assert!(wedge_fraction(1499.5, 1500) < wedge_fraction(1500.0, 1500));
+        // Longer periods get a dial long enough to hold them
+        assert_eq!(dial_seconds(5400), 7200);
+        assert_eq!(wedge_fraction(5400.0, 5400), 0.75);
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{Datelike, NaiveDate};
+
+// The work length experiment alternates two lengths day by day and keeps
+// score of how often pomodoros of each length are finished or abandoned.
+pub const MIN_ATTEMPTS: u32 = 5;
+
+// The regular length on even days, the one being tried on odd days. A
+// second length of 0 means no experiment.
+pub fn work_seconds(regular: u32, trial: u32, day: NaiveDate) -> u32 {
+    if trial == 0 || day.num_days_from_ce() % 2 == 0 {
+        regular
+    } else {
+        trial
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthResult {
+    pub work_seconds: u32,
+    pub finished: u32,
+    // Reset or skipped once started
+    pub abandoned: u32,
+}
+
+impl LengthResult {
+    pub fn attempts(&self) -> u32 {
+        self.finished + self.abandoned
+    }
+
+    pub fn completion_percent(&self) -> u32 {
+        (self.finished * 100)
+            .checked_div(self.attempts())
+            .unwrap_or(0)
+    }
+
+    pub fn abandonment_percent(&self) -> u32 {
+        (self.abandoned * 100)
+            .checked_div(self.attempts())
+            .unwrap_or(0)
+    }
+}
+
+// The length that gets finished most often, once there are at least two
+// lengths with enough attempts to compare.
+pub fn better(results: &[LengthResult]) -> Option<LengthResult> {
+    let tried: Vec<&LengthResult> = results
+        .iter()
+        .filter(|result| result.attempts() >= MIN_ATTEMPTS)
+        .collect();
+    if tried.len() < 2 {
+        return None;
+    }
+    tried.into_iter().copied().max_by(|a, b| {
+        // finished/attempts, compared without rounding
+        (a.finished * b.attempts())
+            .cmp(&(b.finished * a.attempts()))
+            .then(b.abandoned.cmp(&a.abandoned))
+    })
+}
+
+#[cfg(test)]
+mod experiment_tests {
+    use super::*;
+    use crate::streak::parse_day;
+
+    #[test]
+    fn lengths_alternate_and_the_more_finished_one_wins() {
+        let monday = parse_day("2026-03-02").unwrap();
+        let tuesday = parse_day("2026-03-03").unwrap();
+        assert_ne!(
+            work_seconds(1500, 3000, monday),
+            work_seconds(1500, 3000, tuesday)
+        );
+        assert_eq!(work_seconds(1500, 0, tuesday), 1500);
+
+        let short = LengthResult {
+            work_seconds: 1500,
+            finished: 9,
+            abandoned: 1,
+        };
+        let long = LengthResult {
+            work_seconds: 3000,
+            finished: 3,
+            abandoned: 3,
+        };
+        assert_eq!(better(&[short, long]), Some(short));
+        assert_eq!(long.completion_percent(), 50);
+        // Too few attempts at the long length to tell yet
+        let early = LengthResult {
+            finished: 2,
+            abandoned: 1,
+            ..long
+        };
+        assert_eq!(better(&[short, early]), None);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::platform::processes::{self, Process};
+
+// Programs the focus guard knows about. Allowed ones stay on the list but
+// are left alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardedApp {
+    pub name: String,
+    pub blocked: bool,
+}
+
+// Names compare without case or an .exe/.app ending. Linux cuts process
+// names to 15 characters, so a longer name matches its first 15.
+pub fn matches(app: &str, process: &str) -> bool {
+    let normalize = |name: &str| {
+        let name = name.trim().to_lowercase();
+        match name
+            .strip_suffix(".exe")
+            .or_else(|| name.strip_suffix(".app"))
+        {
+            Some(stem) => stem.to_string(),
+            None => name,
+        }
+    };
+    let (app, process) = (normalize(app), normalize(process));
+    app == process || (process.chars().count() == 15 && app.starts_with(&process))
+}
+
+pub fn offenders<'a>(apps: &[GuardedApp], running: &'a [Process]) -> Vec<&'a Process> {
+    running
+        .iter()
+        .filter(|process| {
+            apps.iter()
+                .any(|app| app.blocked && matches(&app.name, &process.name))
+        })
+        .collect()
+}
+
+// The blocked apps that are running, by the names they were listed under,
+// minimizing them first if asked
+pub async fn check(apps: Vec<GuardedApp>, minimize: bool) -> Vec<String> {
+    tokio::task::spawn_blocking(move || {
+        let running = processes::running();
+        let offenders = offenders(&apps, &running);
+        if minimize {
+            offenders
+                .iter()
+                .for_each(|process| processes::minimize(process));
+        }
+        let mut names: Vec<String> = apps
+            .into_iter()
+            .filter(|app| {
+                offenders
+                    .iter()
+                    .any(|process| matches(&app.name, &process.name))
+            })
+            .map(|app| app.name)
+            .collect();
+        names.dedup();
+        names
+    })
+    .await
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod focus_guard_tests {
+    use super::*;
+
+    #[test]
+    fn only_blocked_apps_are_caught() {
+        assert!(matches("Discord", "discord"));
+        assert!(matches("steam", "Steam.exe"));
+        assert!(matches("thunderbird-bin-wrapper", "thunderbird-bin"));
+        assert!(!matches("code", "codex"));
+
+        let running = vec![
+            Process {
+                pid: 1,
+                name: "firefox".to_string(),
+            },
+            Process {
+                pid: 2,
+                name: "Discord".to_string(),
+            },
+        ];
+        let apps = vec![
+            GuardedApp {
+                name: "discord".to_string(),
+                blocked: true,
+            },
+            GuardedApp {
+                name: "firefox".to_string(),
+                blocked: false,
+            },
+        ];
+        let caught = offenders(&apps, &running);
+        assert_eq!(caught.len(), 1);
+        assert_eq!(caught[0].pid, 2);
+    }
+}
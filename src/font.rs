@@ -0,0 +1,139 @@
+use iced::Font;
+use std::{path::Path, sync::Mutex};
+
+// Family names handed to iced, which wants them for the life of the app.
+// Kept so that saving the same font again doesn't leak another copy.
+static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+fn intern(name: &str) -> &'static str {
+    let mut names = NAMES.lock().unwrap_or_else(|err| err.into_inner());
+    match names.iter().find(|known| **known == name) {
+        Some(known) => known,
+        None => {
+            let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+            names.push(name);
+            name
+        }
+    }
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<usize> {
+    Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize)
+}
+
+// The family a TrueType or OpenType file declares in its `name` table,
+// preferring the typographic family (ID 16) over the legacy one (ID 1).
+// Collections use their first font.
+pub fn family_name(data: &[u8]) -> Option<String> {
+    let font = if data.starts_with(b"ttcf") {
+        u32_at(data, 12)?
+    } else {
+        0
+    };
+    let tables = u16_at(data, font + 4)? as usize;
+    let name = (0..tables)
+        .map(|i| font + 12 + i * 16)
+        .find(|record| data.get(*record..*record + 4) == Some(b"name"))
+        .and_then(|record| u32_at(data, record + 8))?;
+
+    let count = u16_at(data, name + 2)? as usize;
+    let strings = name + u16_at(data, name + 4)? as usize;
+    let mut found: Vec<(u16, String)> = Vec::new();
+    for record in (0..count).map(|i| name + 6 + i * 12) {
+        let platform = u16_at(data, record)?;
+        let id = u16_at(data, record + 6)?;
+        if id != 1 && id != 16 {
+            continue;
+        }
+        let length = u16_at(data, record + 8)? as usize;
+        let start = strings + u16_at(data, record + 10)? as usize;
+        let bytes = data.get(start..start + length)?;
+        let value = match platform {
+            // Unicode and Windows names are UTF-16BE, Mac names are close
+            // enough to ASCII for family names
+            0 | 3 => String::from_utf16(
+                &bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect::<Vec<_>>(),
+            )
+            .ok()?,
+            1 => bytes.iter().map(|b| *b as char).collect(),
+            _ => continue,
+        };
+        found.push((id, value));
+    }
+    found.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+    found.into_iter().map(|(_, value)| value).next()
+}
+
+pub fn is_font_file(setting: &str) -> bool {
+    Path::new(setting).extension().is_some_and(|ext| {
+        ["ttf", "otf", "ttc"]
+            .iter()
+            .any(|known| ext.eq_ignore_ascii_case(known))
+    })
+}
+
+// The countdown font for a setting: empty for the default, `monospace`,
+// an installed family name, or a font file. Files come with the bytes
+// iced needs to load before the font can be used.
+pub fn countdown_font(setting: &str) -> Result<(Font, Option<Vec<u8>>), String> {
+    if setting.is_empty() {
+        return Ok((Font::DEFAULT, None));
+    }
+    if setting.eq_ignore_ascii_case("monospace") {
+        return Ok((Font::MONOSPACE, None));
+    }
+    if !is_font_file(setting) {
+        return Ok((Font::with_name(intern(setting)), None));
+    }
+
+    let path = crate::daily_log::expand_home(setting);
+    let data = std::fs::read(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let family = family_name(&data).ok_or_else(|| format!("{}: not a font", path.display()))?;
+    Ok((Font::with_name(intern(&family)), Some(data)))
+}
+
+#[cfg(test)]
+mod font_tests {
+    use super::*;
+
+    // A font with nothing but a name table
+    fn font_named(family: &str) -> Vec<u8> {
+        let name: Vec<u8> = family
+            .encode_utf16()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect();
+        let mut data = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        data.extend(b"name");
+        data.extend([0; 4]);
+        data.extend(28u32.to_be_bytes());
+        data.extend((18 + name.len() as u32).to_be_bytes());
+        // Format, one record, strings after it
+        data.extend([0, 0, 0, 1, 0, 18]);
+        // Windows, Unicode BMP, en-US, family
+        data.extend([0, 3, 0, 1, 4, 9, 0, 1]);
+        data.extend((name.len() as u16).to_be_bytes());
+        data.extend([0, 0]);
+        data.extend(name);
+        data
+    }
+
+    #[test]
+    fn family_names_come_from_the_name_table() {
+        assert_eq!(
+            family_name(&font_named("JetBrains Mono")),
+            Some("JetBrains Mono".to_string())
+        );
+        assert_eq!(family_name(b"not a font"), None);
+        assert!(is_font_file("~/fonts/Inter.TTF"));
+        assert!(!is_font_file("Inter"));
+    }
+}
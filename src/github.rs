@@ -0,0 +1,152 @@
+use crate::i18n::{t, tf};
+use serde::Deserialize;
+use std::{fmt, time::Duration};
+
+const API_URL: &str = "https://api.github.com";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// Name of the access token in the system keychain
+pub const TOKEN_NAME: &str = "github-token";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubConfig {
+    // Optional for reading public issues, needed for commenting
+    pub token: String,
+    // Pomodoros on the active task between offers to comment
+    pub every: u32,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            every: 3,
+        }
+    }
+}
+
+// An issue or pull request, which GitHub comments on alike
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueRef {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+impl IssueRef {
+    // The first GitHub issue or pull request address in the text, so a task
+    // may carry it in its title or as a Markdown link
+    pub fn find(text: &str) -> Option<Self> {
+        text.match_indices("github.com/").find_map(|(at, prefix)| {
+            let rest = &text[at + prefix.len()..];
+            let end = rest
+                .find(|c: char| c.is_whitespace() || matches!(c, ')' | ']' | '>' | '"'))
+                .unwrap_or(rest.len());
+            let mut parts = rest[..end].split(['/', '#', '?']);
+            let owner = parts.next().filter(|part| !part.is_empty())?;
+            let repo = parts.next().filter(|part| !part.is_empty())?;
+            if !matches!(parts.next(), Some("issues" | "pull")) {
+                return None;
+            }
+            Some(Self {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                number: parts.next()?.parse().ok()?,
+            })
+        })
+    }
+
+    fn api_url(&self) -> String {
+        format!(
+            "{}/repos/{}/{}/issues/{}",
+            API_URL, self.owner, self.repo, self.number
+        )
+    }
+}
+
+impl fmt::Display for IssueRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    title: String,
+}
+
+fn request(request: ureq::Request, token: &str) -> ureq::Request {
+    let request = request
+        .set("Accept", "application/vnd.github+json")
+        .set("X-GitHub-Api-Version", "2022-11-28");
+    if token.is_empty() {
+        request
+    } else {
+        request.set("Authorization", &format!("Bearer {}", token))
+    }
+}
+
+fn describe(err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(401, _) => t("github-token-rejected").to_string(),
+        ureq::Error::Status(403 | 404, _) => t("github-issue-not-found").to_string(),
+        ureq::Error::Status(code, _) => tf("github-http-error", &[("code", &code)]),
+        err => err.kind().to_string(),
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build()
+}
+
+pub async fn fetch_title(token: String, issue: IssueRef) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        request(agent().get(&issue.api_url()), &token)
+            .call()
+            .map_err(describe)?
+            .into_json::<Issue>()
+            .map(|issue| issue.title)
+            .map_err(|err| err.to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+pub async fn comment(token: String, issue: IssueRef, body: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        request(
+            agent().post(&format!("{}/comments", issue.api_url())),
+            &token,
+        )
+        .send_json(serde_json::json!({ "body": body }))
+        .map(|_| ())
+        .map_err(describe)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[cfg(test)]
+mod github_tests {
+    use super::*;
+
+    #[test]
+    fn issue_links_are_found_in_task_text() {
+        let issue = IssueRef::find("Fix the parser [#42](https://github.com/acme/tool/issues/42)");
+        assert_eq!(
+            issue,
+            Some(IssueRef {
+                owner: "acme".to_string(),
+                repo: "tool".to_string(),
+                number: 42,
+            })
+        );
+        assert_eq!(issue.unwrap().to_string(), "acme/tool#42");
+        assert_eq!(
+            IssueRef::find("Review https://github.com/acme/tool/pull/7#discussion_r1")
+                .map(|issue| issue.number),
+            Some(7)
+        );
+        assert_eq!(IssueRef::find("See https://github.com/acme/tool"), None);
+        assert_eq!(IssueRef::find("Write the report"), None);
+    }
+}
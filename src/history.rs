@@ -0,0 +1,45 @@
+pub const PAGE_SIZE: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySession {
+    pub id: i64,
+    pub ended_at: i64,
+    pub duration_seconds: u32,
+    pub tag: String,
+    pub intention: String,
+    // No editor activity was heard during it
+    pub idle: bool,
+}
+
+// The session being corrected, as typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryDraft {
+    pub id: i64,
+    pub tag: String,
+    pub minutes: String,
+}
+
+impl HistoryDraft {
+    pub fn from_session(session: &HistorySession) -> Self {
+        Self {
+            id: session.id,
+            tag: session.tag.clone(),
+            minutes: (session.duration_seconds / 60).to_string(),
+        }
+    }
+
+    // The corrected session keeps its end time; a session can't be edited
+    // down to nothing, that's what deleting is for.
+    pub fn apply(&self, session: &HistorySession) -> Option<HistorySession> {
+        let minutes: u32 = self.minutes.trim().parse().ok().filter(|m| *m > 0)?;
+        Some(HistorySession {
+            duration_seconds: minutes.saturating_mul(60),
+            tag: self.tag.trim().to_string(),
+            ..session.clone()
+        })
+    }
+}
+
+pub fn page_count(sessions: usize) -> usize {
+    sessions.div_ceil(PAGE_SIZE).max(1)
+}
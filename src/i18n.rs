@@ -0,0 +1,267 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        LazyLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+// User-visible strings live in assets/i18n/<language>.ftl, written in the
+// subset of Fluent syntax we need: `key = value` messages, indented
+// continuation lines, `{ $name }` variables, `{ "{" }` literals and
+// selectors on a number, with one line per variant:
+//
+//   sessions = { $count ->
+//       [one] { $count } session
+//      *[other] { $count } sessions
+//   }
+//
+// Missing messages fall back to English, then to the key itself.
+const CATALOGS: [&str; 2] = [
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/i18n/en.ftl")),
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/i18n/de.ftl")),
+];
+
+static MESSAGES: LazyLock<Vec<HashMap<&'static str, String>>> =
+    LazyLock::new(|| CATALOGS.iter().map(|catalog| parse(catalog)).collect());
+
+// Index into CATALOGS of the language in use
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    // Follow the operating system's language
+    #[default]
+    System,
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::System, Language::English, Language::German];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Language::System => "",
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.key() == key)
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> usize {
+        match self {
+            Language::System => sys_locale::get_locale()
+                .filter(|locale| locale.to_lowercase().starts_with("de"))
+                .map_or(0, |_| 1),
+            Language::English => 0,
+            Language::German => 1,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::System => write!(f, "{}", t("language-system")),
+            // Languages are listed in their own name
+            Language::English => write!(f, "English"),
+            Language::German => write!(f, "Deutsch"),
+        }
+    }
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language.catalog(), Ordering::Relaxed);
+}
+
+// Resolves `{ "..." }` literals now and keeps `{ $name }` for `tf`.
+fn resolve(value: &str) -> String {
+    let mut resolved = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('{') {
+        resolved.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            rest = &rest[start..];
+            break;
+        };
+        // `{ "}" }` holds a brace of its own
+        let end = if rest[start + 1..end].trim() == "\"" {
+            rest[end + 1..].find('}').map_or(end, |next| end + 1 + next)
+        } else {
+            end
+        };
+        let inner = rest[start + 1..end].trim();
+        match inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(literal) => resolved.push_str(literal),
+            None => resolved.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    resolved.push_str(rest);
+    resolved
+}
+
+fn parse(catalog: &str) -> HashMap<&str, String> {
+    let mut messages: HashMap<&str, String> = HashMap::new();
+    let mut current: Option<&str> = None;
+    for line in catalog.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // A selector's closing brace may start its line
+        if line.starts_with([' ', '}']) {
+            if let Some(message) = current.and_then(|key| messages.get_mut(key)) {
+                if !message.is_empty() {
+                    message.push('\n');
+                }
+                message.push_str(&resolve(line.trim()));
+            }
+            continue;
+        }
+        current = line.split_once('=').map(|(key, value)| {
+            let key = key.trim();
+            messages.insert(key, resolve(value.trim()));
+            key
+        });
+    }
+    messages
+}
+
+pub fn t(key: &'static str) -> &'static str {
+    let messages = &*MESSAGES;
+    messages[CURRENT.load(Ordering::Relaxed)]
+        .get(key)
+        .or_else(|| messages[0].get(key))
+        .map_or(key, |message| message.as_str())
+}
+
+// English and German both only tell one from everything else
+fn plural_category(value: &str) -> &'static str {
+    if value == "1" { "one" } else { "other" }
+}
+
+// Replaces each `{ $name -> ... }` selector with the variant matching the
+// value exactly, or its plural category, or else the `*` default.
+fn select(message: &str, args: &[(&str, String)]) -> String {
+    let mut selected = String::new();
+    let mut rest = message;
+    while let Some(start) = rest.find("{ $") {
+        let Some((name, body)) = rest[start + 3..].split_once(" ->") else {
+            break;
+        };
+        let Some(end) = body.find("\n}") else {
+            break;
+        };
+        if name.contains(['}', '\n']) {
+            selected.push_str(&rest[..start + 3]);
+            rest = &rest[start + 3..];
+            continue;
+        }
+        let value = args
+            .iter()
+            .find(|(arg, _)| *arg == name)
+            .map_or("", |(_, value)| value.as_str());
+        let variants: Vec<(bool, &str, &str)> = body[..end]
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                let (default, line) = match line.strip_prefix('*') {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                let (key, text) = line.strip_prefix('[')?.split_once(']')?;
+                Some((default, key.trim(), text.trim()))
+            })
+            .collect();
+        let variant = variants
+            .iter()
+            .find(|(_, key, _)| *key == value)
+            .or_else(|| {
+                variants
+                    .iter()
+                    .find(|(_, key, _)| *key == plural_category(value))
+            })
+            .or_else(|| variants.iter().find(|(default, _, _)| *default));
+        selected.push_str(&rest[..start]);
+        selected.push_str(variant.map_or("", |(_, _, text)| text));
+        rest = &body[end + 2..];
+    }
+    selected.push_str(rest);
+    selected
+}
+
+// A message with its selectors resolved and `{ $name }` variables filled in.
+pub fn tf(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let values: Vec<(&str, String)> = args
+        .iter()
+        .map(|(name, value)| (*name, value.to_string()))
+        .collect();
+    let mut message = select(t(key), &values);
+    for (name, value) in &values {
+        message = message.replace(&format!("{{ ${} }}", name), value);
+    }
+    message
+}
+
+#[cfg(test)]
+mod i18n_tests {
+    use super::*;
+
+    fn variables(message: &str) -> Vec<&str> {
+        let mut variables: Vec<&str> = message
+            .split("{ $")
+            .skip(1)
+            .filter_map(|part| part.split_once(" }").map(|(name, _)| name))
+            .collect();
+        variables.sort();
+        variables.dedup();
+        variables
+    }
+
+    #[test]
+    fn catalogs_match_english() {
+        let english = &MESSAGES[0];
+        for messages in &MESSAGES[1..] {
+            let mut keys: Vec<&&str> = english.keys().collect();
+            keys.retain(|key| !messages.contains_key(**key));
+            assert!(keys.is_empty(), "untranslated: {:?}", keys);
+            for (key, message) in messages {
+                let source = english.get(key);
+                assert!(source.is_some(), "unknown message: {}", key);
+                assert_eq!(variables(message), variables(source.unwrap()), "{}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn placeables_resolve() {
+        assert_eq!(
+            resolve(r#"~/notes/{"{"}date{"}"}.md in { $folder }"#),
+            "~/notes/{date}.md in { $folder }"
+        );
+    }
+
+    #[test]
+    fn selectors_pick_a_variant() {
+        let messages = parse(
+            "sessions = { $count ->\n    [one] { $count } session\n   *[other] { $count } sessions\n}\n\
+             totals = 🍅 { $done ->\n    [0] nothing yet\n   *[other] { $done } done\n    } today",
+        );
+        let pick = |key: &str, name: &str, value: &str| {
+            let values = [(name, value.to_string())];
+            select(&messages[key], &values).replace(&format!("{{ ${} }}", name), value)
+        };
+        assert_eq!(pick("sessions", "count", "1"), "1 session");
+        assert_eq!(pick("sessions", "count", "3"), "3 sessions");
+        assert_eq!(pick("totals", "done", "0"), "🍅 nothing yet today");
+        assert_eq!(pick("totals", "done", "1"), "🍅 1 done today");
+    }
+}
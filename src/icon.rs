@@ -0,0 +1,198 @@
+// The tray and window icon, drawn at runtime so it can show what the timer
+// is doing: a red tomato during work, a green one during breaks and a grey
+// one while paused, with the minutes left on top.
+pub const SIZE: u32 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    Break,
+    Paused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IconStatus {
+    pub phase: Phase,
+    pub minutes: Option<u32>,
+}
+
+// 3x5 digits, one row per byte with the leftmost pixel in the highest bit
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+fn body_color(phase: Phase) -> [f32; 3] {
+    match phase {
+        Phase::Work => [0.90, 0.22, 0.16],
+        Phase::Break => [0.30, 0.70, 0.32],
+        Phase::Paused => [0.58, 0.58, 0.58],
+    }
+}
+
+struct Canvas {
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Canvas {
+    // Blends `color` over a pixel, `coverage` being how much of it is hit
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 3], coverage: f32) {
+        if x >= SIZE || y >= SIZE || coverage <= 0.0 {
+            return;
+        }
+        let coverage = coverage.min(1.0);
+        let pixel = &mut self.pixels[(y * SIZE + x) as usize];
+        for (channel, value) in pixel.iter_mut().zip(color) {
+            *channel = *channel * (1.0 - coverage) + value * coverage;
+        }
+        pixel[3] = pixel[3] + (1.0 - pixel[3]) * coverage;
+    }
+
+    fn ellipse(&mut self, center: (f32, f32), radii: (f32, f32), color: [f32; 3]) {
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let dx = (x as f32 + 0.5 - center.0) / radii.0;
+                let dy = (y as f32 + 0.5 - center.1) / radii.1;
+                // Roughly a pixel of soft edge
+                let edge = (1.0 - (dx * dx + dy * dy).sqrt()) * radii.0.min(radii.1);
+                self.blend(x, y, color, edge + 0.5);
+            }
+        }
+    }
+
+    fn rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: [f32; 3]) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.blend(px, py, color, 1.0);
+            }
+        }
+    }
+
+    // Digits centred on the body, with a dark outline to stay legible
+    fn number(&mut self, value: u32, center_y: u32) {
+        let digits: Vec<usize> = value
+            .to_string()
+            .bytes()
+            .map(|digit| (digit - b'0') as usize)
+            .collect();
+        let scale = if digits.len() > 2 { 3 } else { 4 };
+        let width = digits.len() as u32 * 4 * scale - scale;
+        let left = (SIZE - width.min(SIZE)) / 2;
+        let top = center_y - 5 * scale / 2;
+        for (outline, color) in [(1, [0.15, 0.05, 0.05]), (0, [1.0, 1.0, 1.0])] {
+            for (index, digit) in digits.iter().enumerate() {
+                for (row, bits) in DIGITS[*digit].iter().enumerate() {
+                    for column in 0..3 {
+                        if bits & (0b100 >> column) == 0 {
+                            continue;
+                        }
+                        let x = left + (index as u32 * 4 + column) * scale;
+                        let y = top + row as u32 * scale;
+                        self.rect(
+                            x.saturating_sub(outline),
+                            y.saturating_sub(outline),
+                            scale + 2 * outline,
+                            scale + 2 * outline,
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Straight RGBA, SIZE pixels square
+pub fn render(status: IconStatus) -> Vec<u8> {
+    let mut canvas = Canvas {
+        pixels: vec![[0.0; 4]; (SIZE * SIZE) as usize],
+    };
+    let size = SIZE as f32;
+    let [r, g, b] = body_color(status.phase);
+    canvas.ellipse(
+        (size * 0.5, size * 0.56),
+        (size * 0.44, size * 0.40),
+        [r, g, b],
+    );
+    // A highlight, and the leaves on top
+    canvas.ellipse(
+        (size * 0.34, size * 0.40),
+        (size * 0.10, size * 0.07),
+        [
+            (r + 0.35).min(1.0),
+            (g + 0.35).min(1.0),
+            (b + 0.35).min(1.0),
+        ],
+    );
+    let leaf = [0.18, 0.48, 0.16];
+    canvas.ellipse((size * 0.40, size * 0.17), (size * 0.13, size * 0.05), leaf);
+    canvas.ellipse((size * 0.60, size * 0.17), (size * 0.13, size * 0.05), leaf);
+    canvas.rect(SIZE * 15 / 32, SIZE / 32, SIZE / 16, SIZE * 5 / 32, leaf);
+
+    if let Some(minutes) = status.minutes {
+        canvas.number(minutes.min(999), SIZE * 18 / 32);
+    }
+
+    canvas
+        .pixels
+        .iter()
+        .flat_map(|pixel| {
+            // Colors are blended premultiplied, so undo that for RGBA
+            let alpha = pixel[3];
+            let channel = |value: f32| {
+                if alpha > 0.0 {
+                    (value / alpha * 255.0).round().clamp(0.0, 255.0) as u8
+                } else {
+                    0
+                }
+            };
+            [
+                channel(pixel[0]),
+                channel(pixel[1]),
+                channel(pixel[2]),
+                (alpha * 255.0).round() as u8,
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod icon_tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], x: u32, y: u32) -> &[u8] {
+        let start = ((y * SIZE + x) * 4) as usize;
+        &rgba[start..start + 4]
+    }
+
+    #[test]
+    fn icons_follow_the_phase_and_show_the_minutes() {
+        let work = render(IconStatus {
+            phase: Phase::Work,
+            minutes: None,
+        });
+        assert_eq!(work.len(), (SIZE * SIZE * 4) as usize);
+        // Transparent corners, a red body
+        assert_eq!(pixel(&work, 0, SIZE - 1)[3], 0);
+        let body = pixel(&work, SIZE / 2, SIZE * 3 / 4);
+        assert!(body[0] > 200 && body[1] < 100 && body[3] == 255);
+
+        let paused = render(IconStatus {
+            phase: Phase::Paused,
+            minutes: Some(18),
+        });
+        let body = pixel(&paused, SIZE / 2, SIZE * 7 / 8);
+        assert_eq!(body[0], body[1]);
+        // The top-left pixel of the 1 is white
+        let one = pixel(&paused, (SIZE - 28) / 2 + 4, SIZE * 18 / 32 - 10);
+        assert_eq!(one, [255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::DateTime;
+
+// A completed work period, as stored in the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusSession {
+    pub ended_at: i64,
+    pub duration_seconds: u32,
+    pub device_id: String,
+}
+
+fn timestamp(unix: i64) -> String {
+    DateTime::from_timestamp(unix, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+// An iCalendar feed with one event per session. Times are in UTC so that
+// calendar apps show them in whatever zone they are set to, and UIDs stay
+// stable so re-importing or re-subscribing never duplicates events.
+pub fn calendar(sessions: &[FocusSession]) -> String {
+    let now = timestamp(crate::db::unix_now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//roth-pomodoro//Focus Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Focus Sessions".to_string(),
+    ];
+    for session in sessions {
+        let minutes = session.duration_seconds / 60;
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}-{}@roth-pomodoro",
+                session.ended_at, session.device_id
+            ),
+            format!("DTSTAMP:{}", now),
+            format!(
+                "DTSTART:{}",
+                timestamp(session.ended_at - session.duration_seconds as i64)
+            ),
+            format!("DTEND:{}", timestamp(session.ended_at)),
+            format!("SUMMARY:🍅 Focus ({} min)", minutes),
+            "TRANSP:OPAQUE".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut calendar = lines.join("\r\n");
+    calendar.push_str("\r\n");
+    calendar
+}
+
+#[cfg(test)]
+mod ics_tests {
+    use super::*;
+
+    #[test]
+    fn sessions_become_utc_events() {
+        let calendar = calendar(&[FocusSession {
+            ended_at: 1_717_164_000,
+            duration_seconds: 25 * 60,
+            device_id: "laptop".to_string(),
+        }]);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("\r\nUID:1717164000-laptop@roth-pomodoro\r\n"));
+        assert!(calendar.contains("\r\nDTSTART:20240531T133500Z\r\n"));
+        assert!(calendar.contains("\r\nDTEND:20240531T140000Z\r\n"));
+        assert!(calendar.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,419 @@
+use crate::i18n::{t, tf};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use serde_json::Value;
+use std::fmt;
+
+// Sessions closer together than this are taken to be the same one
+pub const DUPLICATE_WINDOW_SECONDS: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportFormat {
+    #[default]
+    PomotroidJson,
+    FocusToDoCsv,
+    TogglCsv,
+}
+
+impl ImportFormat {
+    pub const ALL: [ImportFormat; 3] = [
+        ImportFormat::PomotroidJson,
+        ImportFormat::FocusToDoCsv,
+        ImportFormat::TogglCsv,
+    ];
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFormat::PomotroidJson => write!(f, "Pomotroid (JSON)"),
+            ImportFormat::FocusToDoCsv => write!(f, "Focus To-Do (CSV)"),
+            ImportFormat::TogglCsv => write!(f, "Toggl Track (CSV)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedSession {
+    pub ended_at: i64,
+    pub duration_seconds: u32,
+    pub tag: String,
+}
+
+// What an import would do, shown before anything is written.
+#[derive(Debug, Clone, Default)]
+pub struct ImportPreview {
+    pub sessions: Vec<ImportedSession>,
+    pub duplicates: usize,
+    // Records that were not focus sessions or could not be read
+    pub skipped: usize,
+}
+
+impl ImportPreview {
+    pub fn summary(&self) -> String {
+        let range = match (self.sessions.first(), self.sessions.last()) {
+            (Some(first), Some(last)) => tf(
+                "import-range",
+                &[
+                    ("first", &day_label(first.ended_at)),
+                    ("last", &day_label(last.ended_at)),
+                ],
+            ),
+            _ => String::new(),
+        };
+        tf(
+            "import-summary",
+            &[
+                ("count", &self.sessions.len()),
+                ("range", &range),
+                ("duplicates", &self.duplicates),
+                ("skipped", &self.skipped),
+            ],
+        )
+    }
+}
+
+fn day_label(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|at| at.with_timezone(&Local).format("%-d %b %Y").to_string())
+        .unwrap_or_default()
+}
+
+// Reads the file and leaves out sessions within a minute of one in
+// `existing` (sorted end times) or earlier in the same file.
+pub fn preview(
+    format: ImportFormat,
+    contents: &str,
+    existing: &[i64],
+) -> Result<ImportPreview, String> {
+    let (records, skipped) = match format {
+        ImportFormat::PomotroidJson => parse_pomotroid(contents)?,
+        ImportFormat::FocusToDoCsv => parse_focus_todo(contents)?,
+        ImportFormat::TogglCsv => parse_toggl(contents)?,
+    };
+
+    let mut preview = ImportPreview {
+        skipped,
+        ..ImportPreview::default()
+    };
+    let mut seen: Vec<i64> = existing.to_vec();
+    let mut records = records;
+    records.sort_by_key(|session| session.ended_at);
+    for session in records {
+        let start = seen.partition_point(|t| *t < session.ended_at - DUPLICATE_WINDOW_SECONDS);
+        if seen
+            .get(start)
+            .is_some_and(|t| *t <= session.ended_at + DUPLICATE_WINDOW_SECONDS)
+        {
+            preview.duplicates += 1;
+            continue;
+        }
+        seen.insert(start, session.ended_at);
+        preview.sessions.push(session);
+    }
+    Ok(preview)
+}
+
+// RFC 3339, `2024-05-31 14:05[:00]` in local time, or unix seconds or
+// milliseconds.
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(number) = value.parse::<i64>() {
+        return Some(if number > 100_000_000_000 {
+            number / 1000
+        } else {
+            number
+        });
+    }
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.timestamp());
+    }
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y/%m/%d %H:%M:%S",
+        "%Y/%m/%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .and_then(local_timestamp)
+}
+
+fn local_timestamp(local: NaiveDateTime) -> Option<i64> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|at| at.timestamp())
+}
+
+// `00:25:00` or `25:00`
+fn parse_clock_duration(value: &str) -> Option<u32> {
+    let parts: Vec<u32> = value
+        .trim()
+        .split(':')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [hours, minutes, seconds] => Some(hours * 3600 + minutes * 60 + seconds),
+        [minutes, seconds] => Some(minutes * 60 + seconds),
+        _ => None,
+    }
+}
+
+fn session(ended_at: i64, duration_seconds: u32, tag: &str) -> Option<ImportedSession> {
+    (duration_seconds > 0).then(|| ImportedSession {
+        ended_at,
+        duration_seconds,
+        tag: tag.trim().to_string(),
+    })
+}
+
+// Pomotroid keeps finished rounds as JSON objects. Field names have varied
+// between versions, so the common spellings are all accepted; only work
+// rounds are imported.
+fn parse_pomotroid(contents: &str) -> Result<(Vec<ImportedSession>, usize), String> {
+    let json: Value = serde_json::from_str(contents)
+        .map_err(|err| tf("import-invalid-json", &[("error", &err)]))?;
+    let rounds = match &json {
+        Value::Array(rounds) => rounds,
+        Value::Object(object) => ["sessions", "rounds", "history", "entries"]
+            .iter()
+            .find_map(|key| object.get(*key).and_then(Value::as_array))
+            .ok_or(t("import-no-rounds"))?,
+        _ => return Err(t("import-no-rounds").to_string()),
+    };
+
+    let field = |round: &Value, keys: &[&str]| -> Option<String> {
+        keys.iter().find_map(|key| match round.get(*key)? {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        })
+    };
+
+    let mut sessions = Vec::new();
+    let mut skipped = 0;
+    for round in rounds {
+        let kind = field(round, &["type", "roundType", "kind"]).unwrap_or_default();
+        if !kind.is_empty()
+            && !matches!(kind.to_lowercase().as_str(), "work" | "focus" | "pomodoro")
+        {
+            skipped += 1;
+            continue;
+        }
+        let end = field(
+            round,
+            &["completedAt", "endedAt", "end", "endTime", "timestamp"],
+        )
+        .and_then(|value| parse_timestamp(&value));
+        let start = field(round, &["startedAt", "start", "startTime"])
+            .and_then(|value| parse_timestamp(&value));
+        let duration = field(round, &["duration", "durationSeconds", "elapsed"])
+            .and_then(|value| value.parse::<f64>().ok())
+            .map(|seconds| seconds.round() as i64)
+            .or_else(|| Some(end? - start?));
+        let tag = field(round, &["task", "label", "tag"]).unwrap_or_default();
+
+        let parsed = match (end.or_else(|| Some(start? + duration?)), duration) {
+            (Some(end), Some(duration)) if duration > 0 => session(end, duration as u32, &tag),
+            _ => None,
+        };
+        match parsed {
+            Some(parsed) => sessions.push(parsed),
+            None => skipped += 1,
+        }
+    }
+    Ok((sessions, skipped))
+}
+
+// Splits CSV into rows of fields, with quoted fields that may hold commas,
+// doubled quotes and line breaks.
+fn csv_rows(contents: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = contents.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                row.push(
+                    std::mem::take(&mut field)
+                        .trim_end_matches('\r')
+                        .to_string(),
+                );
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field.trim_end_matches('\r').to_string());
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+// CSV records keyed by their (lowercased) header.
+struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn parse(contents: &str) -> Result<Self, String> {
+        let mut rows = csv_rows(contents).into_iter();
+        let header = rows
+            .next()
+            .ok_or(t("import-empty-file"))?
+            .into_iter()
+            .map(|name| name.trim().to_lowercase())
+            .collect();
+        Ok(Self {
+            header,
+            rows: rows.collect(),
+        })
+    }
+
+    // The first column whose name contains any of `names`
+    fn column(&self, names: &[&str]) -> Option<usize> {
+        names
+            .iter()
+            .find_map(|name| self.header.iter().position(|h| h.contains(name)))
+    }
+
+    fn get(row: &[String], column: Option<usize>) -> &str {
+        column
+            .and_then(|index| row.get(index))
+            .map_or("", |value| value.trim())
+    }
+}
+
+// Focus To-Do's export has one row per pomodoro with its start and end time
+// and the task (and project) it was for.
+fn parse_focus_todo(contents: &str) -> Result<(Vec<ImportedSession>, usize), String> {
+    let table = Table::parse(contents)?;
+    let start = table.column(&["start"]);
+    let end = table.column(&["end"]);
+    if end.is_none() && start.is_none() {
+        return Err(t("import-no-time-columns").to_string());
+    }
+    let duration = table.column(&["duration", "minutes", "focus time"]);
+    let task = table.column(&["task"]);
+    let project = table.column(&["project"]);
+
+    let mut sessions = Vec::new();
+    let mut skipped = 0;
+    for row in &table.rows {
+        let start = parse_timestamp(Table::get(row, start));
+        let end = parse_timestamp(Table::get(row, end));
+        let minutes = Table::get(row, duration);
+        let duration = parse_clock_duration(minutes)
+            .or_else(|| Some((minutes.parse::<f64>().ok()? * 60.0).round() as u32))
+            .or_else(|| u32::try_from(end? - start?).ok());
+        let tag = match (Table::get(row, task), Table::get(row, project)) {
+            ("", project) => project,
+            (task, _) => task,
+        };
+        let parsed = match (end.or_else(|| Some(start? + duration? as i64)), duration) {
+            (Some(end), Some(duration)) => session(end, duration, tag),
+            _ => None,
+        };
+        match parsed {
+            Some(parsed) => sessions.push(parsed),
+            None => skipped += 1,
+        }
+    }
+    Ok((sessions, skipped))
+}
+
+// Toggl Track's detailed report CSV: `Start date`, `Start time`,
+// `Duration` (hh:mm:ss), `Description`, `Project` and more.
+fn parse_toggl(contents: &str) -> Result<(Vec<ImportedSession>, usize), String> {
+    let table = Table::parse(contents)?;
+    let (Some(start_date), Some(start_time), Some(duration)) = (
+        table.column(&["start date"]),
+        table.column(&["start time"]),
+        table.column(&["duration"]),
+    ) else {
+        return Err(t("import-toggl-columns").to_string());
+    };
+    let description = table.column(&["description"]);
+    let project = table.column(&["project"]);
+
+    let mut sessions = Vec::new();
+    let mut skipped = 0;
+    for row in &table.rows {
+        let start = NaiveDate::parse_from_str(Table::get(row, Some(start_date)), "%Y-%m-%d")
+            .ok()
+            .zip(NaiveTime::parse_from_str(Table::get(row, Some(start_time)), "%H:%M:%S").ok())
+            .and_then(|(date, time)| local_timestamp(date.and_time(time)));
+        let duration = parse_clock_duration(Table::get(row, Some(duration)));
+        let tag = match (Table::get(row, description), Table::get(row, project)) {
+            ("", project) => project,
+            (description, _) => description,
+        };
+        let parsed = match (start, duration) {
+            (Some(start), Some(duration)) => session(start + duration as i64, duration, tag),
+            _ => None,
+        };
+        match parsed {
+            Some(parsed) => sessions.push(parsed),
+            None => skipped += 1,
+        }
+    }
+    Ok((sessions, skipped))
+}
+
+#[cfg(test)]
+mod import_tests {
+    use super::*;
+
+    #[test]
+    fn toggl_csv_is_imported_without_duplicates() {
+        let csv = "\u{feff}User,Email,Project,Description,Start date,Start time,End date,End time,Duration,Tags\r\n\
+            Ann,ann@example.com,Thesis,\"Write, edit\",2024-05-31,14:05:00,2024-05-31,14:30:00,00:25:00,pomodoro\r\n\
+            Ann,ann@example.com,Thesis,,2024-05-31,15:00:00,2024-05-31,15:25:00,00:25:00,\r\n\
+            Ann,ann@example.com,Thesis,Broken,yesterday,15:00:00,,,00:25:00,\r\n";
+        let end = |hour: u32, minute: u32| {
+            Local
+                .with_ymd_and_hms(2024, 5, 31, hour, minute, 0)
+                .unwrap()
+                .timestamp()
+        };
+
+        let preview = preview(ImportFormat::TogglCsv, csv, &[end(15, 25) + 30]).unwrap();
+        assert_eq!(
+            preview.sessions,
+            [ImportedSession {
+                ended_at: end(14, 30),
+                duration_seconds: 1500,
+                tag: "Write, edit".to_string(),
+            }]
+        );
+        assert_eq!(preview.duplicates, 1);
+        assert_eq!(preview.skipped, 1);
+    }
+
+    #[test]
+    fn pomotroid_rounds_are_imported() {
+        let json = r#"[
+            {"type": "work", "startedAt": 1717164000000, "duration": 1500},
+            {"type": "short-break", "startedAt": 1717165500000, "duration": 300},
+            {"type": "work", "completedAt": "2024-05-31T15:00:00Z", "duration": 1500},
+            {"type": "work", "completedAt": "2024-05-31T15:00:20Z", "duration": 1500}
+        ]"#;
+
+        let preview = preview(ImportFormat::PomotroidJson, json, &[]).unwrap();
+        let ends: Vec<i64> = preview.sessions.iter().map(|s| s.ended_at).collect();
+        assert_eq!(ends, [1_717_165_500, 1_717_167_600]);
+        assert_eq!((preview.duplicates, preview.skipped), (1, 1));
+    }
+}
@@ -0,0 +1,239 @@
+use iced::futures::{SinkExt, Stream, channel::mpsc};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::Mutex,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt},
+    net::tcp::OwnedReadHalf,
+};
+
+// Instances find each other through a loopback socket whose port is kept
+// next to the database, so portable copies stay independent. A client
+// sends the greeting, its arguments one per line and an empty line; the
+// running instance answers with a single line.
+const GREETING: &str = "roth-pomodoro";
+const ACK: &str = "ok";
+const UNKNOWN_COMMAND: &str = "unknown command";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+// A client that doesn't finish its request in time, or sends more than
+// this, is dropped so it can't hold up the next one.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+static LISTENER: Mutex<Option<TcpListener>> = Mutex::new(None);
+
+// Subcommands that drive the running timer, e.g. from a hotkey daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Start,
+    Pause,
+    Skip,
+    Reset,
+    Toggle,
+    // Sent by editor plugins while the user is typing
+    Heartbeat,
+}
+
+impl Command {
+    pub const ALL: [Command; 6] = [
+        Command::Start,
+        Command::Pause,
+        Command::Skip,
+        Command::Reset,
+        Command::Toggle,
+        Command::Heartbeat,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Command::Start => "start",
+            Command::Pause => "pause",
+            Command::Skip => "skip",
+            Command::Reset => "reset",
+            Command::Toggle => "toggle",
+            Command::Heartbeat => "heartbeat",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|command| command.key() == key)
+    }
+}
+
+// Options such as `--portable` start with dashes; anything else is taken
+// as a subcommand.
+pub fn subcommand(args: &[String]) -> Option<&str> {
+    args.iter()
+        .map(String::as_str)
+        .find(|arg| !arg.starts_with("--"))
+}
+
+fn port_file() -> PathBuf {
+    crate::db::db_path().with_file_name("instance.port")
+}
+
+fn read_port() -> Option<u16> {
+    std::fs::read_to_string(port_file())
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+// Sends `args` to a running instance and returns its reply, or None when
+// there is no instance to talk to.
+pub fn send(args: &[String]) -> Option<String> {
+    send_to(read_port()?, args)
+}
+
+fn send_to(port: u16, args: &[String]) -> Option<String> {
+    let mut stream =
+        TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, port).into(), CONNECT_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT)).ok()?;
+
+    let mut request = format!("{GREETING}\n");
+    for arg in args {
+        request.push_str(&arg.replace('\n', " "));
+        request.push('\n');
+    }
+    request.push('\n');
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).ok()?;
+    Some(reply.trim_end().to_string())
+}
+
+// Any running instance answers, even requests it doesn't know. Whatever
+// else may have taken over a stale port doesn't answer like it.
+pub fn is_running() -> bool {
+    send(&["ping".to_string()]).is_some_and(|reply| reply == ACK || reply == UNKNOWN_COMMAND)
+}
+
+fn acknowledged(port: u16, args: &[String]) -> bool {
+    send_to(port, args).is_some_and(|reply| reply == ACK)
+}
+
+// Becomes the primary instance unless one is already running. Returns false
+// when the arguments were handed to the running instance instead. A port
+// file left behind by a crash, whose port another program may have taken
+// since, is replaced.
+pub fn claim(args: &[String]) -> bool {
+    if read_port().is_some_and(|port| acknowledged(port, args)) {
+        return false;
+    }
+    let _ = std::fs::remove_file(port_file());
+
+    match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+        Ok(listener) => {
+            if let Ok(address) = listener.local_addr()
+                && let Err(err) = std::fs::write(port_file(), address.port().to_string())
+            {
+                println!("Error writing instance port: {}", err);
+            }
+            if let Ok(mut slot) = LISTENER.lock() {
+                *slot = Some(listener);
+            }
+        }
+        Err(err) => println!("Error listening for other instances: {}", err),
+    }
+    true
+}
+
+// The arguments after the greeting, or None when it isn't one of us
+// talking or the request is cut off.
+async fn read_args(reader: OwnedReadHalf) -> Option<Vec<String>> {
+    let mut lines = tokio::io::BufReader::new(reader)
+        .take(MAX_REQUEST_BYTES)
+        .lines();
+    if lines.next_line().await.ok()?? != GREETING {
+        return None;
+    }
+    let mut args = Vec::new();
+    loop {
+        let line = lines.next_line().await.ok()??;
+        if line.is_empty() {
+            return Some(args);
+        }
+        args.push(line);
+    }
+}
+
+async fn serve(stream: tokio::net::TcpStream, mut output: mpsc::Sender<Option<Command>>) {
+    let (reader, mut writer) = stream.into_split();
+    let Ok(Some(args)) = tokio::time::timeout(REQUEST_TIMEOUT, read_args(reader)).await else {
+        return;
+    };
+
+    let request = match subcommand(&args) {
+        None => None,
+        Some(key) => match Command::from_key(key) {
+            Some(command) => Some(command),
+            None => {
+                let _ = writer
+                    .write_all(format!("{UNKNOWN_COMMAND}\n").as_bytes())
+                    .await;
+                return;
+            }
+        },
+    };
+    let _ = writer.write_all(format!("{ACK}\n").as_bytes()).await;
+    let _ = output.send(request).await;
+}
+
+// Requests from processes started after this one: a command to run, or
+// None when it was a plain launch that should raise the window. Each
+// connection gets its own task, so a stalled client can't make later
+// launches think nothing is running.
+pub fn requests() -> impl Stream<Item = Option<Command>> {
+    iced::stream::channel(8, async |output| {
+        let Some(listener) = LISTENER.lock().ok().and_then(|mut slot| slot.take()) else {
+            return;
+        };
+        let listener = match listener
+            .set_nonblocking(true)
+            .and_then(|_| tokio::net::TcpListener::from_std(listener))
+        {
+            Ok(listener) => listener,
+            Err(err) => {
+                println!("Error listening for other instances: {}", err);
+                return;
+            }
+        };
+
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve(stream, output.clone()));
+        }
+    })
+}
+
+#[cfg(test)]
+mod instance_tests {
+    use super::*;
+    use std::io::Read;
+
+    // Answers one connection with `reply` after reading the request
+    fn serve_once(reply: &'static str) -> u16 {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 64];
+            let _ = stream.read(&mut request);
+            let _ = stream.write_all(reply.as_bytes());
+        });
+        port
+    }
+
+    #[test]
+    fn only_an_instance_that_acknowledges_is_running() {
+        assert!(acknowledged(serve_once("ok\n"), &[]));
+        // Another service took over the port of a crashed instance
+        assert!(!acknowledged(serve_once("SSH-2.0-OpenSSH_9.6\r\n"), &[]));
+        assert!(!acknowledged(serve_once(""), &[]));
+    }
+}
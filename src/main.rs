@@ -1,12 +1,60 @@
 use iced::window;
 
+mod achievements;
+mod api;
+mod break_screen;
+mod clock;
+mod config;
+mod daily_log;
 mod db;
+mod dial;
+mod experiment;
+mod focus_guard;
+mod font;
+mod github;
+mod history;
+mod i18n;
+mod icon;
+mod ics;
+mod import;
+mod instance;
+mod meetings;
+mod mqtt;
+mod obs;
+mod overlay;
+mod palette;
+mod plan;
+mod platform;
 mod pomodoro_timer;
+mod profile;
+mod push;
+mod quick_timer;
+mod report;
+mod review;
+mod schedule;
+mod secrets;
+mod sequence;
 mod settings;
+mod shortcuts;
+mod stats;
+mod stopwatch;
+mod streak;
+mod sync;
+mod sync_server;
+mod team;
+mod telegram;
+mod todoist;
+mod toggl;
+mod window_titles;
+mod worklog;
 
+use i18n::tf;
 use pomodoro_timer::PomodoroTimer;
+use settings::{Settings, WindowGeometry};
+use std::path::Path;
 
 pub mod audio;
+mod background;
 // const WORK_LENGTH: u32 = 15;
 // const BREAK_LENGTH: u32 = 3;
 // const LONG_BREAK_LENGTH: u32 = 9;
@@ -15,20 +63,28 @@ pub const WORK_LENGTH: u32 = 1500;
 pub const BREAK_LENGTH: u32 = 300;
 pub const LONG_BREAK_LENGTH: u32 = 900;
 
-fn main() -> iced::Result {
-    // Add a logo for this app
-    iced::application(
-        PomodoroTimer::new,
-        PomodoroTimer::update,
-        PomodoroTimer::view,
-    )
-    .title("Pomodoro Timer")
-    .subscription(PomodoroTimer::subscription)
-    .theme(iced::Theme::CatppuccinLatte)
-    .window(window::Settings {
-        size: iced::Size::new(600.0, 500.0),
+pub fn main_window_level(always_on_top: bool) -> window::Level {
+    if always_on_top {
+        window::Level::AlwaysOnTop
+    } else {
+        window::Level::Normal
+    }
+}
+
+pub fn main_window_settings(geometry: WindowGeometry, settings: &Settings) -> window::Settings {
+    window::Settings {
+        visible: !(settings.start_minimized && platform::tray::SUPPORTED),
+        // Close requests are handled by the timer, which may hide to the tray
+        exit_on_close_request: false,
+        size: geometry.size,
+        position: match geometry.position {
+            Some(point) => window::Position::Specific(point),
+            None => window::Position::Default,
+        },
+        min_size: Some(WindowGeometry::MIN_SIZE),
         resizable: true,
-        level: window::Level::Normal,
+        level: main_window_level(settings.always_on_top),
+        transparent: true,
         icon: Some(
             window::icon::from_file(concat!(
                 env!("CARGO_MANIFEST_DIR"),
@@ -37,6 +93,89 @@ fn main() -> iced::Result {
             .expect("icon file should be reachable and in ICO file format"),
         ),
         ..Default::default()
-    })
+    }
+}
+
+// `backup` and `restore <file>` work on the database directly and exit.
+fn database_command(key: &str, args: &[String]) -> Option<Result<String, String>> {
+    match key {
+        "backup" => Some(db::backup().map(|path| tf("saved-file", &[("path", &path.display())]))),
+        "restore" => {
+            let Some(path) = args.iter().filter(|arg| !arg.starts_with("--")).nth(1) else {
+                return Some(Err("Usage: roth-pomodoro restore <backup file>".to_string()));
+            };
+            if instance::is_running() {
+                return Some(Err(
+                    "Quit roth-pomodoro before restoring, or restore from its settings".to_string(),
+                ));
+            }
+            Some(db::restore(Path::new(path)).map(|previous| {
+                tf(
+                    "restored-backup",
+                    &[("path", path), ("previous", &previous.display())],
+                )
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn main() -> iced::Result {
+    // Database subcommands run here; the others only talk to the running
+    // instance
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(key) = instance::subcommand(&args)
+        && let Some(result) = database_command(key, &args)
+    {
+        db::flush();
+        match result {
+            Ok(message) => {
+                println!("{}", message);
+                return Ok(());
+            }
+            Err(err) => {
+                println!("{}", err);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(key) = instance::subcommand(&args) {
+        if instance::Command::from_key(key).is_none() {
+            let commands: Vec<&str> = instance::Command::ALL.iter().map(|c| c.key()).collect();
+            println!(
+                "Unknown command '{}'. Use one of: {}, backup, restore",
+                key,
+                commands.join(", ")
+            );
+            std::process::exit(2);
+        }
+        match instance::send(&args).as_deref() {
+            Some("ok") => return Ok(()),
+            Some(reply) => println!("{}", reply),
+            None => println!("roth-pomodoro is not running"),
+        }
+        std::process::exit(1);
+    }
+
+    // A second launch just brings the running timer to the front
+    if !instance::claim(&args) {
+        return Ok(());
+    }
+
+    if clock::is_fast() {
+        println!("Fast clock: every second counts as a minute");
+    }
+
+    // Add a logo for this app
+    iced::daemon(
+        PomodoroTimer::new,
+        PomodoroTimer::update,
+        PomodoroTimer::view,
+    )
+    .title(PomodoroTimer::title)
+    .subscription(PomodoroTimer::subscription)
+    .theme(PomodoroTimer::theme)
+    .scale_factor(PomodoroTimer::scale_factor)
+    .style(PomodoroTimer::style)
     .run()
 }
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use iced::futures::{SinkExt, Stream};
+use std::time::Duration;
+
+const REFRESH_EVERY: Duration = Duration::from_secs(10 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+// Only meetings this close are worth keeping around
+const LOOKAHEAD_HOURS: i64 = 24;
+
+// Unfolds continuation lines and splits `NAME;PARAMS:value` into the name
+// with its parameters and the value.
+fn properties(ics: &str) -> Vec<(String, String)> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+        .into_iter()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.to_uppercase(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+// `20240531T140000Z` is UTC. Times with a TZID or without a zone are taken
+// as local time, which is right for anyone whose meetings are in their own
+// zone. All-day dates return None: they are not meetings.
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    match value.strip_suffix('Z') {
+        Some(utc) => {
+            let utc = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+            Some(
+                Utc.from_utc_datetime(&utc)
+                    .with_timezone(&Local)
+                    .naive_local(),
+            )
+        }
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok(),
+    }
+}
+
+fn timestamp(local: NaiveDateTime) -> Option<i64> {
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|time| time.timestamp())
+}
+
+#[derive(Default)]
+struct Event {
+    start: Option<NaiveDateTime>,
+    rule: Option<String>,
+    cancelled: bool,
+    free: bool,
+}
+
+// Daily and weekly repeats (with INTERVAL, BYDAY and UNTIL) cover the usual
+// standups and one-on-ones; other rules only count their first occurrence.
+fn occurs_on(start: NaiveDateTime, rule: &str, day: NaiveDate) -> bool {
+    let mut frequency = "";
+    let mut interval = 1;
+    let mut weekdays = Vec::new();
+    let mut until = None;
+    for part in rule.split(';') {
+        match part.split_once('=') {
+            Some(("FREQ", value)) => frequency = value,
+            Some(("INTERVAL", value)) => interval = value.parse().unwrap_or(1).max(1),
+            Some(("BYDAY", value)) => {
+                weekdays = value
+                    .split(',')
+                    .map(|day| day.trim_start_matches(|c: char| !c.is_alphabetic()))
+                    .map(str::to_string)
+                    .collect()
+            }
+            Some(("UNTIL", value)) => {
+                until = parse_time(value).or_else(|| {
+                    NaiveDate::parse_from_str(value, "%Y%m%d")
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(23, 59, 59))
+                })
+            }
+            _ => {}
+        }
+    }
+
+    let occurrence = day.and_time(start.time());
+    if day < start.date() || until.is_some_and(|until| occurrence > until) {
+        return false;
+    }
+    let days = (day - start.date()).num_days();
+    match frequency {
+        "DAILY" => days % interval == 0,
+        "WEEKLY" => {
+            let weekday = day.weekday().to_string()[..2].to_uppercase();
+            let on_day = if weekdays.is_empty() {
+                day.weekday() == start.weekday()
+            } else {
+                weekdays.contains(&weekday)
+            };
+            let first_monday = start.date()
+                - chrono::Duration::days(start.weekday().num_days_from_monday() as i64);
+            let weeks = (day - first_monday).num_days() / 7;
+            on_day && weeks % interval == 0
+        }
+        _ => day == start.date(),
+    }
+}
+
+// Start times of the meetings in an iCalendar feed that begin within the
+// next day, soonest first. Cancelled events and ones marked as free time
+// are left out.
+pub fn upcoming(ics: &str, now: DateTime<Local>) -> Vec<i64> {
+    let horizon = now + chrono::Duration::hours(LOOKAHEAD_HOURS);
+    let mut starts = Vec::new();
+    let mut event: Option<Event> = None;
+
+    for (name, value) in properties(ics) {
+        let name = name.split(';').next().unwrap_or_default();
+        match (name, event.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => event = Some(Event::default()),
+            ("DTSTART", Some(event)) => event.start = parse_time(&value),
+            ("RRULE", Some(event)) => event.rule = Some(value),
+            ("STATUS", Some(event)) => event.cancelled = value == "CANCELLED",
+            ("TRANSP", Some(event)) => event.free = value == "TRANSPARENT",
+            ("END", Some(_)) if value == "VEVENT" => {
+                let Some(Event {
+                    start: Some(start),
+                    rule,
+                    cancelled: false,
+                    free: false,
+                }) = event.take()
+                else {
+                    continue;
+                };
+                let occurrences = match rule {
+                    Some(rule) => [now.date_naive(), horizon.date_naive()]
+                        .into_iter()
+                        .filter(|day| occurs_on(start, &rule, *day))
+                        .filter_map(|day| timestamp(day.and_time(start.time())))
+                        .collect(),
+                    None => timestamp(start).into_iter().collect::<Vec<_>>(),
+                };
+                starts.extend(
+                    occurrences
+                        .into_iter()
+                        .filter(|start| (now.timestamp()..=horizon.timestamp()).contains(start)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    starts.sort_unstable();
+    starts.dedup();
+    starts
+}
+
+fn fetch(url: &str) -> Result<String, String> {
+    ureq::AgentBuilder::new()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .get(url)
+        .call()
+        .map_err(|err| match err {
+            // Secret calendar addresses should not end up in the log
+            ureq::Error::Status(code, _) => format!("HTTP {}", code),
+            ureq::Error::Transport(transport) => transport.kind().to_string(),
+        })?
+        .into_string()
+        .map_err(|err| err.to_string())
+}
+
+// Downloads the calendar now and every few minutes after, yielding the
+// upcoming meeting start times each time.
+pub fn watch(url: &str) -> impl Stream<Item = Vec<i64>> + use<> {
+    let url = url.to_string();
+    iced::stream::channel(1, async move |mut output| {
+        loop {
+            let fetched = {
+                let url = url.clone();
+                tokio::task::spawn_blocking(move || fetch(&url)).await
+            };
+            match fetched {
+                Ok(Ok(ics)) => {
+                    let _ = output.send(upcoming(&ics, Local::now())).await;
+                }
+                Ok(Err(err)) => println!("Error fetching meeting calendar: {}", err),
+                Err(_) => return,
+            }
+            tokio::time::sleep(REFRESH_EVERY).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod meetings_tests {
+    use super::*;
+
+    #[test]
+    fn upcoming_meetings_are_found() {
+        // Friday morning
+        let now = Local.with_ymd_and_hms(2024, 5, 31, 9, 0, 0).unwrap();
+        let at = |day: u32, hour: u32, minute: u32| {
+            Local
+                .with_ymd_and_hms(2024, 5, day, hour, minute, 0)
+                .unwrap()
+                .timestamp()
+        };
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\nDTSTART;TZID=Europe/Berlin:20240531T141500\r\n\
+            SUMMARY:Planning\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20240531\r\nSUMMARY:Holiday\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART:20240531T110000\r\nSTATUS:CANCELLED\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART:20240531T120000\r\nTRANSP:TRANSPARENT\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART:20240530T080000\r\nSUMMARY:Yesterday\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART:20240506T093000\r\n\
+            RRULE:FREQ=WEEKLY;BYDAY=MO,\r\n WE,FR\r\nSUMMARY:Standup\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nDTSTART:20240401T100000\r\nRRULE:FREQ=DAILY;UNTIL=20240430\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        assert_eq!(upcoming(ics, now), [at(31, 9, 30), at(31, 14, 15)]);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::api::Status;
+use iced::futures::Stream;
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use std::{convert::Infallible, time::Duration};
+
+const DEFAULT_PORT: u16 = 1883;
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+// Name of the broker password in the system keychain
+pub const PASSWORD_NAME: &str = "mqtt-password";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MqttConfig {
+    // host or host:port; empty turns publishing off
+    pub broker: String,
+    pub topic_prefix: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            broker: String::new(),
+            topic_prefix: "roth-pomodoro".to_string(),
+            username: String::new(),
+            password: String::new(),
+        }
+    }
+}
+
+impl MqttConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.broker.trim().is_empty()
+    }
+
+    fn host_and_port(&self) -> Option<(String, u16)> {
+        let broker = self.broker.trim();
+        match broker.rsplit_once(':') {
+            Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+            None => Some((broker.to_string(), DEFAULT_PORT)),
+        }
+    }
+
+    fn topic(&self, name: &str) -> String {
+        format!(
+            "{}/{}",
+            self.topic_prefix.trim().trim_end_matches('/'),
+            name
+        )
+    }
+}
+
+// Retained messages for whatever changed since the last publish, so a
+// broker always holds the latest state and `remaining` is the only topic
+// that updates every second while running.
+fn messages(
+    config: &MqttConfig,
+    previous: Option<&Status>,
+    status: &Status,
+) -> Vec<(String, String)> {
+    let mut messages = Vec::new();
+    if previous.map(|p| p.state) != Some(status.state) {
+        messages.push((config.topic("state"), status.state.to_string()));
+    }
+    if previous.map(|p| p.period) != Some(status.period) {
+        messages.push((config.topic("period"), status.period.to_string()));
+    }
+    if previous.map(|p| p.remaining_seconds) != Some(status.remaining_seconds) {
+        messages.push((
+            config.topic("remaining"),
+            status.remaining_seconds.to_string(),
+        ));
+    }
+    if previous != Some(status) {
+        messages.push((
+            config.topic("status"),
+            serde_json::to_string(status).unwrap_or_default(),
+        ));
+    }
+    messages
+}
+
+// Publishes the timer status to the broker for as long as the subscription
+// lives. Status comes from the same feed as the HTTP API.
+pub fn publish_status(config: &MqttConfig) -> impl Stream<Item = Infallible> + use<> {
+    let config = config.clone();
+    iced::stream::channel(1, async move |_output| {
+        let Some((host, port)) = config.host_and_port() else {
+            println!("Invalid MQTT broker address: {}", config.broker);
+            return;
+        };
+
+        let mut options =
+            MqttOptions::new(format!("roth-pomodoro-{}", std::process::id()), host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+        options.set_last_will(LastWill::new(
+            config.topic("availability"),
+            "offline",
+            QoS::AtLeastOnce,
+            true,
+        ));
+        if !config.username.is_empty() {
+            options.set_credentials(config.username.clone(), config.password.clone());
+        }
+
+        let (client, mut eventloop) = AsyncClient::new(options, 16);
+        let mut updates = crate::api::subscribe();
+        let mut published: Option<Status> = None;
+        let mut connected = false;
+
+        loop {
+            tokio::select! {
+                event = eventloop.poll() => match event {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        connected = true;
+                        published = None;
+                        let _ = client.try_publish(
+                            config.topic("availability"),
+                            QoS::AtLeastOnce,
+                            true,
+                            "online",
+                        );
+                        updates.mark_changed();
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        if connected {
+                            println!("MQTT connection lost: {}", err);
+                        } else {
+                            println!("Error connecting to MQTT broker: {}", err);
+                        }
+                        connected = false;
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                    }
+                },
+                changed = updates.changed(), if connected => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    let Some(status) = updates.borrow_and_update().clone() else {
+                        continue;
+                    };
+                    // Never wait on the client here: its queue only drains
+                    // while the event loop is polled.
+                    for (topic, payload) in messages(&config, published.as_ref(), &status) {
+                        if let Err(err) = client.try_publish(topic, QoS::AtMostOnce, true, payload) {
+                            println!("Error publishing to MQTT: {}", err);
+                        }
+                    }
+                    published = Some(status);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod mqtt_tests {
+    use super::*;
+
+    #[test]
+    fn only_changed_topics_are_published() {
+        let config = MqttConfig {
+            broker: "localhost".to_string(),
+            topic_prefix: "home/pomodoro/".to_string(),
+            ..MqttConfig::default()
+        };
+        let status = Status {
+            state: "running",
+            period: "work",
+            remaining_seconds: 1500,
+            completed_pomodoros: 0,
+            guest: false,
+        };
+        let topics = |previous: Option<&Status>, status: &Status| -> Vec<String> {
+            messages(&config, previous, status)
+                .into_iter()
+                .map(|(topic, _)| topic)
+                .collect()
+        };
+
+        assert_eq!(
+            topics(None, &status),
+            [
+                "home/pomodoro/state",
+                "home/pomodoro/period",
+                "home/pomodoro/remaining",
+                "home/pomodoro/status"
+            ]
+        );
+
+        let tick = Status {
+            remaining_seconds: 1499,
+            ..status.clone()
+        };
+        assert_eq!(
+            topics(Some(&status), &tick),
+            ["home/pomodoro/remaining", "home/pomodoro/status"]
+        );
+        assert!(topics(Some(&tick), &tick).is_empty());
+
+        assert_eq!(
+            config.host_and_port(),
+            Some(("localhost".to_string(), 1883))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+// Plain text files that OBS text sources can read from disk: the countdown
+// and the period name, rewritten whenever they change.
+pub const COUNTDOWN_FILE: &str = "pomodoro-countdown.txt";
+pub const PERIOD_FILE: &str = "pomodoro-period.txt";
+
+struct TextOutput {
+    dir: PathBuf,
+    countdown: String,
+    period: String,
+}
+
+static WRITER: OnceLock<Sender<TextOutput>> = OnceLock::new();
+
+// Files are written on a worker thread so `update()` never waits on the
+// disk.
+fn spawn_writer() -> Sender<TextOutput> {
+    let (sender, receiver) = mpsc::channel::<TextOutput>();
+    thread::spawn(move || {
+        for output in receiver {
+            for (name, contents) in [
+                (COUNTDOWN_FILE, &output.countdown),
+                (PERIOD_FILE, &output.period),
+            ] {
+                if let Err(err) = std::fs::write(output.dir.join(name), contents) {
+                    println!("Error writing {}: {}", name, err);
+                }
+            }
+        }
+    });
+
+    sender
+}
+
+pub fn write(dir: PathBuf, countdown: String, period: String) {
+    let _ = WRITER.get_or_init(spawn_writer).send(TextOutput {
+        dir,
+        countdown,
+        period,
+    });
+}
@@ -0,0 +1,69 @@
+use crate::settings::{OverlayCorner, Settings};
+use iced::{
+    Background, Border, Color, Element, Length, Point, Size,
+    widget::{container, text},
+    window,
+};
+
+const MARGIN: f32 = 24.0;
+
+fn top_left(_window: Size, _monitor: Size) -> Point {
+    Point::new(MARGIN, MARGIN)
+}
+
+fn top_right(window: Size, monitor: Size) -> Point {
+    Point::new(monitor.width - window.width - MARGIN, MARGIN)
+}
+
+fn bottom_left(window: Size, monitor: Size) -> Point {
+    Point::new(MARGIN, monitor.height - window.height - MARGIN)
+}
+
+fn bottom_right(window: Size, monitor: Size) -> Point {
+    Point::new(
+        monitor.width - window.width - MARGIN,
+        monitor.height - window.height - MARGIN,
+    )
+}
+
+pub fn window_settings(settings: &Settings) -> window::Settings {
+    let font_size = settings.overlay_font_size as f32;
+    let position = match settings.overlay_corner {
+        OverlayCorner::TopLeft => top_left,
+        OverlayCorner::TopRight => top_right,
+        OverlayCorner::BottomLeft => bottom_left,
+        OverlayCorner::BottomRight => bottom_right,
+    };
+
+    window::Settings {
+        size: Size::new(font_size * 3.6, font_size * 1.8),
+        position: window::Position::SpecificWith(position),
+        resizable: false,
+        decorations: false,
+        transparent: true,
+        level: window::Level::AlwaysOnTop,
+        ..Default::default()
+    }
+}
+
+pub fn view<'a, Message: 'a>(
+    time_left: u32,
+    color: Color,
+    settings: &Settings,
+) -> Element<'a, Message> {
+    let countdown = text(format!("{:02}:{:02}", time_left / 60, time_left % 60))
+        .size(settings.overlay_font_size as f32)
+        .color(color);
+
+    container(countdown)
+        .center(Length::Fill)
+        .style(|_theme| container::Style {
+            background: Some(Background::Color(Color::from_rgba(0.0, 0.0, 0.0, 0.55))),
+            border: Border {
+                radius: 8.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into()
+}
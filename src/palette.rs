@@ -0,0 +1,142 @@
+use crate::i18n::t;
+use iced::{Color, Theme};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// The palette in use, read by style functions that only get the theme
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaletteKind {
+    #[default]
+    Standard,
+    HighContrast,
+    // Both keep work and breaks apart by blue against orange rather than
+    // red against green
+    Deuteranopia,
+    Protanopia,
+}
+
+impl PaletteKind {
+    pub const ALL: [PaletteKind; 4] = [
+        PaletteKind::Standard,
+        PaletteKind::HighContrast,
+        PaletteKind::Deuteranopia,
+        PaletteKind::Protanopia,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            PaletteKind::Standard => "standard",
+            PaletteKind::HighContrast => "high_contrast",
+            PaletteKind::Deuteranopia => "deuteranopia",
+            PaletteKind::Protanopia => "protanopia",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
+impl std::fmt::Display for PaletteKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PaletteKind::Standard => t("palette-standard"),
+            PaletteKind::HighContrast => t("palette-high-contrast"),
+            PaletteKind::Deuteranopia => t("palette-deuteranopia"),
+            PaletteKind::Protanopia => t("palette-protanopia"),
+        })
+    }
+}
+
+pub struct Palette {
+    pub work: Color,
+    pub short_break: Color,
+    pub long_break: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub button: Color,
+    pub button_hovered: Color,
+    pub button_pressed: Color,
+    pub button_text: Color,
+    pub high_contrast: bool,
+}
+
+const STANDARD: Palette = Palette {
+    work: Color::from_rgb(1.0, 0.42, 0.42),         // Tomato red
+    short_break: Color::from_rgb(0.31, 0.80, 0.77), // Light blue
+    long_break: Color::from_rgb(0.58, 0.88, 0.83),  // Teal
+    warning: Color::from_rgb(0.9, 0.5, 0.1),
+    error: Color::from_rgb(1.0, 0.3, 0.3),
+    button: Color::from_rgb(0.024, 0.58, 0.58),
+    button_hovered: Color::from_rgb(0.024, 0.48, 0.48),
+    button_pressed: Color::from_rgb(0.024, 0.42, 0.42),
+    button_text: Color::from_rgb(0.3, 0.3, 0.3),
+    high_contrast: false,
+};
+
+// Dark, saturated colors on white, and white on black buttons
+const HIGH_CONTRAST: Palette = Palette {
+    work: Color::from_rgb(0.70, 0.0, 0.0),
+    short_break: Color::from_rgb(0.0, 0.25, 0.65),
+    long_break: Color::from_rgb(0.0, 0.42, 0.15),
+    warning: Color::from_rgb(0.55, 0.27, 0.0),
+    error: Color::from_rgb(0.75, 0.0, 0.0),
+    button: Color::BLACK,
+    button_hovered: Color::from_rgb(0.2, 0.2, 0.2),
+    button_pressed: Color::from_rgb(0.35, 0.35, 0.35),
+    button_text: Color::WHITE,
+    high_contrast: true,
+};
+
+// From the Okabe-Ito palette: vermillion, sky blue and blue
+const DEUTERANOPIA: Palette = Palette {
+    work: Color::from_rgb(0.84, 0.37, 0.0),
+    short_break: Color::from_rgb(0.34, 0.71, 0.91),
+    long_break: Color::from_rgb(0.0, 0.45, 0.70),
+    warning: Color::from_rgb(0.80, 0.60, 0.0),
+    error: Color::from_rgb(0.84, 0.37, 0.0),
+    ..STANDARD
+};
+
+// Reds look dark without working red cones, so work is a bright orange
+const PROTANOPIA: Palette = Palette {
+    work: Color::from_rgb(0.90, 0.62, 0.0),
+    warning: Color::from_rgb(0.80, 0.47, 0.65),
+    error: Color::from_rgb(0.80, 0.47, 0.65),
+    ..DEUTERANOPIA
+};
+
+pub fn set(kind: PaletteKind) {
+    CURRENT.store(kind as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> &'static Palette {
+    match PaletteKind::ALL.get(CURRENT.load(Ordering::Relaxed) as usize) {
+        Some(PaletteKind::HighContrast) => &HIGH_CONTRAST,
+        Some(PaletteKind::Deuteranopia) => &DEUTERANOPIA,
+        Some(PaletteKind::Protanopia) => &PROTANOPIA,
+        _ => &STANDARD,
+    }
+}
+
+// Partway from one color to another, for fading between them
+pub fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let amount = amount.clamp(0.0, 1.0);
+    Color::from_rgba(
+        from.r + (to.r - from.r) * amount,
+        from.g + (to.g - from.g) * amount,
+        from.b + (to.b - from.b) * amount,
+        from.a + (to.a - from.a) * amount,
+    )
+}
+
+impl Palette {
+    pub fn theme(&self) -> Theme {
+        if self.high_contrast {
+            Theme::Light
+        } else {
+            Theme::CatppuccinLatte
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::NaiveDate;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanItem {
+    pub title: String,
+    pub estimate: u32,
+    pub actual: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanDay {
+    Today,
+    Tomorrow,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanDraft {
+    pub day: PlanDay,
+    // Title, estimate as typed, and pomodoros already done
+    pub items: Vec<(String, String, u32)>,
+}
+
+impl PlanDraft {
+    pub fn from_items(day: PlanDay, items: &[PlanItem]) -> Self {
+        Self {
+            day,
+            items: items
+                .iter()
+                .map(|item| (item.title.clone(), item.estimate.to_string(), item.actual))
+                .collect(),
+        }
+    }
+
+    // Every task needs a name and an estimate of at least one pomodoro.
+    pub fn parse(&self) -> Option<Vec<PlanItem>> {
+        self.items
+            .iter()
+            .map(|(title, estimate, actual)| {
+                let title = title.trim();
+                let estimate: u32 = estimate.trim().parse().ok().filter(|e| *e > 0)?;
+                (!title.is_empty()).then(|| PlanItem {
+                    title: title.to_string(),
+                    estimate,
+                    actual: *actual,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for PlanDraft {
+    fn default() -> Self {
+        Self {
+            day: PlanDay::Tomorrow,
+            items: Vec::new(),
+        }
+    }
+}
+
+// An entry in the timer screen's picker for the task being worked on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanChoice {
+    pub index: usize,
+    pub label: String,
+}
+
+impl fmt::Display for PlanChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+pub fn choices(items: &[PlanItem]) -> Vec<PlanChoice> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| PlanChoice {
+            index,
+            label: format!("{} · {}/{}", item.title, item.actual, item.estimate),
+        })
+        .collect()
+}
+
+// The first task that still has pomodoros left, so work moves down the
+// plan on its own.
+pub fn next_unfinished(items: &[PlanItem]) -> Option<usize> {
+    items.iter().position(|item| item.actual < item.estimate)
+}
+
+pub fn totals(items: &[PlanItem]) -> (u32, u32) {
+    items.iter().fold((0, 0), |(planned, done), item| {
+        (planned + item.estimate, done + item.actual)
+    })
+}
+
+// How close the estimates came, as a percentage: each task contributes the
+// smaller of estimate and actual against the larger, so overruns and
+// unfinished work count alike.
+pub fn accuracy<'a>(items: impl IntoIterator<Item = &'a PlanItem>) -> Option<u32> {
+    let (matched, total) = items.into_iter().fold((0, 0), |(matched, total), item| {
+        (
+            matched + item.estimate.min(item.actual),
+            total + item.estimate.max(item.actual),
+        )
+    });
+    (total > 0).then(|| matched * 100 / total)
+}
+
+#[derive(Debug, Clone)]
+pub struct DayPlan {
+    pub day: NaiveDate,
+    pub items: Vec<PlanItem>,
+}
+
+#[cfg(test)]
+mod plan_tests {
+    use super::*;
+
+    #[test]
+    fn accuracy_counts_overruns_and_shortfalls() {
+        let item = |estimate, actual| PlanItem {
+            title: "Task".to_string(),
+            estimate,
+            actual,
+        };
+
+        assert_eq!(accuracy(&[item(4, 4)]), Some(100));
+        // 3 of 4 done, and 2 more than the 2 planned
+        assert_eq!(accuracy(&[item(4, 3), item(2, 4)]), Some(62));
+        assert_eq!(accuracy(&[]), None);
+        assert_eq!(next_unfinished(&[item(2, 2), item(3, 1)]), Some(1));
+    }
+}
@@ -0,0 +1,61 @@
+// The title of the window in front, for telling focused time from
+// distracted time. None when it can't be told, e.g. on Wayland.
+#[cfg(target_os = "linux")]
+pub fn title() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowname"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The front app's name, with its window title when System Events may read it
+#[cfg(target_os = "macos")]
+pub fn title() -> Option<String> {
+    const SCRIPT: &str = "tell application \"System Events\"\n\
+                          set app to first application process whose frontmost is true\n\
+                          try\n\
+                          return (name of app) & \" - \" & (name of front window of app)\n\
+                          end try\n\
+                          return name of app\n\
+                          end tell";
+    let output = std::process::Command::new("osascript")
+        .args(["-e", SCRIPT])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "windows")]
+pub fn title() -> Option<String> {
+    use std::ffi::c_void;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn GetForegroundWindow() -> *mut c_void;
+        fn GetWindowTextW(window: *mut c_void, text: *mut u16, max: i32) -> i32;
+    }
+
+    let mut buffer = [0u16; 512];
+    // SAFETY: the buffer length is passed along, and a null window just
+    // gives no text.
+    let length = unsafe {
+        let window = GetForegroundWindow();
+        if window.is_null() {
+            return None;
+        }
+        GetWindowTextW(window, buffer.as_mut_ptr(), buffer.len() as i32)
+    };
+    Some(String::from_utf16_lossy(&buffer[..length.max(0) as usize]))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn title() -> Option<String> {
+    None
+}
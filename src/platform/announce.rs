@@ -0,0 +1,211 @@
+use iced::futures::Stream;
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+// Period changes go out as desktop notifications, which screen readers
+// (Orca, VoiceOver) read aloud as they arrive. iced has no accessibility
+// tree to announce through yet.
+static SENDER: OnceLock<Sender<Announcement>> = OnceLock::new();
+
+// The notification on screen, whose buttons are the only ones we act on
+#[cfg(target_os = "linux")]
+static SHOWN: AtomicU32 = AtomicU32::new(0);
+
+// Buttons offered on a notification, where the server supports them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    StartBreak,
+    SkipBreak,
+    Snooze,
+    StartWork,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [
+        Action::StartBreak,
+        Action::SkipBreak,
+        Action::Snooze,
+        Action::StartWork,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Action::StartBreak => "start-break",
+            Action::SkipBreak => "skip-break",
+            Action::Snooze => "snooze",
+            Action::StartWork => "start-work",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.key() == key)
+    }
+}
+
+struct Announcement {
+    summary: String,
+    body: String,
+    actions: Vec<(Action, String)>,
+}
+
+fn spawn() -> Sender<Announcement> {
+    let (sender, receiver) = mpsc::channel::<Announcement>();
+    thread::spawn(move || {
+        let mut notifier = Notifier::default();
+        for announcement in receiver {
+            notifier.notify(&announcement);
+        }
+    });
+
+    sender
+}
+
+pub fn announce(summary: String, body: String, actions: Vec<(Action, String)>) {
+    let _ = SENDER.get_or_init(spawn).send(Announcement {
+        summary,
+        body,
+        actions,
+    });
+}
+
+#[cfg(target_os = "linux")]
+pub fn actions() -> impl Stream<Item = Action> {
+    iced::stream::channel(4, async |output| {
+        if let Err(err) = watch_actions(output).await {
+            println!("Error watching notification actions: {}", err);
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn actions() -> impl Stream<Item = Action> {
+    iced::futures::stream::empty()
+}
+
+#[cfg(target_os = "linux")]
+async fn watch_actions(
+    mut output: iced::futures::channel::mpsc::Sender<Action>,
+) -> zbus::Result<()> {
+    use iced::futures::{SinkExt, StreamExt};
+
+    let connection = zbus::Connection::session().await?;
+    let notifications = zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.Notifications",
+        "/org/freedesktop/Notifications",
+        "org.freedesktop.Notifications",
+    )
+    .await?;
+    let mut invoked = notifications.receive_signal("ActionInvoked").await?;
+    while let Some(signal) = invoked.next().await {
+        let Ok((id, key)) = signal.body().deserialize::<(u32, String)>() else {
+            continue;
+        };
+        if id == SHOWN.load(Ordering::Relaxed)
+            && let Some(action) = Action::from_key(&key)
+        {
+            let _ = output.send(action).await;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct Notifier {
+    connection: Option<zbus::blocking::Connection>,
+    // Each announcement replaces the previous one instead of piling up
+    last_id: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl Notifier {
+    fn notify(&mut self, announcement: &Announcement) {
+        match self.send(announcement) {
+            Ok(id) => {
+                self.last_id = id;
+                SHOWN.store(id, Ordering::Relaxed);
+            }
+            Err(err) => println!("Error announcing period change: {}", err),
+        }
+    }
+
+    fn send(&mut self, announcement: &Announcement) -> zbus::Result<u32> {
+        use std::collections::HashMap;
+        use zbus::zvariant::Value;
+
+        let connection = match &self.connection {
+            Some(connection) => connection.clone(),
+            None => {
+                let connection = zbus::blocking::Connection::session()?;
+                self.connection = Some(connection.clone());
+                connection
+            }
+        };
+        // Normal urgency, and the default timeout
+        let hints = HashMap::from([("urgency", Value::U8(1))]);
+        let actions: Vec<&str> = announcement
+            .actions
+            .iter()
+            .flat_map(|(action, label)| [action.key(), label.as_str()])
+            .collect();
+        connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(
+                    "roth-pomodoro",
+                    self.last_id,
+                    "",
+                    announcement.summary.as_str(),
+                    announcement.body.as_str(),
+                    actions,
+                    hints,
+                    -1i32,
+                ),
+            )?
+            .body()
+            .deserialize()
+    }
+}
+
+#[cfg(target_os = "macos")]
+#[derive(Default)]
+struct Notifier;
+
+#[cfg(target_os = "macos")]
+impl Notifier {
+    // AppleScript notifications can't carry buttons
+    fn notify(&mut self, announcement: &Announcement) {
+        // AppleScript strings take the same escapes as Rust's Debug output
+        let script = format!(
+            "display notification {:?} with title {:?}",
+            announcement.body, announcement.summary
+        );
+        if let Err(err) = std::process::Command::new("osascript")
+            .args(["-e", &script])
+            .status()
+        {
+            println!("Error announcing period change: {}", err);
+        }
+    }
+}
+
+// Windows has no notification route without a toast library yet.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+#[derive(Default)]
+struct Notifier;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+impl Notifier {
+    fn notify(&mut self, _announcement: &Announcement) {}
+}
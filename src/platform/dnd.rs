@@ -0,0 +1,148 @@
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+    time::Duration,
+};
+
+// Only Linux desktops let apps silence notifications: KDE through its
+// notification server, GNOME through the banner setting. macOS Focus and
+// Windows Focus Assist have no public API.
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+pub enum DndCommand {
+    // Whether notifications should be silenced
+    Set(bool),
+    // Puts back what was there before, then answers
+    Restore(Sender<()>),
+}
+
+// Turns Do Not Disturb on and off from a worker thread, as the desktop
+// calls can block for a moment. Whatever the desktop had before is put
+// back when it's turned off.
+pub fn spawn() -> Sender<DndCommand> {
+    let (sender, receiver) = mpsc::channel::<DndCommand>();
+    thread::spawn(move || {
+        let mut silencer = Silencer::default();
+        for command in receiver {
+            match command {
+                DndCommand::Set(true) => silencer.enable(),
+                DndCommand::Set(false) => silencer.disable(),
+                DndCommand::Restore(done) => {
+                    silencer.disable();
+                    let _ = done.send(());
+                }
+            }
+        }
+        silencer.disable();
+    });
+
+    sender
+}
+
+// For quitting: the desktop shouldn't be left silenced after we're gone
+pub fn restore(sender: &Sender<DndCommand>) {
+    let (done, wait) = mpsc::channel();
+    if sender.send(DndCommand::Restore(done)).is_ok() {
+        let _ = wait.recv_timeout(Duration::from_secs(2));
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct Silencer {
+    connection: Option<zbus::blocking::Connection>,
+    // KDE lifts the inhibition when the cookie is handed back, or when our
+    // connection closes
+    cookie: Option<u32>,
+    // GNOME's banner setting as it was before
+    gnome_banners: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+impl Silencer {
+    const GNOME_SCHEMA: &str = "org.gnome.desktop.notifications";
+    const GNOME_KEY: &str = "show-banners";
+
+    fn enable(&mut self) {
+        if self.cookie.is_some() || self.gnome_banners.is_some() {
+            return;
+        }
+        match self.inhibit() {
+            Ok(cookie) => self.cookie = Some(cookie),
+            Err(inhibit_err) => match gsettings(&["get", Self::GNOME_SCHEMA, Self::GNOME_KEY]) {
+                Some(previous) => {
+                    if gsettings(&["set", Self::GNOME_SCHEMA, Self::GNOME_KEY, "false"]).is_some() {
+                        self.gnome_banners = Some(previous);
+                    }
+                }
+                None => println!("Error turning on Do Not Disturb: {}", inhibit_err),
+            },
+        }
+    }
+
+    fn inhibit(&mut self) -> zbus::Result<u32> {
+        use std::collections::HashMap;
+        use zbus::zvariant::Value;
+
+        let connection = match &self.connection {
+            Some(connection) => connection.clone(),
+            None => {
+                let connection = zbus::blocking::Connection::session()?;
+                self.connection = Some(connection.clone());
+                connection
+            }
+        };
+        let hints: HashMap<&str, Value> = HashMap::new();
+        connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Inhibit",
+                &("roth-pomodoro", "Focusing on a pomodoro", hints),
+            )?
+            .body()
+            .deserialize()
+    }
+
+    fn disable(&mut self) {
+        if let (Some(connection), Some(cookie)) = (&self.connection, self.cookie.take())
+            && let Err(err) = connection.call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "UnInhibit",
+                &(cookie,),
+            )
+        {
+            println!("Error turning off Do Not Disturb: {}", err);
+        }
+        if let Some(previous) = self.gnome_banners.take() {
+            gsettings(&["set", Self::GNOME_SCHEMA, Self::GNOME_KEY, &previous]);
+        }
+    }
+}
+
+// The trimmed output, or None if gsettings isn't there or fails
+#[cfg(target_os = "linux")]
+fn gsettings(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("gsettings")
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+#[derive(Default)]
+struct Silencer;
+
+#[cfg(not(target_os = "linux"))]
+impl Silencer {
+    fn enable(&mut self) {}
+
+    fn disable(&mut self) {}
+}
@@ -0,0 +1,227 @@
+#[cfg(target_os = "macos")]
+use {super::tray::TrayEvent, iced::futures::Stream};
+
+// On macOS the dock tile shows the minutes left as a badge and its menu
+// offers Start/Pause and Skip, while a menu bar extra stands in for the
+// tray icon other desktops have.
+#[cfg(target_os = "macos")]
+pub fn set_minutes(minutes: Option<u32>) {
+    appkit::set_minutes(minutes);
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_minutes(_minutes: Option<u32>) {}
+
+#[cfg(target_os = "macos")]
+pub fn set_tooltip(text: &str) {
+    appkit::set_tooltip(text);
+}
+
+#[cfg(target_os = "macos")]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    use iced::futures::{SinkExt, StreamExt};
+
+    iced::stream::channel(16, async |mut output| {
+        let receiver = appkit::EVENTS
+            .1
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take();
+        if let Some(mut receiver) = receiver {
+            while let Some(event) = receiver.next().await {
+                let _ = output.send(event).await;
+            }
+        }
+    })
+}
+
+#[cfg(target_os = "macos")]
+mod appkit {
+    use super::TrayEvent;
+    use crate::i18n::t;
+    use iced::futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+    use objc2::{
+        DefinedClass, MainThreadMarker, MainThreadOnly, define_class, msg_send,
+        rc::Retained,
+        runtime::{AnyClass, AnyObject, Imp, NSObject, Sel},
+        sel,
+    };
+    use objc2_app_kit::{
+        NSApplication, NSMenu, NSMenuItem, NSStatusBar, NSStatusItem, NSVariableStatusItemLength,
+    };
+    use objc2_foundation::NSString;
+    use std::{
+        cell::RefCell,
+        sync::{LazyLock, Mutex},
+    };
+
+    // Menu choices are made on the main thread and read by the tray
+    // subscription, which takes the receiver once
+    pub static EVENTS: LazyLock<(
+        UnboundedSender<TrayEvent>,
+        Mutex<Option<UnboundedReceiver<TrayEvent>>>,
+    )> = LazyLock::new(|| {
+        let (sender, receiver) = mpsc::unbounded();
+        (sender, Mutex::new(Some(receiver)))
+    });
+
+    const EVENTS_BY_TAG: [TrayEvent; 4] = [
+        TrayEvent::ShowWindow,
+        TrayEvent::StartStop,
+        TrayEvent::Skip,
+        TrayEvent::Quit,
+    ];
+
+    define_class!(
+        // SAFETY: NSObject has no subclassing requirements and MenuTarget
+        // doesn't implement Drop.
+        #[unsafe(super(NSObject))]
+        #[thread_kind = MainThreadOnly]
+        struct MenuTarget;
+
+        impl MenuTarget {
+            #[unsafe(method(chosen:))]
+            fn chosen(&self, item: &NSMenuItem) {
+                if let Some(event) = EVENTS_BY_TAG.get(item.tag() as usize) {
+                    let _ = EVENTS.0.unbounded_send(*event);
+                }
+            }
+        }
+    );
+
+    impl MenuTarget {
+        fn new(mtm: MainThreadMarker) -> Retained<Self> {
+            let this = Self::alloc(mtm).set_ivars(());
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    struct Menus {
+        // Menu items don't retain their target
+        _target: Retained<MenuTarget>,
+        dock_menu: Retained<NSMenu>,
+        status_item: Retained<NSStatusItem>,
+    }
+
+    thread_local! {
+        static MENUS: RefCell<Option<Menus>> = const { RefCell::new(None) };
+    }
+
+    fn menu(
+        mtm: MainThreadMarker,
+        target: &MenuTarget,
+        items: &[(&str, TrayEvent)],
+    ) -> Retained<NSMenu> {
+        let menu = NSMenu::new(mtm);
+        for (label, event) in items {
+            if *event == TrayEvent::Quit {
+                menu.addItem(&NSMenuItem::separatorItem(mtm));
+            }
+            let item = unsafe {
+                NSMenuItem::initWithTitle_action_keyEquivalent(
+                    NSMenuItem::alloc(mtm),
+                    &NSString::from_str(label),
+                    Some(sel!(chosen:)),
+                    &NSString::from_str(""),
+                )
+            };
+            let tag = EVENTS_BY_TAG.iter().position(|known| known == event);
+            item.setTag(tag.unwrap_or_default() as isize);
+            unsafe { item.setTarget(Some(target)) };
+            menu.addItem(&item);
+        }
+        menu
+    }
+
+    // AppKit asks the application delegate for the dock menu. winit owns
+    // the delegate, so the method is added to its class.
+    extern "C-unwind" fn dock_menu(_this: &AnyObject, _cmd: Sel, _app: &AnyObject) -> *mut NSMenu {
+        MENUS.with_borrow(|menus| match menus {
+            Some(menus) => Retained::as_ptr(&menus.dock_menu) as *mut NSMenu,
+            None => std::ptr::null_mut(),
+        })
+    }
+
+    fn install(mtm: MainThreadMarker) -> Menus {
+        let target = MenuTarget::new(mtm);
+        let dock_menu = menu(
+            mtm,
+            &target,
+            &[
+                (t("tray-start-pause"), TrayEvent::StartStop),
+                (t("tray-skip"), TrayEvent::Skip),
+            ],
+        );
+        let status_item =
+            NSStatusBar::systemStatusBar().statusItemWithLength(NSVariableStatusItemLength);
+        status_item.setMenu(Some(&menu(
+            mtm,
+            &target,
+            &[
+                (t("tray-show-timer"), TrayEvent::ShowWindow),
+                (t("tray-start-pause"), TrayEvent::StartStop),
+                (t("tray-skip"), TrayEvent::Skip),
+                (t("tray-quit"), TrayEvent::Quit),
+            ],
+        )));
+
+        if let Some(delegate) = NSApplication::sharedApplication(mtm).delegate() {
+            let delegate: &AnyObject = (*delegate).as_ref();
+            let class = unsafe { objc2::ffi::object_getClass(delegate) };
+            let imp: Imp = unsafe {
+                std::mem::transmute::<
+                    extern "C-unwind" fn(&AnyObject, Sel, &AnyObject) -> *mut NSMenu,
+                    Imp,
+                >(dock_menu)
+            };
+            unsafe {
+                objc2::ffi::class_addMethod(
+                    class as *mut AnyClass,
+                    sel!(applicationDockMenu:),
+                    imp,
+                    c"@@:@".as_ptr(),
+                );
+            }
+        }
+
+        Menus {
+            _target: target,
+            dock_menu,
+            status_item,
+        }
+    }
+
+    pub fn set_tooltip(text: &str) {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        MENUS.with_borrow_mut(|menus| {
+            let menus = menus.get_or_insert_with(|| install(mtm));
+            if let Some(button) = menus.status_item.button(mtm) {
+                button.setToolTip(Some(&NSString::from_str(text)));
+            }
+        });
+    }
+
+    pub fn set_minutes(minutes: Option<u32>) {
+        // Only ever called from update(), which runs on the main thread
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let label = minutes.map(|minutes| NSString::from_str(&minutes.to_string()));
+        NSApplication::sharedApplication(mtm)
+            .dockTile()
+            .setBadgeLabel(label.as_deref());
+
+        MENUS.with_borrow_mut(|menus| {
+            let menus = menus.get_or_insert_with(|| install(mtm));
+            if let Some(button) = menus.status_item.button(mtm) {
+                let title = match minutes {
+                    Some(minutes) => format!("🍅 {}", minutes),
+                    None => "🍅".to_string(),
+                };
+                button.setTitle(&NSString::from_str(&title));
+            }
+        });
+    }
+}
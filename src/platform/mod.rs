@@ -0,0 +1,10 @@
+pub mod active_window;
+pub mod announce;
+pub mod dnd;
+pub mod dock;
+pub mod monitors;
+pub mod power;
+pub mod processes;
+pub mod session;
+pub mod sleep;
+pub mod tray;
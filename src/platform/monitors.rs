@@ -0,0 +1,204 @@
+use iced::{Point, Size};
+
+// A display as the desktop lays it out, in logical pixels from the top left
+// of the primary one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monitor {
+    pub position: Point,
+    pub size: Size,
+    pub primary: bool,
+}
+
+impl Monitor {
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.x < self.position.x + self.size.width
+            && point.y < self.position.y + self.size.height
+    }
+
+    // Where a window of the given size opens so that it lies on this
+    // monitor, and goes full screen there
+    pub fn place(&self, window: Size) -> Point {
+        Point::new(
+            self.position.x + ((self.size.width - window.width) / 2.0).max(0.0),
+            self.position.y + ((self.size.height - window.height) / 2.0).max(0.0),
+        )
+    }
+}
+
+// Empty when the monitors can't be told apart, e.g. on Wayland, where
+// windows can't place themselves anyway
+#[cfg(target_os = "linux")]
+pub fn list() -> Vec<Monitor> {
+    match std::process::Command::new("xrandr")
+        .arg("--listmonitors")
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            parse_xrandr(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Lines like " 0: +*DP-1 2560/597x1440/336+0+0  DP-1", where * marks the
+// primary monitor
+#[cfg(target_os = "linux")]
+fn parse_xrandr(output: &str) -> Vec<Monitor> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(1);
+            let name = fields.next()?;
+            let geometry = fields.next()?;
+            let (width, rest) = geometry.split_once('x')?;
+            let (height, rest) = rest.split_once('+')?;
+            let (x, y) = rest.split_once('+')?;
+            let pixels = |field: &str| field.split('/').next()?.parse::<f32>().ok();
+            Some(Monitor {
+                position: Point::new(x.parse().ok()?, y.parse().ok()?),
+                size: Size::new(pixels(width)?, pixels(height)?),
+                primary: name.trim_start_matches('+').starts_with('*'),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn list() -> Vec<Monitor> {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Rect {
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    }
+
+    // Some fields are only there for Windows to read or fill in
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct MonitorInfo {
+        size: u32,
+        monitor: Rect,
+        work: Rect,
+        flags: u32,
+    }
+
+    const MONITORINFOF_PRIMARY: u32 = 1;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn EnumDisplayMonitors(
+            hdc: *mut c_void,
+            clip: *const Rect,
+            callback: unsafe extern "system" fn(*mut c_void, *mut c_void, *mut Rect, isize) -> i32,
+            data: isize,
+        ) -> i32;
+        fn GetMonitorInfoW(monitor: *mut c_void, info: *mut MonitorInfo) -> i32;
+    }
+
+    unsafe extern "system" fn collect(
+        monitor: *mut c_void,
+        _hdc: *mut c_void,
+        _clip: *mut Rect,
+        data: isize,
+    ) -> i32 {
+        // SAFETY: data is the address of the Vec below, which outlives the
+        // enumeration, and info is sized as the call requires.
+        unsafe {
+            let monitors = &mut *(data as *mut Vec<Monitor>);
+            let mut info = MonitorInfo {
+                size: std::mem::size_of::<MonitorInfo>() as u32,
+                ..Default::default()
+            };
+            if GetMonitorInfoW(monitor, &mut info) != 0 {
+                let area = info.monitor;
+                monitors.push(Monitor {
+                    position: Point::new(area.left as f32, area.top as f32),
+                    size: Size::new(
+                        (area.right - area.left) as f32,
+                        (area.bottom - area.top) as f32,
+                    ),
+                    primary: info.flags & MONITORINFOF_PRIMARY != 0,
+                });
+            }
+        }
+        1
+    }
+
+    let mut monitors: Vec<Monitor> = Vec::new();
+    // SAFETY: no device context or clipping; the callback only runs during
+    // this call.
+    unsafe {
+        EnumDisplayMonitors(
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            collect,
+            &mut monitors as *mut Vec<Monitor> as isize,
+        );
+    }
+    monitors
+}
+
+#[cfg(target_os = "macos")]
+pub fn list() -> Vec<Monitor> {
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::NSScreen;
+
+    let Some(mtm) = MainThreadMarker::new() else {
+        return Vec::new();
+    };
+    let screens = NSScreen::screens(mtm);
+    // AppKit counts up from the bottom of the primary screen, the first one
+    let Some(primary_height) = screens
+        .iter()
+        .next()
+        .map(|screen| screen.frame().size.height)
+    else {
+        return Vec::new();
+    };
+    screens
+        .iter()
+        .enumerate()
+        .map(|(index, screen)| {
+            let frame = screen.frame();
+            Monitor {
+                position: Point::new(
+                    frame.origin.x as f32,
+                    (primary_height - frame.origin.y - frame.size.height) as f32,
+                ),
+                size: Size::new(frame.size.width as f32, frame.size.height as f32),
+                primary: index == 0,
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+pub fn list() -> Vec<Monitor> {
+    Vec::new()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod monitors_tests {
+    use super::*;
+
+    #[test]
+    fn xrandr_monitors_are_read_with_the_primary_marked() {
+        let output = "Monitors: 2\n \
+                      0: +*DP-1 2560/597x1440/336+0+0  DP-1\n \
+                      1: +HDMI-1 1920/527x1080/296+2560+180  HDMI-1\n";
+        let monitors = parse_xrandr(output);
+        assert_eq!(monitors.len(), 2);
+        assert!(monitors[0].primary);
+        assert!(!monitors[1].primary);
+        assert_eq!(monitors[1].position, Point::new(2560.0, 180.0));
+        assert!(monitors[1].contains(Point::new(3000.0, 500.0)));
+        assert!(!monitors[0].contains(Point::new(3000.0, 500.0)));
+    }
+}
@@ -0,0 +1,58 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PowerState {
+    pub on_battery: bool,
+    pub battery_percent: Option<u32>,
+}
+
+impl PowerState {
+    // Low-power mode kicks in when unplugged and at or below the threshold;
+    // a threshold of 0 turns it off and 100 means "whenever unplugged".
+    pub fn is_low_power(&self, threshold_percent: u32) -> bool {
+        threshold_percent > 0
+            && self.on_battery
+            && self
+                .battery_percent
+                .is_none_or(|percent| percent <= threshold_percent)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn read() -> PowerState {
+    use std::{fs, path::Path};
+
+    let attribute = |dir: &Path, name: &str| {
+        fs::read_to_string(dir.join(name))
+            .map(|value| value.trim().to_string())
+            .ok()
+    };
+
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return PowerState::default();
+    };
+
+    let mut state = PowerState::default();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if attribute(&dir, "type").as_deref() != Some("Battery") {
+            continue;
+        }
+        if attribute(&dir, "status").as_deref() == Some("Discharging") {
+            state.on_battery = true;
+        }
+        if let Some(percent) = attribute(&dir, "capacity").and_then(|c| c.parse().ok()) {
+            state.battery_percent = Some(
+                state
+                    .battery_percent
+                    .map_or(percent, |p: u32| p.min(percent)),
+            );
+        }
+    }
+
+    state
+}
+
+// Other platforms are treated as always plugged in for now.
+#[cfg(not(target_os = "linux"))]
+pub fn read() -> PowerState {
+    PowerState::default()
+}
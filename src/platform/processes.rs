@@ -0,0 +1,145 @@
+// Running programs by name, and putting their windows out of the way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub pid: u32,
+    pub name: String,
+}
+
+#[cfg(target_os = "linux")]
+pub fn running() -> Vec<Process> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse().ok()?;
+            let name = std::fs::read_to_string(entry.path().join("comm")).ok()?;
+            Some(Process {
+                pid,
+                name: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+// Minimizing needs xdotool, and an X11 session
+#[cfg(target_os = "linux")]
+pub fn minimize(process: &Process) {
+    let result = std::process::Command::new("xdotool")
+        .args([
+            "search",
+            "--pid",
+            &process.pid.to_string(),
+            "windowminimize",
+            "%@",
+        ])
+        .output();
+    if let Err(err) = result {
+        println!("Error minimizing {}: {}", process.name, err);
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn running() -> Vec<Process> {
+    let Ok(output) = std::process::Command::new("ps")
+        .args(["-axco", "pid=,comm="])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, name) = line.trim().split_once(' ')?;
+            Some(Process {
+                pid: pid.parse().ok()?,
+                name: name.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+// Hides the app, as Cmd+H would
+#[cfg(target_os = "macos")]
+pub fn minimize(process: &Process) {
+    let script = format!(
+        "tell application \"System Events\" to set visible of \
+         (first process whose unix id is {}) to false",
+        process.pid
+    );
+    if let Err(err) = std::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+    {
+        println!("Error hiding {}: {}", process.name, err);
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn running() -> Vec<Process> {
+    let Ok(output) = std::process::Command::new("tasklist")
+        .args(["/fo", "csv", "/nh"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    // "name.exe","1234","Console","1","12,345 K"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split("\",\"");
+            let name = fields.next()?.trim_start_matches('"');
+            let pid = fields.next()?.parse().ok()?;
+            Some(Process {
+                pid,
+                name: name.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn minimize(process: &Process) {
+    use std::ffi::c_void;
+
+    const SW_MINIMIZE: i32 = 6;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn EnumWindows(
+            callback: unsafe extern "system" fn(*mut c_void, isize) -> i32,
+            data: isize,
+        ) -> i32;
+        fn GetWindowThreadProcessId(window: *mut c_void, pid: *mut u32) -> u32;
+        fn IsWindowVisible(window: *mut c_void) -> i32;
+        fn ShowWindow(window: *mut c_void, command: i32) -> i32;
+    }
+
+    unsafe extern "system" fn minimize_owned(window: *mut c_void, data: isize) -> i32 {
+        let mut owner = 0;
+        // SAFETY: the window handle comes from EnumWindows and `owner` is a
+        // valid out pointer.
+        unsafe {
+            GetWindowThreadProcessId(window, &mut owner);
+            if owner == data as u32 && IsWindowVisible(window) != 0 {
+                ShowWindow(window, SW_MINIMIZE);
+            }
+        }
+        1
+    }
+
+    // SAFETY: the callback only runs during this call and gets the pid by
+    // value.
+    unsafe {
+        EnumWindows(minimize_owned, process.pid as isize);
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn running() -> Vec<Process> {
+    Vec::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn minimize(_process: &Process) {}
@@ -0,0 +1,81 @@
+use iced::futures::Stream;
+
+// Windows and macOS report locks through window messages and workspace
+// notifications, which iced doesn't hand over, so only Linux is covered.
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    Locked,
+    Unlocked,
+}
+
+// Lock and unlock of the desktop session. On Linux this follows logind,
+// which desktop environments and screen lockers report to.
+#[cfg(target_os = "linux")]
+pub fn events() -> impl Stream<Item = SessionEvent> {
+    iced::stream::channel(8, async |output| {
+        if let Err(err) = logind::watch(output).await {
+            println!("Error watching for screen lock: {}", err);
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn events() -> impl Stream<Item = SessionEvent> {
+    iced::futures::stream::empty()
+}
+
+#[cfg(target_os = "linux")]
+mod logind {
+    use super::SessionEvent;
+    use iced::futures::{SinkExt, StreamExt, channel::mpsc, stream};
+    use zbus::{Connection, Proxy, zvariant::OwnedObjectPath};
+
+    const DESTINATION: &str = "org.freedesktop.login1";
+
+    pub async fn watch(mut output: mpsc::Sender<SessionEvent>) -> zbus::Result<()> {
+        let conn = Connection::system().await?;
+        let manager = Proxy::new(
+            &conn,
+            DESTINATION,
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )
+        .await?;
+        let path: OwnedObjectPath = manager.call("GetSession", &("auto",)).await?;
+        let session =
+            Proxy::new(&conn, DESTINATION, path, "org.freedesktop.login1.Session").await?;
+
+        // Lockers either answer logind's Lock/Unlock signals or set the
+        // LockedHint property themselves, so listen for both.
+        let locks = session
+            .receive_signal("Lock")
+            .await?
+            .map(|_| SessionEvent::Locked);
+        let unlocks = session
+            .receive_signal("Unlock")
+            .await?
+            .map(|_| SessionEvent::Unlocked);
+        let hints = session
+            .receive_property_changed::<bool>("LockedHint")
+            .await
+            .filter_map(|change| async move {
+                let locked = change.get().await.ok()?;
+                Some(if locked {
+                    SessionEvent::Locked
+                } else {
+                    SessionEvent::Unlocked
+                })
+            });
+
+        let mut events = std::pin::pin!(stream::select(stream::select(locks, unlocks), hints));
+        while let Some(event) = events.next().await {
+            if output.send(event).await.is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
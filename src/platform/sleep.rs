@@ -0,0 +1,297 @@
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+const REASON: &str = "A pomodoro timer is running";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inhibit {
+    Nothing,
+    Sleep,
+    // Sleep, and the screen blanking or locking for want of input
+    SleepAndScreen,
+}
+
+// Keeps the machine awake while a period runs so that suspending doesn't
+// desync the timer, and the screen on as well if asked. Send what should
+// be held; a change releases the old inhibitor before taking the new one.
+// The platform handles live on one worker thread, which Windows requires
+// since its execution state is per thread.
+pub fn spawn() -> Sender<Inhibit> {
+    let (sender, receiver) = mpsc::channel::<Inhibit>();
+    thread::spawn(move || {
+        let mut inhibitor = Inhibitor::default();
+        let mut held = Inhibit::Nothing;
+        for wanted in receiver {
+            if wanted == held {
+                continue;
+            }
+            inhibitor.release();
+            if wanted != Inhibit::Nothing {
+                inhibitor.acquire(wanted == Inhibit::SleepAndScreen);
+            }
+            held = wanted;
+        }
+        inhibitor.release();
+    });
+
+    sender
+}
+
+// Linux goes through the XDG desktop portal, which also works inside
+// Flatpak. The inhibition lasts until its request handle is closed. Where
+// there's no portal, the screen is kept on through the older screensaver
+// interface, which holds until its cookie is given back.
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct Inhibitor {
+    connection: Option<zbus::blocking::Connection>,
+    handle: Option<zbus::zvariant::OwnedObjectPath>,
+    screensaver_cookie: Option<u32>,
+}
+
+#[cfg(target_os = "linux")]
+impl Inhibitor {
+    // Flags from the portal spec: 4 = suspend, 8 = idle.
+    const SUSPEND: u32 = 4;
+    const IDLE: u32 = 8;
+
+    fn acquire(&mut self, screen: bool) {
+        if self.handle.is_some() || self.screensaver_cookie.is_some() {
+            return;
+        }
+        let flags = if screen {
+            Self::SUSPEND | Self::IDLE
+        } else {
+            Self::SUSPEND
+        };
+        match self.inhibit(flags) {
+            Ok(handle) => self.handle = Some(handle),
+            Err(err) => {
+                println!("Error inhibiting sleep: {}", err);
+                if screen {
+                    match self.inhibit_screensaver() {
+                        Ok(cookie) => self.screensaver_cookie = Some(cookie),
+                        Err(err) => println!("Error keeping the screen on: {}", err),
+                    }
+                }
+            }
+        }
+    }
+
+    fn connection(&mut self) -> zbus::Result<zbus::blocking::Connection> {
+        if let Some(connection) = &self.connection {
+            return Ok(connection.clone());
+        }
+        let connection = zbus::blocking::Connection::session()?;
+        self.connection = Some(connection.clone());
+        Ok(connection)
+    }
+
+    fn inhibit(&mut self, flags: u32) -> zbus::Result<zbus::zvariant::OwnedObjectPath> {
+        use std::collections::HashMap;
+        use zbus::zvariant::Value;
+
+        let options = HashMap::from([("reason", Value::from(REASON))]);
+        self.connection()?
+            .call_method(
+                Some("org.freedesktop.portal.Desktop"),
+                "/org/freedesktop/portal/desktop",
+                Some("org.freedesktop.portal.Inhibit"),
+                "Inhibit",
+                &("", flags, options),
+            )?
+            .body()
+            .deserialize()
+    }
+
+    fn inhibit_screensaver(&mut self) -> zbus::Result<u32> {
+        self.connection()?
+            .call_method(
+                Some("org.freedesktop.ScreenSaver"),
+                "/org/freedesktop/ScreenSaver",
+                Some("org.freedesktop.ScreenSaver"),
+                "Inhibit",
+                &("roth-pomodoro", REASON),
+            )?
+            .body()
+            .deserialize()
+    }
+
+    fn release(&mut self) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        if let Some(handle) = self.handle.take()
+            && let Err(err) = connection.call_method(
+                Some("org.freedesktop.portal.Desktop"),
+                &handle,
+                Some("org.freedesktop.portal.Request"),
+                "Close",
+                &(),
+            )
+        {
+            println!("Error releasing sleep inhibitor: {}", err);
+        }
+        if let Some(cookie) = self.screensaver_cookie.take()
+            && let Err(err) = connection.call_method(
+                Some("org.freedesktop.ScreenSaver"),
+                "/org/freedesktop/ScreenSaver",
+                Some("org.freedesktop.ScreenSaver"),
+                "UnInhibit",
+                &(cookie,),
+            )
+        {
+            println!("Error letting the screen blank again: {}", err);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+#[derive(Default)]
+struct Inhibitor {
+    active: bool,
+}
+
+#[cfg(target_os = "windows")]
+impl Inhibitor {
+    const ES_CONTINUOUS: u32 = 0x8000_0000;
+    const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+    const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+    fn acquire(&mut self, screen: bool) {
+        if !self.active {
+            let display = if screen { Self::ES_DISPLAY_REQUIRED } else { 0 };
+            self.active =
+                set_execution_state(Self::ES_CONTINUOUS | Self::ES_SYSTEM_REQUIRED | display);
+        }
+    }
+
+    fn release(&mut self) {
+        if self.active {
+            set_execution_state(Self::ES_CONTINUOUS);
+            self.active = false;
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_execution_state(flags: u32) -> bool {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+
+    // SAFETY: plain flags in, previous state out; no pointers involved.
+    unsafe { SetThreadExecutionState(flags) != 0 }
+}
+
+#[cfg(target_os = "macos")]
+#[derive(Default)]
+struct Inhibitor {
+    assertion: Option<u32>,
+}
+
+#[cfg(target_os = "macos")]
+impl Inhibitor {
+    fn acquire(&mut self, screen: bool) {
+        if self.assertion.is_none() {
+            // Keeping the display awake keeps the system awake too
+            let assertion_type = if screen {
+                "PreventUserIdleDisplaySleep"
+            } else {
+                "PreventUserIdleSystemSleep"
+            };
+            self.assertion = iokit::create_assertion(assertion_type, REASON);
+        }
+    }
+
+    fn release(&mut self) {
+        if let Some(assertion) = self.assertion.take() {
+            iokit::release(assertion);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod iokit {
+    use std::ffi::{CString, c_char, c_void};
+
+    type CFStringRef = *const c_void;
+
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+    const IOPM_ASSERTION_LEVEL_ON: u32 = 255;
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    unsafe extern "C" {
+        fn CFStringCreateWithCString(
+            allocator: *const c_void,
+            string: *const c_char,
+            encoding: u32,
+        ) -> CFStringRef;
+        fn CFRelease(value: *const c_void);
+    }
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOPMAssertionCreateWithName(
+            assertion_type: CFStringRef,
+            level: u32,
+            name: CFStringRef,
+            assertion_id: *mut u32,
+        ) -> i32;
+        fn IOPMAssertionRelease(assertion_id: u32) -> i32;
+    }
+
+    fn cf_string(value: &str) -> Option<CFStringRef> {
+        let value = CString::new(value).ok()?;
+        // SAFETY: `value` is a valid NUL-terminated string for the call.
+        let string = unsafe {
+            CFStringCreateWithCString(std::ptr::null(), value.as_ptr(), CF_STRING_ENCODING_UTF8)
+        };
+        (!string.is_null()).then_some(string)
+    }
+
+    pub fn create_assertion(assertion_type: &str, reason: &str) -> Option<u32> {
+        let assertion_type = cf_string(assertion_type)?;
+        let Some(name) = cf_string(reason) else {
+            // SAFETY: created above and not used afterwards.
+            unsafe { CFRelease(assertion_type) };
+            return None;
+        };
+
+        let mut assertion_id = 0;
+        // SAFETY: both strings are live CFStrings and `assertion_id` is a
+        // valid out pointer; the strings are released once IOKit is done.
+        let result = unsafe {
+            let result = IOPMAssertionCreateWithName(
+                assertion_type,
+                IOPM_ASSERTION_LEVEL_ON,
+                name,
+                &mut assertion_id,
+            );
+            CFRelease(assertion_type);
+            CFRelease(name);
+            result
+        };
+        (result == 0).then_some(assertion_id)
+    }
+
+    pub fn release(assertion_id: u32) {
+        // SAFETY: the id came from a successful IOPMAssertionCreateWithName.
+        unsafe { IOPMAssertionRelease(assertion_id) };
+    }
+}
+
+// Elsewhere the machine is simply allowed to sleep.
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+#[derive(Default)]
+struct Inhibitor;
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+impl Inhibitor {
+    fn acquire(&mut self, _screen: bool) {}
+
+    fn release(&mut self) {}
+}
@@ -0,0 +1,194 @@
+use iced::futures::Stream;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    ShowWindow,
+    StartStop,
+    Skip,
+    Quit,
+}
+
+// Hiding the window is only offered where there is a tray icon, or the
+// macOS menu bar extra, to bring it back from.
+pub const SUPPORTED: bool = cfg!(any(target_os = "linux", target_os = "macos"));
+
+// Tray icon with a small menu. On Linux this is a StatusNotifierItem, which
+// KDE, GNOME (with the AppIndicator extension) and most bars display.
+#[cfg(target_os = "linux")]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    use ksni::TrayMethods;
+
+    iced::stream::channel(16, async |output| {
+        let mut shown = sni::SHOWN.subscribe();
+        let current = shown.borrow_and_update().clone();
+        let tray = sni::PomodoroTray {
+            sender: output,
+            icon: current.icon.or_else(sni::icon),
+            tooltip: current.tooltip,
+        };
+        match tray.spawn().await {
+            Ok(handle) => {
+                while shown.changed().await.is_ok() {
+                    let current = shown.borrow_and_update().clone();
+                    handle
+                        .update(|tray| {
+                            tray.icon = current.icon;
+                            tray.tooltip = current.tooltip;
+                        })
+                        .await;
+                }
+                iced::futures::future::pending::<()>().await
+            }
+            Err(err) => println!("Error creating tray icon: {}", err),
+        }
+    })
+}
+
+// Replaces the tray icon with one drawn by the app, as square RGBA
+#[cfg(target_os = "linux")]
+pub fn set_icon(rgba: &[u8], size: u32) {
+    sni::SHOWN.send_modify(|shown| shown.icon = Some(sni::argb(rgba, size)));
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_icon(_rgba: &[u8], _size: u32) {}
+
+// What hovering over the tray icon shows: a one-line summary and details
+#[cfg(target_os = "linux")]
+pub fn set_tooltip(title: String, description: String) {
+    sni::SHOWN.send_if_modified(|shown| {
+        let tooltip = Some((title, description));
+        let changed = shown.tooltip != tooltip;
+        shown.tooltip = tooltip;
+        changed
+    });
+}
+
+#[cfg(target_os = "macos")]
+pub fn set_tooltip(title: String, description: String) {
+    super::dock::set_tooltip(&format!("{}\n{}", title, description));
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn set_tooltip(_title: String, _description: String) {}
+
+// The menu bar extra and dock menu live with the dock badge
+#[cfg(target_os = "macos")]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    super::dock::events()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn events() -> impl Stream<Item = TrayEvent> {
+    iced::futures::stream::empty()
+}
+
+#[cfg(target_os = "linux")]
+mod sni {
+    use super::TrayEvent;
+    use crate::i18n::t;
+    use iced::futures::channel::mpsc;
+    use ksni::{Icon, MenuItem, ToolTip, menu::StandardItem};
+    use std::sync::LazyLock;
+    use tokio::sync::watch;
+
+    const ICON_SIZE: u32 = 64;
+
+    #[derive(Clone, Default)]
+    pub struct Shown {
+        pub icon: Option<Icon>,
+        pub tooltip: Option<(String, String)>,
+    }
+
+    // The latest icon drawn and tooltip written for the timer's state,
+    // picked up by the tray
+    pub static SHOWN: LazyLock<watch::Sender<Shown>> =
+        LazyLock::new(|| watch::Sender::new(Shown::default()));
+
+    pub struct PomodoroTray {
+        pub sender: mpsc::Sender<TrayEvent>,
+        pub icon: Option<Icon>,
+        pub tooltip: Option<(String, String)>,
+    }
+
+    impl PomodoroTray {
+        fn send(&mut self, event: TrayEvent) {
+            let _ = self.sender.try_send(event);
+        }
+    }
+
+    impl ksni::Tray for PomodoroTray {
+        fn id(&self) -> String {
+            "roth-pomodoro".to_string()
+        }
+
+        fn title(&self) -> String {
+            t("window-timer").to_string()
+        }
+
+        fn icon_pixmap(&self) -> Vec<Icon> {
+            self.icon.iter().cloned().collect()
+        }
+
+        fn tool_tip(&self) -> ToolTip {
+            let (title, description) = self
+                .tooltip
+                .clone()
+                .unwrap_or_else(|| (t("window-timer").to_string(), String::new()));
+            ToolTip {
+                title,
+                description,
+                ..Default::default()
+            }
+        }
+
+        fn activate(&mut self, _x: i32, _y: i32) {
+            self.send(TrayEvent::ShowWindow);
+        }
+
+        fn menu(&self) -> Vec<MenuItem<Self>> {
+            let item = |label: &str, event: TrayEvent| {
+                StandardItem {
+                    label: label.to_string(),
+                    activate: Box::new(move |tray: &mut Self| tray.send(event)),
+                    ..Default::default()
+                }
+                .into()
+            };
+
+            vec![
+                item(t("tray-show-timer"), TrayEvent::ShowWindow),
+                item(t("tray-start-pause"), TrayEvent::StartStop),
+                item(t("tray-skip"), TrayEvent::Skip),
+                MenuItem::Separator,
+                item(t("tray-quit"), TrayEvent::Quit),
+            ]
+        }
+    }
+
+    // StatusNotifierItem pixmaps are ARGB rather than RGBA
+    pub fn argb(rgba: &[u8], size: u32) -> Icon {
+        Icon {
+            width: size as i32,
+            height: size as i32,
+            data: rgba
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[3], pixel[0], pixel[1], pixel[2]])
+                .collect(),
+        }
+    }
+
+    // The app icon, scaled down and converted to the ARGB the spec expects.
+    pub fn icon() -> Option<Icon> {
+        let png = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/images/icon.png"
+        ));
+        let image = image::load_from_memory_with_format(png, image::ImageFormat::Png).ok()?;
+        let image = image
+            .resize(ICON_SIZE, ICON_SIZE, image::imageops::FilterType::Triangle)
+            .to_rgba8();
+
+        Some(argb(image.as_raw(), ICON_SIZE))
+    }
+}
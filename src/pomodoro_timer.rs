@@ -1,29 +1,304 @@
-use crate::settings::{Screen, Settings, SettingsDraft};
+use crate::achievements::{Achievement, Record};
+use crate::audio::{AlarmPattern, Ambience, AudioCommand};
+use crate::background::Backgrounds;
+use crate::dial::Dial;
+use crate::experiment::LengthResult;
+use crate::focus_guard::GuardedApp;
+use crate::github::IssueRef;
+use crate::history::{HistoryDraft, HistorySession};
+use crate::i18n::{Language, t, tf};
+use crate::icon::{self, IconStatus};
+use crate::import::{ImportFormat, ImportPreview};
+use crate::instance::Command;
+use crate::palette::{self, PaletteKind};
+use crate::plan::{DayPlan, PlanChoice, PlanDay, PlanDraft, PlanItem};
+use crate::platform::{
+    announce::{self, Action},
+    dnd::{self, DndCommand},
+    dock,
+    monitors::{self, Monitor},
+    power::{self, PowerState},
+    session::{self, SessionEvent},
+    sleep::Inhibit,
+    tray::{self, TrayEvent},
+};
+use crate::profile::Profile;
+use crate::push::PushService;
+use crate::quick_timer::{PRESET_MINUTES, QuickTimer, QuickTimerDraft};
+use crate::report::WeeklyReport;
+use crate::review::{ReviewDraft, ReviewStep, WEEK_SECONDS, WeeklyReview, WeeklyStats};
+use crate::schedule::{DayProfile, Schedule, WEEKDAYS, weekday_label};
+use crate::sequence::{PRESETS, SequenceDraft, SequenceStep};
+use crate::settings::{
+    AmbientSound, BreakScreenPlacement, CadencePattern, CompletionCriteria, FocusGuard,
+    FocusMessages, Integrations, LongBreakSkip, OverlayCorner, PeriodKind, QuietHours, Screen,
+    ScreenLockAction, Settings, SettingsDraft, TimerFace, WindowGeometry, format_duration,
+};
+use crate::shortcuts::{Binding, Shortcut, Shortcuts};
+use crate::stats::{DayTotals, StatsDraft, TagFilter};
+use crate::stopwatch::Stopwatch;
+use crate::team::{TeamEvent, TeamHost, TeamStatus};
+use crate::todoist::{EVERY_CHOICES, TaskAction, TodoistTask};
+use crate::window_titles::TitleTally;
+use crate::worklog::{WorklogService, WorklogStatus};
+use chrono::NaiveDate;
 use iced::{
     Alignment::Center,
-    Background, Border, Color, Element, Length, Subscription, Theme, time,
-    widget::{Column, button, container, row, text, text_input, tooltip},
+    Animation, Background, Border, Color, Element, Font, Length, Point, Size, Subscription, Task,
+    Theme, keyboard, theme, time,
+    widget::{
+        Column, button, canvas, checkbox, container, image, mouse_area, operation, pick_list, row,
+        scrollable, slider, text, text_input, tooltip,
+    },
+    window,
 };
-use rodio::{Sink, Source};
 use std::{
-    sync::mpsc::{self, Sender},
-    thread,
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    sync::mpsc::Sender,
     time::{Duration, Instant},
 };
 
 pub struct PomodoroTimer {
-    time_left: u32,
+    main_window: window::Id,
+    // Minimized or hidden to the tray, so nobody is watching the countdown
+    main_window_hidden: bool,
+    // When the last animation frame was drawn
+    now: Instant,
+    // The period whose color is shown, fading in from the one before
+    shown_period: PeriodKind,
+    faded_from: Color,
+    period_fade: Animation<bool>,
+    window_geometry: WindowGeometry,
+    overlay_window: Option<window::Id>,
+    // One full-screen break window per monitor covered, and the monitors
+    // they were opened for, to notice when those come and go
+    break_windows: Vec<window::Id>,
+    break_monitors: Vec<Monitor>,
+    break_screen_shown: bool,
+    // What the tray and window icons last showed
+    icon_status: Option<IconStatus>,
+    time_left: Duration,
     end_time: Option<Instant>,
     work_periods: u32,
+    // A skipped long break to take at the next break instead
+    long_break_owed: bool,
+    // The long break that's due was turned into a short one
+    long_break_shortened: bool,
+    // When the pomodoro before the current break ended, and when the break
+    // first started, to record how long it really was
+    break_after: Option<i64>,
+    break_started_at: Option<Instant>,
+    // Pomodoros finished since the last break that ran its course
+    pomodoros_without_break: u32,
     completed_pomodoros: u32,
-    is_running: bool,
-    started: bool,
+    state: TimerState,
+    ring_pulse: bool,
+    ticking: bool,
+    ambient_playing: bool,
+    alarm_repeat_at: Option<Instant>,
     is_work_period: bool,
+    // Snoozed at the end of a pomodoro: working on for a few minutes while
+    // the break waits
+    extending_work: bool,
+    // Work time since the last eye break, counted across periods so the
+    // reminders keep their own cadence
+    eye_strain: Duration,
+    eye_break_until: Option<Instant>,
+    // What the current pomodoro is for, and the prompt asking for it
+    intention: String,
+    intention_draft: Option<String>,
     audio_sender: Sender<AudioCommand>,
+    sleep_sender: Sender<Inhibit>,
+    inhibiting: Inhibit,
+    dnd_sender: Sender<DndCommand>,
+    dnd_on: bool,
+    guarded_apps: Vec<GuardedApp>,
+    // Blocked apps found running during the current work period
+    distractions: Vec<String>,
+    distracting_titles: Vec<String>,
+    // The current work period's sampled window titles
+    title_tally: TitleTally,
+    // Worklogs in the outbox after the last attempt to post them
+    worklog_status: WorklogStatus,
+    // An editor reported activity during the current work period
+    heard_activity: bool,
+    muted: bool,
+    guest: bool,
+    // Free focus mode, in place of the countdown while set
+    stopwatch: Option<Stopwatch>,
+    // Keeps alarms and notifications quiet until turned off
+    meeting_mode: bool,
+    // Countdowns running next to the pomodoro
+    quick_timers: Vec<QuickTimer>,
+    quick_timer_draft: QuickTimerDraft,
+    next_quick_timer_id: u64,
+    pending_credit: Option<PendingCredit>,
+    paused_by_lock: bool,
     screen: Screen,
     settings: Settings,
     settings_draft: SettingsDraft,
     settings_error: Option<String>,
+    // The shortcut waiting for its new key in settings
+    recording_shortcut: Option<Shortcut>,
+    shortcut_conflict: Option<String>,
+    calendar_export: Option<String>,
+    report_status: Option<String>,
+    import_format: ImportFormat,
+    import_path: String,
+    import_preview: Option<ImportPreview>,
+    import_status: Option<String>,
+    restore_path: String,
+    // Restoring is offered once the file at `restore_path` passed its check
+    restore_checked: bool,
+    // Shown instead of everything else once a backup was restored, until
+    // the user quits to load it
+    restored: Option<String>,
+    backup_status: Option<String>,
+    pending_settings: Option<Settings>,
+    undo: Option<UndoSnapshot>,
+    focus_messages: FocusMessages,
+    backgrounds: Backgrounds,
+    review_draft: ReviewDraft,
+    review_error: Option<String>,
+    latest_review: Option<WeeklyReview>,
+    goal_progress: u32,
+    power: PowerState,
+    active_days: HashSet<NaiveDate>,
+    holidays: Vec<NaiveDate>,
+    pomodoros_by_day: HashMap<NaiveDate, u32>,
+    experiment_results: Vec<LengthResult>,
+    // Badges already earned, with when
+    achievements: Vec<(Achievement, i64)>,
+    ambient_path: String,
+    countdown_font_setting: String,
+    countdown_font: Font,
+    font_error: Option<String>,
+    integrations: Integrations,
+    obs_written: Option<(String, String)>,
+    todoist_tasks: Vec<TodoistTask>,
+    tasks_loading: bool,
+    tasks_error: Option<String>,
+    active_task: Option<TodoistTask>,
+    active_task_pomodoros: u32,
+    // A GitHub issue address given for the active task
+    active_task_issue: String,
+    // Fetched when asked for in the task list
+    issue_titles: HashMap<IssueRef, Result<String, String>>,
+    // The issue and pomodoro count a comment is offered for
+    issue_comment_offer: Option<(IssueRef, u32)>,
+    issue_comment_status: Option<String>,
+    // Start times of the meetings coming up in the next day
+    meetings: Vec<i64>,
+    sequence: Vec<SequenceStep>,
+    sequence_index: usize,
+    pending_sequence: Option<Vec<SequenceStep>>,
+    sequence_draft: SequenceDraft,
+    sequence_error: Option<String>,
+    profiles: Vec<Profile>,
+    active_profile: String,
+    profile_name: String,
+    schedule: Schedule,
+    schedule_draft: Schedule,
+    schedule_day: Option<NaiveDate>,
+    plan_day: NaiveDate,
+    plan: Vec<PlanItem>,
+    // The planned task pomodoros are credited to
+    plan_current: Option<usize>,
+    plan_draft: PlanDraft,
+    plan_error: Option<String>,
+    plan_history: Vec<DayPlan>,
+    stats_draft: StatsDraft,
+    stats_days: Vec<DayTotals>,
+    stats_titles: TitleTally,
+    stats_tags: Vec<String>,
+    stats_error: Option<String>,
+    history: Vec<HistorySession>,
+    history_page: usize,
+    history_total: usize,
+    history_draft: Option<HistoryDraft>,
+    history_error: Option<String>,
+    // Corrections are still being written, and derived stats are stale
+    history_changed: bool,
+    team_name: String,
+    // The session name while hosting, and how many are following it
+    team_hosting: Option<String>,
+    team_followers: usize,
+    team_hosts: Vec<TeamHost>,
+    team_joined: Option<TeamHost>,
+    team_notice: Option<String>,
+}
+
+// A finished work period that has not been counted yet because the
+// completion criteria ask for an acknowledgement or confirmation first.
+#[derive(Debug, Clone, Copy)]
+struct PendingCredit {
+    ended_at: i64,
+    duration_seconds: u32,
+    pomodoros: u32,
+    title_tally: TitleTally,
+    idle: bool,
+    // Worked on after the period ended by snoozing its alarm
+    snoozed_seconds: u32,
+}
+
+const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+// How often the front window's title is looked at during work
+const TITLE_SAMPLE_SECONDS: u32 = 15;
+
+const INTENTION_INPUT: &str = "intention";
+
+// The 20-20-20 rule for screen work
+const EYE_BREAK_EVERY: Duration = Duration::from_secs(20 * 60);
+const EYE_BREAK_LENGTH: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimerState {
+    Idle,
+    Running,
+    Paused,
+    // A period just ended and the alarm is sounding until acknowledged.
+    Ringing {
+        finished: PeriodKind,
+        snoozed_until: Option<Instant>,
+    },
+}
+
+// State captured before a destructive action so it can be undone for a
+// short while afterwards.
+struct UndoSnapshot {
+    label: &'static str,
+    taken_at: Instant,
+    kind: UndoKind,
+}
+
+enum UndoKind {
+    CountReset { completed_pomodoros: u32 },
+    TimerReset(Box<TimerSnapshot>),
+}
+
+// Everything a timer reset changes. Abandoning an experiment pomodoro is
+// only recorded once the reset can no longer be undone.
+struct TimerSnapshot {
+    settings: Settings,
+    pending_settings: Option<Settings>,
+    sequence: Vec<SequenceStep>,
+    pending_sequence: Option<Vec<SequenceStep>>,
+    work_periods: u32,
+    pomodoros_without_break: u32,
+    long_break_owed: bool,
+    long_break_shortened: bool,
+    break_after: Option<i64>,
+    break_started_at: Option<Instant>,
+    is_work_period: bool,
+    sequence_index: usize,
+    extending_work: bool,
+    title_tally: TitleTally,
+    heard_activity: bool,
+    time_left: Duration,
+    state: TimerState,
+    abandoned_experiment: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -32,433 +307,7165 @@ pub enum Message {
     StartStop,
     Reset,
     ResetPomoCounter,
+    UndoLastAction,
+    ExpireUndo(Instant),
+    StopAlarm,
+    SnoozeAlarm,
+    NotificationAction(Action),
+    RingPulse(Instant),
+    Frame(Instant),
+    CheckMonitors,
+    CheckDistractions,
+    SampleWindowTitle,
+    WindowTitleSampled(Option<String>),
+    DistractionsFound(Vec<String>),
+    PowerPoll,
+    AcknowledgeAlarm,
+    ConfirmFocus(bool),
+    IntentionChanged(String),
+    IntentionConfirmed,
+    IntentionSkipped,
+    ToggleMute,
+    ToggleGuest,
+    ToggleMeetingMode,
+    ToggleStopwatch,
+    StopwatchStartStop,
+    FinishStopwatch,
+    TogglePin,
+    ToggleOverlay,
+    ExtendBreak,
+    SkipBreak,
+    SkipPeriod,
+    WindowClosed(window::Id),
+    WindowMoved(window::Id, Point),
+    WindowCloseRequested(window::Id),
+    MainWindowMinimized(Option<bool>),
+    Tray(TrayEvent),
+    InstanceRequest(Option<Command>),
+    RemoteCommand(Command),
+    WindowResized(window::Id, Size),
     OpenSettings,
     CloseSettings,
-    SettingsWorkMinutesChanged(String),
-    SettingsShortBreakMinutesChanged(String),
-    SettingsLongBreakMinutesChanged(String),
-    SettingsLongBreakEveryChanged(String),
+    SettingsWorkMinutesChanged(u32),
+    SettingsShortBreakMinutesChanged(u32),
+    SettingsLongBreakMinutesChanged(u32),
+    SettingsLongBreakEveryChanged(u32),
+    SettingsLongBreaksToggled(bool),
+    SettingsExperimentToggled(bool),
+    SettingsExperimentMinutesChanged(u32),
+    SettingsExperimentDurationChanged(String),
+    SettingsAlarmTonesChanged(String),
+    SettingsSnoozeMinutesChanged(u32),
+    SettingsBreakExtensionChanged(u32),
+    SettingsAdaptiveBreakChanged(u32),
+    SettingsAlarmBeepsChanged(u32),
+    SettingsAlarmBeepLengthChanged(String),
+    SettingsAlarmGapChanged(String),
+    SettingsAlarmLengthChanged(String),
+    SettingsWorkDurationChanged(String),
+    SettingsShortBreakDurationChanged(String),
+    SettingsLongBreakDurationChanged(String),
+    SettingsLongBreakPatternChanged(String),
+    SettingsAlarmVolumeChanged(String),
+    PreviewAlarm,
+    SettingsTickVolumeChanged(String),
+    SettingsAmbientSoundChanged(AmbientSound),
+    SettingsAmbientVolumeChanged(String),
+    SettingsAmbientPathChanged(String),
+    SettingsBreakScreenToggled(bool),
+    SettingsEyeBreaksToggled(bool),
+    SettingsAskIntentionToggled(bool),
+    SettingsAnnounceTransitionsToggled(bool),
+    SettingsStrictModeToggled(bool),
+    SettingsScreenLockActionChanged(ScreenLockAction),
+    SettingsLongBreakSkipChanged(LongBreakSkip),
+    SettingsTimerFaceChanged(TimerFace),
+    SettingsBreakScreenPlacementChanged(BreakScreenPlacement),
+    SettingsKeepScreenOnToggled(bool),
+    SettingsDoNotDisturbToggled(bool),
+    SettingsFocusGuardChanged(FocusGuard),
+    SettingsGuardedAppChanged(String),
+    AddGuardedApp,
+    SettingsGuardedAppToggled(usize, bool),
+    RemoveGuardedApp(usize),
+    SettingsTrackWindowTitlesToggled(bool),
+    SettingsEditorHeartbeatsToggled(bool),
+    SettingsDistractingTitlesChanged(String),
+    SettingsQuietHoursChanged(String),
+    SettingsLanguageChanged(Language),
+    SettingsPaletteChanged(PaletteKind),
+    SettingsUiScaleChanged(String),
+    SettingsCountdownFontChanged(String),
+    CountdownFontLoaded(Result<Font, String>),
+    KeyPressed(Binding),
+    RecordShortcut(Shortcut),
+    ResetShortcuts,
+    SettingsAlwaysOnTopToggled(bool),
+    SettingsStartMinimizedToggled(bool),
+    SettingsCloseToTrayToggled(bool),
+    SettingsMinimizeToTrayToggled(bool),
+    SettingsApiPortChanged(String),
+    SettingsObsOutputDirChanged(String),
+    SettingsMqttBrokerChanged(String),
+    SettingsMqttTopicPrefixChanged(String),
+    SettingsMqttUsernameChanged(String),
+    SettingsMqttPasswordChanged(String),
+    SettingsPushServiceChanged(PushService),
+    SettingsNtfyTopicChanged(String),
+    SettingsPushoverUserChanged(String),
+    SettingsPushoverTokenChanged(String),
+    SettingsTelegramTokenChanged(String),
+    SettingsTelegramChatIdChanged(String),
+    SettingsTogglTokenChanged(String),
+    SettingsTogglWorkspaceChanged(String),
+    SettingsTogglProjectChanged(String),
+    SettingsTogglDescriptionChanged(String),
+    SettingsWorklogServiceChanged(WorklogService),
+    SettingsWorklogTokenChanged(String),
+    SettingsWorklogJiraUrlChanged(String),
+    SettingsWorklogJiraEmailChanged(String),
+    SettingsWorklogWorkspaceChanged(String),
+    SettingsWorklogTaskKeyChanged(String),
+    SettingsTodoistTokenChanged(String),
+    SettingsTodoistActionChanged(TaskAction),
+    SettingsTodoistEveryChanged(u32),
+    SettingsGithubTokenChanged(String),
+    SettingsGithubEveryChanged(u32),
+    SettingsDailyLogPathChanged(String),
+    SettingsDailyLogFormatChanged(String),
+    SettingsMeetingCalendarUrlChanged(String),
+    ExportCalendar,
+    SettingsReportDirChanged(String),
+    SettingsReportHtmlToggled(bool),
+    SaveReport,
+    ImportFormatChanged(ImportFormat),
+    ImportPathChanged(String),
+    PreviewImport,
+    ConfirmImport,
+    BackUpNow,
+    RestorePathChanged(String),
+    CheckBackup,
+    ConfirmRestore,
+    QuitAfterRestore,
+    CopyReport,
+    MeetingsLoaded(Vec<i64>),
+    MeetingClock,
+    OpenPlan,
+    ClosePlan,
+    PlanDaySelected(PlanDay),
+    PlanTitleChanged(usize, String),
+    PlanEstimateChanged(usize, String),
+    PlanAddItem,
+    PlanRemoveItem(usize),
+    SavePlan,
+    SelectPlanItem(PlanChoice),
+    OpenStats,
+    CloseStats,
+    StatsFromChanged(String),
+    StatsToChanged(String),
+    StatsTagSelected(TagFilter),
+    StatsPreset(i64),
+    OpenHistory,
+    CloseHistory,
+    OpenTeam,
+    CloseTeam,
+    OpenQuickTimer,
+    CloseQuickTimer,
+    QuickTimerLabelChanged(String),
+    QuickTimerDurationChanged(String),
+    QuickTimerPreset(u32),
+    AddQuickTimer,
+    RestartQuickTimer(u64),
+    RemoveQuickTimer(u64),
+    QuickTimerTick(u64, Instant),
+    TeamNameChanged(String),
+    HostTeam,
+    StopHosting,
+    TeamFollowers(usize),
+    TeamHostsFound(Vec<TeamHost>),
+    JoinTeam(TeamHost),
+    LeaveTeam,
+    Team(TeamEvent),
+    HistoryPage(usize),
+    EditSession(HistorySession),
+    HistoryTagChanged(String),
+    HistoryMinutesChanged(String),
+    SaveSession,
+    DeleteSession,
+    CancelSessionEdit,
+    OpenTasks,
+    CloseTasks,
+    RefreshTasks,
+    TasksLoaded(Result<Vec<TodoistTask>, String>),
+    SelectTask(TodoistTask),
+    ActiveTaskIssueChanged(String),
+    FetchIssueTitle(IssueRef),
+    IssueTitleFetched(IssueRef, Result<String, String>),
+    PostIssueComment,
+    DismissIssueComment,
+    IssueCommentPosted(Result<(), String>),
+    ClearActiveTask,
+    SessionChanged(SessionEvent),
+    SettingsProfileNameChanged(String),
+    SelectProfile(String),
+    EditProfile(String),
+    SaveProfile,
+    DeleteProfile(String),
+    OpenSchedule,
+    CloseSchedule,
+    ScheduleDayChanged(usize, DayProfile),
+    SaveSchedule,
+    ScheduleCheck,
+    SettingsOverlayCornerChanged(OverlayCorner),
+    SettingsOverlayFontSizeChanged(String),
+    SettingsLowPowerThresholdChanged(String),
+    SettingsCompletionCriteriaChanged(CompletionCriteria),
+    SettingsAlarmRepeatChanged(String),
+    SettingsFocusMessageChanged(PeriodKind, String),
+    SettingsDayStartHourChanged(String),
+    SettingsHolidaysChanged(String),
+    SettingsRemoteSourcesChanged(String),
+    SettingsSyncDirChanged(String),
+    SyncJournal,
+    SettingsSyncServerUrlChanged(String),
+    SettingsSyncServerTokenChanged(String),
+    ServerSettingsPulled((i64, toml::Table)),
+    WorklogsWaiting(WorklogStatus),
+    SettingsFocusImageChanged(PeriodKind, String),
+    SettingsBackgroundImageChanged(PeriodKind, String),
+    SettingsBackgroundGradientChanged(PeriodKind, String),
+    SettingsBackgroundDimChanged(u32),
+    SettingsFocusHeaderChanged(PeriodKind, String),
     SaveSettings,
-}
-
-#[derive(Debug, Clone)]
-enum AudioCommand {
-    Alarm,
-    Stop,
+    OpenSequenceEditor,
+    CloseSequenceEditor,
+    SequenceKindChanged(usize, PeriodKind),
+    SequenceMinutesChanged(usize, String),
+    SequenceAddStep,
+    SequenceRemoveStep(usize),
+    SequencePreset(usize),
+    SequenceClear,
+    SaveSequence,
+    OpenWeeklyReview,
+    CloseWeeklyReview,
+    ReviewNext,
+    ReviewBack,
+    ReviewReflectionChanged(String),
+    ReviewGoalChanged(String),
+    SaveWeeklyReview,
 }
 
 impl PomodoroTimer {
-    pub fn new() -> PomodoroTimer {
-        let (sender, receiver) = mpsc::channel();
-        thread::spawn(move || {
-            let (_stream, stream_handle) = rodio::OutputStream::try_default().unwrap();
-            let sink = rodio::Sink::try_new(&stream_handle).unwrap();
+    pub fn new() -> (PomodoroTimer, Task<Message>) {
+        let settings = crate::config::apply_overrides(crate::db::load_settings());
+        crate::i18n::set_language(settings.language);
+        palette::set(settings.palette);
+        let completed_pomodoros = crate::db::load_completed_pomodoros();
+        let (work_periods, long_break_owed) = crate::db::load_cycle_position();
+        let focus_messages = crate::db::load_focus_messages();
 
-            loop {
-                if let Ok(command) = receiver.try_recv() {
-                    process_audio_command(command, &sink);
-                }
-                thread::sleep(std::time::Duration::from_millis(100));
-            }
-        });
+        let sender = crate::audio::spawn();
+        let ambient_path = crate::db::load_ambient_path();
+        let countdown_font = crate::db::load_countdown_font();
+        let (active_task, active_task_pomodoros) = crate::db::load_active_task();
+        let integrations = Integrations {
+            obs_output_dir: crate::db::load_obs_output_dir(),
+            mqtt: crate::db::load_mqtt_config(),
+            push: crate::db::load_push_config(),
+            telegram: crate::db::load_telegram_config(),
+            toggl: crate::db::load_toggl_config(),
+            worklog: crate::db::load_worklog_config(),
+            todoist: crate::db::load_todoist_config(),
+            github: crate::db::load_github_config(),
+            daily_log: crate::db::load_daily_log(),
+            meeting_calendar_url: crate::db::load_meeting_calendar_url(),
+            report: crate::db::load_report_config(),
+            sync_dir: crate::db::load_sync_dir(),
+            sync_server: crate::db::load_sync_server_config(),
+        };
+        crate::sync::write_journal(&integrations.sync_dir);
+        let sequence = crate::db::load_period_sequence();
+        let latest_review = crate::db::load_latest_review();
+        let goal_progress = latest_review
+            .as_ref()
+            .map(|review| crate::db::load_stats_since(review.created_at).completed_pomodoros)
+            .unwrap_or(0);
 
-        let settings = crate::db::load_settings();
-        let completed_pomodoros = crate::db::load_completed_pomodoros();
+        let active_days = crate::db::load_active_days(settings.day_start_hour);
+        let holidays = crate::db::load_holidays();
+        let plan_day = crate::streak::today(settings.day_start_hour);
+        let plan = crate::db::load_plan(plan_day);
 
-        PomodoroTimer {
-            time_left: settings.work_seconds,
+        let window_geometry = crate::db::load_window_geometry().on_screen(&monitors::list());
+        let (main_window, open_main) =
+            window::open(crate::main_window_settings(window_geometry, &settings));
+
+        let mut timer = PomodoroTimer {
+            main_window,
+            main_window_hidden: settings.start_minimized && tray::SUPPORTED,
+            now: Instant::now(),
+            shown_period: PeriodKind::Work,
+            faded_from: palette::current().work,
+            period_fade: Animation::new(true),
+            window_geometry,
+            overlay_window: None,
+            break_windows: Vec::new(),
+            break_monitors: Vec::new(),
+            break_screen_shown: false,
+            time_left: Duration::from_secs(sequence.first().map_or(
+                settings.work_seconds_on(crate::streak::today(settings.day_start_hour)),
+                |step| step.seconds,
+            ) as u64),
             end_time: None,
-            work_periods: 0,
+            work_periods,
+            long_break_owed,
+            long_break_shortened: false,
+            break_after: None,
+            break_started_at: None,
+            pomodoros_without_break: 0,
             completed_pomodoros,
-            is_running: false,
-            started: false,
-            is_work_period: true,
+            icon_status: None,
+            state: TimerState::Idle,
+            ring_pulse: false,
+            ticking: false,
+            ambient_playing: false,
+            alarm_repeat_at: None,
+            is_work_period: sequence
+                .first()
+                .is_none_or(|step| step.kind == PeriodKind::Work),
+            extending_work: false,
+            eye_strain: Duration::ZERO,
+            eye_break_until: None,
+            intention: String::new(),
+            intention_draft: None,
             audio_sender: sender,
+            sleep_sender: crate::platform::sleep::spawn(),
+            inhibiting: Inhibit::Nothing,
+            dnd_sender: dnd::spawn(),
+            dnd_on: false,
+            guarded_apps: crate::db::load_guarded_apps(),
+            distractions: Vec::new(),
+            distracting_titles: crate::db::load_distracting_titles(),
+            title_tally: TitleTally::default(),
+            worklog_status: WorklogStatus::default(),
+            heard_activity: false,
+            muted: false,
+            guest: false,
+            stopwatch: None,
+            meeting_mode: false,
+            quick_timers: Vec::new(),
+            quick_timer_draft: QuickTimerDraft::default(),
+            next_quick_timer_id: 0,
+            pending_credit: None,
+            paused_by_lock: false,
             screen: Screen::Timer,
             settings,
-            settings_draft: SettingsDraft::from_settings(settings),
+            settings_draft: SettingsDraft::from_settings(
+                settings,
+                &focus_messages,
+                &[],
+                &[],
+                &ambient_path,
+                &countdown_font,
+                &integrations,
+            ),
             settings_error: None,
-        }
+            recording_shortcut: None,
+            shortcut_conflict: None,
+            calendar_export: None,
+            report_status: None,
+            import_format: ImportFormat::default(),
+            import_path: String::new(),
+            import_preview: None,
+            import_status: None,
+            restore_path: String::new(),
+            restore_checked: false,
+            restored: None,
+            backup_status: None,
+            pending_settings: None,
+            undo: None,
+            focus_messages,
+            backgrounds: crate::db::load_backgrounds(),
+            review_draft: ReviewDraft::new(WeeklyStats::default(), None),
+            review_error: None,
+            latest_review,
+            goal_progress,
+            power: power::read(),
+            active_days,
+            holidays,
+            pomodoros_by_day: crate::db::load_pomodoros_by_day(settings.day_start_hour),
+            achievements: crate::db::load_achievements(),
+            experiment_results: Vec::new(),
+            ambient_path,
+            countdown_font_setting: countdown_font,
+            countdown_font: Font::DEFAULT,
+            font_error: None,
+            integrations,
+            obs_written: None,
+            todoist_tasks: Vec::new(),
+            tasks_loading: false,
+            tasks_error: None,
+            active_task,
+            active_task_pomodoros,
+            active_task_issue: crate::db::load_active_task_issue(),
+            issue_titles: HashMap::new(),
+            issue_comment_offer: None,
+            issue_comment_status: None,
+            meetings: Vec::new(),
+            sequence,
+            sequence_index: 0,
+            pending_sequence: None,
+            sequence_draft: SequenceDraft::default(),
+            sequence_error: None,
+            profiles: crate::db::load_profiles(),
+            active_profile: crate::db::load_active_profile(),
+            profile_name: String::new(),
+            schedule: crate::db::load_schedule(),
+            schedule_draft: Schedule::default(),
+            schedule_day: crate::db::load_schedule_day(),
+            plan_day,
+            plan_current: crate::plan::next_unfinished(&plan),
+            plan,
+            plan_draft: PlanDraft::default(),
+            plan_error: None,
+            plan_history: Vec::new(),
+            stats_draft: StatsDraft::default(),
+            stats_days: Vec::new(),
+            stats_titles: TitleTally::default(),
+            stats_tags: Vec::new(),
+            stats_error: None,
+            history: Vec::new(),
+            history_page: 0,
+            history_total: 0,
+            history_draft: None,
+            history_error: None,
+            history_changed: false,
+            team_name: String::new(),
+            team_hosting: None,
+            team_followers: 0,
+            team_hosts: Vec::new(),
+            team_joined: None,
+            team_notice: None,
+        };
+        timer.apply_schedule();
+        timer.send_audio_settings();
+        timer.shown_period = timer.current_period();
+        // Badges earned before they existed are recorded without fanfare
+        timer.check_achievements(false);
+        let load_font = timer.load_countdown_font();
+
+        (timer, Task::batch([open_main.discard(), load_font]))
     }
 
-    pub fn view(&self) -> Element<'_, Message> {
-        match self.screen {
-            Screen::Timer => self.view_timer(),
-            Screen::Settings => self.view_settings(),
+    pub fn title(&self, window: window::Id) -> String {
+        if Some(window) == self.overlay_window {
+            t("window-overlay").to_string()
+        } else if self.break_windows.contains(&window) {
+            t("window-break").to_string()
+        } else if self.guest {
+            t("window-guest").to_string()
+        } else {
+            t("window-timer").to_string()
         }
     }
 
-    fn view_timer(&self) -> Element<'_, Message> {
-        // Determine current period type and color
-        let (period_text, period_color) = if self.is_work_period {
-            ("🍅 Work Time", [1.0, 0.42, 0.42]) // Tomato red
-        } else if self.work_periods % self.settings.long_break_every == 0 {
-            ("☕ Long Break", [0.58, 0.88, 0.83]) // Teal
+    // The overlay has a font size of its own and is sized to fit it
+    pub fn scale_factor(&self, window: window::Id) -> f32 {
+        if Some(window) == self.overlay_window {
+            1.0
         } else {
-            ("☕ Short Break", [0.31, 0.80, 0.77]) // Light blue
-        };
+            self.settings.ui_scale as f32 / 100.0
+        }
+    }
 
-        // Progress indicator
-        let current_cycle = (self.work_periods % self.settings.long_break_every) + 1;
-        let progress_text = if self.is_work_period {
-            format!(
-                "Pomodoro {}/{} until long break",
-                current_cycle, self.settings.long_break_every
-            )
-        } else {
-            "Break time - relax!".to_string()
-        };
+    pub fn theme(&self, _window: window::Id) -> Theme {
+        palette::current().theme()
+    }
 
-        // Top-right utility buttons (icon-only with tooltips)
-        let reset_button = tooltip(
-            button(text("↻").size(20))
-                .padding(10)
-                .style(transparent_button_style)
-                .on_press(Message::Reset),
-            "Reset",
-            tooltip::Position::Bottom,
-        );
+    // Windows are transparent so the overlay can float over other apps; the
+    // main window paints its own background in `view`.
+    pub fn style(&self, theme: &Theme) -> theme::Style {
+        theme::Style {
+            background_color: Color::TRANSPARENT,
+            text_color: theme.palette().text,
+        }
+    }
 
-        let reset_counter_button = tooltip(
-            button(text("⟲").size(20))
-                .padding(10)
-                .style(transparent_button_style)
-                .on_press(Message::ResetPomoCounter),
-            "Reset Count",
-            tooltip::Position::Bottom,
-        );
+    pub fn view(&self, window: window::Id) -> Element<'_, Message> {
+        if Some(window) == self.overlay_window {
+            let (_, color) = period_style(self.current_period());
+            return crate::overlay::view(self.display_seconds(), color, &self.settings);
+        }
+        if self.break_windows.contains(&window) {
+            let (_, color) = period_style(self.current_period());
+            // The break screen darkens whatever is under it already
+            return crate::background::behind(
+                crate::break_screen::view(
+                    self.display_seconds(),
+                    color,
+                    self.adaptive_break_note(),
+                    self.state == TimerState::Running,
+                    Message::StartStop,
+                    Message::SkipBreak,
+                ),
+                self.backgrounds.get(self.current_period()),
+                0.0,
+                Color::BLACK,
+            );
+        }
 
-        let settings_button = tooltip(
-            button(text("⚙").size(20))
-                .padding(10)
-                .style(transparent_button_style)
-                .on_press(Message::OpenSettings),
-            "Settings",
-            tooltip::Position::Bottom,
-        );
+        if let Some(restored) = &self.restored {
+            let notice = Column::new()
+                .spacing(16)
+                .padding(24)
+                .max_width(520)
+                .align_x(Center)
+                .push(text(restored).size(16))
+                .push(
+                    button(text(t("quit-after-restore")).size(16))
+                        .style(transparent_button_style)
+                        .on_press(Message::QuitAfterRestore)
+                        .padding([8, 12]),
+                );
+            return container(notice)
+                .center(Length::Fill)
+                .style(|theme: &Theme| {
+                    container::Style::default().background(theme.palette().background)
+                })
+                .into();
+        }
 
-        let top_right_buttons =
-            row![reset_button, reset_counter_button, settings_button].spacing(10);
+        let content = match self.screen {
+            Screen::Timer => crate::background::behind(
+                self.view_timer(),
+                self.backgrounds.get(self.current_period()),
+                self.settings.background_dim as f32 / 100.0,
+                palette::current().theme().palette().background,
+            ),
+            Screen::Settings => self.view_settings(),
+            Screen::WeeklyReview => self.view_weekly_review(),
+            Screen::Sequence => self.view_sequence(),
+            Screen::Schedule => self.view_schedule(),
+            Screen::Tasks => self.view_tasks(),
+            Screen::Plan => self.view_plan(),
+            Screen::Stats => self.view_stats(),
+            Screen::History => self.view_history(),
+            Screen::Team => self.view_team(),
+            Screen::QuickTimer => self.view_quick_timer(),
+        };
 
-        // Top bar with buttons aligned to the right
-        let top_bar = row![
-            container(text("")).width(Length::Fill), // Spacer to push buttons right
-            top_right_buttons
-        ]
-        .padding(10)
-        .width(Length::Fill);
+        // Clicking anywhere in the window acknowledges a repeating alarm
+        mouse_area(
+            container(content)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|theme: &Theme| {
+                    container::Style::default().background(theme.palette().background)
+                }),
+        )
+        .on_press(Message::AcknowledgeAlarm)
+        .into()
+    }
 
-        // Period type header
-        let period_header = text(period_text).size(32).color(period_color);
+    fn period_duration(&self, period: PeriodKind) -> Duration {
+        let seconds = match period {
+            PeriodKind::Work => self
+                .settings
+                .work_seconds_on(crate::streak::today(self.settings.day_start_hour)),
+            PeriodKind::ShortBreak => self.settings.short_break_seconds,
+            PeriodKind::LongBreak => self.settings.long_break_seconds,
+        };
+        Duration::from_secs(seconds as u64)
+    }
 
-        // Large timer display
-        let timer_display = text(format!(
-            "{:02}:{:02}",
-            self.time_left / 60,
-            self.time_left % 60
+    // A custom sequence sets its own lengths; otherwise they come from the
+    // settings for the current kind of period.
+    fn current_duration(&self) -> Duration {
+        if self.extending_work {
+            return self.snooze_length();
+        }
+        match self.sequence.get(self.sequence_index) {
+            Some(step) => Duration::from_secs(step.seconds as u64),
+            None if self.is_work_period => self.period_duration(PeriodKind::Work),
+            None => self.period_duration(self.current_period()) + self.break_bonus(),
+        }
+    }
+
+    // Back-to-back pomodoros earn a longer break: the adaptive minutes for
+    // each one after the first
+    fn break_bonus(&self) -> Duration {
+        let extra = self.pomodoros_without_break.saturating_sub(1);
+        Duration::from_secs((self.settings.adaptive_break_minutes * extra) as u64 * 60)
+    }
+
+    fn adaptive_break_note(&self) -> Option<String> {
+        if self.is_work_period || !self.sequence.is_empty() || self.break_bonus().is_zero() {
+            return None;
+        }
+        Some(tf(
+            "adaptive-break-note",
+            &[
+                ("minutes", &(self.break_bonus().as_secs() / 60)),
+                ("pomodoros", &self.pomodoros_without_break),
+            ],
         ))
-        .size(100)
-        .color(period_color);
+    }
 
-        // Progress and completed count
-        let progress_info = Column::new()
-            .align_x(Center)
-            .spacing(5)
-            .push(text(progress_text).size(16))
-            .push(text(format!("✓ Completed: {}", self.completed_pomodoros)).size(18));
+    // Moves on to the period after the one that just finished.
+    fn advance_period(&mut self) {
+        if let Some(sequence) = self.pending_sequence.take() {
+            self.sequence = sequence;
+            self.sequence_index = 0;
+            if let Some(first) = self.sequence.first() {
+                self.is_work_period = first.kind == PeriodKind::Work;
+                return;
+            }
+        } else if !self.sequence.is_empty() {
+            self.sequence_index = crate::sequence::next_index(&self.sequence, self.sequence_index);
+            self.is_work_period = self.sequence[self.sequence_index].kind == PeriodKind::Work;
+            return;
+        }
+        self.is_work_period = !self.is_work_period;
+    }
 
-        // Large centered start/stop button
-        let start_stop_button = button(
-            text(if self.is_running {
-                "⏸ Pause"
-            } else if self.started {
-                "▶ Resume"
-            } else {
-                "▶ Start"
-            })
-            .size(28),
-        )
-        .padding([20, 40])
-        .style(transparent_button_style)
-        .on_press(Message::StartStop);
+    // Remaining time is tracked precisely and only rounded up for display,
+    // so the countdown shows 25:00 at the start and 00:01 in its last second.
+    fn display_seconds(&self) -> u32 {
+        self.time_left.as_millis().div_ceil(1000) as u32
+    }
 
-        // Center content column
-        let center_content = Column::new()
-            .align_x(Center)
-            .spacing(30)
-            .push(period_header)
-            .push(timer_display)
-            .push(progress_info)
-            .push(text("").size(20)) // Spacer
-            .push(start_stop_button);
+    // The time left as of the last frame, finer than ticks bring it
+    fn smooth_seconds_left(&self) -> f32 {
+        let time_left = match (self.state, self.end_time) {
+            (TimerState::Running, Some(end_time)) => self.time_left.min(crate::clock::timer(
+                end_time.saturating_duration_since(self.now),
+            )),
+            _ => self.time_left,
+        };
+        time_left.as_secs_f32()
+    }
 
-        // Main column with top bar and centered content
-        let main_column = Column::new().push(top_bar).push(
-            container(center_content)
-                .center(Length::Fill)
-                .height(Length::Fill),
-        );
+    fn period_color(&self) -> Color {
+        let (_, color) = period_style(self.shown_period);
+        let amount = self.period_fade.interpolate(0.0, 1.0, self.now);
+        palette::mix(self.faded_from, color, amount)
+    }
 
-        container(main_column)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+    // A new period's color fades in rather than replacing the last at once
+    fn sync_period_fade(&mut self) {
+        let period = self.current_period();
+        if period == self.shown_period {
+            return;
+        }
+        let now = Instant::now();
+        self.now = now;
+        self.faded_from = self.period_color();
+        self.shown_period = period;
+        self.period_fade = Animation::new(self.low_power())
+            .duration(Duration::from_millis(600))
+            .go(true, now);
     }
 
-    fn view_settings(&self) -> Element<'_, Message> {
-        // Settings header
-        let header = text("⚙ Settings").size(40);
+    // Frames are only asked for while something moves on screen: a fade,
+    // or the dial's wedge, which sweeps smoothly between ticks
+    fn is_animating(&self) -> bool {
+        let dial_moving = matches!(self.screen, Screen::Timer)
+            && self.settings.timer_face == TimerFace::Dial
+            && self.state == TimerState::Running
+            && !self.low_power();
+        !self.main_window_hidden && (dial_moving || self.period_fade.is_animating(self.now))
+    }
 
-        // Form fields with improved layout
-        let work = Column::new()
-            .spacing(8)
-            .push(text("🍅 Work Duration (minutes)").size(16))
-            .push(
-                text_input("25", &self.settings_draft.work_minutes)
-                    .on_input(Message::SettingsWorkMinutesChanged)
-                    .padding(12)
-                    .size(16),
-            );
+    // Timing changes never cut into a period that has already started; they
+    // are queued until the next period. Everything else applies right away.
+    fn apply_settings(&mut self, settings: Settings) {
+        if self.is_started() {
+            self.settings = Settings {
+                work_seconds: self.settings.work_seconds,
+                experiment_work_seconds: self.settings.experiment_work_seconds,
+                short_break_seconds: self.settings.short_break_seconds,
+                long_break_seconds: self.settings.long_break_seconds,
+                long_break_every: self.settings.long_break_every,
+                long_break_pattern: self.settings.long_break_pattern,
+                adaptive_break_minutes: self.settings.adaptive_break_minutes,
+                ..settings
+            };
+            self.pending_settings = Some(settings);
+        } else {
+            self.settings = settings;
+            self.pending_settings = None;
+            self.time_left = self.current_duration();
+        }
+        crate::i18n::set_language(settings.language);
+        palette::set(settings.palette);
+    }
 
-        let short_break = Column::new()
-            .spacing(8)
-            .push(text("☕ Short Break (minutes)").size(16))
-            .push(
-                text_input("5", &self.settings_draft.short_break_minutes)
-                    .on_input(Message::SettingsShortBreakMinutesChanged)
-                    .padding(12)
-                    .size(16),
+    // Keeps the OBS text files in step with the display, writing only when
+    // the text actually changes.
+    fn sync_text_output(&mut self) {
+        if self.integrations.obs_output_dir.is_empty() {
+            return;
+        }
+        let seconds = self.display_seconds();
+        let text = (
+            format!("{:02}:{:02}", seconds / 60, seconds % 60),
+            self.current_period().to_string(),
+        );
+        if self.obs_written.as_ref() != Some(&text) {
+            crate::obs::write(
+                self.integrations.obs_output_dir.clone().into(),
+                text.0.clone(),
+                text.1.clone(),
             );
+            self.obs_written = Some(text);
+        }
+    }
 
-        let long_break = Column::new()
-            .spacing(8)
-            .push(text("☕ Long Break (minutes)").size(16))
-            .push(
-                text_input("15", &self.settings_draft.long_break_minutes)
-                    .on_input(Message::SettingsLongBreakMinutesChanged)
-                    .padding(12)
-                    .size(16),
-            );
+    fn api_status(&self) -> crate::api::Status {
+        crate::api::Status {
+            state: match self.state {
+                TimerState::Idle => "idle",
+                TimerState::Running => "running",
+                TimerState::Paused => "paused",
+                TimerState::Ringing { .. } => "ringing",
+            },
+            period: self.current_period().key(),
+            remaining_seconds: self.display_seconds(),
+            completed_pomodoros: self.completed_pomodoros,
+            guest: self.guest,
+        }
+    }
 
-        let long_every = Column::new()
-            .spacing(8)
-            .push(text("🔄 Long Break Every (pomodoros)").size(16))
-            .push(
-                text_input("4", &self.settings_draft.long_break_every)
-                    .on_input(Message::SettingsLongBreakEveryChanged)
-                    .padding(12)
-                    .size(16),
-            );
+    fn raise_main_window(&mut self) -> Task<Message> {
+        self.main_window_hidden = false;
+        window::set_mode(self.main_window, window::Mode::Windowed)
+            .chain(window::minimize(self.main_window, false))
+            .chain(window::gain_focus(self.main_window))
+    }
 
-        // Action buttons with distinct styling
-        let actions = row![
-            button(text("✓ Save").size(18))
-                .style(transparent_button_style)
-                .on_press(Message::SaveSettings)
-                .padding([12, 24]),
-            button(text("✕ Cancel").size(18))
-                .style(transparent_button_style)
-                .on_press(Message::CloseSettings)
-                .padding([12, 24])
-        ]
-        .spacing(15);
+    // Every settings write goes through here, so the server sync sees when
+    // they last changed. `updated_at` is now, except for settings that came
+    // from the server.
+    fn save_settings(&self, settings: Settings, updated_at: i64) {
+        crate::db::save_settings(settings);
+        crate::config::save_settings(settings);
+        crate::db::save_settings_updated_at(updated_at);
+        crate::sync_server::nudge();
+    }
 
-        // Build main column
-        let mut column = Column::new()
-            .align_x(Center)
-            .spacing(20)
-            .padding(40)
-            .push(header)
-            .push(text("").size(5)) // Spacer
-            .push(work)
-            .push(short_break)
-            .push(long_break)
-            .push(long_every);
+    fn select_profile(&mut self, name: String) {
+        let Some(profile) = self.profiles.iter().find(|p| p.name == name).cloned() else {
+            return;
+        };
+        let settings = profile.apply_to(self.pending_settings.unwrap_or(self.settings));
+        self.save_settings(settings, crate::db::unix_now());
+        self.apply_settings(settings);
+        self.ambient_path = profile.ambient_path;
+        crate::db::save_ambient_path(self.ambient_path.clone());
+        self.send_audio_settings();
+        crate::db::save_active_profile(name.clone());
+        self.active_profile = name;
+    }
 
-        // Error message with red color
-        if let Some(error) = &self.settings_error {
-            column = column.push(text(format!("⚠ {}", error)).size(16).color([1.0, 0.3, 0.3]));
+    // Switches to the weekday's profile once per day, when the day (as
+    // defined for streaks) rolls over.
+    fn apply_schedule(&mut self) {
+        let today = crate::streak::today(self.settings.day_start_hour);
+        let Some(day) = self.schedule.on_new_day(self.schedule_day, today).cloned() else {
+            return;
+        };
+        self.schedule_day = Some(today);
+        crate::db::save_schedule_day(today);
+        if let DayProfile::Profile(name) = day {
+            self.select_profile(name);
         }
+    }
 
-        column = column
-            .push(text("").size(5)) // Spacer
-            .push(actions);
-
-        container(column).center(Length::Fill).into()
+    fn weekly_report(&self) -> WeeklyReport {
+        let today = crate::streak::today(self.settings.day_start_hour);
+        let streak = crate::streak::current_streak(
+            &self.active_days,
+            &self.holidays.iter().copied().collect(),
+            today,
+        );
+        // A day of slack covers time zones and the day start hour
+        let sessions =
+            crate::db::load_report_sessions(crate::db::unix_now() - WEEK_SECONDS - 24 * 60 * 60);
+        WeeklyReport::new(today, &sessions, self.settings.day_start_hour, streak)
     }
 
-    pub fn subscription(&self) -> Subscription<Message> {
-        match self.is_running {
-            true => time::every(Duration::from_millis(100)).map(Message::Tick),
-            false => Subscription::none(),
+    fn load_history(&mut self, page: usize) {
+        // Reads must see the corrections queued so far
+        if self.history_changed {
+            crate::db::flush();
         }
+        self.history_total = crate::db::count_sessions();
+        self.history_page = page.min(crate::history::page_count(self.history_total) - 1);
+        self.history = crate::db::load_history_page(self.history_page);
+        self.history_draft = None;
+        self.history_error = None;
     }
 
-    pub fn update(&mut self, message: Message) {
-        match message {
-            Message::Tick(now) => {
-                if self.is_running && self.time_left > 0 {
-                    self.time_left = (self.end_time.unwrap() - now).as_secs() as u32;
-                }
-                if self.time_left == 0 {
-                    self.started = false;
-                    if self.is_work_period {
-                        self.work_periods += 1;
-                        self.completed_pomodoros = self.completed_pomodoros.saturating_add(1);
-                        crate::db::save_completed_pomodoros(self.completed_pomodoros);
-                    }
+    fn load_stats(&mut self) {
+        match self.stats_draft.parse() {
+            Some((from, to, tag)) => {
+                let filter = crate::db::SessionFilter {
+                    from,
+                    to,
+                    tag,
+                    day_start_hour: self.settings.day_start_hour,
+                };
+                self.stats_days = crate::db::load_daily_totals(&filter);
+                self.stats_titles = crate::db::load_title_tally(&filter);
+                self.stats_error = None;
+            }
+            None => {
+                self.stats_error = Some(t("stats-date-error").to_string());
+            }
+        }
+    }
 
-                    self.is_work_period = !self.is_work_period;
+    fn plan_date(&self, day: PlanDay) -> NaiveDate {
+        let today = crate::streak::today(self.settings.day_start_hour);
+        match day {
+            PlanDay::Today => today,
+            PlanDay::Tomorrow => today + chrono::Duration::days(1),
+        }
+    }
 
-                    self.time_left = if self.is_work_period {
-                        self.settings.work_seconds
-                    } else if self.work_periods % self.settings.long_break_every == 0 {
-                        self.settings.long_break_seconds
-                    } else {
-                        self.settings.short_break_seconds
-                    };
-                    self.is_running = false;
+    // Picks up the new day's plan once the day rolls over.
+    fn refresh_plan(&mut self) {
+        let today = self.plan_date(PlanDay::Today);
+        if self.plan_day != today {
+            self.plan_day = today;
+            self.plan = crate::db::load_plan(today);
+            self.plan_current = crate::plan::next_unfinished(&self.plan);
+        }
+    }
 
-                    if let Err(err) = rodio::OutputStream::try_default() {
-                        println!("Error initializing sound: {}", err);
-                    } else {
-                        self.audio_sender
-                            .send(AudioCommand::Alarm)
-                            .expect("Could not send audio command");
-                    }
-                }
+    // Counter, session history, streak and weekly goal are all updated here
+    // so that every completion criterion affects them the same way.
+    fn credit_pomodoro(&mut self, credit: PendingCredit) {
+        // Guest sessions run the same cycle but leave no trace
+        if self.guest {
+            return;
+        }
+        self.completed_pomodoros = self.completed_pomodoros.saturating_add(credit.pomodoros);
+        crate::db::save_completed_pomodoros(self.completed_pomodoros);
+        self.refresh_plan();
+        let description = &self.session_description();
+        // The session keeps the period's end, which its break and tallies
+        // are recorded against; time trackers see when work really stopped.
+        let duration_seconds = credit.duration_seconds + credit.snoozed_seconds;
+        let worked_until = credit.ended_at + credit.snoozed_seconds as i64;
+        crate::db::record_session(
+            credit.ended_at,
+            duration_seconds,
+            description,
+            &self.intention,
+            credit.pomodoros,
+        );
+        if credit.title_tally.tracked_seconds() > 0 {
+            crate::db::record_title_tally(credit.ended_at, credit.title_tally);
+        }
+        if credit.idle {
+            crate::db::record_idle_session(credit.ended_at);
+        }
+        crate::sync::write_journal(&self.integrations.sync_dir);
+        crate::sync_server::nudge();
+        crate::toggl::record(
+            &self.integrations.toggl,
+            worked_until,
+            duration_seconds,
+            description,
+        );
+        if credit.pomodoros > 0 {
+            crate::worklog::record(
+                &self.integrations.worklog,
+                worked_until,
+                duration_seconds,
+                description,
+            );
+        }
+        crate::daily_log::append(
+            &self.integrations.daily_log,
+            worked_until,
+            duration_seconds,
+            description,
+        );
+        if credit.pomodoros > 0
+            && let Some(issue) = self.active_issue()
+        {
+            let pomodoros = self.active_task_pomodoros + credit.pomodoros;
+            if pomodoros.is_multiple_of(self.integrations.github.every.max(1)) {
+                self.issue_comment_offer = Some((issue, pomodoros));
+                self.issue_comment_status = None;
             }
-            Message::StartStop => {
-                self.is_running = !self.is_running;
-                if self.is_running {
-                    self.audio_sender
-                        .send(AudioCommand::Stop)
-                        .expect("Could not send stop command");
-                    self.started = true;
-                    self.end_time =
-                        Some(Instant::now() + Duration::from_secs(self.time_left as u64));
+        }
+        if let Some(task) = &self.active_task
+            && credit.pomodoros > 0
+        {
+            self.active_task_pomodoros += credit.pomodoros;
+            let todoist = &self.integrations.todoist;
+            if self
+                .active_task_pomodoros
+                .is_multiple_of(todoist.every.max(1))
+            {
+                crate::todoist::report(todoist, task, self.active_task_pomodoros);
+                if todoist.action == TaskAction::Complete {
+                    self.active_task = None;
+                    self.active_task_pomodoros = 0;
                 }
             }
-            Message::Reset => {
-                self.audio_sender
-                    .send(AudioCommand::Stop)
-                    .expect("Could not send stop command");
-                self.is_running = false;
-                self.is_work_period = true;
-                self.time_left = self.settings.work_seconds;
-                self.started = false;
-                self.end_time = None;
-                self.work_periods = 0;
+            crate::db::save_active_task(self.active_task.clone(), self.active_task_pomodoros);
+        }
+        if let Some(item) = self.plan_current.and_then(|index| self.plan.get_mut(index))
+            && credit.pomodoros > 0
+        {
+            item.actual += credit.pomodoros;
+            if item.actual >= item.estimate {
+                self.plan_current = crate::plan::next_unfinished(&self.plan).or(self.plan_current);
             }
-            Message::ResetPomoCounter => {
-                self.completed_pomodoros = 0;
-                crate::db::save_completed_pomodoros(self.completed_pomodoros);
+            crate::db::save_plan(self.plan_day, self.plan.clone());
+        }
+        let today = crate::streak::today(self.settings.day_start_hour);
+        self.active_days.insert(today);
+        *self.pomodoros_by_day.entry(today).or_default() += credit.pomodoros;
+        self.check_achievements(true);
+        self.goal_progress = self.goal_progress.saturating_add(credit.pomodoros);
+    }
+
+    // Scores the work period that just ended for the length experiment.
+    // Custom sequences and snoozes don't take part.
+    fn record_experiment(&self, finished: bool) {
+        if let Some(seconds) = self.experiment_seconds() {
+            crate::db::record_experiment_period(seconds, finished);
+        }
+    }
+
+    // The length of the current period if it takes part in the experiment
+    fn experiment_seconds(&self) -> Option<u32> {
+        (self.settings.experiment_work_seconds > 0
+            && self.sequence.is_empty()
+            && self.is_work_period
+            && !self.extending_work)
+            .then(|| self.current_duration().as_secs() as u32)
+    }
+
+    fn achievement_record(&self) -> Record {
+        Record::from_days(
+            &self.pomodoros_by_day,
+            &self.holidays.iter().copied().collect(),
+        )
+    }
+
+    fn check_achievements(&mut self, announce: bool) {
+        let record = self.achievement_record();
+        for achievement in Achievement::ALL {
+            if !achievement.is_earned(&record)
+                || self
+                    .achievements
+                    .iter()
+                    .any(|(earned, _)| *earned == achievement)
+            {
+                continue;
             }
-            Message::OpenSettings => {
-                self.is_running = false;
+            let now = crate::db::unix_now();
+            self.achievements.push((achievement, now));
+            crate::db::save_achievement(achievement, now);
+            if announce && !self.is_quiet() {
+                announce::announce(
+                    tf("achievement-unlocked", &[("title", &achievement.title())]),
+                    achievement.description().to_string(),
+                    Vec::new(),
+                );
+            }
+        }
+    }
+
+    // What a session is logged as: the task being worked on, else the
+    // planned one, else the profile
+    fn session_description(&self) -> String {
+        let planned_task = self
+            .plan_current
+            .and_then(|index| self.plan.get(index))
+            .map(|item| item.title.clone());
+        match (&self.active_task, planned_task) {
+            (Some(task), _) => task.content.clone(),
+            (None, Some(title)) => title,
+            (None, None) => self.active_profile.clone(),
+        }
+    }
+
+    // Snoozing a finished pomodoro keeps working on it, so the snoozed time
+    // goes to its credit while that still waits for confirmation, or else
+    // to the session already recorded and the time trackers.
+    fn end_snooze(&mut self) {
+        self.extending_work = false;
+        let snoozed = self
+            .snooze_length()
+            .saturating_sub(self.time_left)
+            .as_secs() as u32;
+        if snoozed == 0 || self.guest {
+            return;
+        }
+        if let Some(credit) = &mut self.pending_credit {
+            credit.snoozed_seconds += snoozed;
+            return;
+        }
+        let Some(work_ended_at) = self.break_after else {
+            return;
+        };
+        crate::db::record_snooze(work_ended_at, snoozed);
+        crate::sync::write_journal(&self.integrations.sync_dir);
+        crate::sync_server::nudge();
+        let description = self.session_description();
+        let now = crate::db::unix_now();
+        crate::toggl::record(&self.integrations.toggl, now, snoozed, &description);
+        crate::worklog::record(&self.integrations.worklog, now, snoozed, &description);
+        crate::daily_log::append(&self.integrations.daily_log, now, snoozed, &description);
+    }
+
+    fn acknowledge_alarm(&mut self) {
+        if self.settings.completion_criteria == CompletionCriteria::Acknowledged
+            && let Some(credit) = self.pending_credit.take()
+        {
+            self.credit_pomodoro(credit);
+        }
+    }
+
+    // Counts work time towards the next 20-20-20 reminder, which shows a
+    // banner for the 20 seconds and a desktop notification.
+    fn strain_eyes(&mut self, worked: Duration, now: Instant) {
+        if !self.settings.eye_breaks {
+            return;
+        }
+        self.eye_strain += worked;
+        if self.eye_strain < EYE_BREAK_EVERY {
+            return;
+        }
+
+        // Time away, e.g. while the machine slept, never queues up reminders
+        self.eye_strain = Duration::ZERO;
+        self.eye_break_until = Some(now + crate::clock::wall(EYE_BREAK_LENGTH));
+        if self.is_quiet() {
+            return;
+        }
+        announce::announce(
+            t("eye-break").to_string(),
+            t("eye-break-hint").to_string(),
+            Vec::new(),
+        );
+    }
+
+    // In meeting mode or quiet hours periods still end, but without alarms,
+    // notifications or anything starting by itself
+    fn is_quiet(&self) -> bool {
+        self.meeting_mode
+            || self
+                .settings
+                .quiet_hours
+                .contains(chrono::Local::now().time())
+    }
+
+    fn snooze_length(&self) -> Duration {
+        Duration::from_secs(self.settings.snooze_minutes as u64 * 60)
+    }
+
+    // Plays the alarm and, if enabled, schedules it to repeat until the user
+    // acknowledges it.
+    fn ring(&mut self) {
+        if self.is_quiet() {
+            self.alarm_repeat_at = None;
+            return;
+        }
+        self.audio_sender
+            .send(AudioCommand::Alarm)
+            .expect("Could not send audio command");
+        self.alarm_repeat_at = (self.settings.alarm_repeat_seconds > 0).then(|| {
+            Instant::now() + Duration::from_secs(self.settings.alarm_repeat_seconds as u64)
+        });
+    }
+
+    // Lets a phone know the period ended, for when nobody is at the desk to
+    // hear the alarm.
+    fn push_transition(&self, finished: PeriodKind) {
+        if self.is_quiet() {
+            return;
+        }
+        let title = match finished {
+            PeriodKind::Work => t("push-work-finished").to_string(),
+            _ => tf("push-break-over", &[("period", &finished)]),
+        };
+        let message = tf(
+            "push-next-up",
+            &[
+                ("period", &self.current_period()),
+                ("minutes", &(self.current_duration().as_secs() / 60)),
+            ],
+        );
+        crate::telegram::send(
+            &self.integrations.telegram,
+            format!("{}\n{}", title, message),
+        );
+        if self.settings.announce_transitions {
+            // The buttons start whatever comes next, so the window never
+            // has to be raised between periods
+            let actions = match finished {
+                PeriodKind::Work => vec![
+                    (Action::StartBreak, t("start-break").to_string()),
+                    (Action::SkipBreak, t("skip-break").to_string()),
+                    (
+                        Action::Snooze,
+                        tf(
+                            "snooze-minutes",
+                            &[("minutes", &self.settings.snooze_minutes)],
+                        ),
+                    ),
+                ],
+                _ => vec![(Action::StartWork, t("notify-start-next").to_string())],
+            };
+            announce::announce(title.clone(), message.clone(), actions);
+        }
+        crate::push::send(&self.integrations.push, title, message);
+    }
+
+    // Font files have to be loaded into iced before their family can be used.
+    fn load_countdown_font(&mut self) -> Task<Message> {
+        match crate::font::countdown_font(&self.countdown_font_setting) {
+            Ok((font, None)) => {
+                self.countdown_font = font;
+                self.font_error = None;
+                Task::none()
+            }
+            Ok((font, Some(data))) => iced::font::load(data).map(move |loaded| {
+                Message::CountdownFontLoaded(
+                    loaded.map(|_| font).map_err(|err| format!("{:?}", err)),
+                )
+            }),
+            Err(err) => {
+                self.font_error = Some(err);
+                Task::none()
+            }
+        }
+    }
+
+    fn shortcut(&mut self, shortcut: Shortcut) -> Task<Message> {
+        let on_timer = matches!(self.screen, Screen::Timer);
+        match shortcut {
+            Shortcut::FocusNext => operation::focus_next(),
+            Shortcut::FocusPrevious => operation::focus_previous(),
+            Shortcut::Back => match self.screen {
+                Screen::Timer if matches!(self.state, TimerState::Ringing { .. }) => {
+                    self.handle(Message::StopAlarm)
+                }
+                Screen::Timer => Task::none(),
+                Screen::Settings => self.handle(Message::CloseSettings),
+                Screen::WeeklyReview => self.handle(Message::CloseWeeklyReview),
+                Screen::Sequence => self.handle(Message::CloseSequenceEditor),
+                Screen::Schedule => self.handle(Message::CloseSchedule),
+                Screen::Tasks => self.handle(Message::CloseTasks),
+                Screen::Plan => self.handle(Message::ClosePlan),
+                Screen::Stats => self.handle(Message::CloseStats),
+                Screen::History => self.handle(Message::CloseHistory),
+                Screen::Team => self.handle(Message::CloseTeam),
+                Screen::QuickTimer => self.handle(Message::CloseQuickTimer),
+            },
+            // The timer's own keys only act where its buttons are shown
+            _ if !on_timer => Task::none(),
+            Shortcut::StartStop => self.handle(Message::StartStop),
+            Shortcut::Reset => self.handle(Message::Reset),
+            Shortcut::Skip => self.handle(Message::SkipPeriod),
+        }
+    }
+
+    fn low_power(&self) -> bool {
+        self.power.is_low_power(self.settings.low_power_threshold)
+    }
+
+    // The countdown shows whole seconds, so a few ticks a second keep it
+    // current. When nothing shows it, neither a window nor anything we
+    // publish to, ticks only need to catch the end of the period, which the
+    // Tick handler works out from the wall clock. They still come at least
+    // once a minute, as timers may not count time the machine spent asleep.
+    fn tick_interval(&self) -> Duration {
+        let watched = !self.main_window_hidden
+            || self.overlay_window.is_some()
+            || !self.break_windows.is_empty()
+            || !self.integrations.obs_output_dir.is_empty()
+            || self.settings.api_port > 0
+            || self.integrations.mqtt.is_enabled()
+            || self.integrations.telegram.is_enabled()
+            || self.team_hosting.is_some();
+        if !watched {
+            let seconds = crate::clock::wall(self.time_left)
+                .as_millis()
+                .div_ceil(1000) as u64;
+            Duration::from_secs(seconds.clamp(1, 30))
+        } else if self.low_power() {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_millis(250)
+        }
+    }
+
+    // Strict mode keeps a running pomodoro indivisible: no pausing or
+    // resetting until it ends.
+    fn work_locked(&self) -> bool {
+        self.settings.strict_mode
+            && (self.is_work_period || self.extending_work)
+            && self.state == TimerState::Running
+    }
+
+    fn next_meeting(&self) -> Option<i64> {
+        let now = crate::db::unix_now();
+        self.meetings.iter().copied().find(|start| *start > now)
+    }
+
+    fn is_started(&self) -> bool {
+        matches!(self.state, TimerState::Running | TimerState::Paused)
+    }
+
+    // Replaces any earlier undo, which can't be taken back anymore
+    fn offer_undo(&mut self, label: &'static str, kind: UndoKind) {
+        self.settle_undo();
+        self.undo = Some(UndoSnapshot {
+            label,
+            taken_at: Instant::now(),
+            kind,
+        });
+    }
+
+    // Records what a reset left for later once it is final
+    fn settle_undo(&mut self) {
+        if let Some(UndoSnapshot {
+            kind: UndoKind::TimerReset(timer),
+            ..
+        }) = self.undo.take()
+            && let Some(seconds) = timer.abandoned_experiment
+        {
+            crate::db::record_experiment_period(seconds, false);
+        }
+    }
+
+    fn timer_snapshot(&self) -> TimerSnapshot {
+        TimerSnapshot {
+            settings: self.settings,
+            pending_settings: self.pending_settings,
+            sequence: self.sequence.clone(),
+            pending_sequence: self.pending_sequence.clone(),
+            work_periods: self.work_periods,
+            pomodoros_without_break: self.pomodoros_without_break,
+            long_break_owed: self.long_break_owed,
+            long_break_shortened: self.long_break_shortened,
+            break_after: self.break_after,
+            break_started_at: self.break_started_at,
+            is_work_period: self.is_work_period,
+            sequence_index: self.sequence_index,
+            extending_work: self.extending_work,
+            title_tally: self.title_tally,
+            heard_activity: self.heard_activity,
+            time_left: self.time_left,
+            state: self.state,
+            abandoned_experiment: self
+                .is_started()
+                .then(|| self.experiment_seconds())
+                .flatten(),
+        }
+    }
+
+    fn restore_timer(&mut self, timer: TimerSnapshot) {
+        self.settings = timer.settings;
+        self.pending_settings = timer.pending_settings;
+        self.sequence = timer.sequence;
+        self.pending_sequence = timer.pending_sequence;
+        self.work_periods = timer.work_periods;
+        self.pomodoros_without_break = timer.pomodoros_without_break;
+        self.long_break_owed = timer.long_break_owed;
+        self.long_break_shortened = timer.long_break_shortened;
+        self.break_after = timer.break_after;
+        self.break_started_at = timer.break_started_at;
+        self.is_work_period = timer.is_work_period;
+        self.sequence_index = timer.sequence_index;
+        self.extending_work = timer.extending_work;
+        self.title_tally = timer.title_tally;
+        self.heard_activity = timer.heard_activity;
+        self.time_left = timer.time_left;
+        self.end_time = None;
+        self.state = match timer.state {
+            TimerState::Running => TimerState::Paused,
+            state => state,
+        };
+        self.save_cycle_position();
+    }
+
+    // Settles a long break that's due but not being taken, as the long
+    // break skip setting says. Returns whether it became a short break.
+    fn skip_long_break(&mut self) -> bool {
+        if self.is_work_period || self.current_period() != PeriodKind::LongBreak {
+            return false;
+        }
+        match self.settings.long_break_skip {
+            LongBreakSkip::Defer => {
+                self.long_break_owed = self.settings.long_break_every > 0;
+                false
+            }
+            LongBreakSkip::Forfeit => {
+                self.long_break_owed = false;
+                false
+            }
+            LongBreakSkip::Shorten => {
+                self.long_break_shortened = true;
+                true
+            }
+        }
+    }
+
+    fn end_break(&mut self, now: Instant) {
+        if let (Some(started), Some(work_ended_at)) =
+            (self.break_started_at.take(), self.break_after.take())
+        {
+            let seconds = now.saturating_duration_since(started).as_secs() as u32;
+            crate::db::record_break(work_ended_at, seconds);
+        }
+    }
+
+    fn silence_quick_timer(&self, id: u64) {
+        if self
+            .quick_timers
+            .iter()
+            .any(|timer| timer.id == id && timer.ringing)
+        {
+            self.audio_sender
+                .send(AudioCommand::Stop)
+                .expect("Could not send stop command");
+        }
+    }
+
+    fn save_cycle_position(&self) {
+        crate::db::save_cycle_position(self.work_periods, self.long_break_owed);
+    }
+
+    fn open_overlay(&mut self) -> Task<Message> {
+        let (id, open) = window::open(crate::overlay::window_settings(&self.settings));
+        self.overlay_window = Some(id);
+        open.then(window::enable_mouse_passthrough)
+    }
+
+    fn current_period(&self) -> PeriodKind {
+        if self.extending_work {
+            return PeriodKind::Work;
+        }
+        if let Some(step) = self.sequence.get(self.sequence_index) {
+            step.kind
+        } else if self.is_work_period {
+            PeriodKind::Work
+        } else if self.long_break_shortened {
+            PeriodKind::ShortBreak
+        } else if self.long_break_owed || self.settings.is_long_break_after(self.work_periods) {
+            PeriodKind::LongBreak
+        } else {
+            PeriodKind::ShortBreak
+        }
+    }
+
+    fn view_timer(&self) -> Element<'_, Message> {
+        // Determine current period type and color
+        let period = self.current_period();
+        let (mut period_text, _) = period_style(period);
+        let period_color = self.period_color();
+        let header = &self.focus_messages.get(period).header;
+        if !header.is_empty() {
+            period_text = header;
+        }
+
+        // Progress indicator
+        let progress_text = if !self.sequence.is_empty() {
+            tf(
+                "progress-sequence",
+                &[
+                    ("step", &(self.sequence_index + 1)),
+                    ("steps", &self.sequence.len()),
+                ],
+            )
+        } else if self.is_work_period && self.settings.long_break_every == 0 {
+            tf(
+                "progress-no-long-breaks",
+                &[("pomodoro", &(self.work_periods + 1))],
+            )
+        } else if self.is_work_period && self.settings.long_break_pattern.is_empty() {
+            tf(
+                "progress-cycle",
+                &[
+                    (
+                        "pomodoro",
+                        &(self.work_periods % self.settings.long_break_every + 1),
+                    ),
+                    ("every", &self.settings.long_break_every),
+                ],
+            )
+        } else if self.is_work_period {
+            let next_long_break = (self.work_periods + 1..)
+                .find(|n| self.settings.is_long_break_after(*n))
+                .unwrap_or_default();
+            tf(
+                "progress-pattern",
+                &[
+                    ("pomodoro", &(self.work_periods + 1)),
+                    ("long-break", &next_long_break),
+                ],
+            )
+        } else if let Some(note) = self.adaptive_break_note() {
+            note
+        } else {
+            t("progress-break").to_string()
+        };
+
+        // Top-right utility buttons (icon-only with tooltips)
+        let reset_button = tooltip(
+            button(text("↻").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press_maybe((!self.work_locked()).then_some(Message::Reset)),
+            if self.work_locked() {
+                t("strict-mode-no-reset")
+            } else {
+                t("reset-tooltip")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let reset_counter_button = tooltip(
+            button(text("⟲").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press_maybe((!self.guest).then_some(Message::ResetPomoCounter)),
+            t("reset-count"),
+            tooltip::Position::Bottom,
+        );
+
+        let settings_button = tooltip(
+            button(text("⚙").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::OpenSettings),
+            t("settings-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let stats_button = tooltip(
+            button(text("📊").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::OpenStats),
+            t("stats-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let plan_button = tooltip(
+            button(text("🗓").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::OpenPlan),
+            t("plan-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let quick_timer_button = tooltip(
+            button(text("⏲").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::OpenQuickTimer),
+            t("quick-timer-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let team_button = tooltip(
+            button(text("🤝").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::OpenTeam),
+            t("team-session-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let review_button = tooltip(
+            button(text("📋").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press_maybe((!self.guest).then_some(Message::OpenWeeklyReview)),
+            t("weekly-review-tooltip"),
+            tooltip::Position::Bottom,
+        );
+
+        let pin_button = tooltip(
+            button(
+                text(if self.settings.always_on_top {
+                    "📌"
+                } else {
+                    "📍"
+                })
+                .size(20),
+            )
+            .padding(10)
+            .style(transparent_button_style)
+            .on_press(Message::TogglePin),
+            if self.settings.always_on_top {
+                t("unpin-from-top")
+            } else {
+                t("keep-on-top")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let mute_button = tooltip(
+            button(text(if self.muted { "🔕" } else { "🔔" }).size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::ToggleMute),
+            if self.muted { t("unmute") } else { t("mute") },
+            tooltip::Position::Bottom,
+        );
+
+        let overlay_button = tooltip(
+            button(text("⧉").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::ToggleOverlay),
+            if self.overlay_window.is_some() {
+                t("hide-overlay")
+            } else {
+                t("show-overlay")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let streak = crate::streak::current_streak(
+            &self.active_days,
+            &self.holidays.iter().copied().collect(),
+            crate::streak::today(self.settings.day_start_hour),
+        );
+        let streak_badge = tooltip(
+            container(text(format!("🔥 {}", streak)).size(18)).padding(10),
+            text(tf(
+                "streak-tooltip",
+                &[("hour", &format!("{:02}", self.settings.day_start_hour))],
+            ))
+            .size(14),
+            tooltip::Position::Bottom,
+        );
+
+        let guest_button = tooltip(
+            button(text("👤").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::ToggleGuest),
+            if self.guest {
+                t("end-guest-session")
+            } else {
+                t("guest-session-tooltip")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let stopwatch_button = tooltip(
+            button(text("⏱").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press_maybe(
+                    (self.state == TimerState::Idle
+                        && self
+                            .stopwatch
+                            .is_none_or(|stopwatch| stopwatch.elapsed(Instant::now()).is_zero()))
+                    .then_some(Message::ToggleStopwatch),
+                ),
+            if self.stopwatch.is_some() {
+                t("back-to-pomodoros")
+            } else {
+                t("stopwatch-tooltip")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let meeting_button = tooltip(
+            button(text("🤫").size(20))
+                .padding(10)
+                .style(transparent_button_style)
+                .on_press(Message::ToggleMeetingMode),
+            if self.meeting_mode {
+                t("end-meeting-mode")
+            } else {
+                t("meeting-mode-tooltip")
+            },
+            tooltip::Position::Bottom,
+        );
+
+        let top_right_buttons = row![
+            streak_badge,
+            meeting_button,
+            stopwatch_button,
+            quick_timer_button,
+            guest_button,
+            overlay_button,
+            pin_button,
+            mute_button,
+            reset_button,
+            reset_counter_button,
+            plan_button,
+            stats_button,
+            team_button,
+            review_button,
+            settings_button
+        ]
+        .spacing(10);
+
+        let power_indicator = self.low_power().then(|| {
+            let label = match self.power.battery_percent {
+                Some(percent) => tf("low-power-percent", &[("percent", &percent)]),
+                None => t("low-power").to_string(),
+            };
+            text(label).size(14)
+        });
+        let distraction_indicator = (!self.distractions.is_empty()).then(|| {
+            text(tf(
+                "distracting-apps-running",
+                &[("apps", &self.distractions.join(", "))],
+            ))
+            .size(14)
+            .color(palette::current().warning)
+        });
+        let guest_indicator = self.guest.then(|| {
+            text(t("guest-session-banner"))
+                .size(14)
+                .color(palette::current().warning)
+        });
+        let profile_picker = (!self.profiles.is_empty()).then(|| {
+            let names: Vec<String> = self.profiles.iter().map(|p| p.name.clone()).collect();
+            let selected = names
+                .iter()
+                .find(|name| **name == self.active_profile)
+                .cloned();
+            pick_list(names, selected, Message::SelectProfile)
+                .placeholder(t("profile"))
+                .padding(6)
+                .text_size(14)
+        });
+        let task_button = self.integrations.todoist.is_enabled().then(|| {
+            let label = match &self.active_task {
+                Some(task) => format!("📋 {}", task.content),
+                None => t("pick-a-task").to_string(),
+            };
+            button(text(label).size(14))
+                .padding([4, 6])
+                .style(transparent_button_style)
+                .on_press(Message::OpenTasks)
+        });
+        let issue_comment = match (&self.issue_comment_offer, &self.issue_comment_status) {
+            (Some((issue, pomodoros)), _) => Some(
+                row![
+                    text(tf(
+                        "offer-issue-comment",
+                        &[
+                            (
+                                "comment",
+                                &tf("issue-comment-body", &[("pomodoros", pomodoros)]),
+                            ),
+                            ("issue", issue),
+                        ],
+                    ))
+                    .size(14),
+                    button(text(t("post")).size(14))
+                        .padding([4, 8])
+                        .style(transparent_button_style)
+                        .on_press(Message::PostIssueComment),
+                    button(text(t("not-now")).size(14))
+                        .padding([4, 8])
+                        .style(transparent_button_style)
+                        .on_press(Message::DismissIssueComment),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            ),
+            (None, Some(status)) => Some(
+                row![
+                    text(status).size(14),
+                    button(text("✕").size(14))
+                        .padding([4, 8])
+                        .style(transparent_button_style)
+                        .on_press(Message::DismissIssueComment),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            ),
+            (None, None) => None,
+        };
+        // A meeting that starts before this pomodoro would end is called out
+        // while there is still time to decide not to start it
+        let meeting_indicator = self.next_meeting().and_then(|start| {
+            let seconds = start - crate::db::unix_now();
+            let label = tf("next-meeting", &[("minutes", &((seconds + 59) / 60))]);
+            let collides = self.is_work_period && seconds < self.display_seconds() as i64;
+            if collides && !self.is_started() {
+                Some(
+                    text(tf("meeting-collides", &[("meeting", &label)]))
+                        .size(14)
+                        .color(palette::current().warning),
+                )
+            } else if seconds <= 60 * 60 {
+                Some(text(label).size(14))
+            } else {
+                None
+            }
+        });
+        let plan_indicator = (!self.plan.is_empty()).then(|| {
+            let (planned, done) = crate::plan::totals(&self.plan);
+            let choices = crate::plan::choices(&self.plan);
+            let selected = self
+                .plan_current
+                .and_then(|index| choices.get(index).cloned());
+            row![
+                text(tf(
+                    "planned-progress",
+                    &[("done", &done), ("planned", &planned)]
+                ))
+                .size(14),
+                pick_list(choices, selected, Message::SelectPlanItem)
+                    .placeholder(t("working-on"))
+                    .padding(6)
+                    .text_size(14),
+            ]
+            .spacing(8)
+            .align_y(Center)
+        });
+        let team_indicator = match (&self.team_joined, &self.team_hosting) {
+            (Some(host), _) => Some(
+                text(format!(
+                    "🤝 {}",
+                    tf("team-following", &[("host", &host.name)])
+                ))
+                .size(14),
+            ),
+            (None, Some(name)) => Some(
+                text(format!(
+                    "🤝 {}",
+                    tf(
+                        "team-hosting",
+                        &[("name", name), ("followers", &self.team_followers)]
+                    )
+                ))
+                .size(14),
+            ),
+            (None, None) => None,
+        };
+        // A quick timer keeps counting down while the pomodoro is on screen
+
+        let quiet_indicator = self.is_quiet().then(|| {
+            text(if self.meeting_mode {
+                t("meeting-mode-on")
+            } else {
+                t("quiet-hours-on")
+            })
+            .size(14)
+            .color(palette::current().warning)
+        });
+
+        let status = Column::new()
+            .spacing(2)
+            .push(quiet_indicator)
+            .push(team_indicator)
+            .push(profile_picker)
+            .push(task_button)
+            .push(issue_comment)
+            .push(plan_indicator)
+            .push(meeting_indicator)
+            .push(distraction_indicator)
+            .push(guest_indicator)
+            .push(power_indicator);
+
+        // Top bar with buttons aligned to the right
+        let top_bar = row![
+            container(status).width(Length::Fill), // Spacer to push buttons right
+            top_right_buttons
+        ]
+        .align_y(Center)
+        .padding(10)
+        .width(Length::Fill);
+
+        // Period type header
+        let period_header = text(period_text).size(32).color(period_color);
+
+        // Large timer display, or the kitchen timer dial with the digits
+        // kept small beneath it
+        let countdown = format!(
+            "{:02}:{:02}",
+            self.display_seconds() / 60,
+            self.display_seconds() % 60
+        );
+        let timer_display: Element<Message> = match self.settings.timer_face {
+            TimerFace::Digits => text(countdown)
+                .size(100)
+                .font(self.countdown_font)
+                .color(period_color)
+                .into(),
+            TimerFace::Dial => Column::new()
+                .align_x(Center)
+                .spacing(10)
+                .push(
+                    canvas(Dial {
+                        seconds_left: self.smooth_seconds_left(),
+                        period_seconds: self.current_duration().as_secs() as u32,
+                        color: period_color,
+                    })
+                    .width(240)
+                    .height(240),
+                )
+                .push(
+                    text(countdown)
+                        .size(28)
+                        .font(self.countdown_font)
+                        .color(period_color),
+                )
+                .into(),
+        };
+
+        // The intention typed for this pomodoro
+        let intention = (period == PeriodKind::Work && !self.intention.is_empty())
+            .then(|| text(&self.intention).size(20).color(period_color));
+
+        // Progress and completed count
+        let mut progress_info = Column::new()
+            .align_x(Center)
+            .spacing(5)
+            .push(text(progress_text).size(16))
+            .push(text(tf("completed", &[("count", &self.completed_pomodoros)])).size(18));
+
+        if self.paused_by_lock && self.state == TimerState::Paused {
+            progress_info = progress_info.push(text(t("screen-lock-paused")).size(14));
+        }
+
+        if self.pending_settings.is_some() {
+            progress_info = progress_info.push(text(t("durations-apply-next-period")).size(14));
+        }
+
+        if let Some(review) = &self.latest_review {
+            progress_info = progress_info.push(
+                text(tf(
+                    "weekly-goal-progress",
+                    &[
+                        ("done", &self.goal_progress),
+                        ("goal", &review.next_week_goal),
+                    ],
+                ))
+                .size(14),
+            );
+        }
+
+        // Large centered start/stop button
+        let start_stop_button = button(
+            text(match self.state {
+                TimerState::Running if self.is_work_period => t("pause"),
+                TimerState::Running => t("pause-break"),
+                TimerState::Paused if self.is_work_period => t("resume"),
+                TimerState::Paused => t("resume-break"),
+                TimerState::Idle | TimerState::Ringing { .. } => t("start"),
+            })
+            .size(28),
+        )
+        .padding([20, 40])
+        .style(transparent_button_style)
+        .on_press_maybe((!self.work_locked()).then_some(Message::StartStop));
+        let start_stop_button: Element<'_, Message> = if let Some(draft) = &self.intention_draft {
+            Column::new()
+                .align_x(Center)
+                .spacing(10)
+                .push(text(t("intention-prompt")).size(20))
+                .push(
+                    text_input(t("intention-placeholder"), draft)
+                        .id(INTENTION_INPUT)
+                        .on_input(Message::IntentionChanged)
+                        .on_submit(Message::IntentionConfirmed)
+                        .padding(12)
+                        .size(16)
+                        .width(360),
+                )
+                .push(
+                    row![
+                        button(text(t("start")).size(16))
+                            .padding([8, 18])
+                            .style(transparent_button_style)
+                            .on_press(Message::IntentionConfirmed),
+                        button(text(t("start-without-intention")).size(16))
+                            .padding([8, 18])
+                            .style(transparent_button_style)
+                            .on_press(Message::IntentionSkipped),
+                    ]
+                    .spacing(10),
+                )
+                .into()
+        } else if self.work_locked() {
+            tooltip(
+                start_stop_button,
+                t("strict-mode-no-pause"),
+                tooltip::Position::Bottom,
+            )
+            .into()
+        } else if !self.is_work_period
+            && self.is_started()
+            && self.settings.break_extension_minutes > 0
+        {
+            row![
+                start_stop_button,
+                button(
+                    text(tf(
+                        "extend-break",
+                        &[("minutes", &self.settings.break_extension_minutes)],
+                    ))
+                    .size(20),
+                )
+                .padding([20, 24])
+                .style(transparent_button_style)
+                .on_press(Message::ExtendBreak),
+            ]
+            .spacing(10)
+            .align_y(Center)
+            .into()
+        } else {
+            start_stop_button.into()
+        };
+
+        // Custom message and image for the current period
+        let focus_message = self.focus_messages.get(period);
+        let mut message_content = Column::new().align_x(Center).spacing(10);
+        if !focus_message.image_path.is_empty()
+            && std::path::Path::new(&focus_message.image_path).exists()
+        {
+            message_content = message_content.push(image(&focus_message.image_path).height(120));
+        }
+        if !focus_message.text.is_empty() {
+            message_content = message_content.push(text(&focus_message.text).size(20));
+        }
+
+        // Alarm banner while the finished period is waiting to be acknowledged
+        let ringing_banner = match self.state {
+            TimerState::Ringing {
+                finished,
+                snoozed_until,
+            } => {
+                let (finished_text, finished_color) = period_style(finished);
+                let alpha = if snoozed_until.is_none() && self.ring_pulse {
+                    0.45
+                } else {
+                    1.0
+                };
+                let status = match snoozed_until {
+                    Some(_) => tf("period-snoozed", &[("period", &finished_text)]),
+                    None => tf("period-finished", &[("period", &finished_text)]),
+                };
+
+                Some(
+                    Column::new()
+                        .align_x(Center)
+                        .spacing(10)
+                        .push(text(status).size(22).color(Color {
+                            a: alpha,
+                            ..finished_color
+                        }))
+                        .push(
+                            row![
+                                button(text(t("stop-alarm")).size(16))
+                                    .padding([8, 18])
+                                    .style(transparent_button_style)
+                                    .on_press(Message::StopAlarm),
+                                button(
+                                    text(tf(
+                                        "snooze-minutes",
+                                        &[("minutes", &self.settings.snooze_minutes)],
+                                    ))
+                                    .size(16),
+                                )
+                                .padding([8, 18])
+                                .style(transparent_button_style)
+                                .on_press_maybe(
+                                    snoozed_until.is_none().then_some(Message::SnoozeAlarm)
+                                ),
+                            ]
+                            .spacing(10),
+                        ),
+                )
+            }
+            _ => None,
+        };
+
+        // Post-session prompt when pomodoros only count once confirmed,
+        // asked once any snooze is over
+        let focus_prompt = self
+            .pending_credit
+            .filter(|_| {
+                self.settings.completion_criteria == CompletionCriteria::Confirmed
+                    && !self.extending_work
+            })
+            .map(|_| {
+                row![
+                    text(t("focus-check-prompt")).size(16),
+                    button(text(t("focused-yes")).size(16))
+                        .padding([8, 18])
+                        .style(transparent_button_style)
+                        .on_press(Message::ConfirmFocus(true)),
+                    button(text(t("focused-no")).size(16))
+                        .padding([8, 18])
+                        .style(transparent_button_style)
+                        .on_press(Message::ConfirmFocus(false)),
+                ]
+                .spacing(10)
+                .align_y(Center)
+            });
+
+        // A quiet 20-20-20 reminder while work carries on
+        let eye_break = self
+            .eye_break_until
+            .filter(|_| {
+                self.state == TimerState::Running && self.current_period() == PeriodKind::Work
+            })
+            .map(|_| {
+                Column::new()
+                    .align_x(Center)
+                    .spacing(4)
+                    .push(text(t("eye-break")).size(18))
+                    .push(text(t("eye-break-hint")).size(14))
+            });
+
+        // Center content column
+        let center_content = Column::new()
+            .align_x(Center)
+            .spacing(30)
+            .push(ringing_banner)
+            .push(eye_break)
+            .push(focus_prompt)
+            .push(period_header)
+            .push(message_content)
+            .push(timer_display)
+            .push(intention)
+            .push(progress_info)
+            .push(text("").size(20)) // Spacer
+            .push(start_stop_button);
+
+        // Undo toast for the last destructive action
+        let undo_toast = self.undo.as_ref().map(|undo| {
+            container(
+                row![
+                    text(undo.label).size(16),
+                    button(text(t("undo")).size(16))
+                        .padding([6, 14])
+                        .style(transparent_button_style)
+                        .on_press(Message::UndoLastAction),
+                ]
+                .spacing(15)
+                .align_y(Center),
+            )
+            .padding(10)
+            .center_x(Length::Fill)
+        });
+
+        // Main column with top bar and centered content
+        let center_content = match self.stopwatch {
+            Some(stopwatch) => self.view_stopwatch(stopwatch),
+            None => center_content.into(),
+        };
+        let main_column = Column::new()
+            .push(top_bar)
+            .push(
+                container(center_content)
+                    .center(Length::Fill)
+                    .height(Length::Fill),
+            )
+            .push(self.quick_timer_cards())
+            .push(undo_toast);
+
+        container(main_column)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    // Counts up with no fixed length; finishing records the time and
+    // credits the whole pomodoros it was worth
+    fn view_stopwatch(&self, stopwatch: Stopwatch) -> Element<'_, Message> {
+        let (_, color) = period_style(PeriodKind::Work);
+        let seconds = stopwatch.elapsed(Instant::now()).as_secs() as u32;
+        let work_seconds = self
+            .settings
+            .work_seconds_on(crate::streak::today(self.settings.day_start_hour));
+        let pomodoros = crate::stopwatch::equivalent_pomodoros(seconds, work_seconds);
+
+        let start_stop = button(
+            text(if stopwatch.is_running() {
+                t("pause")
+            } else if seconds > 0 {
+                t("resume")
+            } else {
+                t("start")
+            })
+            .size(28),
+        )
+        .padding([20, 40])
+        .style(transparent_button_style)
+        .on_press(Message::StopwatchStartStop);
+        let finish = button(text(t("finish-session")).size(28))
+            .padding([20, 40])
+            .style(transparent_button_style)
+            .on_press_maybe(
+                (seconds >= crate::stopwatch::MIN_SECONDS).then_some(Message::FinishStopwatch),
+            );
+
+        Column::new()
+            .align_x(Center)
+            .spacing(30)
+            .push(text(t("free-focus")).size(32).color(color))
+            .push(
+                text(crate::stopwatch::format_elapsed(seconds))
+                    .size(100)
+                    .font(self.countdown_font)
+                    .color(color),
+            )
+            .push(
+                Column::new()
+                    .align_x(Center)
+                    .spacing(5)
+                    .push(text(tf("stopwatch-pomodoros", &[("count", &pomodoros)])).size(16))
+                    .push(text(tf("completed", &[("count", &self.completed_pomodoros)])).size(18)),
+            )
+            .push(text("").size(20)) // Spacer
+            .push(row![start_stop, finish].spacing(10))
+            .into()
+    }
+
+    fn view_settings(&self) -> Element<'_, Message> {
+        // Settings header
+        let header = text(t("settings")).size(40);
+
+        let language = Column::new()
+            .spacing(8)
+            .push(text(t("language")).size(16))
+            .push(
+                pick_list(
+                    Language::ALL,
+                    Some(self.settings_draft.language),
+                    Message::SettingsLanguageChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+
+        let colors = Column::new()
+            .spacing(8)
+            .push(text(t("colors")).size(16))
+            .push(
+                pick_list(
+                    PaletteKind::ALL,
+                    Some(self.settings_draft.palette),
+                    Message::SettingsPaletteChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+
+        let timer_face = Column::new()
+            .spacing(8)
+            .push(text(t("timer-face")).size(16))
+            .push(
+                pick_list(
+                    TimerFace::ALL,
+                    Some(self.settings_draft.timer_face),
+                    Message::SettingsTimerFaceChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+
+        let ui_scale = Column::new()
+            .spacing(8)
+            .push(text(t("ui-scale")).size(16))
+            .push(
+                text_input("100", &self.settings_draft.ui_scale)
+                    .on_input(Message::SettingsUiScaleChanged)
+                    .padding(12)
+                    .size(16),
+            );
+
+        let countdown_font = Column::new()
+            .spacing(8)
+            .push(text(t("countdown-font")).size(16))
+            .push(
+                text_input(
+                    t("countdown-font-placeholder"),
+                    &self.settings_draft.countdown_font,
+                )
+                .on_input(Message::SettingsCountdownFontChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(self.font_error.as_ref().map(|error| {
+                text(format!("⚠ {}", error))
+                    .size(14)
+                    .color(palette::current().error)
+            }));
+
+        // Form fields with improved layout
+        let work = Column::new()
+            .spacing(8)
+            .push(text(t("work-duration-minutes")).size(16))
+            .push(stepper(
+                duration_minutes(&self.settings_draft.work_duration),
+                1..=120,
+                Message::SettingsWorkMinutesChanged,
+                duration_input(
+                    &self.settings_draft.work_duration,
+                    Message::SettingsWorkDurationChanged,
+                ),
+            ))
+            .push(
+                checkbox(self.settings_draft.experiment)
+                    .label(t("experiment-lengths"))
+                    .on_toggle(Message::SettingsExperimentToggled)
+                    .size(18)
+                    .text_size(16),
+            )
+            .push(self.settings_draft.experiment.then(|| {
+                Column::new()
+                    .spacing(8)
+                    .push(stepper(
+                        duration_minutes(&self.settings_draft.experiment_duration),
+                        1..=120,
+                        Message::SettingsExperimentMinutesChanged,
+                        duration_input(
+                            &self.settings_draft.experiment_duration,
+                            Message::SettingsExperimentDurationChanged,
+                        ),
+                    ))
+                    .push(text(t("experiment-hint")).size(14))
+            }));
+
+        let short_break = Column::new()
+            .spacing(8)
+            .push(text(t("short-break-minutes")).size(16))
+            .push(stepper(
+                duration_minutes(&self.settings_draft.short_break_duration),
+                1..=60,
+                Message::SettingsShortBreakMinutesChanged,
+                duration_input(
+                    &self.settings_draft.short_break_duration,
+                    Message::SettingsShortBreakDurationChanged,
+                ),
+            ));
+
+        let long_break = Column::new()
+            .spacing(8)
+            .push(text(t("long-break-minutes")).size(16))
+            .push(stepper(
+                duration_minutes(&self.settings_draft.long_break_duration),
+                1..=120,
+                Message::SettingsLongBreakMinutesChanged,
+                duration_input(
+                    &self.settings_draft.long_break_duration,
+                    Message::SettingsLongBreakDurationChanged,
+                ),
+            ));
+
+        let long_breaks_enabled = self.settings_draft.long_break_every > 0;
+        let long_every = Column::new()
+            .spacing(8)
+            .push(text(t("long-break-every-pomodoros")).size(16))
+            .push(
+                checkbox(long_breaks_enabled)
+                    .label(t("take-long-breaks"))
+                    .on_toggle(Message::SettingsLongBreaksToggled)
+                    .size(18)
+                    .text_size(16),
+            )
+            .push(long_breaks_enabled.then(|| {
+                stepper(
+                    self.settings_draft.long_break_every,
+                    1..=12,
+                    Message::SettingsLongBreakEveryChanged,
+                    text(self.settings_draft.long_break_every.to_string())
+                        .size(16)
+                        .width(40),
+                )
+            }));
+
+        // Advanced: explicit long-break positions, previewed as you type
+        let pattern_preview = match CadencePattern::parse(&self.settings_draft.long_break_pattern) {
+            Some(pattern) if pattern.is_empty() => {
+                text(t("long-break-pattern-empty-hint")).size(14)
+            }
+            Some(pattern) => {
+                let preview = Settings {
+                    long_break_pattern: pattern,
+                    ..self.settings
+                }
+                .long_breaks_preview(6);
+                let positions: Vec<String> = preview.iter().map(|n| format!("#{n}")).collect();
+                text(tf(
+                    "long-break-preview",
+                    &[("positions", &positions.join(", "))],
+                ))
+                .size(14)
+            }
+            None => text(tf(
+                "long-break-pattern-error",
+                &[("max", &CadencePattern::MAX_POSITION)],
+            ))
+            .size(14)
+            .color(palette::current().error),
+        };
+        let long_pattern = Column::new()
+            .spacing(8)
+            .push(text(t("long-break-pattern-advanced")).size(16))
+            .push(
+                text_input("e.g. 2,5", &self.settings_draft.long_break_pattern)
+                    .on_input(Message::SettingsLongBreakPatternChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(pattern_preview);
+
+        let adaptive_break = Column::new()
+            .spacing(8)
+            .push(text(t("adaptive-break-length")).size(16))
+            .push(stepper(
+                self.settings_draft.adaptive_break_minutes,
+                0..=10,
+                Message::SettingsAdaptiveBreakChanged,
+                text(self.settings_draft.adaptive_break_minutes.to_string())
+                    .size(16)
+                    .width(40),
+            ))
+            .push(text(t("adaptive-break-hint")).size(14));
+
+        let quiet_hours = Column::new()
+            .spacing(8)
+            .push(text(t("quiet-hours")).size(16))
+            .push(
+                text_input("12:00-13:00, 22:00-07:00", &self.settings_draft.quiet_hours)
+                    .on_input(Message::SettingsQuietHoursChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(match QuietHours::parse(&self.settings_draft.quiet_hours) {
+                Some(_) => text(t("quiet-hours-hint")).size(14),
+                None => text(t("quiet-hours-error"))
+                    .size(14)
+                    .color(palette::current().error),
+            });
+
+        let mut shortcuts = Column::new()
+            .spacing(8)
+            .push(text(t("keyboard-shortcuts")).size(16));
+        for shortcut in Shortcut::ALL {
+            let binding = match self.recording_shortcut {
+                Some(recording) if recording == shortcut => t("press-a-key").to_string(),
+                _ => self.settings_draft.shortcuts.get(shortcut).to_string(),
+            };
+            shortcuts = shortcuts.push(
+                row![
+                    text(shortcut.to_string()).size(16).width(Length::Fill),
+                    button(text(binding).size(16))
+                        .padding([8, 16])
+                        .style(transparent_button_style)
+                        .on_press(Message::RecordShortcut(shortcut)),
+                ]
+                .spacing(10)
+                .align_y(Center),
+            );
+        }
+        let shortcuts = shortcuts
+            .push(match &self.shortcut_conflict {
+                Some(conflict) => text(conflict.clone())
+                    .size(14)
+                    .color(palette::current().error),
+                None => text(t("keyboard-shortcuts-hint")).size(14),
+            })
+            .push(
+                button(text(t("reset-shortcuts")).size(16))
+                    .padding([12, 24])
+                    .style(transparent_button_style)
+                    .on_press_maybe(
+                        (self.settings_draft.shortcuts != Shortcuts::default())
+                            .then_some(Message::ResetShortcuts),
+                    ),
+            );
+
+        let long_skip = Column::new()
+            .spacing(8)
+            .push(text(t("long-break-skip")).size(16))
+            .push(
+                pick_list(
+                    LongBreakSkip::ALL,
+                    Some(self.settings_draft.long_break_skip),
+                    Message::SettingsLongBreakSkipChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+
+        let volume = Column::new()
+            .spacing(8)
+            .push(text(t("alarm-volume")).size(16))
+            .push(
+                row![
+                    text_input("100", &self.settings_draft.alarm_volume)
+                        .on_input(Message::SettingsAlarmVolumeChanged)
+                        .padding(12)
+                        .size(16),
+                    button(text(t("test-sound")).size(16))
+                        .padding([12, 18])
+                        .style(transparent_button_style)
+                        .on_press(Message::PreviewAlarm),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+
+        let tick_volume = Column::new()
+            .spacing(8)
+            .push(text(t("ticking-volume")).size(16))
+            .push(
+                text_input("0", &self.settings_draft.tick_volume)
+                    .on_input(Message::SettingsTickVolumeChanged)
+                    .padding(12)
+                    .size(16),
+            );
+
+        let mut ambience = Column::new()
+            .spacing(8)
+            .push(text(t("ambient-sound")).size(16))
+            .push(
+                row![
+                    pick_list(
+                        AmbientSound::ALL,
+                        Some(self.settings_draft.ambient_sound),
+                        Message::SettingsAmbientSoundChanged,
+                    )
+                    .padding(12)
+                    .text_size(16),
+                    text_input("50", &self.settings_draft.ambient_volume)
+                        .on_input(Message::SettingsAmbientVolumeChanged)
+                        .padding(12)
+                        .size(16),
+                    text("%").size(16),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        if self.settings_draft.ambient_sound == AmbientSound::File {
+            ambience = ambience.push(
+                text_input(
+                    t("ambient-file-placeholder"),
+                    &self.settings_draft.ambient_path,
+                )
+                .on_input(Message::SettingsAmbientPathChanged)
+                .padding(12)
+                .size(16),
+            );
+        }
+
+        let break_screen = Column::new()
+            .spacing(8)
+            .push(
+                checkbox(self.settings_draft.break_screen)
+                    .label(t("break-screen"))
+                    .on_toggle(Message::SettingsBreakScreenToggled)
+                    .size(18)
+                    .text_size(16),
+            )
+            .push(self.settings_draft.break_screen.then(|| {
+                Column::new()
+                    .spacing(8)
+                    .push(
+                        pick_list(
+                            BreakScreenPlacement::ALL,
+                            Some(self.settings_draft.break_screen_placement),
+                            Message::SettingsBreakScreenPlacementChanged,
+                        )
+                        .padding(12)
+                        .text_size(16),
+                    )
+                    .push(text(t("break-screen-placement-hint")).size(14))
+            }));
+
+        let keep_screen_on = checkbox(self.settings_draft.keep_screen_on)
+            .label(t("keep-screen-on"))
+            .on_toggle(Message::SettingsKeepScreenOnToggled)
+            .size(18)
+            .text_size(16);
+
+        let do_not_disturb = dnd::SUPPORTED.then(|| {
+            Column::new()
+                .spacing(8)
+                .push(
+                    checkbox(self.settings_draft.do_not_disturb)
+                        .label(t("do-not-disturb"))
+                        .on_toggle(Message::SettingsDoNotDisturbToggled)
+                        .size(18)
+                        .text_size(16),
+                )
+                .push(text(t("do-not-disturb-hint")).size(14))
+        });
+
+        // Apps the focus guard watches for during work
+        let mut focus_guard = Column::new()
+            .spacing(8)
+            .push(text(t("focus-guard")).size(16))
+            .push(
+                pick_list(
+                    FocusGuard::ALL,
+                    Some(self.settings_draft.focus_guard),
+                    Message::SettingsFocusGuardChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+        if self.settings_draft.focus_guard != FocusGuard::Off {
+            for (index, app) in self.settings_draft.guarded_apps.iter().enumerate() {
+                focus_guard = focus_guard.push(
+                    row![
+                        text(&app.name).size(14).width(Length::Fill),
+                        checkbox(app.blocked)
+                            .label(t("blocked"))
+                            .on_toggle(move |blocked| {
+                                Message::SettingsGuardedAppToggled(index, blocked)
+                            })
+                            .size(16)
+                            .text_size(14),
+                        button(text("✕").size(14))
+                            .padding([6, 10])
+                            .style(transparent_button_style)
+                            .on_press(Message::RemoveGuardedApp(index)),
+                    ]
+                    .spacing(8)
+                    .align_y(Center),
+                );
+            }
+            focus_guard = focus_guard
+                .push(
+                    row![
+                        text_input(
+                            t("guarded-app-placeholder"),
+                            &self.settings_draft.new_guarded_app
+                        )
+                        .on_input(Message::SettingsGuardedAppChanged)
+                        .on_submit(Message::AddGuardedApp)
+                        .padding(12)
+                        .size(16),
+                        button(text(t("add-guarded-app")).size(16))
+                            .padding([12, 18])
+                            .style(transparent_button_style)
+                            .on_press(Message::AddGuardedApp),
+                    ]
+                    .spacing(8)
+                    .align_y(Center),
+                )
+                .push(
+                    text(match self.settings_draft.focus_guard {
+                        FocusGuard::Minimize => t("focus-guard-minimize-hint"),
+                        _ => t("focus-guard-hint"),
+                    })
+                    .size(14),
+                );
+        }
+
+        let window_titles = Column::new()
+            .spacing(8)
+            .push(
+                checkbox(self.settings_draft.track_window_titles)
+                    .label(t("track-window-titles"))
+                    .on_toggle(Message::SettingsTrackWindowTitlesToggled)
+                    .size(18)
+                    .text_size(16),
+            )
+            .push(self.settings_draft.track_window_titles.then(|| {
+                text_input(
+                    "youtube; reddit; twitter",
+                    &self.settings_draft.distracting_titles,
+                )
+                .on_input(Message::SettingsDistractingTitlesChanged)
+                .padding(12)
+                .size(16)
+            }))
+            .push(text(t("track-window-titles-hint")).size(14));
+
+        let editor_heartbeats = Column::new()
+            .spacing(8)
+            .push(
+                checkbox(self.settings_draft.editor_heartbeats)
+                    .label(t("editor-heartbeats"))
+                    .on_toggle(Message::SettingsEditorHeartbeatsToggled)
+                    .size(18)
+                    .text_size(16),
+            )
+            .push(text(t("editor-heartbeats-hint")).size(14));
+
+        let eye_breaks = checkbox(self.settings_draft.eye_breaks)
+            .label(t("eye-breaks"))
+            .on_toggle(Message::SettingsEyeBreaksToggled)
+            .size(18)
+            .text_size(16);
+
+        let ask_intention = checkbox(self.settings_draft.ask_intention)
+            .label(t("ask-intention"))
+            .on_toggle(Message::SettingsAskIntentionToggled)
+            .size(18)
+            .text_size(16);
+
+        let announce_transitions = checkbox(self.settings_draft.announce_transitions)
+            .label(t("announce-transitions"))
+            .on_toggle(Message::SettingsAnnounceTransitionsToggled)
+            .size(18)
+            .text_size(16);
+
+        let always_on_top = checkbox(self.settings_draft.always_on_top)
+            .label(t("always-on-top"))
+            .on_toggle(Message::SettingsAlwaysOnTopToggled)
+            .size(18)
+            .text_size(16);
+
+        let tray_options = tray::SUPPORTED.then(|| {
+            Column::new()
+                .spacing(8)
+                .push(
+                    checkbox(self.settings_draft.start_minimized)
+                        .label(t("start-minimized"))
+                        .on_toggle(Message::SettingsStartMinimizedToggled)
+                        .size(18)
+                        .text_size(16),
+                )
+                .push(
+                    checkbox(self.settings_draft.close_to_tray)
+                        .label(t("close-to-tray"))
+                        .on_toggle(Message::SettingsCloseToTrayToggled)
+                        .size(18)
+                        .text_size(16),
+                )
+                .push(
+                    checkbox(self.settings_draft.minimize_to_tray)
+                        .label(t("minimize-to-tray"))
+                        .on_toggle(Message::SettingsMinimizeToTrayToggled)
+                        .size(18)
+                        .text_size(16),
+                )
+                .push(text(t("close-to-tray-hint")).size(14))
+        });
+
+        let strict_mode = checkbox(self.settings_draft.strict_mode)
+            .label(t("strict-mode"))
+            .on_toggle(Message::SettingsStrictModeToggled)
+            .size(18)
+            .text_size(16);
+
+        let screen_lock = session::SUPPORTED.then(|| {
+            Column::new()
+                .spacing(8)
+                .push(text(t("screen-lock-action")).size(16))
+                .push(
+                    pick_list(
+                        ScreenLockAction::ALL,
+                        Some(self.settings_draft.screen_lock_action),
+                        Message::SettingsScreenLockActionChanged,
+                    )
+                    .padding(12)
+                    .text_size(16),
+                )
+        });
+
+        // Labels line up with the inputs they describe
+        let labelled = |label: &'static str, input: Element<'static, Message>| {
+            row![text(t(label)).size(14).width(160), input]
+                .spacing(8)
+                .align_y(Center)
+        };
+        let pattern_input = |value: &str, placeholder: &str, on_input: fn(String) -> Message| {
+            text_input(placeholder, value)
+                .on_input(on_input)
+                .padding(8)
+                .size(14)
+                .into()
+        };
+        let alarm_pattern = Column::new()
+            .spacing(8)
+            .push(text(t("alarm-pattern")).size(16))
+            .push(labelled(
+                "alarm-tones",
+                pattern_input(
+                    &self.settings_draft.alarm_tones,
+                    "240, 340, 440",
+                    Message::SettingsAlarmTonesChanged,
+                ),
+            ))
+            .push(labelled(
+                "alarm-beeps",
+                stepper(
+                    self.settings_draft.alarm_beeps,
+                    1..=AlarmPattern::MAX_BEEPS,
+                    Message::SettingsAlarmBeepsChanged,
+                    text(self.settings_draft.alarm_beeps.to_string())
+                        .size(16)
+                        .width(40),
+                ),
+            ))
+            .push(labelled(
+                "alarm-beep-length",
+                pattern_input(
+                    &self.settings_draft.alarm_beep_ms,
+                    "500",
+                    Message::SettingsAlarmBeepLengthChanged,
+                ),
+            ))
+            .push(labelled(
+                "alarm-gap",
+                pattern_input(
+                    &self.settings_draft.alarm_gap_ms,
+                    "500",
+                    Message::SettingsAlarmGapChanged,
+                ),
+            ))
+            .push(labelled(
+                "alarm-length",
+                pattern_input(
+                    &self.settings_draft.alarm_length_ms,
+                    "5000",
+                    Message::SettingsAlarmLengthChanged,
+                ),
+            ))
+            .push(text(t("alarm-pattern-hint")).size(14));
+
+        let snooze = Column::new()
+            .spacing(8)
+            .push(text(t("snooze-length")).size(16))
+            .push(stepper(
+                self.settings_draft.snooze_minutes,
+                1..=30,
+                Message::SettingsSnoozeMinutesChanged,
+                text(self.settings_draft.snooze_minutes.to_string())
+                    .size(16)
+                    .width(40),
+            ))
+            .push(text(t("snooze-hint")).size(14));
+
+        let break_extension = Column::new()
+            .spacing(8)
+            .push(text(t("break-extension-length")).size(16))
+            .push(stepper(
+                self.settings_draft.break_extension_minutes,
+                0..=15,
+                Message::SettingsBreakExtensionChanged,
+                text(self.settings_draft.break_extension_minutes.to_string())
+                    .size(16)
+                    .width(40),
+            ))
+            .push(text(t("break-extension-hint")).size(14));
+
+        let alarm_repeat = Column::new()
+            .spacing(8)
+            .push(text(t("alarm-repeat")).size(16))
+            .push(
+                text_input("0", &self.settings_draft.alarm_repeat_seconds)
+                    .on_input(Message::SettingsAlarmRepeatChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("alarm-repeat-hint")).size(14));
+
+        let overlay = Column::new()
+            .spacing(8)
+            .push(text(t("countdown-overlay")).size(16))
+            .push(
+                row![
+                    pick_list(
+                        OverlayCorner::ALL,
+                        Some(self.settings_draft.overlay_corner),
+                        Message::SettingsOverlayCornerChanged,
+                    )
+                    .padding(12)
+                    .text_size(16),
+                    text_input("32", &self.settings_draft.overlay_font_size)
+                        .on_input(Message::SettingsOverlayFontSizeChanged)
+                        .padding(12)
+                        .size(16),
+                    text("px").size(16),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+
+        let completion = Column::new()
+            .spacing(8)
+            .push(text(t("completion-criteria")).size(16))
+            .push(
+                pick_list(
+                    CompletionCriteria::ALL,
+                    Some(self.settings_draft.completion_criteria),
+                    Message::SettingsCompletionCriteriaChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+
+        let low_power = Column::new()
+            .spacing(8)
+            .push(text(t("low-power-threshold")).size(16))
+            .push(
+                text_input("0", &self.settings_draft.low_power_threshold)
+                    .on_input(Message::SettingsLowPowerThresholdChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("low-power-hint")).size(14));
+
+        // Per-period focus messages
+        let mut messages = Column::new()
+            .spacing(8)
+            .push(text(t("focus-messages")).size(16));
+        for kind in PeriodKind::ALL {
+            let label = kind.to_string();
+            let message = self.settings_draft.focus_messages.get(kind);
+            messages = messages.push(
+                row![
+                    text(label).size(14).width(120),
+                    text_input(t("focus-header-placeholder"), &message.header)
+                        .on_input(move |value| Message::SettingsFocusHeaderChanged(kind, value))
+                        .padding(8)
+                        .size(14),
+                    text_input(t("focus-message-placeholder"), &message.text)
+                        .on_input(move |value| Message::SettingsFocusMessageChanged(kind, value))
+                        .padding(8)
+                        .size(14),
+                    text_input(t("image-path-optional"), &message.image_path)
+                        .on_input(move |value| Message::SettingsFocusImageChanged(kind, value))
+                        .padding(8)
+                        .size(14),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+
+        // Per-period backgrounds behind the timer
+        let mut backgrounds = Column::new()
+            .spacing(8)
+            .push(text(t("backgrounds")).size(16));
+        for kind in PeriodKind::ALL {
+            let background = self.settings_draft.backgrounds.get(kind);
+            backgrounds = backgrounds.push(
+                row![
+                    text(kind.to_string()).size(14).width(120),
+                    text_input(t("image-path-optional"), &background.image_path)
+                        .on_input(move |value| {
+                            Message::SettingsBackgroundImageChanged(kind, value)
+                        })
+                        .padding(8)
+                        .size(14),
+                    text_input("#ff6b6b, #4ecdc4", &background.gradient)
+                        .on_input(move |value| {
+                            Message::SettingsBackgroundGradientChanged(kind, value)
+                        })
+                        .padding(8)
+                        .size(14),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+        let backgrounds = backgrounds
+            .push(if self.settings_draft.backgrounds.is_valid() {
+                text(t("backgrounds-hint")).size(14)
+            } else {
+                text(t("background-gradient-error"))
+                    .size(14)
+                    .color(palette::current().error)
+            })
+            .push(text(t("background-dim")).size(16))
+            .push(stepper(
+                self.settings_draft.background_dim,
+                0..=90,
+                Message::SettingsBackgroundDimChanged,
+                text(format!("{}%", self.settings_draft.background_dim))
+                    .size(16)
+                    .width(50),
+            ));
+
+        let streak_rules = Column::new()
+            .spacing(8)
+            .push(text(t("streak-day-start")).size(16))
+            .push(
+                text_input("0", &self.settings_draft.day_start_hour)
+                    .on_input(Message::SettingsDayStartHourChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("holidays")).size(16))
+            .push(
+                text_input("2026-12-25; 2026-12-26", &self.settings_draft.holidays)
+                    .on_input(Message::SettingsHolidaysChanged)
+                    .padding(12)
+                    .size(16),
+            );
+
+        let calendar = Column::new()
+            .spacing(8)
+            .push(text(t("calendar")).size(16))
+            .push(
+                button(text(t("export-calendar")).size(16))
+                    .style(transparent_button_style)
+                    .on_press(Message::ExportCalendar)
+                    .padding([8, 12]),
+            )
+            .push(
+                self.calendar_export
+                    .as_ref()
+                    .map(|status| text(status).size(14)),
+            )
+            .push(text(t("calendar-subscribe-hint")).size(14))
+            .push(text(t("meetings-calendar")).size(14))
+            .push(
+                text_input(
+                    "https://calendar.google.com/calendar/ical/.../basic.ics",
+                    &self.settings_draft.integrations.meeting_calendar_url,
+                )
+                .on_input(Message::SettingsMeetingCalendarUrlChanged)
+                .padding(12)
+                .size(16),
+            );
+
+        let import_button = self
+            .import_preview
+            .as_ref()
+            .filter(|preview| !preview.sessions.is_empty())
+            .map(|preview| {
+                button(text(tf("import-sessions", &[("count", &preview.sessions.len())])).size(16))
+                    .style(transparent_button_style)
+                    .on_press(Message::ConfirmImport)
+                    .padding([8, 12])
+            });
+        let import = Column::new()
+            .spacing(8)
+            .push(text(t("import-from-app")).size(16))
+            .push(
+                row![
+                    pick_list(
+                        ImportFormat::ALL,
+                        Some(self.import_format),
+                        Message::ImportFormatChanged
+                    )
+                    .padding(8)
+                    .text_size(14),
+                    text_input(t("import-path-placeholder"), &self.import_path)
+                        .on_input(Message::ImportPathChanged)
+                        .padding(8)
+                        .size(14),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            )
+            .push(
+                row![
+                    button(text(t("preview")).size(16))
+                        .style(transparent_button_style)
+                        .on_press(Message::PreviewImport)
+                        .padding([8, 12]),
+                ]
+                .push(import_button)
+                .spacing(10),
+            )
+            .push(
+                self.import_status
+                    .as_ref()
+                    .map(|status| text(status).size(14)),
+            );
+
+        let restore_button = self.restore_checked.then(|| {
+            button(text(t("restore-backup")).size(16))
+                .style(transparent_button_style)
+                .on_press(Message::ConfirmRestore)
+                .padding([8, 12])
+        });
+        let backup = Column::new()
+            .spacing(8)
+            .push(text(t("backup")).size(16))
+            .push(
+                button(text(t("backup-now")).size(16))
+                    .style(transparent_button_style)
+                    .on_press(Message::BackUpNow)
+                    .padding([8, 12]),
+            )
+            .push(
+                text_input(
+                    &crate::db::backup_dir()
+                        .join("roth-pomodoro-….sqlite")
+                        .display()
+                        .to_string(),
+                    &self.restore_path,
+                )
+                .on_input(Message::RestorePathChanged)
+                .padding(8)
+                .size(14),
+            )
+            .push(
+                row![
+                    button(text(t("check-backup")).size(16))
+                        .style(transparent_button_style)
+                        .on_press(Message::CheckBackup)
+                        .padding([8, 12]),
+                ]
+                .push(restore_button)
+                .spacing(10),
+            )
+            .push(
+                self.backup_status
+                    .as_ref()
+                    .map(|status| text(status).size(14)),
+            );
+
+        let report = Column::new()
+            .spacing(8)
+            .push(text(t("weekly-report-folder")).size(16))
+            .push(
+                text_input(
+                    "~/Documents/Pomodoro",
+                    &self.settings_draft.integrations.report.dir,
+                )
+                .on_input(Message::SettingsReportDirChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(
+                checkbox(self.settings_draft.integrations.report.html)
+                    .label(t("report-html"))
+                    .on_toggle(Message::SettingsReportHtmlToggled)
+                    .size(18)
+                    .text_size(16),
+            );
+
+        let api = Column::new()
+            .spacing(8)
+            .push(text(t("api-port")).size(16))
+            .push(
+                text_input("0", &self.settings_draft.api_port)
+                    .on_input(Message::SettingsApiPortChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("api-port-hint")).size(14));
+
+        let mqtt = Column::new()
+            .spacing(8)
+            .push(text(t("mqtt-broker")).size(16))
+            .push(
+                text_input(
+                    "localhost:1883",
+                    &self.settings_draft.integrations.mqtt.broker,
+                )
+                .on_input(Message::SettingsMqttBrokerChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(
+                text_input(
+                    t("topic-prefix"),
+                    &self.settings_draft.integrations.mqtt.topic_prefix,
+                )
+                .on_input(Message::SettingsMqttTopicPrefixChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(
+                row![
+                    text_input(
+                        t("username"),
+                        &self.settings_draft.integrations.mqtt.username
+                    )
+                    .on_input(Message::SettingsMqttUsernameChanged)
+                    .padding(12)
+                    .size(16),
+                    text_input(
+                        t("password"),
+                        &self.settings_draft.integrations.mqtt.password
+                    )
+                    .on_input(Message::SettingsMqttPasswordChanged)
+                    .secure(true)
+                    .padding(12)
+                    .size(16),
+                ]
+                .spacing(8),
+            )
+            .push(text(t("mqtt-hint")).size(14));
+
+        let push_draft = &self.settings_draft.integrations.push;
+        let mut push = Column::new()
+            .spacing(8)
+            .push(text(t("push-notifications")).size(16))
+            .push(
+                pick_list(
+                    PushService::ALL,
+                    Some(push_draft.service),
+                    Message::SettingsPushServiceChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+        match push_draft.service {
+            PushService::Off => {}
+            PushService::Ntfy => {
+                push = push.push(
+                    text_input(t("ntfy-topic"), &push_draft.ntfy_topic)
+                        .on_input(Message::SettingsNtfyTopicChanged)
+                        .padding(12)
+                        .size(16),
+                );
+            }
+            PushService::Pushover => {
+                push = push.push(
+                    row![
+                        text_input(t("user-key"), &push_draft.pushover_user)
+                            .on_input(Message::SettingsPushoverUserChanged)
+                            .padding(12)
+                            .size(16),
+                        text_input(t("application-token"), &push_draft.pushover_token)
+                            .on_input(Message::SettingsPushoverTokenChanged)
+                            .secure(true)
+                            .padding(12)
+                            .size(16),
+                    ]
+                    .spacing(8),
+                );
+            }
+        }
+        push = push.push(text(t("push-hint")).size(14));
+
+        let telegram = Column::new()
+            .spacing(8)
+            .push(text(t("telegram-bot")).size(16))
+            .push(
+                row![
+                    text_input(
+                        t("bot-token"),
+                        &self.settings_draft.integrations.telegram.token
+                    )
+                    .on_input(Message::SettingsTelegramTokenChanged)
+                    .secure(true)
+                    .padding(12)
+                    .size(16),
+                    text_input(
+                        t("chat-id"),
+                        &self.settings_draft.integrations.telegram.chat_id
+                    )
+                    .on_input(Message::SettingsTelegramChatIdChanged)
+                    .padding(12)
+                    .size(16),
+                ]
+                .spacing(8),
+            )
+            .push(text(t("telegram-hint")).size(14));
+
+        let toggl_draft = &self.settings_draft.integrations.toggl;
+        let toggl = Column::new()
+            .spacing(8)
+            .push(text(t("toggl-track")).size(16))
+            .push(
+                text_input(t("api-token-empty-off"), &toggl_draft.api_token)
+                    .on_input(Message::SettingsTogglTokenChanged)
+                    .secure(true)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(
+                row![
+                    text_input(t("workspace-id"), &toggl_draft.workspace_id)
+                        .on_input(Message::SettingsTogglWorkspaceChanged)
+                        .padding(12)
+                        .size(16),
+                    text_input(t("project-id-optional"), &toggl_draft.project_id)
+                        .on_input(Message::SettingsTogglProjectChanged)
+                        .padding(12)
+                        .size(16),
+                ]
+                .spacing(8),
+            )
+            .push(
+                text_input(t("description"), &toggl_draft.description)
+                    .on_input(Message::SettingsTogglDescriptionChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("toggl-hint")).size(14));
+
+        let worklog_draft = &self.settings_draft.integrations.worklog;
+        let mut worklog = Column::new()
+            .spacing(8)
+            .push(text(t("worklog")).size(16))
+            .push(
+                pick_list(
+                    WorklogService::ALL,
+                    Some(worklog_draft.service),
+                    Message::SettingsWorklogServiceChanged,
+                )
+                .padding(12)
+                .text_size(16),
+            );
+        worklog = match worklog_draft.service {
+            WorklogService::Off => worklog,
+            WorklogService::Clockify => worklog.push(
+                row![
+                    text_input(t("workspace-id"), &worklog_draft.clockify_workspace_id)
+                        .on_input(Message::SettingsWorklogWorkspaceChanged)
+                        .padding(12)
+                        .size(16),
+                    text_input(t("project-id-optional"), &worklog_draft.task_key)
+                        .on_input(Message::SettingsWorklogTaskKeyChanged)
+                        .padding(12)
+                        .size(16),
+                ]
+                .spacing(8),
+            ),
+            WorklogService::Jira => worklog
+                .push(
+                    text_input("https://example.atlassian.net", &worklog_draft.jira_url)
+                        .on_input(Message::SettingsWorklogJiraUrlChanged)
+                        .padding(12)
+                        .size(16),
+                )
+                .push(
+                    row![
+                        text_input(t("jira-email"), &worklog_draft.jira_email)
+                            .on_input(Message::SettingsWorklogJiraEmailChanged)
+                            .padding(12)
+                            .size(16),
+                        text_input(t("jira-issue-key"), &worklog_draft.task_key)
+                            .on_input(Message::SettingsWorklogTaskKeyChanged)
+                            .padding(12)
+                            .size(16),
+                    ]
+                    .spacing(8),
+                ),
+        };
+        let worklog = if worklog_draft.service == WorklogService::Off {
+            worklog
+        } else {
+            worklog
+                .push(
+                    text_input(t("api-token"), &worklog_draft.token)
+                        .on_input(Message::SettingsWorklogTokenChanged)
+                        .secure(true)
+                        .padding(12)
+                        .size(16),
+                )
+                .push(text(t("worklog-hint")).size(14))
+                .push((self.worklog_status.waiting > 0).then(|| {
+                    text(tf(
+                        "worklogs-waiting",
+                        &[("count", &self.worklog_status.waiting)],
+                    ))
+                    .size(14)
+                    .color(palette::current().warning)
+                }))
+                .push((!self.worklog_status.last_error.is_empty()).then(|| {
+                    text(tf(
+                        "worklog-last-error",
+                        &[("error", &self.worklog_status.last_error)],
+                    ))
+                    .size(14)
+                    .color(palette::current().warning)
+                }))
+        };
+
+        let todoist_draft = &self.settings_draft.integrations.todoist;
+        let todoist = Column::new()
+            .spacing(8)
+            .push(text(t("todoist")).size(16))
+            .push(
+                text_input(t("api-token-empty-off"), &todoist_draft.token)
+                    .on_input(Message::SettingsTodoistTokenChanged)
+                    .secure(true)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(
+                row![
+                    text(t("todoist-every")).size(16),
+                    pick_list(
+                        EVERY_CHOICES,
+                        Some(todoist_draft.every),
+                        Message::SettingsTodoistEveryChanged,
+                    )
+                    .padding(8)
+                    .text_size(14),
+                    text(t("active-task-pomodoros-suffix")).size(16),
+                    pick_list(
+                        TaskAction::ALL,
+                        Some(todoist_draft.action),
+                        Message::SettingsTodoistActionChanged,
+                    )
+                    .padding(8)
+                    .text_size(14),
+                ]
+                .spacing(8)
+                .align_y(Center)
+                .wrap(),
+            )
+            .push(text(t("todoist-hint")).size(14));
+
+        let github_draft = &self.settings_draft.integrations.github;
+        let github = Column::new()
+            .spacing(8)
+            .push(text(t("github")).size(16))
+            .push(
+                text_input(t("github-token"), &github_draft.token)
+                    .on_input(Message::SettingsGithubTokenChanged)
+                    .secure(true)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(
+                row![
+                    text(t("github-every")).size(16),
+                    pick_list(
+                        EVERY_CHOICES,
+                        Some(github_draft.every),
+                        Message::SettingsGithubEveryChanged,
+                    )
+                    .padding(8)
+                    .text_size(14),
+                    text(t("active-task-pomodoros-suffix")).size(16),
+                ]
+                .spacing(8)
+                .align_y(Center)
+                .wrap(),
+            )
+            .push(text(t("github-hint")).size(14));
+
+        let daily_log_draft = &self.settings_draft.integrations.daily_log;
+        let daily_log = Column::new()
+            .spacing(8)
+            .push(text(t("notes-log")).size(16))
+            .push(
+                text_input(t("notes-log-path"), &daily_log_draft.path)
+                    .on_input(Message::SettingsDailyLogPathChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(
+                text_input(crate::daily_log::DEFAULT_FORMAT, &daily_log_draft.format)
+                    .on_input(Message::SettingsDailyLogFormatChanged)
+                    .padding(12)
+                    .size(16),
+            )
+            .push(text(t("notes-log-hint")).size(14));
+
+        let obs_output = Column::new()
+            .spacing(8)
+            .push(text(t("streaming-overlay")).size(16))
+            .push(
+                text_input(
+                    t("obs-folder-placeholder"),
+                    &self.settings_draft.integrations.obs_output_dir,
+                )
+                .on_input(Message::SettingsObsOutputDirChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(
+                text(tf(
+                    "streaming-overlay-hint",
+                    &[
+                        ("countdown", &crate::obs::COUNTDOWN_FILE),
+                        ("period", &crate::obs::PERIOD_FILE),
+                    ],
+                ))
+                .size(14),
+            );
+
+        let remote_sources = Column::new()
+            .spacing(8)
+            .push(text(t("remote-databases")).size(16))
+            .push(
+                text_input(
+                    "/path/to/laptop/roth-pomodoro.sqlite",
+                    &self.settings_draft.remote_sources,
+                )
+                .on_input(Message::SettingsRemoteSourcesChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(text(t("sync-folder")).size(16))
+            .push(
+                text_input(
+                    "~/Dropbox/roth-pomodoro",
+                    &self.settings_draft.integrations.sync_dir,
+                )
+                .on_input(Message::SettingsSyncDirChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(text(t("sync-folder-hint")).size(14))
+            .push(text(t("sync-server")).size(16))
+            .push(
+                text_input(
+                    "https://sync.example.com/pomodoro",
+                    &self.settings_draft.integrations.sync_server.url,
+                )
+                .on_input(Message::SettingsSyncServerUrlChanged)
+                .padding(12)
+                .size(16),
+            )
+            .push(
+                text_input(
+                    t("access-token"),
+                    &self.settings_draft.integrations.sync_server.token,
+                )
+                .on_input(Message::SettingsSyncServerTokenChanged)
+                .secure(true)
+                .padding(12)
+                .size(16),
+            );
+
+        // Named profiles capture the durations and sounds above
+        let mut profiles = Column::new()
+            .spacing(8)
+            .push(text(t("profiles")).size(16))
+            .push(
+                row![
+                    text_input(t("profile-name-placeholder"), &self.profile_name)
+                        .on_input(Message::SettingsProfileNameChanged)
+                        .on_submit(Message::SaveProfile)
+                        .padding(12)
+                        .size(16),
+                    button(text(t("save-as-profile")).size(16))
+                        .padding([12, 18])
+                        .style(transparent_button_style)
+                        .on_press(Message::SaveProfile),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        for profile in &self.profiles {
+            profiles = profiles.push(
+                row![
+                    text(&profile.name).size(14).width(Length::Fill),
+                    text(format!(
+                        "{}/{}/{} min",
+                        profile.work_seconds / 60,
+                        profile.short_break_seconds / 60,
+                        profile.long_break_seconds / 60
+                    ))
+                    .size(14),
+                    button(text(t("edit")).size(14))
+                        .padding([6, 12])
+                        .style(transparent_button_style)
+                        .on_press(Message::EditProfile(profile.name.clone())),
+                    button(text("✕").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::DeleteProfile(profile.name.clone())),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+
+        let schedule_button = button(text(t("weekday-schedule")).size(16))
+            .padding([12, 24])
+            .style(transparent_button_style)
+            .on_press(Message::OpenSchedule);
+
+        let sequence_button = button(text(t("edit-period-sequence")).size(16))
+            .padding([12, 24])
+            .style(transparent_button_style)
+            .on_press(Message::OpenSequenceEditor);
+
+        // Explain why duration changes may not take effect immediately
+        let in_progress_note = self
+            .is_started()
+            .then(|| text(t("durations-apply-after-period")).size(14));
+
+        // Action buttons with distinct styling
+        let actions = row![
+            button(text(t("save")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::SaveSettings)
+                .padding([12, 24]),
+            button(text(t("cancel")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseSettings)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        // Build main column
+        let mut column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(text("").size(5)) // Spacer
+            .push(in_progress_note)
+            .push(language)
+            .push(text(t("appearance")).size(24))
+            .push(colors)
+            .push(timer_face)
+            .push(ui_scale)
+            .push(countdown_font)
+            .push(backgrounds)
+            .push(work)
+            .push(short_break)
+            .push(long_break)
+            .push(long_every)
+            .push(long_breaks_enabled.then_some(long_pattern))
+            .push(long_breaks_enabled.then_some(long_skip))
+            .push(adaptive_break)
+            .push(sequence_button)
+            .push(profiles)
+            .push(schedule_button)
+            .push(completion)
+            .push(volume)
+            .push(alarm_pattern)
+            .push(tick_volume)
+            .push(ambience)
+            .push(alarm_repeat)
+            .push(snooze)
+            .push(break_extension)
+            .push(break_screen)
+            .push(keep_screen_on)
+            .push(do_not_disturb)
+            .push(focus_guard)
+            .push(window_titles)
+            .push(editor_heartbeats)
+            .push(eye_breaks)
+            .push(ask_intention)
+            .push(announce_transitions)
+            .push(shortcuts)
+            .push(always_on_top)
+            .push(tray_options)
+            .push(strict_mode)
+            .push(screen_lock)
+            .push(quiet_hours)
+            .push(overlay)
+            .push(low_power)
+            .push(messages)
+            .push(streak_rules)
+            .push(remote_sources)
+            .push(api)
+            .push(calendar)
+            .push(report)
+            .push(import)
+            .push(backup)
+            .push(mqtt)
+            .push(push)
+            .push(telegram)
+            .push(toggl)
+            .push(worklog)
+            .push(todoist)
+            .push(github)
+            .push(daily_log)
+            .push(obs_output);
+
+        // Error message with red color
+        if let Some(error) = &self.settings_error {
+            column = column.push(
+                text(format!("⚠ {}", error))
+                    .size(16)
+                    .color(palette::current().error),
+            );
+        }
+
+        column = column
+            .push(text("").size(5)) // Spacer
+            .push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_tasks(&self) -> Element<'_, Message> {
+        let header = text(t("tasks-title")).size(40);
+
+        let status: Option<Element<'_, Message>> = if self.tasks_loading {
+            Some(text(t("tasks-loading")).size(14).into())
+        } else if let Some(error) = &self.tasks_error {
+            Some(text(error).size(14).color(palette::current().error).into())
+        } else if self.todoist_tasks.is_empty() {
+            Some(text(t("tasks-none-due")).size(14).into())
+        } else {
+            None
+        };
+
+        let mut tasks = Column::new().spacing(4);
+        for task in &self.todoist_tasks {
+            let active = self.active_task.as_ref().map(|t| &t.id) == Some(&task.id);
+            let label = if active {
+                format!("● {}", task.content)
+            } else {
+                task.content.clone()
+            };
+            tasks = tasks.push(
+                button(text(label).size(16))
+                    .width(Length::Fill)
+                    .padding([8, 12])
+                    .style(transparent_button_style)
+                    .on_press(Message::SelectTask(task.clone())),
+            );
+            // Issue titles are only looked up when asked for, to stay well
+            // inside GitHub's rate limit
+            if let Some(issue) = IssueRef::find(&task.content) {
+                let line: Element<'_, Message> = match self.issue_titles.get(&issue) {
+                    Some(Ok(title)) => text(format!("↳ {} · {}", issue, title)).size(14).into(),
+                    Some(Err(err)) => text(format!("↳ {} · {}", issue, err))
+                        .size(14)
+                        .color(palette::current().error)
+                        .into(),
+                    None => button(text(tf("show-issue-title", &[("issue", &issue)])).size(14))
+                        .padding([4, 12])
+                        .style(transparent_button_style)
+                        .on_press(Message::FetchIssueTitle(issue))
+                        .into(),
+                };
+                tasks = tasks.push(container(line).padding([0, 12]));
+            }
+        }
+
+        let issue_link = self.active_task.as_ref().map(|task| {
+            Column::new()
+                .spacing(8)
+                .push(text(t("active-task-issue")).size(16))
+                .push(
+                    text_input(
+                        "https://github.com/owner/repo/issues/1",
+                        &self.active_task_issue,
+                    )
+                    .on_input(Message::ActiveTaskIssueChanged)
+                    .padding(12)
+                    .size(16),
+                )
+                .push(
+                    text(match self.active_issue() {
+                        Some(issue) => tf(
+                            "active-task-issue-linked",
+                            &[
+                                ("issue", &issue),
+                                ("every", &self.integrations.github.every),
+                            ],
+                        ),
+                        None if IssueRef::find(&task.content).is_none() => {
+                            t("active-task-issue-hint").to_string()
+                        }
+                        None => String::new(),
+                    })
+                    .size(14),
+                )
+        });
+
+        let todoist = &self.integrations.todoist;
+        let explanation = text(tf(
+            "tasks-hint",
+            &[
+                ("every", &todoist.every),
+                ("action", &todoist.action.to_string().to_lowercase()),
+            ],
+        ))
+        .size(14);
+
+        let actions = row![
+            button(text(t("refresh")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::RefreshTasks)
+                .padding([12, 24]),
+            button(text(t("clear-task")).size(18))
+                .style(transparent_button_style)
+                .on_press_maybe(
+                    self.active_task
+                        .is_some()
+                        .then_some(Message::ClearActiveTask)
+                )
+                .padding([12, 24]),
+            button(text(t("back")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseTasks)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(status)
+            .push(tasks)
+            .push(issue_link)
+            .push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_stats(&self) -> Element<'_, Message> {
+        let header = text(t("stats")).size(40);
+
+        let tags: Vec<TagFilter> = [TagFilter::All, TagFilter::Untagged]
+            .into_iter()
+            .chain(self.stats_tags.iter().cloned().map(TagFilter::Tag))
+            .collect();
+        let filters = row![
+            text(t("date-range-from")).size(14),
+            text_input("2024-05-01", &self.stats_draft.from)
+                .on_input(Message::StatsFromChanged)
+                .padding(8)
+                .size(14)
+                .width(120),
+            text(t("date-range-to")).size(14),
+            text_input("2024-05-31", &self.stats_draft.to)
+                .on_input(Message::StatsToChanged)
+                .padding(8)
+                .size(14)
+                .width(120),
+            pick_list(
+                tags,
+                Some(self.stats_draft.tag.clone()),
+                Message::StatsTagSelected
+            )
+            .padding(8)
+            .text_size(14),
+        ]
+        .spacing(8)
+        .align_y(Center);
+
+        let mut presets = row![].spacing(8);
+        for (label, days) in [
+            (t("stats-7-days"), 7),
+            (t("stats-30-days"), 30),
+            (t("stats-90-days"), 90),
+            (t("stats-year"), 365),
+        ] {
+            presets = presets.push(
+                button(text(label).size(14))
+                    .padding([6, 12])
+                    .style(transparent_button_style)
+                    .on_press(Message::StatsPreset(days)),
+            );
+        }
+
+        let pomodoros: u32 = self.stats_days.iter().map(|d| d.pomodoros).sum();
+        let focus_seconds: u32 = self.stats_days.iter().map(|d| d.focus_seconds).sum();
+        let totals = text(tf(
+            "stats-totals",
+            &[
+                ("pomodoros", &pomodoros),
+                ("hours", &(focus_seconds / 3600)),
+                ("minutes", &format!("{:02}", (focus_seconds % 3600) / 60)),
+                ("days", &self.stats_days.len()),
+            ],
+        ))
+        .size(18);
+
+        let title_breakdown = (self.stats_titles.tracked_seconds() > 0).then(|| {
+            let tally = self.stats_titles;
+            text(tf(
+                "stats-window-titles",
+                &[
+                    ("focused", &(tally.focused_seconds / 60)),
+                    ("distracted", &(tally.distracted_seconds / 60)),
+                    (
+                        "percent",
+                        &(tally.distracted_seconds * 100 / tally.tracked_seconds()),
+                    ),
+                ],
+            ))
+            .size(14)
+        });
+
+        let chart = self.stats_draft.parse().map(|(from, to, _)| {
+            let bars = crate::stats::bars(&self.stats_days, from, to);
+            let most = bars
+                .iter()
+                .map(|(_, n)| *n)
+                .max()
+                .unwrap_or_default()
+                .max(1);
+            let width = (480.0 / bars.len() as f32).clamp(4.0, 40.0);
+            let (_, color) = period_style(PeriodKind::Work);
+            let mut chart = row![].spacing(2).align_y(iced::Alignment::End);
+            for (day, count) in bars {
+                let bar = container(text(""))
+                    .width(width)
+                    .height(2.0 + 120.0 * count as f32 / most as f32)
+                    .style(move |_theme| container::Style {
+                        background: Some(Background::Color(color)),
+                        border: Border {
+                            radius: 2.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    });
+                chart = chart.push(tooltip(
+                    bar,
+                    text(format!("{}: {} 🍅", day.format("%a %-d %b"), count)).size(14),
+                    tooltip::Position::Top,
+                ));
+            }
+            chart
+        });
+
+        let error = self.stats_error.as_ref().map(|error| {
+            text(format!("⚠ {}", error))
+                .size(16)
+                .color(palette::current().error)
+        });
+
+        let record = self.achievement_record();
+        let mut achievements = Column::new()
+            .spacing(8)
+            .width(Length::Fill)
+            .push(text(t("achievements")).size(20));
+        for achievement in Achievement::ALL {
+            let earned = self
+                .achievements
+                .iter()
+                .find(|(earned, _)| *earned == achievement)
+                .map(|(_, at)| *at);
+            let status = match earned.and_then(|at| chrono::DateTime::from_timestamp(at, 0)) {
+                Some(at) => tf(
+                    "achievement-earned-on",
+                    &[(
+                        "date",
+                        &at.with_timezone(&chrono::Local).format("%-d %b %Y"),
+                    )],
+                ),
+                None => {
+                    let (done, needed) = achievement.progress(&record);
+                    format!("{}/{}", done, needed)
+                }
+            };
+            let color = if earned.is_some() {
+                palette::current().work
+            } else {
+                palette::current().button_text
+            };
+            achievements = achievements.push(
+                row![
+                    text(if earned.is_some() { "🏆" } else { "🔒" }).size(18),
+                    Column::new()
+                        .spacing(2)
+                        .push(text(achievement.title()).size(16).color(color))
+                        .push(text(achievement.description()).size(14))
+                        .width(Length::Fill),
+                    text(status).size(14),
+                ]
+                .spacing(10)
+                .align_y(Center),
+            );
+        }
+
+        let experiment = (!self.experiment_results.is_empty()).then(|| {
+            let length = |seconds: u32| crate::settings::format_duration(seconds);
+            let mut experiment = Column::new()
+                .spacing(8)
+                .width(Length::Fill)
+                .push(text(t("stats-experiment")).size(20));
+            for result in &self.experiment_results {
+                experiment = experiment.push(
+                    text(tf(
+                        "experiment-result",
+                        &[
+                            ("length", &length(result.work_seconds)),
+                            ("finished", &result.finished),
+                            ("attempts", &result.attempts()),
+                            ("completion", &result.completion_percent()),
+                            ("abandoned", &result.abandoned),
+                            ("abandonment", &result.abandonment_percent()),
+                        ],
+                    ))
+                    .size(14),
+                );
+            }
+            experiment.push(
+                text(match crate::experiment::better(&self.experiment_results) {
+                    Some(best) => tf(
+                        "experiment-better",
+                        &[("length", &length(best.work_seconds))],
+                    ),
+                    None => tf(
+                        "experiment-too-early",
+                        &[("count", &crate::experiment::MIN_ATTEMPTS)],
+                    ),
+                })
+                .size(16),
+            )
+        });
+
+        let actions = row![
+            button(text(t("history")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::OpenHistory)
+                .padding([12, 24]),
+            button(text(t("back")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseStats)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(filters)
+            .push(presets)
+            .push(error)
+            .push(totals)
+            .push(title_breakdown)
+            .push(chart)
+            .push(experiment.map(|experiment| container(experiment).max_width(560)))
+            .push(container(achievements).max_width(560))
+            .push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_history(&self) -> Element<'_, Message> {
+        let header = text(t("history")).size(40);
+        let explanation = text(t("history-hint")).size(14);
+
+        let mut sessions = Column::new().spacing(6);
+        for session in &self.history {
+            let ended = chrono::DateTime::from_timestamp(session.ended_at, 0)
+                .map(|at| {
+                    at.with_timezone(&chrono::Local)
+                        .format("%a %-d %b %Y %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let editing = self
+                .history_draft
+                .as_ref()
+                .filter(|draft| draft.id == session.id);
+            let line: Element<'_, Message> = match editing {
+                Some(draft) => row![
+                    text(ended).size(14).width(170),
+                    text_input(t("tag-placeholder"), &draft.tag)
+                        .on_input(Message::HistoryTagChanged)
+                        .padding(6)
+                        .size(14)
+                        .width(200),
+                    text_input("25", &draft.minutes)
+                        .on_input(Message::HistoryMinutesChanged)
+                        .padding(6)
+                        .size(14)
+                        .width(60),
+                    text("min").size(14),
+                    button(text("✓").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::SaveSession),
+                    button(text("🗑").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::DeleteSession),
+                    button(text("✕").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::CancelSessionEdit),
+                ]
+                .spacing(8)
+                .align_y(Center)
+                .into(),
+                None => row![
+                    text(ended).size(14).width(170),
+                    text(if session.tag.is_empty() {
+                        "—"
+                    } else {
+                        session.tag.as_str()
+                    })
+                    .size(14)
+                    .width(200),
+                    text(format!("{} min", session.duration_seconds / 60))
+                        .size(14)
+                        .width(80),
+                    text(&session.intention).size(14).width(Length::Fill),
+                    session.idle.then(|| {
+                        text(t("session-idle"))
+                            .size(14)
+                            .color(palette::current().warning)
+                    }),
+                    button(text("✎").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::EditSession(session.clone())),
+                ]
+                .spacing(8)
+                .align_y(Center)
+                .into(),
+            };
+            sessions = sessions.push(line);
+        }
+        if self.history.is_empty() {
+            sessions = sessions.push(text(t("history-empty")).size(16));
+        }
+
+        let pages = crate::history::page_count(self.history_total);
+        let pager = row![
+            button(text(t("newer")).size(14))
+                .padding([6, 12])
+                .style(transparent_button_style)
+                .on_press_maybe(
+                    (self.history_page > 0).then(|| Message::HistoryPage(self.history_page - 1))
+                ),
+            text(tf(
+                "history-page",
+                &[("page", &(self.history_page + 1)), ("pages", &pages)]
+            ))
+            .size(14),
+            button(text(t("older")).size(14))
+                .padding([6, 12])
+                .style(transparent_button_style)
+                .on_press_maybe(
+                    (self.history_page + 1 < pages)
+                        .then(|| Message::HistoryPage(self.history_page + 1))
+                ),
+        ]
+        .spacing(12)
+        .align_y(Center);
+
+        let error = self.history_error.as_ref().map(|error| {
+            text(format!("⚠ {}", error))
+                .size(16)
+                .color(palette::current().error)
+        });
+
+        let back = button(text(t("back")).size(18))
+            .style(transparent_button_style)
+            .on_press(Message::CloseHistory)
+            .padding([12, 24]);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(sessions)
+            .push(error)
+            .push(pager)
+            .push(back);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_team(&self) -> Element<'_, Message> {
+        let header = text(t("team-session")).size(40);
+        let explanation = text(t("team-hint")).size(14);
+
+        let host: Element<'_, Message> = match &self.team_hosting {
+            Some(name) => row![
+                text(tf(
+                    "team-hosting",
+                    &[("name", name), ("followers", &self.team_followers)]
+                ))
+                .size(16),
+                button(text(t("stop-hosting")).size(16))
+                    .padding([8, 12])
+                    .style(transparent_button_style)
+                    .on_press(Message::StopHosting),
+            ]
+            .spacing(12)
+            .align_y(Center)
+            .into(),
+            None => row![
+                text_input(t("team-pomodoro"), &self.team_name)
+                    .on_input(Message::TeamNameChanged)
+                    .padding(8)
+                    .size(16)
+                    .width(260),
+                button(text(t("team-host")).size(16))
+                    .padding([8, 12])
+                    .style(transparent_button_style)
+                    .on_press_maybe(self.team_joined.is_none().then_some(Message::HostTeam)),
+            ]
+            .spacing(12)
+            .align_y(Center)
+            .into(),
+        };
+
+        let mut sessions = Column::new().spacing(6);
+        match &self.team_joined {
+            Some(joined) => {
+                sessions = sessions.push(
+                    row![
+                        text(format!(
+                            "{} ({})",
+                            tf("team-following", &[("host", &joined.name)]),
+                            joined.address
+                        ))
+                        .size(16),
+                        button(text(t("team-leave")).size(16))
+                            .padding([8, 12])
+                            .style(transparent_button_style)
+                            .on_press(Message::LeaveTeam),
+                    ]
+                    .spacing(12)
+                    .align_y(Center),
+                );
+            }
+            None if self.team_hosting.is_some() => {}
+            None => {
+                for host in &self.team_hosts {
+                    sessions = sessions.push(
+                        row![
+                            text(format!("{} ({})", host.name, host.address.ip()))
+                                .size(16)
+                                .width(300),
+                            button(text(t("team-join")).size(16))
+                                .padding([8, 12])
+                                .style(transparent_button_style)
+                                .on_press(Message::JoinTeam(host.clone())),
+                        ]
+                        .spacing(12)
+                        .align_y(Center),
+                    );
+                }
+                if self.team_hosts.is_empty() {
+                    sessions = sessions.push(text(t("team-searching")).size(16));
+                }
+            }
+        }
+
+        let notice = self.team_notice.as_ref().map(|notice| {
+            text(format!("⚠ {}", notice))
+                .size(16)
+                .color(palette::current().error)
+        });
+
+        let back = button(text(t("back")).size(18))
+            .style(transparent_button_style)
+            .on_press(Message::CloseTeam)
+            .padding([12, 24]);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(host)
+            .push(sessions)
+            .push(notice)
+            .push(back);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_quick_timer(&self) -> Element<'_, Message> {
+        let header = text(t("quick-timers")).size(40);
+        let explanation = text(t("quick-timer-hint")).size(14);
+
+        let inputs = row![
+            text_input(t("quick-timer-label"), &self.quick_timer_draft.label)
+                .on_input(Message::QuickTimerLabelChanged)
+                .padding(12)
+                .size(16)
+                .width(240),
+            text_input("10:00", &self.quick_timer_draft.duration)
+                .on_input(Message::QuickTimerDurationChanged)
+                .on_submit(Message::AddQuickTimer)
+                .padding(12)
+                .size(16)
+                .width(100),
+            button(text(t("start")).size(16))
+                .padding([12, 18])
+                .style(transparent_button_style)
+                .on_press_maybe(
+                    self.quick_timer_draft
+                        .seconds()
+                        .map(|_| Message::AddQuickTimer),
+                ),
+        ]
+        .spacing(10)
+        .align_y(Center);
+
+        let presets = PRESET_MINUTES
+            .iter()
+            .fold(row![].spacing(10), |presets, minutes| {
+                presets.push(
+                    button(text(tf("quick-timer-preset", &[("minutes", minutes)])).size(16))
+                        .padding([8, 14])
+                        .style(transparent_button_style)
+                        .on_press(Message::QuickTimerPreset(*minutes)),
+                )
+            });
+
+        let back = button(text(t("back")).size(18))
+            .style(transparent_button_style)
+            .on_press(Message::CloseQuickTimer)
+            .padding([12, 24]);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(inputs)
+            .push(presets)
+            .push(self.quick_timer_cards())
+            .push(back);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    // A card per countdown, shown under the pomodoro and on the quick
+    // timer screen
+    fn quick_timer_cards(&self) -> Option<Element<'_, Message>> {
+        if self.quick_timers.is_empty() {
+            return None;
+        }
+        let now = Instant::now();
+        let cards = self
+            .quick_timers
+            .iter()
+            .fold(row![].spacing(10), |cards, timer| {
+                let status = match timer.remaining(now) {
+                    Some(remaining) => {
+                        text(format_duration(remaining.as_millis().div_ceil(1000) as u32))
+                            .size(28)
+                            .font(self.countdown_font)
+                    }
+                    None => text(t("quick-timer-up"))
+                        .size(28)
+                        .color(palette::current().warning),
+                };
+                let card = Column::new()
+                    .align_x(Center)
+                    .spacing(6)
+                    .push(text(timer.name()).size(16))
+                    .push(status)
+                    .push(
+                        row![
+                            button(text("↻").size(16))
+                                .padding([4, 10])
+                                .style(transparent_button_style)
+                                .on_press(Message::RestartQuickTimer(timer.id)),
+                            button(text("✕").size(16))
+                                .padding([4, 10])
+                                .style(transparent_button_style)
+                                .on_press(Message::RemoveQuickTimer(timer.id)),
+                        ]
+                        .spacing(6),
+                    );
+                cards.push(container(card).padding(12).style(card_style))
+            });
+        Some(container(cards).center_x(Length::Fill).into())
+    }
+
+    // Mirrors the host's timer. Rings are left to the local countdown, which
+    // reaches zero at the same moment, so each follower gets their own alarm
+    // and pomodoro credit.
+    fn follow_team(&mut self, status: TeamStatus) -> Task<Message> {
+        let Some(period) = PeriodKind::from_key(&status.period) else {
+            return Task::none();
+        };
+        let remaining = Duration::from_secs(status.remaining_seconds as u64);
+        let drifted = self.display_seconds().abs_diff(status.remaining_seconds) > 1;
+        let work = period == PeriodKind::Work;
+
+        match status.state.as_str() {
+            // The host's last second, arriving after ours already ran out
+            "running"
+                if matches!(self.state, TimerState::Ringing { .. })
+                    && status.remaining_seconds <= 2 =>
+            {
+                Task::none()
+            }
+            "running" if self.state != TimerState::Running && !self.is_quiet() => {
+                self.is_work_period = work;
+                self.extending_work = false;
+                self.time_left = remaining;
+                self.handle(Message::StartStop)
+            }
+            "running" => {
+                if self.is_work_period != work || drifted {
+                    self.is_work_period = work;
+                    self.extending_work = false;
+                    self.time_left = remaining;
+                    self.end_time = None;
+                }
+                Task::none()
+            }
+            "paused" | "idle" => {
+                let task = if self.state == TimerState::Running {
+                    self.handle(Message::StartStop)
+                } else {
+                    Task::none()
+                };
+                if !matches!(self.state, TimerState::Ringing { .. }) {
+                    self.is_work_period = work;
+                    self.extending_work = false;
+                    self.time_left = remaining;
+                    if status.state == "idle" {
+                        self.state = TimerState::Idle;
+                    }
+                }
+                task
+            }
+            _ => Task::none(),
+        }
+    }
+
+    fn view_plan(&self) -> Element<'_, Message> {
+        let header = text(t("plan")).size(40);
+        let explanation = text(t("plan-hint")).size(14);
+
+        let day_button = |day: PlanDay, label: &'static str| {
+            let label = if self.plan_draft.day == day {
+                format!("● {}", label)
+            } else {
+                label.to_string()
+            };
+            button(text(label).size(16))
+                .padding([8, 14])
+                .style(transparent_button_style)
+                .on_press(Message::PlanDaySelected(day))
+        };
+        let days = row![
+            day_button(PlanDay::Today, t("plan-today")),
+            day_button(PlanDay::Tomorrow, t("plan-tomorrow"))
+        ]
+        .spacing(8);
+
+        let mut items = Column::new().spacing(8);
+        for (index, (title, estimate, actual)) in self.plan_draft.items.iter().enumerate() {
+            items = items.push(
+                row![
+                    text(format!("{}.", index + 1)).size(16).width(30),
+                    text_input(t("task-placeholder"), title)
+                        .on_input(move |value| Message::PlanTitleChanged(index, value))
+                        .padding(8)
+                        .size(14)
+                        .width(260),
+                    text_input("1", estimate)
+                        .on_input(move |value| Message::PlanEstimateChanged(index, value))
+                        .padding(8)
+                        .size(14)
+                        .width(60),
+                    text("🍅").size(14),
+                    (*actual > 0).then(|| text(format!("{} done", actual)).size(14)),
+                    button(text("✕").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::PlanRemoveItem(index)),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+        if self.plan_draft.items.is_empty() {
+            items = items.push(text(t("plan-empty")).size(16));
+        }
+
+        let planned: u32 = self
+            .plan_draft
+            .items
+            .iter()
+            .filter_map(|(_, estimate, _)| estimate.trim().parse::<u32>().ok())
+            .sum();
+        let focus_minutes = planned * self.settings.work_seconds / 60;
+        let total = text(format!(
+            "{} pomodoros · {}h {:02}m of focus",
+            planned,
+            focus_minutes / 60,
+            focus_minutes % 60
+        ))
+        .size(14);
+
+        let add = button(text(t("add-task")).size(14))
+            .padding([8, 14])
+            .style(transparent_button_style)
+            .on_press(Message::PlanAddItem);
+
+        let actions = row![
+            button(text(t("save")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::SavePlan)
+                .padding([12, 24]),
+            button(text(t("cancel")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::ClosePlan)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let mut column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(days)
+            .push(items)
+            .push(add)
+            .push(total);
+
+        if let Some(error) = &self.plan_error {
+            column = column.push(
+                text(format!("⚠ {}", error))
+                    .size(16)
+                    .color(palette::current().error),
+            );
+        }
+
+        column = column.push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_schedule(&self) -> Element<'_, Message> {
+        let header = text(t("weekday-schedule-title")).size(40);
+        let explanation = text(t("schedule-hint")).size(14);
+
+        let choices: Vec<DayProfile> = std::iter::once(DayProfile::Unchanged)
+            .chain(
+                self.profiles
+                    .iter()
+                    .map(|profile| DayProfile::Profile(profile.name.clone())),
+            )
+            .collect();
+
+        let mut days = Column::new().spacing(8);
+        for (index, weekday) in WEEKDAYS.iter().enumerate() {
+            days = days.push(
+                row![
+                    text(weekday_label(*weekday)).size(16).width(120),
+                    pick_list(
+                        choices.clone(),
+                        Some(self.schedule_draft.days[index].clone()),
+                        move |day| Message::ScheduleDayChanged(index, day),
+                    )
+                    .padding(8)
+                    .text_size(14),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+
+        let no_profiles_note = self
+            .profiles
+            .is_empty()
+            .then(|| text(t("schedule-no-profiles")).size(14));
+
+        let actions = row![
+            button(text(t("save")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::SaveSchedule)
+                .padding([12, 24]),
+            button(text(t("cancel")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseSchedule)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(no_profiles_note)
+            .push(days)
+            .push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_sequence(&self) -> Element<'_, Message> {
+        let header = text(t("period-sequence")).size(40);
+        let explanation = text(t("sequence-hint")).size(14);
+
+        let mut steps = Column::new().spacing(8);
+        for (index, (kind, length)) in self.sequence_draft.steps.iter().enumerate() {
+            steps = steps.push(
+                row![
+                    text(format!("{}.", index + 1)).size(16).width(30),
+                    pick_list(PeriodKind::ALL, Some(*kind), move |kind| {
+                        Message::SequenceKindChanged(index, kind)
+                    })
+                    .padding(8)
+                    .text_size(14),
+                    text_input("25:00", length)
+                        .on_input(move |value| Message::SequenceMinutesChanged(index, value))
+                        .padding(8)
+                        .size(14)
+                        .width(80),
+                    button(text("✕").size(14))
+                        .padding([6, 10])
+                        .style(transparent_button_style)
+                        .on_press(Message::SequenceRemoveStep(index)),
+                ]
+                .spacing(8)
+                .align_y(Center),
+            );
+        }
+        if self.sequence_draft.steps.is_empty() {
+            steps = steps.push(text(t("standard-cycle")).size(16));
+        }
+
+        let mut presets = row![
+            button(text(t("add-step")).size(14))
+                .padding([8, 14])
+                .style(transparent_button_style)
+                .on_press(Message::SequenceAddStep)
+        ]
+        .spacing(8);
+        for (index, (name, _)) in PRESETS.iter().enumerate() {
+            presets = presets.push(
+                button(text(*name).size(14))
+                    .padding([8, 14])
+                    .style(transparent_button_style)
+                    .on_press(Message::SequencePreset(index)),
+            );
+        }
+        presets = presets.push(
+            button(text(t("clear")).size(14))
+                .padding([8, 14])
+                .style(transparent_button_style)
+                .on_press(Message::SequenceClear),
+        );
+
+        let in_progress_note = self
+            .is_started()
+            .then(|| text(t("sequence-applies-after-period")).size(14));
+
+        let actions = row![
+            button(text(t("save")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::SaveSequence)
+                .padding([12, 24]),
+            button(text(t("cancel")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseSequenceEditor)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let mut column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(explanation)
+            .push(in_progress_note)
+            .push(steps)
+            .push(presets);
+
+        if let Some(error) = &self.sequence_error {
+            column = column.push(
+                text(format!("⚠ {}", error))
+                    .size(16)
+                    .color(palette::current().error),
+            );
+        }
+
+        column = column.push(actions);
+
+        scrollable(container(column).center_x(Length::Fill)).into()
+    }
+
+    fn view_weekly_review(&self) -> Element<'_, Message> {
+        let draft = &self.review_draft;
+        let header = text(t("weekly-review")).size(40);
+
+        let step_label = match draft.step {
+            ReviewStep::Stats => t("review-step-stats"),
+            ReviewStep::Reflection => t("review-step-reflection"),
+            ReviewStep::Goals => t("review-step-goals"),
+        };
+
+        let body = match draft.step {
+            ReviewStep::Stats => {
+                let mut stats = Column::new()
+                    .align_x(Center)
+                    .spacing(8)
+                    .push(
+                        text(tf(
+                            "review-pomodoros",
+                            &[("count", &draft.stats.completed_pomodoros)],
+                        ))
+                        .size(20),
+                    )
+                    .push(
+                        text(tf(
+                            "review-focus-time",
+                            &[
+                                ("hours", &(draft.stats.focus_seconds / 3600)),
+                                (
+                                    "minutes",
+                                    &format!("{:02}", (draft.stats.focus_seconds % 3600) / 60),
+                                ),
+                            ],
+                        ))
+                        .size(20),
+                    )
+                    .push(
+                        text(tf(
+                            "review-active-days",
+                            &[("days", &draft.stats.active_days)],
+                        ))
+                        .size(20),
+                    );
+
+                if let Some(review) = &self.latest_review {
+                    let reviewed_on = chrono::DateTime::from_timestamp(review.created_at, 0)
+                        .map(|at| {
+                            at.with_timezone(&chrono::Local)
+                                .format("%a %e %b")
+                                .to_string()
+                        })
+                        .unwrap_or_default();
+                    stats = stats.push(
+                        text(tf(
+                            "review-last-goal",
+                            &[
+                                ("goal", &review.next_week_goal),
+                                ("done", &self.goal_progress),
+                                ("since", &reviewed_on),
+                            ],
+                        ))
+                        .size(16),
+                    );
+                }
+
+                if let Some(accuracy) =
+                    crate::plan::accuracy(self.plan_history.iter().flat_map(|plan| &plan.items))
+                {
+                    stats = stats.push(
+                        text(tf(
+                            "review-estimates",
+                            &[("accuracy", &accuracy), ("days", &self.plan_history.len())],
+                        ))
+                        .size(20),
+                    );
+                    for plan in &self.plan_history {
+                        let (planned, done) = crate::plan::totals(&plan.items);
+                        stats = stats.push(
+                            text(tf(
+                                "review-plan-day",
+                                &[
+                                    ("day", &plan.day.format("%a %e %b")),
+                                    ("done", &done),
+                                    ("planned", &planned),
+                                    (
+                                        "accuracy",
+                                        &crate::plan::accuracy(&plan.items).unwrap_or_default(),
+                                    ),
+                                ],
+                            ))
+                            .size(14),
+                        );
+                    }
+                }
+
+                stats = stats.push(
+                    row![
+                        button(text(t("save-report")).size(16))
+                            .style(transparent_button_style)
+                            .on_press(Message::SaveReport)
+                            .padding([8, 12]),
+                        button(text(t("copy-report")).size(16))
+                            .style(transparent_button_style)
+                            .on_press(Message::CopyReport)
+                            .padding([8, 12]),
+                    ]
+                    .spacing(10),
+                );
+                stats.push(
+                    self.report_status
+                        .as_ref()
+                        .map(|status| text(status).size(14)),
+                )
+            }
+            ReviewStep::Reflection => Column::new()
+                .spacing(8)
+                .push(text(t("review-reflection-prompt")).size(16))
+                .push(
+                    text_input(t("review-reflection-placeholder"), &draft.reflection)
+                        .on_input(Message::ReviewReflectionChanged)
+                        .padding(12)
+                        .size(16),
+                ),
+            ReviewStep::Goals => Column::new()
+                .spacing(8)
+                .push(text(t("review-goal")).size(16))
+                .push(
+                    text_input("20", &draft.next_week_goal)
+                        .on_input(Message::ReviewGoalChanged)
+                        .padding(12)
+                        .size(16),
+                ),
+        };
+
+        let forward = if draft.step == ReviewStep::Goals {
+            button(text(t("save")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::SaveWeeklyReview)
+                .padding([12, 24])
+        } else {
+            button(text(t("review-next")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::ReviewNext)
+                .padding([12, 24])
+        };
+
+        let mut back = button(text(t("review-back")).size(18))
+            .style(transparent_button_style)
+            .padding([12, 24]);
+        if draft.step != ReviewStep::Stats {
+            back = back.on_press(Message::ReviewBack);
+        }
+
+        let actions = row![
+            back,
+            forward,
+            button(text(t("cancel")).size(18))
+                .style(transparent_button_style)
+                .on_press(Message::CloseWeeklyReview)
+                .padding([12, 24])
+        ]
+        .spacing(15);
+
+        let mut column = Column::new()
+            .align_x(Center)
+            .spacing(20)
+            .padding(40)
+            .push(header)
+            .push(text(step_label).size(16))
+            .push(body);
+
+        if let Some(error) = &self.review_error {
+            column = column.push(
+                text(format!("⚠ {}", error))
+                    .size(16)
+                    .color(palette::current().error),
+            );
+        }
+
+        column = column
+            .push(text("").size(5)) // Spacer
+            .push(actions);
+
+        container(column).center(Length::Fill).into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        // Low-power mode trades smoothness for fewer wakeups
+        let pulse_every = if self.low_power() {
+            Duration::from_secs(1)
+        } else {
+            Duration::from_millis(500)
+        };
+        let tick = match self.state {
+            TimerState::Running => time::every(self.tick_interval()).map(Message::Tick),
+            TimerState::Ringing { .. } => time::every(pulse_every).map(Message::RingPulse),
+            TimerState::Idle | TimerState::Paused => Subscription::none(),
+        };
+
+        let power_poll = if self.settings.low_power_threshold > 0 {
+            time::every(Duration::from_secs(30)).map(|_| Message::PowerPoll)
+        } else {
+            Subscription::none()
+        };
+
+        let schedule_check = if self.schedule.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_secs(60)).map(|_| Message::ScheduleCheck)
+        };
+
+        let notification_actions = if self.settings.announce_transitions {
+            Subscription::run(announce::actions).map(Message::NotificationAction)
+        } else {
+            Subscription::none()
+        };
+
+        let session_events = if !session::SUPPORTED
+            || self.settings.screen_lock_action == ScreenLockAction::Ignore
+        {
+            Subscription::none()
+        } else {
+            Subscription::run(session::events).map(Message::SessionChanged)
+        };
+
+        let api_requests = if self.settings.api_port > 0 {
+            Subscription::run_with(self.settings.api_port, crate::api::requests)
+                .map(Message::RemoteCommand)
+        } else {
+            Subscription::none()
+        };
+
+        let mqtt = if self.integrations.mqtt.is_enabled() {
+            Subscription::run_with(self.integrations.mqtt.clone(), crate::mqtt::publish_status)
+                .map(|never| match never {})
+        } else {
+            Subscription::none()
+        };
+
+        let telegram = if self.integrations.telegram.is_enabled() {
+            Subscription::run_with(
+                self.integrations.telegram.clone(),
+                crate::telegram::commands,
+            )
+            .map(Message::RemoteCommand)
+        } else {
+            Subscription::none()
+        };
+
+        let meetings = if self.integrations.meeting_calendar_url.is_empty() {
+            Subscription::none()
+        } else {
+            Subscription::run_with(
+                self.integrations.meeting_calendar_url.clone(),
+                |url: &String| crate::meetings::watch(url),
+            )
+            .map(Message::MeetingsLoaded)
+        };
+
+        // Keeps "Next meeting in N min" current while the timer is not ticking
+        let meeting_clock = if self.meetings.is_empty() || self.state == TimerState::Running {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_secs(30)).map(|_| Message::MeetingClock)
+        };
+
+        // Picks up history edits and imports, which aren't journaled as
+        // they happen
+        let team = match (&self.team_joined, &self.team_hosting) {
+            (Some(host), _) => {
+                Subscription::run_with(host.address, crate::team::join).map(Message::Team)
+            }
+            (None, Some(name)) => {
+                Subscription::run_with(name.clone(), |name: &String| crate::team::host(name))
+                    .map(Message::TeamFollowers)
+            }
+            (None, None) if matches!(self.screen, Screen::Team) => {
+                Subscription::run(crate::team::discover).map(Message::TeamHostsFound)
+            }
+            (None, None) => Subscription::none(),
+        };
+
+        let worklog = if self.integrations.worklog.is_enabled() {
+            Subscription::run_with(self.integrations.worklog.clone(), crate::worklog::run)
+                .map(Message::WorklogsWaiting)
+        } else {
+            Subscription::none()
+        };
+
+        let sync_server = if self.integrations.sync_server.is_enabled() {
+            Subscription::run_with(
+                self.integrations.sync_server.clone(),
+                crate::sync_server::run,
+            )
+            .map(Message::ServerSettingsPulled)
+        } else {
+            Subscription::none()
+        };
+
+        let sync_journal = if self.integrations.sync_dir.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_secs(300)).map(|_| Message::SyncJournal)
+        };
+
+        let undo_expiry = match self.undo {
+            Some(_) => time::every(Duration::from_secs(1)).map(Message::ExpireUndo),
+            None => Subscription::none(),
+        };
+
+        // Each countdown ticks on its own
+        let quick_timer_ticks = Subscription::batch(
+            self.quick_timers
+                .iter()
+                .filter(|timer| timer.ends_at.is_some())
+                .map(|timer| {
+                    time::every(Duration::from_millis(500))
+                        .with(timer.id)
+                        .map(|(id, now)| Message::QuickTimerTick(id, now))
+                }),
+        );
+
+        let monitor_check = if self.break_windows.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(Duration::from_secs(5)).map(|_| Message::CheckMonitors)
+        };
+
+        let distraction_check = if self.is_guarding() {
+            time::every(Duration::from_secs(10)).map(|_| Message::CheckDistractions)
+        } else {
+            Subscription::none()
+        };
+
+        let title_sampling = if self.is_sampling_titles() {
+            time::every(Duration::from_secs(TITLE_SAMPLE_SECONDS as u64))
+                .map(|_| Message::SampleWindowTitle)
+        } else {
+            Subscription::none()
+        };
+
+        let frames = if self.is_animating() {
+            window::frames().map(Message::Frame)
+        } else {
+            Subscription::none()
+        };
+
+        let stopwatch_tick = match self.stopwatch {
+            Some(stopwatch) if stopwatch.is_running() => {
+                time::every(Duration::from_secs(1)).map(Message::Tick)
+            }
+            _ => Subscription::none(),
+        };
+
+        Subscription::batch([
+            tick,
+            frames,
+            monitor_check,
+            distraction_check,
+            title_sampling,
+            stopwatch_tick,
+            quick_timer_ticks,
+            undo_expiry,
+            power_poll,
+            schedule_check,
+            session_events,
+            notification_actions,
+            window::close_events().map(Message::WindowClosed),
+            window::close_requests().map(Message::WindowCloseRequested),
+            Subscription::run(tray::events).map(Message::Tray),
+            keyboard::listen()
+                .filter_map(Binding::from_event)
+                .map(Message::KeyPressed),
+            Subscription::run(crate::instance::requests).map(Message::InstanceRequest),
+            api_requests,
+            mqtt,
+            telegram,
+            meetings,
+            meeting_clock,
+            sync_journal,
+            sync_server,
+            worklog,
+            team,
+            window::events().filter_map(|(id, event)| match event {
+                window::Event::Moved(position) => Some(Message::WindowMoved(id, position)),
+                window::Event::Resized(size) => Some(Message::WindowResized(id, size)),
+                _ => None,
+            }),
+        ])
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        let task = self.handle(message);
+        self.sync_background_audio();
+        self.sync_sleep_inhibitor();
+        self.sync_do_not_disturb();
+        if !self.is_guarding() {
+            self.distractions.clear();
+        }
+        if !self.is_work_period {
+            self.title_tally = TitleTally::default();
+            self.heard_activity = false;
+        }
+        if self.settings.api_port > 0
+            || self.integrations.mqtt.is_enabled()
+            || self.integrations.telegram.is_enabled()
+            || self.team_hosting.is_some()
+        {
+            crate::api::publish(self.api_status());
+        }
+        self.sync_text_output();
+        self.sync_tray_tooltip();
+        self.sync_period_fade();
+        Task::batch([task, self.sync_break_screen(), self.sync_icon()])
+    }
+
+    // Enough to check on the timer by hovering over the tray icon. Ticks
+    // come at least every 30 seconds, so the minutes left stay current.
+    fn sync_tray_tooltip(&self) {
+        if !tray::SUPPORTED {
+            return;
+        }
+        let period = self.current_period();
+        let minutes = self.display_seconds().div_ceil(60);
+        let title = match self.state {
+            TimerState::Running => tf(
+                "tray-tooltip-running",
+                &[("period", &period), ("minutes", &minutes)],
+            ),
+            TimerState::Paused => tf(
+                "tray-tooltip-paused",
+                &[("period", &period), ("minutes", &minutes)],
+            ),
+            TimerState::Ringing { finished, .. } => {
+                tf("tray-tooltip-finished", &[("period", &finished)])
+            }
+            TimerState::Idle => tf("tray-tooltip-idle", &[("period", &period)]),
+        };
+        let today = crate::streak::today(self.settings.day_start_hour);
+        let mut description = tf(
+            "tray-tooltip-today",
+            &[(
+                "count",
+                &self
+                    .pomodoros_by_day
+                    .get(&today)
+                    .copied()
+                    .unwrap_or_default(),
+            )],
+        );
+        let task = match &self.active_task {
+            Some(task) => Some(task.content.as_str()),
+            None => self
+                .plan_current
+                .and_then(|index| self.plan.get(index))
+                .map(|item| item.title.as_str()),
+        };
+        if let Some(task) = task {
+            description.push('\n');
+            description.push_str(&tf("tray-tooltip-task", &[("task", &task)]));
+        }
+        tray::set_tooltip(title, description);
+    }
+
+    // The tray and window icons show the period and the minutes left, so
+    // they are redrawn only when one of those changes, about once a minute.
+    fn sync_icon(&mut self) -> Task<Message> {
+        let phase = match self.state {
+            TimerState::Paused | TimerState::Idle => icon::Phase::Paused,
+            _ if self.current_period() == PeriodKind::Work => icon::Phase::Work,
+            _ => icon::Phase::Break,
+        };
+        let minutes = match self.state {
+            TimerState::Running | TimerState::Paused => Some(self.display_seconds().div_ceil(60)),
+            TimerState::Idle | TimerState::Ringing { .. } => None,
+        };
+        let status = IconStatus { phase, minutes };
+        if self.icon_status == Some(status) {
+            return Task::none();
+        }
+
+        self.icon_status = Some(status);
+        let rgba = icon::render(status);
+        tray::set_icon(&rgba, icon::SIZE);
+        dock::set_minutes(minutes);
+        match window::icon::from_rgba(rgba, icon::SIZE, icon::SIZE) {
+            Ok(icon) => window::set_icon(self.main_window, icon),
+            Err(err) => {
+                println!("Error drawing window icon: {}", err);
+                Task::none()
+            }
+        }
+    }
+
+    // The full-screen break window opens once per break and closes as soon
+    // as a work period is up next, whether the break ended or was skipped.
+    fn sync_break_screen(&mut self) -> Task<Message> {
+        if self.is_work_period || self.extending_work {
+            self.break_screen_shown = false;
+            return self.close_break_screen();
+        }
+        if !self.settings.break_screen || self.break_screen_shown {
+            return Task::none();
+        }
+
+        self.break_screen_shown = true;
+        self.open_break_screen(self.break_screen_monitors())
+    }
+
+    // The monitors the settings ask to cover. None are known without a
+    // list of monitors, or a position for the main window.
+    fn break_screen_monitors(&self) -> Vec<Monitor> {
+        let monitors = monitors::list();
+        match self.settings.break_screen_placement {
+            BreakScreenPlacement::AllMonitors => monitors,
+            BreakScreenPlacement::Primary => monitors
+                .into_iter()
+                .filter(|monitor| monitor.primary)
+                .take(1)
+                .collect(),
+            BreakScreenPlacement::Current => {
+                let Some(position) = self.window_geometry.position else {
+                    return Vec::new();
+                };
+                let size = self.window_geometry.size;
+                let center = Point::new(
+                    position.x + size.width / 2.0,
+                    position.y + size.height / 2.0,
+                );
+                monitors
+                    .into_iter()
+                    .filter(|monitor| monitor.contains(center))
+                    .take(1)
+                    .collect()
+            }
+        }
+    }
+
+    // With no monitors to go by, one window goes wherever the system puts it
+    fn open_break_screen(&mut self, monitors: Vec<Monitor>) -> Task<Message> {
+        let size = window::Settings::default().size;
+        let positions: Vec<Option<Point>> = if monitors.is_empty() {
+            vec![None]
+        } else {
+            monitors
+                .iter()
+                .map(|monitor| Some(monitor.place(size)))
+                .collect()
+        };
+        self.break_monitors = monitors;
+        Task::batch(positions.into_iter().map(|position| {
+            let (id, open) = window::open(crate::break_screen::window_settings(position));
+            self.break_windows.push(id);
+            open.discard()
+        }))
+    }
+
+    fn close_break_screen(&mut self) -> Task<Message> {
+        self.break_monitors.clear();
+        Task::batch(self.break_windows.drain(..).map(window::close))
+    }
+
+    // Ticking and ambience follow the state machine: only while a work
+    // period runs, never during breaks or while paused. Ambience also
+    // stays off in low-power mode.
+    fn sync_background_audio(&mut self) {
+        let working =
+            (self.is_work_period || self.extending_work) && self.state == TimerState::Running;
+        if working != self.ticking {
+            self.ticking = working;
+            self.audio_sender
+                .send(AudioCommand::SetTicking(working))
+                .expect("Could not send ticking command");
+        }
+
+        let ambient_playing = working && !self.low_power();
+        if ambient_playing != self.ambient_playing {
+            self.ambient_playing = ambient_playing;
+            self.audio_sender
+                .send(AudioCommand::SetAmbientPlaying(ambient_playing))
+                .expect("Could not send ambience command");
+        }
+    }
+
+    // Notifications stay silenced for as long as a work period is under
+    // way, paused or not
+    fn sync_do_not_disturb(&mut self) {
+        let silenced = self.settings.do_not_disturb && self.is_work_period && self.is_started();
+        if silenced != self.dnd_on {
+            self.dnd_on = silenced;
+            let _ = self.dnd_sender.send(DndCommand::Set(silenced));
+        }
+    }
+
+    // The focus guard looks for blocked apps while work is counting down
+    fn is_guarding(&self) -> bool {
+        self.settings.focus_guard != FocusGuard::Off
+            && self.is_work_period
+            && self.state == TimerState::Running
+            && self.guarded_apps.iter().any(|app| app.blocked)
+    }
+
+    // Coding starts a pomodoro when none is under way, and keeps the one
+    // that is from being marked idle
+    fn heartbeat(&mut self) -> Task<Message> {
+        if !self.settings.editor_heartbeats || !self.is_work_period {
+            return Task::none();
+        }
+        if self.is_started() {
+            self.heard_activity = true;
+            return Task::none();
+        }
+        if self.state == TimerState::Idle && self.stopwatch.is_none() {
+            self.heard_activity = true;
+            return self.handle(Message::StartStop);
+        }
+        Task::none()
+    }
+
+    // Linked by hand, or named in the task's own title
+    fn active_issue(&self) -> Option<IssueRef> {
+        let task = self.active_task.as_ref()?;
+        IssueRef::find(&self.active_task_issue).or_else(|| IssueRef::find(&task.content))
+    }
+
+    fn is_sampling_titles(&self) -> bool {
+        self.settings.track_window_titles
+            && self.is_work_period
+            && self.state == TimerState::Running
+            && !self.guest
+    }
+
+    // The machine stays awake only while a period is counting down, and the
+    // screen too while work does; pausing or finishing hands both back to
+    // the system.
+    fn sync_sleep_inhibitor(&mut self) {
+        let inhibit = match self.state {
+            TimerState::Running if self.is_work_period && self.settings.keep_screen_on => {
+                Inhibit::SleepAndScreen
+            }
+            TimerState::Running => Inhibit::Sleep,
+            _ => Inhibit::Nothing,
+        };
+        if inhibit != self.inhibiting {
+            self.inhibiting = inhibit;
+            let _ = self.sleep_sender.send(inhibit);
+        }
+    }
+
+    fn ambience(&self) -> Option<Ambience> {
+        match self.settings.ambient_sound {
+            AmbientSound::Off => None,
+            AmbientSound::WhiteNoise => Some(Ambience::WhiteNoise),
+            AmbientSound::PinkNoise => Some(Ambience::PinkNoise),
+            AmbientSound::File => Some(Ambience::File(self.ambient_path.clone().into())),
+        }
+    }
+
+    fn send_audio_settings(&self) {
+        for command in [
+            AudioCommand::SetVolume(self.settings.alarm_volume_level()),
+            AudioCommand::SetAlarmPattern(self.settings.alarm_pattern),
+            AudioCommand::SetTickVolume(self.settings.tick_volume_level()),
+            AudioCommand::SetAmbientVolume(self.settings.ambient_volume_level()),
+            AudioCommand::SetAmbience(self.ambience()),
+        ] {
+            self.audio_sender
+                .send(command)
+                .expect("Could not send audio settings");
+        }
+    }
+
+    fn handle(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Tick(now) => {
+                if self.eye_break_until.is_some_and(|until| now >= until) {
+                    self.eye_break_until = None;
+                }
+                if self.state != TimerState::Running {
+                    return Task::none();
+                }
+                let end_time = *self
+                    .end_time
+                    .get_or_insert(now + crate::clock::wall(self.time_left));
+                let time_left = crate::clock::timer(end_time.saturating_duration_since(now));
+                if self.current_period() == PeriodKind::Work {
+                    self.strain_eyes(self.time_left.saturating_sub(time_left), now);
+                }
+                self.time_left = time_left;
+                if self.time_left.is_zero() && self.extending_work {
+                    // The pomodoro was already counted, so this only lets
+                    // the waiting break begin
+                    self.end_snooze();
+                    self.time_left = self.current_duration();
+                    self.end_time = None;
+                    self.push_transition(PeriodKind::Work);
+                    self.state = TimerState::Ringing {
+                        finished: PeriodKind::Work,
+                        snoozed_until: None,
+                    };
+                    self.ring();
+                } else if self.time_left.is_zero() {
+                    // Ticks can arrive long after the deadline, e.g. after the
+                    // machine slept, but the period still ended at the deadline.
+                    let overdue = now.saturating_duration_since(end_time);
+                    let ended_at = crate::db::unix_now() - overdue.as_secs() as i64;
+                    let finished = self.current_period();
+                    if self.is_work_period {
+                        self.work_periods += 1;
+                        self.pomodoros_without_break += 1;
+                    } else {
+                        self.pomodoros_without_break = 0;
+                        if finished == PeriodKind::LongBreak {
+                            self.long_break_owed = false;
+                        }
+                        self.long_break_shortened = false;
+                        self.end_break(end_time);
+                    }
+                    self.save_cycle_position();
+                    if self.is_work_period {
+                        self.break_after = Some(ended_at);
+                        self.record_experiment(true);
+                        let credit = PendingCredit {
+                            ended_at,
+                            duration_seconds: self.current_duration().as_secs() as u32,
+                            pomodoros: 1,
+                            title_tally: std::mem::take(&mut self.title_tally),
+                            idle: self.settings.editor_heartbeats && !self.heard_activity,
+                            snoozed_seconds: 0,
+                        };
+                        match self.settings.completion_criteria {
+                            CompletionCriteria::Elapsed => self.credit_pomodoro(credit),
+                            _ => self.pending_credit = Some(credit),
+                        }
+                    }
+
+                    self.advance_period();
+
+                    if let Some(settings) = self.pending_settings.take() {
+                        self.settings = settings;
+                    }
+                    self.time_left = self.current_duration();
+                    self.end_time = None;
+                    self.push_transition(finished);
+                    self.state = TimerState::Ringing {
+                        finished,
+                        snoozed_until: None,
+                    };
+                    self.ring();
+                }
+            }
+            Message::StartStop => {
+                if self.stopwatch.is_some() {
+                    return self.handle(Message::StopwatchStartStop);
+                }
+                if self.work_locked() {
+                    return Task::none();
+                }
+                self.paused_by_lock = false;
+                if self.state == TimerState::Running {
+                    // Keep the sub-second remainder so pausing never shortens a period
+                    if let Some(end_time) = self.end_time.take() {
+                        self.time_left =
+                            crate::clock::timer(end_time.saturating_duration_since(Instant::now()));
+                    }
+                    self.state = TimerState::Paused;
+                } else {
+                    if let TimerState::Ringing { .. } = self.state {
+                        self.acknowledge_alarm();
+                    }
+                    self.audio_sender
+                        .send(AudioCommand::Stop)
+                        .expect("Could not send stop command");
+                    // A fresh pomodoro first asks what it is for; the prompt
+                    // starts it once answered. Started from the tray or a
+                    // notification with the window hidden, it doesn't ask.
+                    let fresh_work = self.is_work_period
+                        && !self.extending_work
+                        && self.state != TimerState::Paused
+                        && self.intention_draft.is_none();
+                    if fresh_work && self.settings.ask_intention && !self.main_window_hidden {
+                        self.state = TimerState::Idle;
+                        self.intention_draft = Some(self.intention.clone());
+                        return operation::focus(INTENTION_INPUT);
+                    } else if fresh_work {
+                        self.intention.clear();
+                    }
+                    if !self.is_work_period && !self.extending_work {
+                        self.break_started_at.get_or_insert(Instant::now());
+                    }
+                    self.state = TimerState::Running;
+                    self.end_time = Some(Instant::now() + crate::clock::wall(self.time_left));
+                }
+            }
+            Message::IntentionChanged(value) => {
+                if let Some(draft) = &mut self.intention_draft {
+                    *draft = value;
+                }
+            }
+            Message::IntentionConfirmed | Message::IntentionSkipped => {
+                let Some(draft) = &self.intention_draft else {
+                    return Task::none();
+                };
+                self.intention = match message {
+                    Message::IntentionConfirmed => draft.trim().to_string(),
+                    _ => String::new(),
+                };
+                // Still holding the draft keeps StartStop from asking again
+                let start = self.handle(Message::StartStop);
+                self.intention_draft = None;
+                return start;
+            }
+            Message::StopAlarm => {
+                if let TimerState::Ringing { .. } = self.state {
+                    self.acknowledge_alarm();
+                    self.alarm_repeat_at = None;
+                    self.audio_sender
+                        .send(AudioCommand::Stop)
+                        .expect("Could not send stop command");
+                    self.state = TimerState::Idle;
+                }
+            }
+            // After a pomodoro, snoozing keeps working before the break.
+            // After a break it only quiets the alarm for a while.
+            Message::SnoozeAlarm => {
+                let TimerState::Ringing { finished, .. } = self.state else {
+                    return Task::none();
+                };
+                self.audio_sender
+                    .send(AudioCommand::Stop)
+                    .expect("Could not send stop command");
+                if finished == PeriodKind::Work && !self.is_work_period {
+                    self.acknowledge_alarm();
+                    self.extending_work = true;
+                    self.time_left = self.snooze_length();
+                    self.end_time = Some(Instant::now() + crate::clock::wall(self.time_left));
+                    self.state = TimerState::Running;
+                } else {
+                    self.state = TimerState::Ringing {
+                        finished,
+                        snoozed_until: Some(
+                            Instant::now() + crate::clock::wall(self.snooze_length()),
+                        ),
+                    };
+                }
+            }
+            Message::NotificationAction(action) => {
+                // Buttons on a notification that is still up after the timer
+                // moved on are ignored rather than pausing a running period
+                if !matches!(self.state, TimerState::Ringing { .. } | TimerState::Idle) {
+                    return Task::none();
+                }
+                return match action {
+                    Action::Snooze => self.handle(Message::SnoozeAlarm),
+                    Action::StartBreak if !self.is_work_period => self.handle(Message::StartStop),
+                    Action::SkipBreak if !self.is_work_period => {
+                        self.acknowledge_alarm();
+                        let skip = self.handle(Message::SkipBreak);
+                        Task::batch([skip, self.handle(Message::StartStop)])
+                    }
+                    Action::StartWork if self.is_work_period => self.handle(Message::StartStop),
+                    _ => Task::none(),
+                };
+            }
+            Message::RingPulse(now) => {
+                if let TimerState::Ringing {
+                    finished,
+                    snoozed_until: Some(until),
+                } = self.state
+                {
+                    if now >= until {
+                        self.state = TimerState::Ringing {
+                            finished,
+                            snoozed_until: None,
+                        };
+                        self.ring();
+                    }
+                } else {
+                    self.ring_pulse = !self.ring_pulse && !self.low_power();
+                    // A repeat interval shorter than the alarm would otherwise
+                    // queue alarms faster than they play.
+                    if self.alarm_repeat_at.is_some_and(|at| now >= at) {
+                        self.audio_sender
+                            .send(AudioCommand::Stop)
+                            .expect("Could not send stop command");
+                        self.ring();
+                    }
+                }
+            }
+            Message::AcknowledgeAlarm => {
+                if let TimerState::Ringing { .. } = self.state {
+                    self.acknowledge_alarm();
+                    if self.alarm_repeat_at.take().is_some() {
+                        self.audio_sender
+                            .send(AudioCommand::Stop)
+                            .expect("Could not send stop command");
+                    }
+                }
+            }
+            Message::ConfirmFocus(focused) => {
+                if let Some(credit) = self.pending_credit.take()
+                    && focused
+                {
+                    self.credit_pomodoro(credit);
+                }
+            }
+            // Monitors plugged in or out during a break get the break
+            // screen laid out again
+            Message::SampleWindowTitle => {
+                if self.is_sampling_titles() {
+                    return Task::perform(
+                        crate::window_titles::active_title(),
+                        Message::WindowTitleSampled,
+                    );
+                }
+            }
+            // Each sample stands for the time since the one before
+            Message::WindowTitleSampled(title) => {
+                if let Some(title) = title
+                    && self.is_sampling_titles()
+                {
+                    self.title_tally
+                        .add(&title, &self.distracting_titles, TITLE_SAMPLE_SECONDS);
+                }
+            }
+            Message::CheckDistractions => {
+                if self.is_guarding() {
+                    return Task::perform(
+                        crate::focus_guard::check(
+                            self.guarded_apps.clone(),
+                            self.settings.focus_guard == FocusGuard::Minimize,
+                        ),
+                        Message::DistractionsFound,
+                    );
+                }
+            }
+            // The window only comes forward when an app turns up, not every
+            // time it is seen still running
+            Message::DistractionsFound(names) => {
+                if !self.is_guarding() {
+                    return Task::none();
+                }
+                let newly_opened = names.iter().any(|name| !self.distractions.contains(name));
+                self.distractions = names;
+                if newly_opened && self.settings.focus_guard == FocusGuard::Nudge {
+                    return self.raise_main_window();
+                }
+            }
+            Message::CheckMonitors => {
+                if self.break_windows.is_empty() {
+                    return Task::none();
+                }
+                let monitors = self.break_screen_monitors();
+                if monitors != self.break_monitors {
+                    return Task::batch([
+                        self.close_break_screen(),
+                        self.open_break_screen(monitors),
+                    ]);
+                }
+            }
+            Message::Frame(now) => {
+                self.now = now;
+            }
+            Message::PowerPoll => {
+                self.power = power::read();
+            }
+            Message::Reset => {
+                if self.work_locked() {
+                    return Task::none();
+                }
+                let timer = self.timer_snapshot();
+                self.offer_undo(t("undo-timer-reset"), UndoKind::TimerReset(Box::new(timer)));
+                // Shortening makes no sense once the cycle starts over, so
+                // only deferring keeps the long break
+                self.skip_long_break();
+                self.long_break_shortened = false;
+                self.end_break(Instant::now());
+                self.paused_by_lock = false;
+                self.audio_sender
+                    .send(AudioCommand::Stop)
+                    .expect("Could not send stop command");
+                if let Some(settings) = self.pending_settings.take() {
+                    self.settings = settings;
+                }
+                if let Some(sequence) = self.pending_sequence.take() {
+                    self.sequence = sequence;
+                }
+                self.state = TimerState::Idle;
+                self.sequence_index = 0;
+                self.extending_work = false;
+                self.title_tally = TitleTally::default();
+                self.heard_activity = false;
+                self.is_work_period = self
+                    .sequence
+                    .first()
+                    .is_none_or(|step| step.kind == PeriodKind::Work);
+                self.time_left = self.current_duration();
+                self.end_time = None;
+                self.work_periods = 0;
+                self.pomodoros_without_break = 0;
+                self.save_cycle_position();
+            }
+            Message::ToggleOverlay => {
+                return match self.overlay_window.take() {
+                    Some(id) => window::close(id),
+                    None => self.open_overlay(),
+                };
+            }
+            // The geometry is saved when the window closes or hides, not on
+            // every step of a drag
+            Message::WindowMoved(id, position) => {
+                if id == self.main_window && !WindowGeometry::is_parked(position) {
+                    self.window_geometry.position = Some(position);
+                }
+            }
+            Message::WindowResized(id, size) => {
+                let min = WindowGeometry::MIN_SIZE;
+                if id == self.main_window && size.width >= min.width && size.height >= min.height {
+                    self.window_geometry.size = size;
+                }
+                // Minimizing shows up as a resize on most platforms
+                if id == self.main_window {
+                    return window::is_minimized(id).map(Message::MainWindowMinimized);
+                }
+            }
+            Message::MainWindowMinimized(Some(minimized)) => {
+                self.main_window_hidden = minimized;
+                if minimized && self.settings.minimize_to_tray && tray::SUPPORTED {
+                    return window::set_mode(self.main_window, window::Mode::Hidden);
+                }
+            }
+            Message::MainWindowMinimized(None) => {}
+            Message::WindowCloseRequested(id) => {
+                if id == self.main_window && self.restored.is_some() {
+                    return self.handle(Message::QuitAfterRestore);
+                }
+                if id == self.main_window {
+                    crate::db::save_window_geometry(self.window_geometry);
+                }
+                if id == self.main_window && self.settings.close_to_tray && tray::SUPPORTED {
+                    self.main_window_hidden = true;
+                    return window::set_mode(id, window::Mode::Hidden);
+                }
+                return window::close(id);
+            }
+            Message::InstanceRequest(Some(command)) => {
+                return self.handle(Message::RemoteCommand(command));
+            }
+            Message::RemoteCommand(command) => {
+                return match command {
+                    Command::Toggle => self.handle(Message::StartStop),
+                    Command::Start if self.state != TimerState::Running => {
+                        self.handle(Message::StartStop)
+                    }
+                    Command::Pause if self.state == TimerState::Running => {
+                        self.handle(Message::StartStop)
+                    }
+                    Command::Start | Command::Pause => Task::none(),
+                    Command::Skip => self.handle(Message::SkipPeriod),
+                    Command::Reset => self.handle(Message::Reset),
+                    Command::Heartbeat => self.heartbeat(),
+                };
+            }
+            Message::Tray(TrayEvent::ShowWindow) | Message::InstanceRequest(None) => {
+                return self.raise_main_window();
+            }
+            Message::Tray(TrayEvent::StartStop) => {
+                return self.handle(Message::StartStop);
+            }
+            Message::Tray(TrayEvent::Skip) => {
+                return self.handle(Message::SkipPeriod);
+            }
+            Message::Tray(TrayEvent::Quit) if self.restored.is_some() => {
+                return self.handle(Message::QuitAfterRestore);
+            }
+            Message::Tray(TrayEvent::Quit) => {
+                self.settle_undo();
+                dnd::restore(&self.dnd_sender);
+                crate::db::save_window_geometry(self.window_geometry);
+                crate::db::flush();
+                return iced::exit();
+            }
+            Message::WindowClosed(id) => {
+                if id == self.main_window {
+                    self.settle_undo();
+                    dnd::restore(&self.dnd_sender);
+                    crate::db::flush();
+                    return iced::exit();
+                }
+                if Some(id) == self.overlay_window {
+                    self.overlay_window = None;
+                }
+                self.break_windows.retain(|window| *window != id);
+            }
+            // Breaks can run long without touching the work statistics
+            Message::ExtendBreak => {
+                let minutes = self.settings.break_extension_minutes;
+                if self.is_work_period || !self.is_started() || minutes == 0 {
+                    return Task::none();
+                }
+                let extension = Duration::from_secs(minutes as u64 * 60);
+                self.time_left += extension;
+                if let Some(end_time) = &mut self.end_time {
+                    *end_time += crate::clock::wall(extension);
+                }
+            }
+            Message::SkipBreak => {
+                if !self.is_work_period {
+                    self.audio_sender
+                        .send(AudioCommand::Stop)
+                        .expect("Could not send stop command");
+                    if self.skip_long_break() {
+                        self.end_time = None;
+                        self.time_left = self.current_duration();
+                        self.state = TimerState::Idle;
+                        return Task::none();
+                    }
+                    self.end_break(Instant::now());
+                    self.advance_period();
+                    // Bounded in case a stored sequence has no work step
+                    for _ in 0..self.sequence.len() {
+                        if self.is_work_period {
+                            break;
+                        }
+                        self.advance_period();
+                    }
+                    self.long_break_shortened = false;
+                    self.save_cycle_position();
+                    self.end_time = None;
+                    self.time_left = self.current_duration();
+                    self.state = TimerState::Idle;
+                }
+            }
+            Message::SkipPeriod => {
+                // Skipping the rest of a snooze starts the waiting break
+                if self.extending_work {
+                    self.end_snooze();
+                    self.end_time = None;
+                    self.time_left = self.current_duration();
+                    self.state = TimerState::Idle;
+                    return Task::none();
+                }
+                if !self.is_work_period {
+                    return self.handle(Message::SkipBreak);
+                }
+                // Skipping work never credits a pomodoro, and strict mode
+                // doesn't allow it at all once started.
+                if self.work_locked() {
+                    return Task::none();
+                }
+                if self.is_started() {
+                    self.record_experiment(false);
+                }
+                // No pomodoro to keep the coming break with
+                self.break_after = None;
+                self.audio_sender
+                    .send(AudioCommand::Stop)
+                    .expect("Could not send stop command");
+                self.advance_period();
+                if let Some(settings) = self.pending_settings.take() {
+                    self.settings = settings;
+                }
                 self.end_time = None;
+                self.time_left = self.current_duration();
+                self.state = TimerState::Idle;
+            }
+            Message::ToggleMute => {
+                self.muted = !self.muted;
+                self.audio_sender
+                    .send(AudioCommand::SetMuted(self.muted))
+                    .expect("Could not send mute command");
+            }
+            Message::TogglePin => {
+                let always_on_top = !self.settings.always_on_top;
+                self.settings.always_on_top = always_on_top;
+                if let Some(pending) = &mut self.pending_settings {
+                    pending.always_on_top = always_on_top;
+                }
+                self.save_settings(
+                    self.pending_settings.unwrap_or(self.settings),
+                    crate::db::unix_now(),
+                );
+                return window::set_level(
+                    self.main_window,
+                    crate::main_window_level(always_on_top),
+                );
+            }
+            Message::ToggleStopwatch => {
+                if self.state != TimerState::Idle {
+                    return Task::none();
+                }
+                self.stopwatch = match self.stopwatch {
+                    None => {
+                        self.intention.clear();
+                        Some(Stopwatch::default())
+                    }
+                    // Time on the clock has to be finished first
+                    Some(stopwatch) if stopwatch.elapsed(Instant::now()).is_zero() => None,
+                    stopwatch => stopwatch,
+                };
+            }
+            Message::StopwatchStartStop => {
+                if let Some(stopwatch) = &mut self.stopwatch {
+                    let now = Instant::now();
+                    if stopwatch.is_running() {
+                        stopwatch.pause(now);
+                    } else {
+                        stopwatch.start(now);
+                    }
+                }
+            }
+            Message::FinishStopwatch => {
+                let Some(stopwatch) = self.stopwatch else {
+                    return Task::none();
+                };
+                let seconds = stopwatch.elapsed(Instant::now()).as_secs() as u32;
+                if seconds < crate::stopwatch::MIN_SECONDS {
+                    return Task::none();
+                }
+                self.stopwatch = Some(Stopwatch::default());
+                let work_seconds = self
+                    .settings
+                    .work_seconds_on(crate::streak::today(self.settings.day_start_hour));
+                self.credit_pomodoro(PendingCredit {
+                    ended_at: crate::db::unix_now(),
+                    duration_seconds: seconds,
+                    pomodoros: crate::stopwatch::equivalent_pomodoros(seconds, work_seconds),
+                    title_tally: TitleTally::default(),
+                    idle: false,
+                    snoozed_seconds: 0,
+                });
+            }
+            Message::ToggleMeetingMode => {
+                self.meeting_mode = !self.meeting_mode;
+                if self.meeting_mode && self.alarm_repeat_at.take().is_some() {
+                    self.audio_sender
+                        .send(AudioCommand::Stop)
+                        .expect("Could not send stop command");
+                }
+            }
+            Message::ToggleGuest => {
+                self.guest = !self.guest;
+            }
+            Message::ResetPomoCounter => {
+                self.offer_undo(
+                    t("undo-count-reset"),
+                    UndoKind::CountReset {
+                        completed_pomodoros: self.completed_pomodoros,
+                    },
+                );
+                self.completed_pomodoros = 0;
+                crate::db::save_completed_pomodoros(self.completed_pomodoros);
+            }
+            Message::UndoLastAction => match self.undo.take().map(|undo| undo.kind) {
+                Some(UndoKind::CountReset {
+                    completed_pomodoros,
+                }) => {
+                    self.completed_pomodoros = completed_pomodoros;
+                    crate::db::save_completed_pomodoros(self.completed_pomodoros);
+                }
+                Some(UndoKind::TimerReset(timer)) => self.restore_timer(*timer),
+                None => {}
+            },
+            Message::ExpireUndo(now) => {
+                if self
+                    .undo
+                    .as_ref()
+                    .is_some_and(|undo| now.duration_since(undo.taken_at) >= UNDO_WINDOW)
+                {
+                    self.settle_undo();
+                }
+            }
+            Message::OpenSettings => {
                 self.settings_error = None;
-                self.settings_draft = SettingsDraft::from_settings(self.settings);
+                self.recording_shortcut = None;
+                self.shortcut_conflict = None;
+                self.calendar_export = None;
+                self.import_preview = None;
+                self.import_status = None;
+                self.restore_checked = false;
+                self.backup_status = None;
+                self.settings_draft = SettingsDraft {
+                    backgrounds: self.backgrounds.clone(),
+                    guarded_apps: self.guarded_apps.clone(),
+                    distracting_titles: self.distracting_titles.join("; "),
+                    ..SettingsDraft::from_settings(
+                        self.pending_settings.unwrap_or(self.settings),
+                        &self.focus_messages,
+                        &crate::db::load_remote_sources(),
+                        &self.holidays,
+                        &self.ambient_path,
+                        &self.countdown_font_setting,
+                        &self.integrations,
+                    )
+                };
                 self.screen = Screen::Settings;
             }
-            Message::CloseSettings => {
-                self.settings_error = None;
+            Message::CloseSettings => {
+                self.settings_error = None;
+                self.recording_shortcut = None;
+                self.screen = Screen::Timer;
+            }
+            Message::SettingsWorkMinutesChanged(minutes) => {
+                set_minutes(&mut self.settings_draft.work_duration, minutes);
+            }
+            Message::SettingsShortBreakMinutesChanged(minutes) => {
+                set_minutes(&mut self.settings_draft.short_break_duration, minutes);
+            }
+            Message::SettingsLongBreakMinutesChanged(minutes) => {
+                set_minutes(&mut self.settings_draft.long_break_duration, minutes);
+            }
+            Message::SettingsWorkDurationChanged(value) => {
+                self.settings_draft.work_duration = value;
+            }
+            Message::SettingsShortBreakDurationChanged(value) => {
+                self.settings_draft.short_break_duration = value;
+            }
+            Message::SettingsLongBreakDurationChanged(value) => {
+                self.settings_draft.long_break_duration = value;
+            }
+            Message::SettingsLongBreakEveryChanged(every) => {
+                self.settings_draft.long_break_every = every;
+            }
+            Message::SettingsExperimentToggled(enabled) => {
+                self.settings_draft.experiment = enabled;
+            }
+            Message::SettingsExperimentMinutesChanged(minutes) => {
+                set_minutes(&mut self.settings_draft.experiment_duration, minutes);
+            }
+            Message::SettingsExperimentDurationChanged(value) => {
+                self.settings_draft.experiment_duration = value;
+            }
+            Message::SettingsLongBreaksToggled(enabled) => {
+                self.settings_draft.long_break_every = if enabled {
+                    Settings::DEFAULT_LONG_BREAK_EVERY
+                } else {
+                    0
+                };
+            }
+            Message::SettingsLongBreakPatternChanged(value) => {
+                self.settings_draft.long_break_pattern = value;
+            }
+            Message::SettingsAlarmVolumeChanged(value) => {
+                self.settings_draft.alarm_volume = value;
+            }
+            Message::SettingsAlarmTonesChanged(value) => {
+                self.settings_draft.alarm_tones = value;
+            }
+            Message::SettingsAlarmBeepsChanged(beeps) => {
+                self.settings_draft.alarm_beeps = beeps;
+            }
+            Message::SettingsAlarmBeepLengthChanged(value) => {
+                self.settings_draft.alarm_beep_ms = value;
+            }
+            Message::SettingsAlarmGapChanged(value) => {
+                self.settings_draft.alarm_gap_ms = value;
+            }
+            Message::SettingsAlarmLengthChanged(value) => {
+                self.settings_draft.alarm_length_ms = value;
+            }
+            Message::PreviewAlarm => {
+                // Try the volume being edited, falling back to the saved one
+                let volume = self
+                    .settings_draft
+                    .alarm_volume
+                    .trim()
+                    .parse::<u32>()
+                    .map(|volume| volume.min(100) as f32 / 100.0)
+                    .unwrap_or(self.settings.alarm_volume_level());
+                let pattern = self
+                    .settings_draft
+                    .parse_alarm_pattern()
+                    .unwrap_or(self.settings.alarm_pattern);
+                self.audio_sender
+                    .send(AudioCommand::Preview(volume, pattern))
+                    .expect("Could not send audio command");
+            }
+            Message::SettingsTickVolumeChanged(value) => {
+                self.settings_draft.tick_volume = value;
+            }
+            Message::SettingsAmbientSoundChanged(sound) => {
+                self.settings_draft.ambient_sound = sound;
+            }
+            Message::SettingsAmbientVolumeChanged(value) => {
+                self.settings_draft.ambient_volume = value;
+            }
+            Message::SettingsAmbientPathChanged(value) => {
+                self.settings_draft.ambient_path = value;
+            }
+            Message::SettingsBreakScreenToggled(enabled) => {
+                self.settings_draft.break_screen = enabled;
+            }
+            Message::SettingsEyeBreaksToggled(enabled) => {
+                self.settings_draft.eye_breaks = enabled;
+            }
+            Message::SettingsAskIntentionToggled(enabled) => {
+                self.settings_draft.ask_intention = enabled;
+            }
+            Message::SettingsAnnounceTransitionsToggled(enabled) => {
+                self.settings_draft.announce_transitions = enabled;
+            }
+            Message::SettingsScreenLockActionChanged(action) => {
+                self.settings_draft.screen_lock_action = action;
+            }
+            Message::SettingsLongBreakSkipChanged(policy) => {
+                self.settings_draft.long_break_skip = policy;
+            }
+            Message::SettingsDoNotDisturbToggled(enabled) => {
+                self.settings_draft.do_not_disturb = enabled;
+            }
+            Message::SettingsTrackWindowTitlesToggled(enabled) => {
+                self.settings_draft.track_window_titles = enabled;
+            }
+            Message::SettingsEditorHeartbeatsToggled(enabled) => {
+                self.settings_draft.editor_heartbeats = enabled;
+            }
+            Message::SettingsDistractingTitlesChanged(value) => {
+                self.settings_draft.distracting_titles = value;
+            }
+            Message::SettingsFocusGuardChanged(guard) => {
+                self.settings_draft.focus_guard = guard;
+            }
+            Message::SettingsGuardedAppChanged(value) => {
+                self.settings_draft.new_guarded_app = value;
+            }
+            Message::AddGuardedApp => {
+                let name = self.settings_draft.new_guarded_app.trim().to_string();
+                let known = self
+                    .settings_draft
+                    .guarded_apps
+                    .iter()
+                    .any(|app| crate::focus_guard::matches(&app.name, &name));
+                if !name.is_empty() && !known {
+                    self.settings_draft.guarded_apps.push(GuardedApp {
+                        name,
+                        blocked: true,
+                    });
+                }
+                self.settings_draft.new_guarded_app.clear();
+            }
+            Message::SettingsGuardedAppToggled(index, blocked) => {
+                if let Some(app) = self.settings_draft.guarded_apps.get_mut(index) {
+                    app.blocked = blocked;
+                }
+            }
+            Message::RemoveGuardedApp(index) => {
+                if index < self.settings_draft.guarded_apps.len() {
+                    self.settings_draft.guarded_apps.remove(index);
+                }
+            }
+            Message::SettingsKeepScreenOnToggled(enabled) => {
+                self.settings_draft.keep_screen_on = enabled;
+            }
+            Message::SettingsBreakScreenPlacementChanged(placement) => {
+                self.settings_draft.break_screen_placement = placement;
+            }
+            Message::SettingsTimerFaceChanged(face) => {
+                self.settings_draft.timer_face = face;
+            }
+            Message::SettingsQuietHoursChanged(value) => {
+                self.settings_draft.quiet_hours = value;
+            }
+            Message::SettingsLanguageChanged(language) => {
+                self.settings_draft.language = language;
+            }
+            Message::SettingsPaletteChanged(palette) => {
+                self.settings_draft.palette = palette;
+            }
+            Message::SettingsUiScaleChanged(value) => {
+                self.settings_draft.ui_scale = value;
+            }
+            Message::SettingsCountdownFontChanged(value) => {
+                self.settings_draft.countdown_font = value;
+            }
+            Message::CountdownFontLoaded(Ok(font)) => {
+                self.countdown_font = font;
+                self.font_error = None;
+            }
+            Message::CountdownFontLoaded(Err(err)) => {
+                self.font_error = Some(err);
+            }
+            Message::KeyPressed(binding) => {
+                if let (Screen::Settings, Some(shortcut)) = (self.screen, self.recording_shortcut) {
+                    self.recording_shortcut = None;
+                    self.shortcut_conflict = self
+                        .settings_draft
+                        .shortcuts
+                        .set(shortcut, binding)
+                        .err()
+                        .map(|other| {
+                            tf(
+                                "shortcut-conflict",
+                                &[("key", &binding), ("shortcut", &other)],
+                            )
+                        });
+                } else if let Some(shortcut) = self.settings.shortcuts.find(binding) {
+                    return self.shortcut(shortcut);
+                }
+            }
+            Message::RecordShortcut(shortcut) => {
+                self.shortcut_conflict = None;
+                self.recording_shortcut =
+                    (self.recording_shortcut != Some(shortcut)).then_some(shortcut);
+            }
+            Message::ResetShortcuts => {
+                self.recording_shortcut = None;
+                self.shortcut_conflict = None;
+                self.settings_draft.shortcuts = Shortcuts::default();
+            }
+            Message::SessionChanged(SessionEvent::Locked) => {
+                // Strict mode keeps the pomodoro going even while away
+                if self.state == TimerState::Running
+                    && self.is_work_period
+                    && !self.work_locked()
+                    && self.settings.screen_lock_action != ScreenLockAction::Ignore
+                {
+                    if let Some(end_time) = self.end_time.take() {
+                        self.time_left =
+                            crate::clock::timer(end_time.saturating_duration_since(Instant::now()));
+                    }
+                    self.state = TimerState::Paused;
+                    self.paused_by_lock = true;
+                }
+            }
+            Message::SessionChanged(SessionEvent::Unlocked) => {
+                if self.paused_by_lock
+                    && self.state == TimerState::Paused
+                    && self.settings.screen_lock_action == ScreenLockAction::PauseAndResume
+                    && !self.is_quiet()
+                {
+                    self.paused_by_lock = false;
+                    self.state = TimerState::Running;
+                    self.end_time = Some(Instant::now() + crate::clock::wall(self.time_left));
+                }
+            }
+            Message::SettingsStartMinimizedToggled(enabled) => {
+                self.settings_draft.start_minimized = enabled;
+            }
+            Message::SettingsCloseToTrayToggled(enabled) => {
+                self.settings_draft.close_to_tray = enabled;
+            }
+            Message::SettingsMinimizeToTrayToggled(enabled) => {
+                self.settings_draft.minimize_to_tray = enabled;
+            }
+            Message::SettingsApiPortChanged(value) => {
+                self.settings_draft.api_port = value;
+            }
+            Message::SettingsObsOutputDirChanged(value) => {
+                self.settings_draft.integrations.obs_output_dir = value;
+            }
+            Message::SettingsMqttBrokerChanged(value) => {
+                self.settings_draft.integrations.mqtt.broker = value;
+            }
+            Message::SettingsMqttTopicPrefixChanged(value) => {
+                self.settings_draft.integrations.mqtt.topic_prefix = value;
+            }
+            Message::SettingsMqttUsernameChanged(value) => {
+                self.settings_draft.integrations.mqtt.username = value;
+            }
+            Message::SettingsMqttPasswordChanged(value) => {
+                self.settings_draft.integrations.mqtt.password = value;
+            }
+            Message::SettingsPushServiceChanged(service) => {
+                self.settings_draft.integrations.push.service = service;
+            }
+            Message::SettingsNtfyTopicChanged(value) => {
+                self.settings_draft.integrations.push.ntfy_topic = value;
+            }
+            Message::SettingsPushoverUserChanged(value) => {
+                self.settings_draft.integrations.push.pushover_user = value;
+            }
+            Message::SettingsPushoverTokenChanged(value) => {
+                self.settings_draft.integrations.push.pushover_token = value;
+            }
+            Message::SettingsTelegramTokenChanged(value) => {
+                self.settings_draft.integrations.telegram.token = value;
+            }
+            Message::SettingsTelegramChatIdChanged(value) => {
+                self.settings_draft.integrations.telegram.chat_id = value;
+            }
+            Message::SettingsTogglTokenChanged(value) => {
+                self.settings_draft.integrations.toggl.api_token = value;
+            }
+            Message::SettingsTogglWorkspaceChanged(value) => {
+                self.settings_draft.integrations.toggl.workspace_id = value;
+            }
+            Message::SettingsTogglProjectChanged(value) => {
+                self.settings_draft.integrations.toggl.project_id = value;
+            }
+            Message::SettingsTogglDescriptionChanged(value) => {
+                self.settings_draft.integrations.toggl.description = value;
+            }
+            Message::SettingsWorklogServiceChanged(service) => {
+                self.settings_draft.integrations.worklog.service = service;
+            }
+            Message::SettingsWorklogTokenChanged(value) => {
+                self.settings_draft.integrations.worklog.token = value;
+            }
+            Message::SettingsWorklogJiraUrlChanged(value) => {
+                self.settings_draft.integrations.worklog.jira_url = value;
+            }
+            Message::SettingsWorklogJiraEmailChanged(value) => {
+                self.settings_draft.integrations.worklog.jira_email = value;
+            }
+            Message::SettingsWorklogWorkspaceChanged(value) => {
+                self.settings_draft
+                    .integrations
+                    .worklog
+                    .clockify_workspace_id = value;
+            }
+            Message::SettingsWorklogTaskKeyChanged(value) => {
+                self.settings_draft.integrations.worklog.task_key = value;
+            }
+            Message::WorklogsWaiting(status) => {
+                self.worklog_status = status;
+            }
+            Message::SettingsTodoistTokenChanged(value) => {
+                self.settings_draft.integrations.todoist.token = value;
+            }
+            Message::SettingsTodoistActionChanged(action) => {
+                self.settings_draft.integrations.todoist.action = action;
+            }
+            Message::SettingsGithubTokenChanged(value) => {
+                self.settings_draft.integrations.github.token = value;
+            }
+            Message::SettingsGithubEveryChanged(every) => {
+                self.settings_draft.integrations.github.every = every;
+            }
+            Message::SettingsTodoistEveryChanged(every) => {
+                self.settings_draft.integrations.todoist.every = every;
+            }
+            Message::SettingsDailyLogPathChanged(value) => {
+                self.settings_draft.integrations.daily_log.path = value;
+            }
+            Message::SettingsDailyLogFormatChanged(value) => {
+                self.settings_draft.integrations.daily_log.format = value;
+            }
+            Message::SettingsMeetingCalendarUrlChanged(value) => {
+                self.settings_draft.integrations.meeting_calendar_url = value;
+            }
+            Message::MeetingsLoaded(meetings) => {
+                self.meetings = meetings;
+            }
+            Message::MeetingClock => {}
+            Message::ExportCalendar => {
+                let path = export_dir("").join("focus-sessions.ics");
+                let calendar = crate::ics::calendar(&crate::db::load_focus_sessions());
+                self.calendar_export = Some(match std::fs::write(&path, calendar) {
+                    Ok(()) => tf("saved-file", &[("path", &path.display())]),
+                    Err(err) => tf(
+                        "error-saving-file",
+                        &[("path", &path.display()), ("error", &err)],
+                    ),
+                });
+            }
+            Message::ImportFormatChanged(format) => {
+                self.import_format = format;
+                self.import_preview = None;
+                self.import_status = None;
+            }
+            Message::ImportPathChanged(value) => {
+                self.import_path = value;
+                self.import_preview = None;
+                self.import_status = None;
+            }
+            Message::PreviewImport => {
+                let path = crate::daily_log::expand_home(self.import_path.trim());
+                let preview = std::fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|contents| {
+                        crate::import::preview(
+                            self.import_format,
+                            &contents,
+                            &crate::db::load_session_end_times(),
+                        )
+                    });
+                match preview {
+                    Ok(preview) => {
+                        self.import_status = Some(preview.summary());
+                        self.import_preview = Some(preview);
+                    }
+                    Err(err) => {
+                        self.import_status = Some(tf(
+                            "error-reading-file",
+                            &[("path", &path.display()), ("error", &err)],
+                        ));
+                        self.import_preview = None;
+                    }
+                }
+            }
+            Message::ConfirmImport => {
+                if let Some(preview) = self.import_preview.take() {
+                    let day_start = self.settings.day_start_hour as i64 * 3600;
+                    self.active_days
+                        .extend(preview.sessions.iter().filter_map(|session| {
+                            chrono::DateTime::from_timestamp(session.ended_at - day_start, 0)
+                                .map(|at| at.with_timezone(&chrono::Local).date_naive())
+                        }));
+                    self.import_status = Some(tf(
+                        "imported-sessions",
+                        &[("count", &preview.sessions.len())],
+                    ));
+                    crate::db::import_sessions(preview.sessions);
+                }
+            }
+            Message::BackUpNow => {
+                self.backup_status = Some(match crate::db::backup() {
+                    Ok(path) => tf("saved-file", &[("path", &path.display())]),
+                    Err(err) => err,
+                });
+            }
+            Message::RestorePathChanged(value) => {
+                self.restore_path = value;
+                self.restore_checked = false;
+                self.backup_status = None;
+            }
+            Message::CheckBackup => {
+                let path = crate::daily_log::expand_home(self.restore_path.trim());
+                let checked = crate::db::check_backup(&path);
+                self.restore_checked = checked.is_ok();
+                self.backup_status = Some(match checked {
+                    Ok(sessions) => tf("backup-intact", &[("count", &sessions)]),
+                    Err(err) => err,
+                });
+            }
+            Message::ConfirmRestore => {
+                if !self.restore_checked {
+                    return Task::none();
+                }
+                let path = crate::daily_log::expand_home(self.restore_path.trim());
+                match crate::db::restore(&path) {
+                    // Everything on screen belongs to the old data, so start
+                    // over from the restored database
+                    Ok(previous) => {
+                        self.restored = Some(tf(
+                            "restored-backup",
+                            &[("path", &path.display()), ("previous", &previous.display())],
+                        ));
+                        self.end_time = None;
+                        self.state = TimerState::Idle;
+                        self.audio_sender
+                            .send(AudioCommand::Stop)
+                            .expect("Could not send stop command");
+                    }
+                    Err(err) => {
+                        self.restore_checked = false;
+                        self.backup_status = Some(err);
+                    }
+                }
+            }
+            Message::QuitAfterRestore => {
+                dnd::restore(&self.dnd_sender);
+                return iced::exit();
+            }
+            Message::SettingsReportDirChanged(value) => {
+                self.settings_draft.integrations.report.dir = value;
+            }
+            Message::SettingsReportHtmlToggled(html) => {
+                self.settings_draft.integrations.report.html = html;
+            }
+            Message::SaveReport => {
+                let report = self.weekly_report();
+                let config = &self.integrations.report;
+                let path =
+                    export_dir(&config.dir).join(format!("pomodoro-report-{}.md", report.last_day));
+                let mut result = std::fs::write(&path, report.markdown());
+                if config.html && result.is_ok() {
+                    result = std::fs::write(path.with_extension("html"), report.html());
+                }
+                self.report_status = Some(match result {
+                    Ok(()) => tf("saved-file", &[("path", &path.display())]),
+                    Err(err) => tf(
+                        "error-saving-file",
+                        &[("path", &path.display()), ("error", &err)],
+                    ),
+                });
+            }
+            Message::CopyReport => {
+                self.report_status = Some(t("copied-to-clipboard").to_string());
+                return iced::clipboard::write(self.weekly_report().markdown());
+            }
+            Message::OpenTasks => {
+                self.screen = Screen::Tasks;
+                return self.handle(Message::RefreshTasks);
+            }
+            Message::CloseTasks => {
+                self.screen = Screen::Timer;
+            }
+            Message::RefreshTasks => {
+                if self.tasks_loading {
+                    return Task::none();
+                }
+                self.tasks_loading = true;
+                self.tasks_error = None;
+                return Task::perform(
+                    crate::todoist::fetch_today(self.integrations.todoist.token.clone()),
+                    Message::TasksLoaded,
+                );
+            }
+            Message::TasksLoaded(result) => {
+                self.tasks_loading = false;
+                match result {
+                    Ok(tasks) => self.todoist_tasks = tasks,
+                    Err(err) => self.tasks_error = Some(err),
+                }
+            }
+            Message::SelectTask(task) => {
+                if self.active_task.as_ref().map(|t| &t.id) != Some(&task.id) {
+                    self.active_task = Some(task);
+                    self.active_task_pomodoros = 0;
+                    crate::db::save_active_task(self.active_task.clone(), 0);
+                    self.active_task_issue.clear();
+                    crate::db::save_active_task_issue(String::new());
+                }
                 self.screen = Screen::Timer;
             }
-            Message::SettingsWorkMinutesChanged(value) => {
-                self.settings_draft.work_minutes = value;
+            Message::ClearActiveTask => {
+                self.active_task = None;
+                self.active_task_pomodoros = 0;
+                crate::db::save_active_task(None, 0);
+                self.active_task_issue.clear();
+                crate::db::save_active_task_issue(String::new());
+            }
+            Message::ActiveTaskIssueChanged(value) => {
+                self.active_task_issue = value;
+                crate::db::save_active_task_issue(self.active_task_issue.trim().to_string());
+            }
+            Message::FetchIssueTitle(issue) => {
+                return Task::perform(
+                    crate::github::fetch_title(
+                        self.integrations.github.token.clone(),
+                        issue.clone(),
+                    ),
+                    move |result| Message::IssueTitleFetched(issue.clone(), result),
+                );
+            }
+            Message::IssueTitleFetched(issue, result) => {
+                self.issue_titles.insert(issue, result);
+            }
+            Message::PostIssueComment => {
+                if let Some((issue, pomodoros)) = self.issue_comment_offer.take() {
+                    if self.integrations.github.token.is_empty() {
+                        self.issue_comment_status = Some(t("github-token-needed").to_string());
+                        return Task::none();
+                    }
+                    self.issue_comment_status =
+                        Some(tf("posting-issue-comment", &[("issue", &issue)]));
+                    return Task::perform(
+                        crate::github::comment(
+                            self.integrations.github.token.clone(),
+                            issue,
+                            tf("issue-comment-body", &[("pomodoros", &pomodoros)]),
+                        ),
+                        Message::IssueCommentPosted,
+                    );
+                }
+            }
+            Message::DismissIssueComment => {
+                self.issue_comment_offer = None;
+                self.issue_comment_status = None;
+            }
+            Message::IssueCommentPosted(result) => {
+                self.issue_comment_status = Some(match result {
+                    Ok(()) => t("issue-comment-posted").to_string(),
+                    Err(err) => err,
+                });
+            }
+            Message::SettingsAlwaysOnTopToggled(enabled) => {
+                self.settings_draft.always_on_top = enabled;
+            }
+            Message::SettingsStrictModeToggled(enabled) => {
+                self.settings_draft.strict_mode = enabled;
+            }
+            Message::SettingsOverlayCornerChanged(corner) => {
+                self.settings_draft.overlay_corner = corner;
+            }
+            Message::SettingsOverlayFontSizeChanged(value) => {
+                self.settings_draft.overlay_font_size = value;
+            }
+            Message::SettingsCompletionCriteriaChanged(criteria) => {
+                self.settings_draft.completion_criteria = criteria;
+            }
+            Message::SettingsLowPowerThresholdChanged(value) => {
+                self.settings_draft.low_power_threshold = value;
             }
-            Message::SettingsShortBreakMinutesChanged(value) => {
-                self.settings_draft.short_break_minutes = value;
+            Message::SettingsSnoozeMinutesChanged(minutes) => {
+                self.settings_draft.snooze_minutes = minutes;
             }
-            Message::SettingsLongBreakMinutesChanged(value) => {
-                self.settings_draft.long_break_minutes = value;
+            Message::SettingsBreakExtensionChanged(minutes) => {
+                self.settings_draft.break_extension_minutes = minutes;
             }
-            Message::SettingsLongBreakEveryChanged(value) => {
-                self.settings_draft.long_break_every = value;
+            Message::SettingsAdaptiveBreakChanged(minutes) => {
+                self.settings_draft.adaptive_break_minutes = minutes;
+            }
+            Message::SettingsAlarmRepeatChanged(value) => {
+                self.settings_draft.alarm_repeat_seconds = value;
+            }
+            Message::SettingsDayStartHourChanged(value) => {
+                self.settings_draft.day_start_hour = value;
+            }
+            Message::SettingsHolidaysChanged(value) => {
+                self.settings_draft.holidays = value;
+            }
+            Message::SettingsRemoteSourcesChanged(value) => {
+                self.settings_draft.remote_sources = value;
+            }
+            Message::SettingsSyncDirChanged(value) => {
+                self.settings_draft.integrations.sync_dir = value;
+            }
+            Message::SyncJournal => {
+                crate::sync::write_journal(&self.integrations.sync_dir);
+            }
+            Message::SettingsSyncServerUrlChanged(value) => {
+                self.settings_draft.integrations.sync_server.url = value;
+            }
+            Message::SettingsSyncServerTokenChanged(value) => {
+                self.settings_draft.integrations.sync_server.token = value;
+            }
+            Message::ServerSettingsPulled((updated_at, table)) => {
+                let settings =
+                    crate::config::apply(self.pending_settings.unwrap_or(self.settings), &table);
+                self.save_settings(settings, updated_at);
+                self.apply_settings(settings);
+                self.send_audio_settings();
+            }
+            Message::SettingsFocusMessageChanged(kind, value) => {
+                self.settings_draft.focus_messages.get_mut(kind).text = value;
+            }
+            Message::SettingsBackgroundImageChanged(kind, value) => {
+                self.settings_draft.backgrounds.get_mut(kind).image_path = value;
+            }
+            Message::SettingsBackgroundGradientChanged(kind, value) => {
+                self.settings_draft.backgrounds.get_mut(kind).gradient = value;
+            }
+            Message::SettingsBackgroundDimChanged(percent) => {
+                self.settings_draft.background_dim = percent;
+            }
+            Message::SettingsFocusImageChanged(kind, value) => {
+                self.settings_draft.focus_messages.get_mut(kind).image_path = value;
+            }
+            Message::SettingsFocusHeaderChanged(kind, value) => {
+                self.settings_draft.focus_messages.get_mut(kind).header = value;
             }
             Message::SaveSettings => {
-                if let Some(settings) = self.settings_draft.parse() {
-                    self.settings = settings;
-                    crate::db::save_settings(self.settings);
+                if let (Some(settings), Some(holidays)) = (
+                    self.settings_draft.parse(),
+                    self.settings_draft.parse_holidays(),
+                ) {
+                    if settings.day_start_hour != self.settings.day_start_hour {
+                        self.active_days = crate::db::load_active_days(settings.day_start_hour);
+                    }
+                    crate::db::save_holidays(holidays.clone());
+                    self.holidays = holidays;
+                    self.save_settings(settings, crate::db::unix_now());
+                    self.apply_settings(settings);
+                    self.focus_messages = self.settings_draft.focus_messages.trimmed();
+                    crate::db::save_focus_messages(&self.focus_messages);
+                    self.backgrounds = self.settings_draft.backgrounds.trimmed();
+                    crate::db::save_backgrounds(&self.backgrounds);
+                    self.guarded_apps = self.settings_draft.guarded_apps.clone();
+                    crate::db::save_guarded_apps(self.guarded_apps.clone());
+                    self.distracting_titles = self.settings_draft.parse_distracting_titles();
+                    crate::db::save_distracting_titles(self.distracting_titles.clone());
+                    crate::db::save_remote_sources(self.settings_draft.parse_remote_sources());
+                    self.ambient_path = self.settings_draft.ambient_path.trim().to_string();
+                    crate::db::save_ambient_path(self.ambient_path.clone());
+                    let countdown_font = self.settings_draft.countdown_font.trim().to_string();
+                    let load_font = if countdown_font != self.countdown_font_setting {
+                        self.countdown_font_setting = countdown_font;
+                        crate::db::save_countdown_font(self.countdown_font_setting.clone());
+                        self.load_countdown_font()
+                    } else {
+                        Task::none()
+                    };
+                    self.integrations = self.settings_draft.integrations.trimmed();
+                    crate::db::save_obs_output_dir(self.integrations.obs_output_dir.clone());
+                    crate::db::save_mqtt_config(self.integrations.mqtt.clone());
+                    crate::db::save_push_config(self.integrations.push.clone());
+                    crate::db::save_telegram_config(self.integrations.telegram.clone());
+                    crate::db::save_toggl_config(self.integrations.toggl.clone());
+                    crate::db::save_worklog_config(self.integrations.worklog.clone());
+                    crate::db::save_todoist_config(self.integrations.todoist.clone());
+                    crate::db::save_github_config(self.integrations.github.clone());
+                    crate::db::save_daily_log(self.integrations.daily_log.clone());
+                    crate::db::save_meeting_calendar_url(
+                        self.integrations.meeting_calendar_url.clone(),
+                    );
+                    crate::db::save_report_config(self.integrations.report.clone());
+                    crate::db::save_sync_dir(self.integrations.sync_dir.clone());
+                    crate::sync::write_journal(&self.integrations.sync_dir);
+                    crate::db::save_sync_server_config(self.integrations.sync_server.clone());
+                    crate::sync_server::nudge();
+                    if self.integrations.meeting_calendar_url.is_empty() {
+                        self.meetings.clear();
+                    }
+                    self.obs_written = None;
+                    self.send_audio_settings();
                     self.settings_error = None;
+                    self.screen = Screen::Timer;
 
-                    self.audio_sender
-                        .send(AudioCommand::Stop)
-                        .expect("Could not send stop command");
-                    self.is_running = false;
-                    self.is_work_period = true;
-                    self.time_left = self.settings.work_seconds;
-                    self.started = false;
-                    self.end_time = None;
-                    self.work_periods = 0;
+                    // Hand edits detach the timer from the profile it was using
+                    let still_matches = self.profiles.iter().any(|profile| {
+                        *profile
+                            == Profile::capture(&self.active_profile, &settings, &self.ambient_path)
+                    });
+                    if !still_matches && !self.active_profile.is_empty() {
+                        self.active_profile.clear();
+                        crate::db::save_active_profile(String::new());
+                    }
 
-                    self.screen = Screen::Timer;
+                    let set_level = window::set_level(
+                        self.main_window,
+                        crate::main_window_level(settings.always_on_top),
+                    );
+
+                    // Reopen the overlay so it picks up the new corner and size
+                    if let Some(id) = self.overlay_window.take() {
+                        return Task::batch([
+                            set_level,
+                            load_font,
+                            window::close(id).chain(self.open_overlay()),
+                        ]);
+                    }
+                    return Task::batch([set_level, load_font]);
+                } else {
+                    self.settings_error = Some(t("invalid-settings").to_string());
+                }
+            }
+            Message::SettingsProfileNameChanged(value) => {
+                self.profile_name = value;
+            }
+            Message::SelectProfile(name) => {
+                self.select_profile(name);
+            }
+            Message::EditProfile(name) => {
+                if let Some(profile) = self.profiles.iter().find(|p| p.name == name) {
+                    profile.fill_draft(&mut self.settings_draft);
+                    self.profile_name = name;
+                }
+            }
+            Message::SaveProfile => {
+                let name = self.profile_name.trim();
+                match self.settings_draft.parse() {
+                    Some(settings) if !name.is_empty() => {
+                        let profile = Profile::capture(
+                            name,
+                            &settings,
+                            self.settings_draft.ambient_path.trim(),
+                        );
+                        crate::db::save_profile(profile.clone());
+                        self.profiles.retain(|p| p.name != profile.name);
+                        self.profiles.push(profile);
+                        self.profiles
+                            .sort_by_key(|profile| profile.name.to_lowercase());
+                        self.profile_name.clear();
+                        self.settings_error = None;
+                    }
+                    Some(_) => {
+                        self.settings_error = Some(t("profile-needs-name").to_string());
+                    }
+                    None => {
+                        self.settings_error = Some(t("profile-invalid-settings").to_string());
+                    }
+                }
+            }
+            Message::DeleteProfile(name) => {
+                self.profiles.retain(|p| p.name != name);
+                if self.schedule.forget_profile(&name) {
+                    crate::db::save_schedule(self.schedule.clone());
+                }
+                if self.active_profile == name {
+                    self.active_profile.clear();
+                    crate::db::save_active_profile(String::new());
+                }
+                crate::db::delete_profile(name);
+            }
+            Message::OpenSchedule => {
+                self.schedule_draft = self.schedule.clone();
+                self.screen = Screen::Schedule;
+            }
+            Message::CloseSchedule => {
+                self.screen = Screen::Settings;
+            }
+            Message::ScheduleDayChanged(index, day) => {
+                if let Some(entry) = self.schedule_draft.days.get_mut(index) {
+                    *entry = day;
+                }
+            }
+            Message::SaveSchedule => {
+                self.schedule = self.schedule_draft.clone();
+                crate::db::save_schedule(self.schedule.clone());
+                self.screen = Screen::Settings;
+            }
+            Message::ScheduleCheck => {
+                self.apply_schedule();
+            }
+            Message::OpenSequenceEditor => {
+                self.sequence_error = None;
+                self.sequence_draft = SequenceDraft::from_steps(
+                    self.pending_sequence.as_ref().unwrap_or(&self.sequence),
+                );
+                self.screen = Screen::Sequence;
+            }
+            Message::CloseSequenceEditor => {
+                self.sequence_error = None;
+                self.screen = Screen::Settings;
+            }
+            Message::SequenceKindChanged(index, kind) => {
+                if let Some(step) = self.sequence_draft.steps.get_mut(index) {
+                    step.0 = kind;
+                }
+            }
+            Message::SequenceMinutesChanged(index, value) => {
+                if let Some(step) = self.sequence_draft.steps.get_mut(index) {
+                    step.1 = value;
+                }
+            }
+            Message::OpenQuickTimer => {
+                self.screen = Screen::QuickTimer;
+            }
+            Message::CloseQuickTimer => {
+                self.screen = Screen::Timer;
+            }
+            Message::QuickTimerLabelChanged(value) => {
+                self.quick_timer_draft.label = value;
+            }
+            Message::QuickTimerDurationChanged(value) => {
+                self.quick_timer_draft.duration = value;
+            }
+            Message::QuickTimerPreset(minutes) => {
+                self.quick_timer_draft.duration = format_duration(minutes * 60);
+                return self.handle(Message::AddQuickTimer);
+            }
+            Message::AddQuickTimer => {
+                if let Some(seconds) = self.quick_timer_draft.seconds() {
+                    self.next_quick_timer_id += 1;
+                    self.quick_timers.push(QuickTimer::new(
+                        self.next_quick_timer_id,
+                        &self.quick_timer_draft.label,
+                        seconds,
+                        Instant::now(),
+                    ));
+                    self.quick_timer_draft.label.clear();
+                }
+            }
+            Message::RestartQuickTimer(id) => {
+                self.silence_quick_timer(id);
+                if let Some(timer) = self.quick_timers.iter_mut().find(|timer| timer.id == id) {
+                    timer.restart(Instant::now());
+                }
+            }
+            // Also silences one that has rung
+            Message::RemoveQuickTimer(id) => {
+                self.silence_quick_timer(id);
+                self.quick_timers.retain(|timer| timer.id != id);
+            }
+            Message::QuickTimerTick(id, now) => {
+                let quiet = self.is_quiet();
+                let Some(timer) = self.quick_timers.iter_mut().find(|timer| timer.id == id) else {
+                    return Task::none();
+                };
+                if timer.check(now) && !quiet {
+                    let name = timer.name().to_string();
+                    self.audio_sender
+                        .send(AudioCommand::Alarm)
+                        .expect("Could not send audio command");
+                    if self.settings.announce_transitions {
+                        announce::announce(
+                            tf("quick-timer-done", &[("label", &name)]),
+                            String::new(),
+                            Vec::new(),
+                        );
+                    }
+                }
+            }
+            Message::OpenTeam => {
+                self.team_notice = None;
+                self.screen = Screen::Team;
+            }
+            Message::CloseTeam => {
+                self.screen = Screen::Timer;
+            }
+            Message::TeamNameChanged(value) => {
+                self.team_name = value;
+            }
+            Message::HostTeam => {
+                let name = self.team_name.trim();
+                self.team_hosting = Some(if name.is_empty() {
+                    t("team-pomodoro").to_string()
                 } else {
-                    self.settings_error = Some(
-                        "Invalid settings. Use positive numbers for minutes and pomos.".to_string(),
+                    name.to_string()
+                });
+                self.team_followers = 0;
+            }
+            Message::StopHosting => {
+                self.team_hosting = None;
+                self.team_followers = 0;
+            }
+            Message::TeamFollowers(followers) => {
+                self.team_followers = followers;
+            }
+            Message::TeamHostsFound(hosts) => {
+                self.team_hosts = hosts;
+            }
+            Message::JoinTeam(host) => {
+                self.team_notice = None;
+                self.team_joined = Some(host);
+            }
+            Message::LeaveTeam => {
+                self.team_joined = None;
+            }
+            Message::Team(TeamEvent::Status(status)) => {
+                if self.team_joined.is_some() {
+                    return self.follow_team(status);
+                }
+            }
+            Message::Team(TeamEvent::Disconnected) => {
+                if let Some(host) = self.team_joined.take() {
+                    self.team_notice = Some(tf("team-lost-connection", &[("host", &host.name)]));
+                }
+            }
+            Message::OpenStats => {
+                self.stats_tags = crate::db::load_session_tags();
+                self.pomodoros_by_day =
+                    crate::db::load_pomodoros_by_day(self.settings.day_start_hour);
+                self.experiment_results = crate::db::load_experiment_results();
+                if self.stats_draft.parse().is_none() {
+                    self.stats_draft = StatsDraft::last_days(
+                        30,
+                        crate::streak::today(self.settings.day_start_hour),
+                        TagFilter::All,
                     );
                 }
+                self.load_stats();
+                self.screen = Screen::Stats;
+            }
+            Message::CloseStats => {
+                self.screen = Screen::Timer;
+            }
+            Message::StatsFromChanged(value) => {
+                self.stats_draft.from = value;
+                self.load_stats();
+            }
+            Message::StatsToChanged(value) => {
+                self.stats_draft.to = value;
+                self.load_stats();
+            }
+            Message::StatsTagSelected(tag) => {
+                self.stats_draft.tag = tag;
+                self.load_stats();
+            }
+            Message::StatsPreset(days) => {
+                self.stats_draft = StatsDraft::last_days(
+                    days,
+                    crate::streak::today(self.settings.day_start_hour),
+                    self.stats_draft.tag.clone(),
+                );
+                self.load_stats();
+            }
+            Message::OpenHistory => {
+                self.load_history(0);
+                self.screen = Screen::History;
+            }
+            Message::CloseHistory => {
+                if self.history_changed {
+                    crate::db::flush();
+                    self.history_changed = false;
+                    self.active_days = crate::db::load_active_days(self.settings.day_start_hour);
+                    self.goal_progress = self
+                        .latest_review
+                        .as_ref()
+                        .map(|review| {
+                            crate::db::load_stats_since(review.created_at).completed_pomodoros
+                        })
+                        .unwrap_or(0);
+                    self.stats_tags = crate::db::load_session_tags();
+                    self.load_stats();
+                }
+                self.history_draft = None;
+                self.screen = Screen::Stats;
+            }
+            Message::HistoryPage(page) => {
+                self.load_history(page);
+            }
+            Message::EditSession(session) => {
+                self.history_draft = Some(HistoryDraft::from_session(&session));
+                self.history_error = None;
+            }
+            Message::HistoryTagChanged(value) => {
+                if let Some(draft) = &mut self.history_draft {
+                    draft.tag = value;
+                }
+            }
+            Message::HistoryMinutesChanged(value) => {
+                if let Some(draft) = &mut self.history_draft {
+                    draft.minutes = value;
+                }
+            }
+            Message::SaveSession => {
+                let Some(draft) = self.history_draft.take() else {
+                    return Task::none();
+                };
+                let Some(session) = self.history.iter_mut().find(|s| s.id == draft.id) else {
+                    return Task::none();
+                };
+                match draft.apply(session) {
+                    Some(corrected) => {
+                        crate::db::update_session(corrected.clone());
+                        *session = corrected;
+                        self.history_changed = true;
+                        self.history_error = None;
+                    }
+                    None => {
+                        self.history_draft = Some(draft);
+                        self.history_error = Some(t("history-length-error").to_string());
+                    }
+                }
+            }
+            Message::DeleteSession => {
+                if let Some(draft) = self.history_draft.take() {
+                    crate::db::delete_session(draft.id);
+                    self.history_changed = true;
+                    self.load_history(self.history_page);
+                }
+            }
+            Message::CancelSessionEdit => {
+                self.history_draft = None;
+                self.history_error = None;
+            }
+            Message::OpenPlan => {
+                self.refresh_plan();
+                // Once today is planned, the evening's planning is for tomorrow
+                let day = if self.plan.is_empty() {
+                    PlanDay::Today
+                } else {
+                    PlanDay::Tomorrow
+                };
+                return self.handle(Message::PlanDaySelected(day));
+            }
+            Message::ClosePlan => {
+                self.plan_error = None;
+                self.screen = Screen::Timer;
+            }
+            Message::PlanDaySelected(day) => {
+                let items = match day {
+                    PlanDay::Today => self.plan.clone(),
+                    PlanDay::Tomorrow => crate::db::load_plan(self.plan_date(day)),
+                };
+                self.plan_draft = PlanDraft::from_items(day, &items);
+                self.plan_error = None;
+                self.screen = Screen::Plan;
+            }
+            Message::PlanTitleChanged(index, value) => {
+                if let Some(item) = self.plan_draft.items.get_mut(index) {
+                    item.0 = value;
+                }
+            }
+            Message::PlanEstimateChanged(index, value) => {
+                if let Some(item) = self.plan_draft.items.get_mut(index) {
+                    item.1 = value;
+                }
+            }
+            Message::PlanAddItem => {
+                self.plan_draft
+                    .items
+                    .push((String::new(), "1".to_string(), 0));
+            }
+            Message::PlanRemoveItem(index) => {
+                if index < self.plan_draft.items.len() {
+                    self.plan_draft.items.remove(index);
+                }
+            }
+            Message::SavePlan => {
+                if let Some(items) = self.plan_draft.parse() {
+                    let day = self.plan_date(self.plan_draft.day);
+                    crate::db::save_plan(day, items.clone());
+                    if self.plan_draft.day == PlanDay::Today {
+                        self.plan_day = day;
+                        self.plan_current = crate::plan::next_unfinished(&items);
+                        self.plan = items;
+                    }
+                    self.plan_error = None;
+                    self.screen = Screen::Timer;
+                } else {
+                    self.plan_error = Some(t("plan-error").to_string());
+                }
+            }
+            Message::SelectPlanItem(choice) => {
+                self.plan_current = Some(choice.index);
+            }
+            Message::SequenceAddStep => {
+                let kind = match self.sequence_draft.steps.last() {
+                    Some((PeriodKind::Work, _)) => PeriodKind::ShortBreak,
+                    _ => PeriodKind::Work,
+                };
+                let seconds = self.period_duration(kind).as_secs() as u32;
+                self.sequence_draft
+                    .steps
+                    .push((kind, format_duration(seconds)));
+            }
+            Message::SequenceRemoveStep(index) => {
+                if index < self.sequence_draft.steps.len() {
+                    self.sequence_draft.steps.remove(index);
+                }
+            }
+            Message::SequencePreset(index) => {
+                if let Some((_, preset)) = PRESETS.get(index) {
+                    self.sequence_draft = SequenceDraft::from_preset(preset);
+                }
+            }
+            Message::SequenceClear => {
+                self.sequence_draft = SequenceDraft::default();
+            }
+            Message::SaveSequence => {
+                if let Some(sequence) = self.sequence_draft.parse() {
+                    crate::db::save_period_sequence(sequence.clone());
+                    // Like timing settings, a new sequence never cuts into a
+                    // period that has already started.
+                    if self.is_started() {
+                        self.pending_sequence = Some(sequence);
+                    } else {
+                        self.pending_sequence = None;
+                        self.sequence = sequence;
+                        self.sequence_index = 0;
+                        self.is_work_period = self
+                            .sequence
+                            .first()
+                            .is_none_or(|step| step.kind == PeriodKind::Work);
+                        self.time_left = self.current_duration();
+                    }
+                    self.sequence_error = None;
+                    self.screen = Screen::Settings;
+                } else {
+                    self.sequence_error = Some(t("sequence-error").to_string());
+                }
+            }
+            Message::OpenWeeklyReview => {
+                let stats = crate::db::load_stats_since(crate::db::unix_now() - WEEK_SECONDS);
+                let previous_goal = self.latest_review.as_ref().map(|r| r.next_week_goal);
+                self.review_draft = ReviewDraft::new(stats, previous_goal);
+                self.plan_history = crate::db::load_plans_since(
+                    self.plan_date(PlanDay::Today) - chrono::Duration::days(6),
+                );
+                self.review_error = None;
+                self.report_status = None;
+                self.screen = Screen::WeeklyReview;
+            }
+            Message::CloseWeeklyReview => {
+                self.review_error = None;
+                self.screen = Screen::Timer;
+            }
+            Message::ReviewNext => {
+                self.review_draft.step = self.review_draft.step.next();
+            }
+            Message::ReviewBack => {
+                self.review_error = None;
+                self.review_draft.step = self.review_draft.step.previous();
+            }
+            Message::ReviewReflectionChanged(value) => {
+                self.review_draft.reflection = value;
+            }
+            Message::ReviewGoalChanged(value) => {
+                self.review_draft.next_week_goal = value;
+            }
+            Message::SaveWeeklyReview => {
+                if let Some(review) = self.review_draft.parse(crate::db::unix_now()) {
+                    crate::db::save_weekly_review(&review);
+                    self.latest_review = Some(review);
+                    self.goal_progress = 0;
+                    self.review_error = None;
+                    self.screen = Screen::Timer;
+                } else {
+                    self.review_error = Some(t("review-goal-error").to_string());
+                }
             }
         }
+
+        Task::none()
+    }
+}
+
+// Where exports are saved: the given folder, or else Downloads, or else
+// next to the database.
+fn export_dir(dir: &str) -> std::path::PathBuf {
+    if !dir.is_empty() {
+        return crate::daily_log::expand_home(dir);
+    }
+    directories::UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(|dir| dir.to_path_buf()))
+        .unwrap_or_else(|| crate::db::db_path().with_file_name(""))
+}
+
+// A whole number picked with -/+ buttons or a slider, so it can't be
+// mistyped, shown by `value_view`. Arrow keys step the slider under the
+// mouse, by five with Shift. Values saved before the range existed stay
+// reachable.
+fn stepper<'a>(
+    value: u32,
+    range: RangeInclusive<u32>,
+    on_change: fn(u32) -> Message,
+    value_view: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let (min, max) = (*range.start(), (*range.end()).max(value));
+    row![
+        button(text("−").size(16))
+            .padding([8, 14])
+            .style(transparent_button_style)
+            .on_press_maybe((value > min).then(|| on_change(value - 1))),
+        slider(min..=max, value.clamp(min, max), on_change)
+            .shift_step(5u32)
+            .width(Length::Fill),
+        button(text("+").size(16))
+            .padding([8, 14])
+            .style(transparent_button_style)
+            .on_press_maybe((value < max).then(|| on_change(value + 1))),
+        value_view.into(),
+    ]
+    .spacing(8)
+    .align_y(Center)
+    .into()
+}
+
+// Durations step by whole minutes and can be typed as MM:SS for the rest
+fn duration_input(value: &str, on_input: fn(String) -> Message) -> Element<'_, Message> {
+    text_input("25:00", value)
+        .on_input(on_input)
+        .padding(8)
+        .size(16)
+        .width(80)
+        .into()
+}
+
+fn duration_minutes(value: &str) -> u32 {
+    crate::settings::parse_duration(value).map_or(0, |seconds| seconds / 60)
+}
+
+// Steps keep any seconds that were typed in
+fn set_minutes(value: &mut String, minutes: u32) {
+    let seconds = crate::settings::parse_duration(value).map_or(0, |seconds| seconds % 60);
+    *value = format_duration(minutes * 60 + seconds);
+}
+
+fn period_style(period: PeriodKind) -> (&'static str, Color) {
+    let palette = palette::current();
+    match period {
+        PeriodKind::Work => (t("work-time"), palette.work),
+        PeriodKind::LongBreak => (t("long-break"), palette.long_break),
+        PeriodKind::ShortBreak => (t("short-break"), palette.short_break),
     }
 }
 
-impl Default for PomodoroTimer {
-    fn default() -> Self {
-        Self::new()
+fn card_style(_theme: &Theme) -> container::Style {
+    let palette = palette::current();
+    container::Style {
+        border: Border {
+            color: palette.button,
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        ..Default::default()
     }
 }
 
 fn transparent_button_style(_theme: &Theme, status: button::Status) -> button::Style {
+    let palette = palette::current();
     let base_style = button::Style {
-        background: Some(Background::Color(Color::from_rgba(0.024, 0.58, 0.58, 1.0))),
+        background: Some(Background::Color(palette.button)),
         border: Border {
-            color: Color::from_rgba(0.024, 0.58, 0.58, 1.0),
+            color: palette.button,
             width: 0.0,
             radius: 4.0.into(),
         },
-        text_color: Color::from_rgb(0.3, 0.3, 0.3),
+        text_color: palette.button_text,
         ..Default::default()
     };
 
     match status {
         button::Status::Hovered => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.024, 0.48, 0.48, 1.0))),
+            background: Some(Background::Color(palette.button_hovered)),
             ..base_style
         },
         button::Status::Pressed => button::Style {
-            background: Some(Background::Color(Color::from_rgba(0.024, 0.42, 0.42, 1.0))),
+            background: Some(Background::Color(palette.button_pressed)),
+            ..base_style
+        },
+        button::Status::Disabled => button::Style {
+            background: Some(Background::Color(palette.button.scale_alpha(0.35))),
+            text_color: palette.button_text.scale_alpha(0.5),
             ..base_style
         },
         _ => base_style,
     }
 }
-
-fn process_audio_command(command: AudioCommand, sink: &Sink) {
-    match command {
-        AudioCommand::Alarm => {
-            let (_stream, stream_handle) = rodio::OutputStream::try_default().unwrap();
-            let source = rodio::source::SineWave::new(240.0)
-                .take_duration(Duration::from_millis(500))
-                .amplify(0.20);
-            stream_handle.play_raw(source.convert_samples()).unwrap();
-            std::thread::sleep(Duration::from_secs(1));
-
-            let source = rodio::source::SineWave::new(340.0)
-                .take_duration(Duration::from_millis(500))
-                .amplify(0.20);
-            stream_handle.play_raw(source.convert_samples()).unwrap();
-            std::thread::sleep(Duration::from_secs(1));
-
-            let source = rodio::source::SineWave::new(440.0)
-                .take_duration(Duration::from_millis(500))
-                .amplify(0.20);
-            stream_handle.play_raw(source.convert_samples()).unwrap();
-            std::thread::sleep(Duration::from_secs(3));
-        }
-        AudioCommand::Stop => {
-            sink.stop();
-        }
-    }
-}
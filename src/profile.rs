@@ -0,0 +1,98 @@
+use crate::settings::{AmbientSound, Settings, SettingsDraft, format_duration};
+
+// A named set of durations and sounds, e.g. "Deep Work" or "Study".
+// Everything else (overlay, streak rules, ...) is shared across profiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub work_seconds: u32,
+    pub short_break_seconds: u32,
+    pub long_break_seconds: u32,
+    pub long_break_every: u32,
+    pub alarm_volume: u32,
+    pub tick_volume: u32,
+    pub ambient_sound: AmbientSound,
+    pub ambient_volume: u32,
+    pub ambient_path: String,
+}
+
+impl Profile {
+    pub fn capture(name: &str, settings: &Settings, ambient_path: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            work_seconds: settings.work_seconds,
+            short_break_seconds: settings.short_break_seconds,
+            long_break_seconds: settings.long_break_seconds,
+            long_break_every: settings.long_break_every,
+            alarm_volume: settings.alarm_volume,
+            tick_volume: settings.tick_volume,
+            ambient_sound: settings.ambient_sound,
+            ambient_volume: settings.ambient_volume,
+            ambient_path: ambient_path.to_string(),
+        }
+    }
+
+    pub fn apply_to(&self, settings: Settings) -> Settings {
+        Settings {
+            work_seconds: self.work_seconds,
+            short_break_seconds: self.short_break_seconds,
+            long_break_seconds: self.long_break_seconds,
+            long_break_every: self.long_break_every,
+            alarm_volume: self.alarm_volume,
+            tick_volume: self.tick_volume,
+            ambient_sound: self.ambient_sound,
+            ambient_volume: self.ambient_volume,
+            ..settings
+        }
+    }
+
+    // Loads the profile into the settings form for editing, leaving the
+    // shared fields as they are.
+    pub fn fill_draft(&self, draft: &mut SettingsDraft) {
+        draft.work_duration = format_duration(self.work_seconds);
+        draft.short_break_duration = format_duration(self.short_break_seconds);
+        draft.long_break_duration = format_duration(self.long_break_seconds);
+        draft.long_break_every = self.long_break_every;
+        draft.alarm_volume = self.alarm_volume.to_string();
+        draft.tick_volume = self.tick_volume.to_string();
+        draft.ambient_sound = self.ambient_sound;
+        draft.ambient_volume = self.ambient_volume.to_string();
+        draft.ambient_path = self.ambient_path.clone();
+    }
+}
+
+#[cfg(test)]
+mod profile_tests {
+    use super::*;
+
+    #[test]
+    fn applying_a_profile_keeps_the_shared_settings() {
+        let shared = Settings {
+            day_start_hour: 4,
+            alarm_repeat_seconds: 30,
+            snooze_minutes: 7,
+            strict_mode: true,
+            ..Settings::default()
+        };
+        let profile = Profile {
+            name: "Study".to_string(),
+            work_seconds: 50 * 60,
+            short_break_seconds: 10 * 60,
+            long_break_seconds: 30 * 60,
+            long_break_every: 3,
+            alarm_volume: 20,
+            tick_volume: 0,
+            ambient_sound: AmbientSound::PinkNoise,
+            ambient_volume: 40,
+            ambient_path: String::new(),
+        };
+
+        let settings = profile.apply_to(shared);
+        assert_eq!(Profile::capture("Study", &settings, ""), profile);
+        assert_eq!(settings.day_start_hour, 4);
+        assert_eq!(settings.alarm_repeat_seconds, 30);
+        assert_eq!(settings.snooze_minutes, 7);
+        assert!(settings.strict_mode);
+        assert_eq!(settings.alarm_pattern, shared.alarm_pattern);
+    }
+}
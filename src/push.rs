@@ -0,0 +1,177 @@
+use crate::i18n::t;
+use std::{
+    fmt,
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+const NTFY_SERVER: &str = "https://ntfy.sh";
+const PUSHOVER_URL: &str = "https://api.pushover.net/1/messages.json";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// Names of the Pushover user key and app token in the system keychain
+pub const PUSHOVER_USER_NAME: &str = "pushover-user";
+pub const PUSHOVER_TOKEN_NAME: &str = "pushover-token";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PushService {
+    #[default]
+    Off,
+    Ntfy,
+    Pushover,
+}
+
+impl PushService {
+    pub const ALL: [PushService; 3] = [PushService::Off, PushService::Ntfy, PushService::Pushover];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            PushService::Off => "off",
+            PushService::Ntfy => "ntfy",
+            PushService::Pushover => "pushover",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|service| service.key() == key)
+    }
+}
+
+impl fmt::Display for PushService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PushService::Off => write!(f, "{}", t("off")),
+            PushService::Ntfy => write!(f, "ntfy"),
+            PushService::Pushover => write!(f, "Pushover"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PushConfig {
+    pub service: PushService,
+    // A topic on ntfy.sh, or a full URL for a self-hosted server
+    pub ntfy_topic: String,
+    pub pushover_user: String,
+    pub pushover_token: String,
+}
+
+impl PushConfig {
+    pub fn is_enabled(&self) -> bool {
+        match self.service {
+            PushService::Off => false,
+            PushService::Ntfy => !self.ntfy_topic.is_empty(),
+            PushService::Pushover => {
+                !self.pushover_user.is_empty() && !self.pushover_token.is_empty()
+            }
+        }
+    }
+
+    fn ntfy_url(&self) -> String {
+        if self.ntfy_topic.contains("://") {
+            self.ntfy_topic.clone()
+        } else {
+            format!("{}/{}", NTFY_SERVER, self.ntfy_topic)
+        }
+    }
+}
+
+struct Notification {
+    config: PushConfig,
+    title: String,
+    message: String,
+}
+
+static SENDER: OnceLock<Sender<Notification>> = OnceLock::new();
+
+// Requests go out one at a time on a worker thread; a slow or unreachable
+// service never holds up the timer.
+fn spawn_sender() -> Sender<Notification> {
+    let (sender, receiver) = mpsc::channel::<Notification>();
+    thread::spawn(move || {
+        let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+        for notification in receiver {
+            if let Err(err) = deliver(&agent, &notification) {
+                println!("Error sending push notification: {}", err);
+            }
+        }
+    });
+
+    sender
+}
+
+fn deliver(agent: &ureq::Agent, notification: &Notification) -> Result<(), Box<ureq::Error>> {
+    let config = &notification.config;
+    match config.service {
+        PushService::Off => {}
+        PushService::Ntfy => {
+            agent
+                .post(&config.ntfy_url())
+                .set("Title", &notification.title)
+                .set("Tags", "tomato")
+                .send_string(&notification.message)?;
+        }
+        PushService::Pushover => {
+            agent.post(PUSHOVER_URL).send_form(&[
+                ("token", config.pushover_token.as_str()),
+                ("user", config.pushover_user.as_str()),
+                ("title", notification.title.as_str()),
+                ("message", notification.message.as_str()),
+            ])?;
+        }
+    }
+    Ok(())
+}
+
+pub fn send(config: &PushConfig, title: String, message: String) {
+    if !config.is_enabled() {
+        return;
+    }
+    let _ = SENDER.get_or_init(spawn_sender).send(Notification {
+        config: config.clone(),
+        title,
+        message,
+    });
+}
+
+#[cfg(test)]
+mod push_tests {
+    use super::*;
+
+    #[test]
+    fn each_service_needs_its_own_fields() {
+        let mut config = PushConfig {
+            ntfy_topic: "my-pomodoros".to_string(),
+            pushover_user: "user".to_string(),
+            ..PushConfig::default()
+        };
+        assert!(!config.is_enabled());
+
+        config.service = PushService::Ntfy;
+        assert!(config.is_enabled());
+
+        config.service = PushService::Pushover;
+        assert!(!config.is_enabled());
+        config.pushover_token = "token".to_string();
+        assert!(config.is_enabled());
+    }
+
+    #[test]
+    fn ntfy_topics_may_name_their_own_server() {
+        let config = PushConfig {
+            service: PushService::Ntfy,
+            ntfy_topic: "my-pomodoros".to_string(),
+            ..PushConfig::default()
+        };
+        assert_eq!(config.ntfy_url(), "https://ntfy.sh/my-pomodoros");
+
+        let config = PushConfig {
+            ntfy_topic: "https://ntfy.example.com/focus".to_string(),
+            ..config
+        };
+        assert_eq!(config.ntfy_url(), "https://ntfy.example.com/focus");
+    }
+}
@@ -0,0 +1,111 @@
+use crate::i18n::t;
+use crate::settings::parse_duration;
+use std::time::{Duration, Instant};
+
+// One-off countdowns for anything besides pomodoros, like tea or the
+// laundry. Several can run next to the pomodoro at once; they ring like the
+// timer does but leave no record.
+pub const PRESET_MINUTES: [u32; 4] = [3, 5, 10, 15];
+
+// The next countdown, as typed
+#[derive(Debug, Clone)]
+pub struct QuickTimerDraft {
+    pub label: String,
+    // In minutes or mm:ss
+    pub duration: String,
+}
+
+impl Default for QuickTimerDraft {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            duration: "10:00".to_string(),
+        }
+    }
+}
+
+impl QuickTimerDraft {
+    pub fn seconds(&self) -> Option<u32> {
+        parse_duration(&self.duration).filter(|seconds| *seconds > 0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QuickTimer {
+    pub id: u64,
+    pub label: String,
+    pub seconds: u32,
+    pub ends_at: Option<Instant>,
+    pub ringing: bool,
+}
+
+impl QuickTimer {
+    // Starts counting down right away
+    pub fn new(id: u64, label: &str, seconds: u32, now: Instant) -> Self {
+        let mut timer = Self {
+            id,
+            label: label.trim().to_string(),
+            seconds,
+            ends_at: None,
+            ringing: false,
+        };
+        timer.restart(now);
+        timer
+    }
+
+    pub fn name(&self) -> &str {
+        match self.label.as_str() {
+            "" => t("quick-timer"),
+            label => label,
+        }
+    }
+
+    pub fn restart(&mut self, now: Instant) {
+        self.ends_at = Some(now + crate::clock::wall(Duration::from_secs(self.seconds as u64)));
+        self.ringing = false;
+    }
+
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.ends_at
+            .map(|ends_at| crate::clock::timer(ends_at.saturating_duration_since(now)))
+    }
+
+    // True once, when the countdown runs out
+    pub fn check(&mut self, now: Instant) -> bool {
+        if self.ends_at.is_some_and(|ends_at| now >= ends_at) {
+            self.ends_at = None;
+            self.ringing = true;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod quick_timer_tests {
+    use super::*;
+
+    #[test]
+    fn quick_timers_ring_once_when_they_run_out() {
+        let now = Instant::now();
+        let mut draft = QuickTimerDraft {
+            duration: "tea".to_string(),
+            ..QuickTimerDraft::default()
+        };
+        assert_eq!(draft.seconds(), None);
+        draft.duration = "3".to_string();
+        assert_eq!(draft.seconds(), Some(180));
+
+        let mut quick = QuickTimer::new(1, " Tea ", 180, now);
+        assert_eq!(quick.name(), "Tea");
+        assert!(!quick.check(now + Duration::from_secs(60)));
+        assert!(quick.remaining(now + Duration::from_secs(60)).is_some());
+        assert!(quick.check(now + Duration::from_secs(180)));
+        assert!(quick.ringing);
+        assert!(!quick.check(now + Duration::from_secs(240)));
+
+        quick.restart(now + Duration::from_secs(240));
+        assert!(!quick.ringing);
+        assert!(quick.remaining(now + Duration::from_secs(240)).is_some());
+    }
+}
@@ -0,0 +1,269 @@
+use chrono::{DateTime, Duration, NaiveDate};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReportConfig {
+    // Empty saves to the Downloads folder
+    pub dir: String,
+    // Writes an HTML copy next to the Markdown file
+    pub html: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportSession {
+    pub ended_at: i64,
+    pub utc_offset_seconds: i32,
+    pub duration_seconds: u32,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    pomodoros: u32,
+    seconds: u32,
+}
+
+impl Totals {
+    fn add(&mut self, session: &ReportSession) {
+        self.pomodoros += 1;
+        self.seconds += session.duration_seconds;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WeeklyReport {
+    pub last_day: NaiveDate,
+    days: Vec<(NaiveDate, Totals)>,
+    tags: Vec<(String, Totals)>,
+    total: Totals,
+    streak: u32,
+}
+
+fn duration(seconds: u32) -> String {
+    format!("{}h {:02}m", seconds / 3600, (seconds % 3600) / 60)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl WeeklyReport {
+    // The seven days up to and including `last_day`. Sessions are placed on
+    // days the same way as for streaks: in the zone they were recorded in,
+    // with days starting at `day_start_hour`.
+    pub fn new(
+        last_day: NaiveDate,
+        sessions: &[ReportSession],
+        day_start_hour: u32,
+        streak: u32,
+    ) -> Self {
+        let first_day = last_day - Duration::days(6);
+        let mut days: Vec<(NaiveDate, Totals)> = first_day
+            .iter_days()
+            .take(7)
+            .map(|day| (day, Totals::default()))
+            .collect();
+        let mut tags: HashMap<&str, Totals> = HashMap::new();
+        let mut total = Totals::default();
+
+        for session in sessions {
+            let local =
+                session.ended_at + session.utc_offset_seconds as i64 - day_start_hour as i64 * 3600;
+            let Some(day) = DateTime::from_timestamp(local, 0).map(|at| at.date_naive()) else {
+                continue;
+            };
+            let Some((_, totals)) = days.iter_mut().find(|(d, _)| *d == day) else {
+                continue;
+            };
+            totals.add(session);
+            tags.entry(session.tag.as_str()).or_default().add(session);
+            total.add(session);
+        }
+
+        let mut tags: Vec<(String, Totals)> = tags
+            .into_iter()
+            .map(|(tag, totals)| (tag.to_string(), totals))
+            .collect();
+        tags.sort_by(|a, b| b.1.seconds.cmp(&a.1.seconds).then(a.0.cmp(&b.0)));
+
+        Self {
+            last_day,
+            days,
+            tags,
+            total,
+            streak,
+        }
+    }
+
+    fn best_day(&self) -> Option<(NaiveDate, Totals)> {
+        self.days
+            .iter()
+            .filter(|(_, totals)| totals.pomodoros > 0)
+            .max_by_key(|(day, totals)| (totals.pomodoros, std::cmp::Reverse(*day)))
+            .copied()
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "Weekly Report: {} – {}",
+            (self.last_day - Duration::days(6)).format("%-d %b"),
+            self.last_day.format("%-d %b %Y")
+        )
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let active_days = self.days.iter().filter(|(_, t)| t.pomodoros > 0).count();
+        let best_day = match self.best_day() {
+            Some((day, totals)) => format!(
+                "{} ({} pomodoros)",
+                day.format("%a %-d %b"),
+                totals.pomodoros
+            ),
+            None => "–".to_string(),
+        };
+        vec![
+            ("Pomodoros", self.total.pomodoros.to_string()),
+            ("Focus time", duration(self.total.seconds)),
+            ("Active days", format!("{}/7", active_days)),
+            ("Best day", best_day),
+            ("Streak", format!("{} days", self.streak)),
+        ]
+    }
+
+    fn tag_rows(&self) -> Vec<[String; 3]> {
+        self.tags
+            .iter()
+            .map(|(tag, totals)| {
+                let tag = if tag.is_empty() { "Untagged" } else { tag };
+                [
+                    tag.to_string(),
+                    totals.pomodoros.to_string(),
+                    duration(totals.seconds),
+                ]
+            })
+            .collect()
+    }
+
+    fn day_rows(&self) -> Vec<[String; 3]> {
+        self.days
+            .iter()
+            .map(|(day, totals)| {
+                [
+                    day.format("%a %-d %b").to_string(),
+                    totals.pomodoros.to_string(),
+                    duration(totals.seconds),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut lines = vec![format!("# 🍅 {}", self.title()), String::new()];
+        for (label, value) in self.summary() {
+            lines.push(format!("- **{}:** {}", label, value));
+        }
+        for (heading, first_column, rows) in [
+            ("By tag", "Tag", self.tag_rows()),
+            ("By day", "Day", self.day_rows()),
+        ] {
+            if rows.is_empty() {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(format!("## {}", heading));
+            lines.push(String::new());
+            lines.push(format!("| {} | Pomodoros | Focus time |", first_column));
+            lines.push("| --- | ---: | ---: |".to_string());
+            for [name, pomodoros, focus] in rows {
+                lines.push(format!(
+                    "| {} | {} | {} |",
+                    name.replace('|', "\\|"),
+                    pomodoros,
+                    focus
+                ));
+            }
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+             <style>body{{font-family:sans-serif;max-width:40em;margin:2em auto}}\
+             table{{border-collapse:collapse}}td,th{{padding:.3em .8em;border-bottom:1px solid #ddd}}\
+             td+td{{text-align:right}}</style>\n</head>\n<body>\n<h1>🍅 {0}</h1>\n<ul>\n",
+            escape(&self.title())
+        );
+        for (label, value) in self.summary() {
+            html.push_str(&format!(
+                "<li><strong>{}:</strong> {}</li>\n",
+                label,
+                escape(&value)
+            ));
+        }
+        html.push_str("</ul>\n");
+        for (heading, first_column, rows) in [
+            ("By tag", "Tag", self.tag_rows()),
+            ("By day", "Day", self.day_rows()),
+        ] {
+            if rows.is_empty() {
+                continue;
+            }
+            html.push_str(&format!(
+                "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>Pomodoros</th><th>Focus time</th></tr>\n",
+                heading, first_column
+            ));
+            for [name, pomodoros, focus] in rows {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    escape(&name),
+                    pomodoros,
+                    focus
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    #[test]
+    fn week_is_summed_by_tag_and_day() {
+        let session = |ended_at: i64, tag: &str| ReportSession {
+            ended_at,
+            utc_offset_seconds: 0,
+            duration_seconds: 25 * 60,
+            tag: tag.to_string(),
+        };
+        // Fri 31 May 2024 14:00 UTC, the day before, and a week earlier
+        let friday = 1_717_164_000;
+        let report = WeeklyReport::new(
+            NaiveDate::from_ymd_opt(2024, 5, 31).unwrap(),
+            &[
+                session(friday, "Write report"),
+                session(friday - 3600, "Write report"),
+                session(friday - 86_400, ""),
+                session(friday - 7 * 86_400, "Too old"),
+            ],
+            0,
+            4,
+        );
+        let markdown = report.markdown();
+
+        assert!(markdown.starts_with("# 🍅 Weekly Report: 25 May – 31 May 2024\n"));
+        assert!(markdown.contains("- **Pomodoros:** 3\n"));
+        assert!(markdown.contains("- **Focus time:** 1h 15m\n"));
+        assert!(markdown.contains("- **Best day:** Fri 31 May (2 pomodoros)\n"));
+        assert!(markdown.contains("- **Streak:** 4 days\n"));
+        assert!(markdown.contains("| Write report | 2 | 0h 50m |\n| Untagged | 1 | 0h 25m |\n"));
+        assert!(!markdown.contains("Too old"));
+    }
+}
@@ -0,0 +1,74 @@
+pub const WEEK_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewStep {
+    Stats,
+    Reflection,
+    Goals,
+}
+
+impl ReviewStep {
+    pub fn next(self) -> Self {
+        match self {
+            ReviewStep::Stats => ReviewStep::Reflection,
+            ReviewStep::Reflection | ReviewStep::Goals => ReviewStep::Goals,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            ReviewStep::Stats | ReviewStep::Reflection => ReviewStep::Stats,
+            ReviewStep::Goals => ReviewStep::Reflection,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WeeklyStats {
+    pub completed_pomodoros: u32,
+    pub focus_seconds: u32,
+    pub active_days: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct WeeklyReview {
+    pub created_at: i64,
+    pub stats: WeeklyStats,
+    pub reflection: String,
+    pub next_week_goal: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewDraft {
+    pub step: ReviewStep,
+    pub stats: WeeklyStats,
+    pub reflection: String,
+    pub next_week_goal: String,
+}
+
+impl ReviewDraft {
+    pub fn new(stats: WeeklyStats, previous_goal: Option<u32>) -> Self {
+        Self {
+            step: ReviewStep::Stats,
+            stats,
+            reflection: String::new(),
+            next_week_goal: previous_goal
+                .unwrap_or(stats.completed_pomodoros)
+                .to_string(),
+        }
+    }
+
+    pub fn parse(&self, created_at: i64) -> Option<WeeklyReview> {
+        let next_week_goal: u32 = self.next_week_goal.trim().parse().ok()?;
+        if next_week_goal == 0 {
+            return None;
+        }
+
+        Some(WeeklyReview {
+            created_at,
+            stats: self.stats,
+            reflection: self.reflection.trim().to_string(),
+            next_week_goal,
+        })
+    }
+}
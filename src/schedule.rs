@@ -0,0 +1,129 @@
+use crate::i18n::t;
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::fmt;
+
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+pub fn weekday_label(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => t("monday"),
+        Weekday::Tue => t("tuesday"),
+        Weekday::Wed => t("wednesday"),
+        Weekday::Thu => t("thursday"),
+        Weekday::Fri => t("friday"),
+        Weekday::Sat => t("saturday"),
+        Weekday::Sun => t("sunday"),
+    }
+}
+
+// What to do when a given weekday starts: keep whatever profile is in use,
+// or switch to a named one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DayProfile {
+    #[default]
+    Unchanged,
+    Profile(String),
+}
+
+impl fmt::Display for DayProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DayProfile::Unchanged => write!(f, "{}", t("schedule-no-change")),
+            DayProfile::Profile(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// Indexed by days from Monday.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Schedule {
+    pub days: [DayProfile; 7],
+}
+
+impl Schedule {
+    pub fn is_empty(&self) -> bool {
+        self.days.iter().all(|day| *day == DayProfile::Unchanged)
+    }
+
+    // What to do on `today` if the schedule hasn't run yet that day, given
+    // the day it last ran.
+    pub fn on_new_day(&self, last_run: Option<NaiveDate>, today: NaiveDate) -> Option<&DayProfile> {
+        (last_run != Some(today))
+            .then(|| &self.days[today.weekday().num_days_from_monday() as usize])
+    }
+
+    // Days pointing at a deleted profile fall back to no change. Returns
+    // whether any day was affected.
+    pub fn forget_profile(&mut self, name: &str) -> bool {
+        let mut changed = false;
+        for day in &mut self.days {
+            if *day == DayProfile::Profile(name.to_string()) {
+                *day = DayProfile::Unchanged;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod schedule_tests {
+    use super::*;
+    use crate::streak::{day_of, parse_day};
+
+    #[test]
+    fn each_weekday_switches_to_its_profile_once() {
+        let mut schedule = Schedule::default();
+        schedule.days[0] = DayProfile::Profile("Deep Work".to_string());
+        schedule.days[5] = DayProfile::Profile("Study".to_string());
+        let monday = parse_day("2026-10-12").unwrap();
+        let saturday = parse_day("2026-10-17").unwrap();
+        let sunday = parse_day("2026-10-18").unwrap();
+
+        assert_eq!(
+            schedule.on_new_day(None, monday),
+            Some(&DayProfile::Profile("Deep Work".to_string()))
+        );
+        assert_eq!(schedule.on_new_day(Some(monday), monday), None);
+        assert_eq!(
+            schedule.on_new_day(Some(monday), saturday),
+            Some(&DayProfile::Profile("Study".to_string()))
+        );
+        assert_eq!(
+            schedule.on_new_day(Some(saturday), sunday),
+            Some(&DayProfile::Unchanged)
+        );
+    }
+
+    #[test]
+    fn days_roll_over_at_the_day_start_hour() {
+        let at = |value| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+        let monday = parse_day("2026-10-12").unwrap();
+        let tuesday = parse_day("2026-10-13").unwrap();
+
+        // Still Monday's schedule in the small hours
+        assert_eq!(day_of(at("2026-10-13 03:59"), 4), monday);
+        assert_eq!(day_of(at("2026-10-13 04:00"), 4), tuesday);
+        assert_eq!(day_of(at("2026-10-13 00:00"), 0), tuesday);
+
+        let schedule = Schedule::default();
+        let last_run = Some(day_of(at("2026-10-12 23:00"), 4));
+        assert_eq!(
+            schedule.on_new_day(last_run, day_of(at("2026-10-13 03:59"), 4)),
+            None
+        );
+        assert!(
+            schedule
+                .on_new_day(last_run, day_of(at("2026-10-13 04:00"), 4))
+                .is_some()
+        );
+    }
+}
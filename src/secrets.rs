@@ -0,0 +1,38 @@
+use keyring::Entry;
+
+const SERVICE: &str = "roth-pomodoro";
+
+// Tokens for online services are kept in the system keychain (Secret
+// Service, macOS Keychain or Windows Credential Manager) rather than in the
+// settings database.
+pub fn load(name: &str) -> String {
+    match Entry::new(SERVICE, name).and_then(|entry| entry.get_password()) {
+        Ok(secret) => secret,
+        Err(keyring::Error::NoEntry) => String::new(),
+        Err(err) => {
+            println!("Error reading {} from the keychain: {}", name, err);
+            String::new()
+        }
+    }
+}
+
+// An empty secret removes the entry. Returns whether the keychain took it.
+pub fn save(name: &str, secret: &str) -> bool {
+    let result = Entry::new(SERVICE, name).and_then(|entry| {
+        if secret.is_empty() {
+            match entry.delete_credential() {
+                Err(keyring::Error::NoEntry) => Ok(()),
+                result => result,
+            }
+        } else {
+            entry.set_password(secret)
+        }
+    });
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            println!("Error saving {} to the keychain: {}", name, err);
+            false
+        }
+    }
+}
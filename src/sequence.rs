@@ -0,0 +1,155 @@
+use crate::settings::{PeriodKind, format_duration, parse_duration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceStep {
+    pub kind: PeriodKind,
+    pub seconds: u32,
+}
+
+// Ready-made sequences, in minutes.
+pub const PRESETS: [(&str, &[(PeriodKind, u32)]); 2] = [
+    (
+        "52/17",
+        &[(PeriodKind::Work, 52), (PeriodKind::ShortBreak, 17)],
+    ),
+    (
+        "25-5-25-5-25-15",
+        &[
+            (PeriodKind::Work, 25),
+            (PeriodKind::ShortBreak, 5),
+            (PeriodKind::Work, 25),
+            (PeriodKind::ShortBreak, 5),
+            (PeriodKind::Work, 25),
+            (PeriodKind::LongBreak, 15),
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct SequenceDraft {
+    pub steps: Vec<(PeriodKind, String)>,
+}
+
+impl SequenceDraft {
+    pub fn from_steps(steps: &[SequenceStep]) -> Self {
+        Self {
+            steps: steps
+                .iter()
+                .map(|step| (step.kind, format_duration(step.seconds)))
+                .collect(),
+        }
+    }
+
+    pub fn from_preset(preset: &[(PeriodKind, u32)]) -> Self {
+        Self {
+            steps: preset
+                .iter()
+                .map(|(kind, minutes)| (*kind, format_duration(minutes * 60)))
+                .collect(),
+        }
+    }
+
+    // An empty sequence is valid and means the standard work/break cycle.
+    // Otherwise every step needs a positive length and there must be at
+    // least one work period to iterate towards.
+    pub fn parse(&self) -> Option<Vec<SequenceStep>> {
+        let steps = self
+            .steps
+            .iter()
+            .map(|(kind, length)| {
+                let seconds = parse_duration(length).filter(|s| *s > 0)?;
+                Some(SequenceStep {
+                    kind: *kind,
+                    seconds,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if !steps.is_empty() && !steps.iter().any(|step| step.kind == PeriodKind::Work) {
+            return None;
+        }
+        Some(steps)
+    }
+}
+
+// The step after `index`, starting over after the last one
+pub fn next_index(steps: &[SequenceStep], index: usize) -> usize {
+    (index + 1) % steps.len()
+}
+
+#[cfg(test)]
+mod sequence_tests {
+    use super::*;
+
+    fn draft(steps: &[(PeriodKind, &str)]) -> SequenceDraft {
+        SequenceDraft {
+            steps: steps
+                .iter()
+                .map(|(kind, length)| (*kind, length.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn sequences_need_a_work_step_and_positive_lengths() {
+        let steps = draft(&[(PeriodKind::Work, "50"), (PeriodKind::ShortBreak, "0:45")])
+            .parse()
+            .unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                SequenceStep {
+                    kind: PeriodKind::Work,
+                    seconds: 50 * 60,
+                },
+                SequenceStep {
+                    kind: PeriodKind::ShortBreak,
+                    seconds: 45,
+                },
+            ]
+        );
+        assert_eq!(draft(&[]).parse(), Some(Vec::new()));
+
+        assert!(
+            draft(&[(PeriodKind::ShortBreak, "5"), (PeriodKind::LongBreak, "15")])
+                .parse()
+                .is_none()
+        );
+        assert!(draft(&[(PeriodKind::Work, "0")]).parse().is_none());
+        assert!(draft(&[(PeriodKind::Work, "x")]).parse().is_none());
+    }
+
+    #[test]
+    fn presets_are_valid_sequences() {
+        for (name, preset) in PRESETS {
+            let steps = SequenceDraft::from_preset(preset).parse().unwrap();
+            assert_eq!(steps.len(), preset.len(), "{name}");
+            assert_eq!(SequenceDraft::from_steps(&steps).parse(), Some(steps));
+        }
+    }
+
+    #[test]
+    fn sequences_wrap_around_after_the_last_step() {
+        let steps = SequenceDraft::from_preset(PRESETS[1].1).parse().unwrap();
+        let mut index = 0;
+        let kinds: Vec<PeriodKind> = (0..8)
+            .map(|_| {
+                index = next_index(&steps, index);
+                steps[index].kind
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                PeriodKind::ShortBreak,
+                PeriodKind::Work,
+                PeriodKind::ShortBreak,
+                PeriodKind::Work,
+                PeriodKind::LongBreak,
+                PeriodKind::Work,
+                PeriodKind::ShortBreak,
+                PeriodKind::Work,
+            ]
+        );
+    }
+}
@@ -1,7 +1,651 @@
+use crate::audio::AlarmPattern;
+use crate::background::Backgrounds;
+use crate::daily_log::DailyLog;
+use crate::focus_guard::GuardedApp;
+use crate::github::GithubConfig;
+use crate::i18n::{Language, t};
+use crate::mqtt::MqttConfig;
+use crate::palette::PaletteKind;
+use crate::platform::monitors::Monitor;
+use crate::push::PushConfig;
+use crate::report::ReportConfig;
+use crate::shortcuts::Shortcuts;
+use crate::sync_server::SyncServerConfig;
+use crate::telegram::TelegramConfig;
+use crate::todoist::TodoistConfig;
+use crate::toggl::TogglConfig;
+use crate::worklog::WorklogConfig;
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use iced::{Point, Size};
+
 #[derive(Debug, Clone, Copy)]
 pub enum Screen {
     Timer,
     Settings,
+    WeeklyReview,
+    Sequence,
+    Schedule,
+    Tasks,
+    Plan,
+    Stats,
+    History,
+    Team,
+    QuickTimer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodKind {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PeriodKind {
+    pub const ALL: [PeriodKind; 3] = [
+        PeriodKind::Work,
+        PeriodKind::ShortBreak,
+        PeriodKind::LongBreak,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            PeriodKind::Work => "work",
+            PeriodKind::ShortBreak => "short_break",
+            PeriodKind::LongBreak => "long_break",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
+impl std::fmt::Display for PeriodKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PeriodKind::Work => t("period-work"),
+            PeriodKind::ShortBreak => t("period-short-break"),
+            PeriodKind::LongBreak => t("period-long-break"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FocusMessage {
+    // Shown instead of the period's name above the countdown
+    pub header: String,
+    pub text: String,
+    pub image_path: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FocusMessages {
+    pub work: FocusMessage,
+    pub short_break: FocusMessage,
+    pub long_break: FocusMessage,
+}
+
+// Connections to other apps and services. They are saved with the other
+// settings but live outside the Copy `Settings` struct.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Integrations {
+    pub obs_output_dir: String,
+    pub mqtt: MqttConfig,
+    pub push: PushConfig,
+    pub telegram: TelegramConfig,
+    pub toggl: TogglConfig,
+    // Clockify or Jira
+    pub worklog: WorklogConfig,
+    pub todoist: TodoistConfig,
+    pub github: GithubConfig,
+    pub daily_log: DailyLog,
+    // An iCalendar address to read upcoming meetings from
+    pub meeting_calendar_url: String,
+    pub report: ReportConfig,
+    // A folder shared between devices, e.g. with Dropbox or Syncthing
+    pub sync_dir: String,
+    pub sync_server: SyncServerConfig,
+}
+
+impl Integrations {
+    pub fn trimmed(&self) -> Self {
+        Self {
+            obs_output_dir: self.obs_output_dir.trim().to_string(),
+            mqtt: MqttConfig {
+                broker: self.mqtt.broker.trim().to_string(),
+                topic_prefix: self.mqtt.topic_prefix.trim().to_string(),
+                ..self.mqtt.clone()
+            },
+            push: PushConfig {
+                ntfy_topic: self.push.ntfy_topic.trim().to_string(),
+                pushover_user: self.push.pushover_user.trim().to_string(),
+                pushover_token: self.push.pushover_token.trim().to_string(),
+                ..self.push.clone()
+            },
+            telegram: TelegramConfig {
+                token: self.telegram.token.trim().to_string(),
+                chat_id: self.telegram.chat_id.trim().to_string(),
+            },
+            toggl: TogglConfig {
+                api_token: self.toggl.api_token.trim().to_string(),
+                workspace_id: self.toggl.workspace_id.trim().to_string(),
+                project_id: self.toggl.project_id.trim().to_string(),
+                description: self.toggl.description.trim().to_string(),
+            },
+            worklog: WorklogConfig {
+                jira_url: self.worklog.jira_url.trim().to_string(),
+                jira_email: self.worklog.jira_email.trim().to_string(),
+                clockify_workspace_id: self.worklog.clockify_workspace_id.trim().to_string(),
+                task_key: self.worklog.task_key.trim().to_string(),
+                token: self.worklog.token.trim().to_string(),
+                ..self.worklog.clone()
+            },
+            todoist: TodoistConfig {
+                token: self.todoist.token.trim().to_string(),
+                ..self.todoist.clone()
+            },
+            github: GithubConfig {
+                token: self.github.token.trim().to_string(),
+                ..self.github.clone()
+            },
+            daily_log: DailyLog {
+                path: self.daily_log.path.trim().to_string(),
+                format: self.daily_log.format.clone(),
+            },
+            meeting_calendar_url: self.meeting_calendar_url.trim().to_string(),
+            report: ReportConfig {
+                dir: self.report.dir.trim().to_string(),
+                html: self.report.html,
+            },
+            sync_dir: self.sync_dir.trim().to_string(),
+            sync_server: SyncServerConfig {
+                url: self.sync_server.url.trim().to_string(),
+                token: self.sync_server.token.trim().to_string(),
+            },
+        }
+    }
+}
+
+impl FocusMessages {
+    pub fn trimmed(&self) -> Self {
+        let trim = |message: &FocusMessage| FocusMessage {
+            header: message.header.trim().to_string(),
+            text: message.text.trim().to_string(),
+            image_path: message.image_path.trim().to_string(),
+        };
+
+        Self {
+            work: trim(&self.work),
+            short_break: trim(&self.short_break),
+            long_break: trim(&self.long_break),
+        }
+    }
+
+    pub fn get(&self, kind: PeriodKind) -> &FocusMessage {
+        match kind {
+            PeriodKind::Work => &self.work,
+            PeriodKind::ShortBreak => &self.short_break,
+            PeriodKind::LongBreak => &self.long_break,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: PeriodKind) -> &mut FocusMessage {
+        match kind {
+            PeriodKind::Work => &mut self.work,
+            PeriodKind::ShortBreak => &mut self.short_break,
+            PeriodKind::LongBreak => &mut self.long_break,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl OverlayCorner {
+    pub const ALL: [OverlayCorner; 4] = [
+        OverlayCorner::TopLeft,
+        OverlayCorner::TopRight,
+        OverlayCorner::BottomLeft,
+        OverlayCorner::BottomRight,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            OverlayCorner::TopLeft => "top_left",
+            OverlayCorner::TopRight => "top_right",
+            OverlayCorner::BottomLeft => "bottom_left",
+            OverlayCorner::BottomRight => "bottom_right",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|corner| corner.key() == key)
+    }
+}
+
+impl std::fmt::Display for OverlayCorner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OverlayCorner::TopLeft => t("corner-top-left"),
+            OverlayCorner::TopRight => t("corner-top-right"),
+            OverlayCorner::BottomLeft => t("corner-bottom-left"),
+            OverlayCorner::BottomRight => t("corner-bottom-right"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionCriteria {
+    Elapsed,
+    Acknowledged,
+    Confirmed,
+}
+
+impl CompletionCriteria {
+    pub const ALL: [CompletionCriteria; 3] = [
+        CompletionCriteria::Elapsed,
+        CompletionCriteria::Acknowledged,
+        CompletionCriteria::Confirmed,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            CompletionCriteria::Elapsed => "elapsed",
+            CompletionCriteria::Acknowledged => "acknowledged",
+            CompletionCriteria::Confirmed => "confirmed",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|criteria| criteria.key() == key)
+    }
+}
+
+impl std::fmt::Display for CompletionCriteria {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CompletionCriteria::Elapsed => t("completion-elapsed"),
+            CompletionCriteria::Acknowledged => t("completion-acknowledged"),
+            CompletionCriteria::Confirmed => t("completion-confirmed"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenLockAction {
+    Ignore,
+    Pause,
+    PauseAndResume,
+}
+
+impl ScreenLockAction {
+    pub const ALL: [ScreenLockAction; 3] = [
+        ScreenLockAction::Ignore,
+        ScreenLockAction::Pause,
+        ScreenLockAction::PauseAndResume,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            ScreenLockAction::Ignore => "ignore",
+            ScreenLockAction::Pause => "pause",
+            ScreenLockAction::PauseAndResume => "pause_and_resume",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.key() == key)
+    }
+}
+
+impl std::fmt::Display for ScreenLockAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ScreenLockAction::Ignore => t("lock-keep-running"),
+            ScreenLockAction::Pause => t("lock-pause"),
+            ScreenLockAction::PauseAndResume => t("lock-pause-and-resume"),
+        })
+    }
+}
+
+// What becomes of a long break that's due but skipped or reset away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LongBreakSkip {
+    Defer,
+    Forfeit,
+    Shorten,
+}
+
+impl LongBreakSkip {
+    pub const ALL: [LongBreakSkip; 3] = [
+        LongBreakSkip::Defer,
+        LongBreakSkip::Forfeit,
+        LongBreakSkip::Shorten,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            LongBreakSkip::Defer => "defer",
+            LongBreakSkip::Forfeit => "forfeit",
+            LongBreakSkip::Shorten => "shorten",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| policy.key() == key)
+    }
+}
+
+impl std::fmt::Display for LongBreakSkip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LongBreakSkip::Defer => t("long-break-skip-defer"),
+            LongBreakSkip::Forfeit => t("long-break-skip-forfeit"),
+            LongBreakSkip::Shorten => t("long-break-skip-shorten"),
+        })
+    }
+}
+
+// Which monitors the full-screen break window covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakScreenPlacement {
+    AllMonitors,
+    Primary,
+    // The one the main window is on
+    Current,
+}
+
+impl BreakScreenPlacement {
+    pub const ALL: [BreakScreenPlacement; 3] = [
+        BreakScreenPlacement::AllMonitors,
+        BreakScreenPlacement::Primary,
+        BreakScreenPlacement::Current,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            BreakScreenPlacement::AllMonitors => "all",
+            BreakScreenPlacement::Primary => "primary",
+            BreakScreenPlacement::Current => "current",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|placement| placement.key() == key)
+    }
+}
+
+impl std::fmt::Display for BreakScreenPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BreakScreenPlacement::AllMonitors => t("break-screen-all-monitors"),
+            BreakScreenPlacement::Primary => t("break-screen-primary-monitor"),
+            BreakScreenPlacement::Current => t("break-screen-current-monitor"),
+        })
+    }
+}
+
+// What the focus guard does when a blocked app is running during work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusGuard {
+    Off,
+    // Brings the timer window forward
+    Nudge,
+    Minimize,
+}
+
+impl FocusGuard {
+    pub const ALL: [FocusGuard; 3] = [FocusGuard::Off, FocusGuard::Nudge, FocusGuard::Minimize];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            FocusGuard::Off => "off",
+            FocusGuard::Nudge => "nudge",
+            FocusGuard::Minimize => "minimize",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|guard| guard.key() == key)
+    }
+}
+
+impl std::fmt::Display for FocusGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FocusGuard::Off => t("focus-guard-off"),
+            FocusGuard::Nudge => t("focus-guard-nudge"),
+            FocusGuard::Minimize => t("focus-guard-minimize"),
+        })
+    }
+}
+
+// How the time left is shown on the timer screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerFace {
+    Digits,
+    Dial,
+}
+
+impl TimerFace {
+    pub const ALL: [TimerFace; 2] = [TimerFace::Digits, TimerFace::Dial];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            TimerFace::Digits => "digits",
+            TimerFace::Dial => "dial",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|face| face.key() == key)
+    }
+}
+
+impl std::fmt::Display for TimerFace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TimerFace::Digits => t("timer-face-digits"),
+            TimerFace::Dial => t("timer-face-dial"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbientSound {
+    Off,
+    WhiteNoise,
+    PinkNoise,
+    File,
+}
+
+impl AmbientSound {
+    pub const ALL: [AmbientSound; 4] = [
+        AmbientSound::Off,
+        AmbientSound::WhiteNoise,
+        AmbientSound::PinkNoise,
+        AmbientSound::File,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            AmbientSound::Off => "off",
+            AmbientSound::WhiteNoise => "white_noise",
+            AmbientSound::PinkNoise => "pink_noise",
+            AmbientSound::File => "file",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sound| sound.key() == key)
+    }
+}
+
+impl std::fmt::Display for AmbientSound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AmbientSound::Off => t("off"),
+            AmbientSound::WhiteNoise => t("ambient-white-noise"),
+            AmbientSound::PinkNoise => t("ambient-pink-noise"),
+            AmbientSound::File => t("ambient-file"),
+        })
+    }
+}
+
+// Long-break positions within a repeating cycle, e.g. "2,5" means a long
+// break after the 2nd and 5th pomodoro and then the cycle starts over. Kept
+// as a bit mask so that Settings stays Copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CadencePattern(u32);
+
+impl CadencePattern {
+    pub const MAX_POSITION: u32 = u32::BITS;
+
+    // An empty string is a valid, empty pattern; anything unparsable is not.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut mask = 0;
+        for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let position: u32 = part.parse().ok()?;
+            if !(1..=Self::MAX_POSITION).contains(&position) {
+                return None;
+            }
+            mask |= 1 << (position - 1);
+        }
+        Some(Self(mask))
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    fn cycle_length(self) -> u32 {
+        u32::BITS - self.0.leading_zeros()
+    }
+
+    fn contains(self, position: u32) -> bool {
+        (1..=Self::MAX_POSITION).contains(&position) && self.0 & (1 << (position - 1)) != 0
+    }
+}
+
+impl std::fmt::Display for CadencePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let positions: Vec<String> = (1..=Self::MAX_POSITION)
+            .filter(|position| self.contains(*position))
+            .map(|position| position.to_string())
+            .collect();
+        f.write_str(&positions.join(","))
+    }
+}
+
+// Times of day when alarms and notifications keep quiet, e.g.
+// "12:00-13:00, 22:00-07:00", in quarter hours. A range may run past
+// midnight. Kept as a bit mask so that Settings stays Copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuietHours(u128);
+
+impl QuietHours {
+    const SLOT_MINUTES: u32 = 15;
+    const SLOTS: u32 = 24 * 60 / Self::SLOT_MINUTES;
+
+    // An empty string is valid and means no quiet hours. A range that ends
+    // where it starts covers the whole day.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut mask = 0;
+        for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (start, end) = part.split_once('-')?;
+            let (start, end) = (Self::slot(start)?, Self::slot(end)?);
+            let mut slot = start;
+            loop {
+                mask |= 1 << slot;
+                slot = (slot + 1) % Self::SLOTS;
+                if slot == end {
+                    break;
+                }
+            }
+        }
+        Some(Self(mask))
+    }
+
+    // "7", "7:30" or "07:30", on a quarter hour
+    fn slot(time: &str) -> Option<u32> {
+        let (hours, minutes) = match time.trim().split_once(':') {
+            Some((hours, minutes)) => (hours.trim(), minutes.trim().parse::<u32>().ok()?),
+            None => (time.trim(), 0),
+        };
+        let hours: u32 = hours.parse().ok()?;
+        if hours > 24 || minutes >= 60 || !minutes.is_multiple_of(Self::SLOT_MINUTES) {
+            return None;
+        }
+        let minutes = hours * 60 + minutes;
+        (minutes <= 24 * 60).then_some(minutes / Self::SLOT_MINUTES % Self::SLOTS)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, time: NaiveTime) -> bool {
+        let slot = (time.hour() * 60 + time.minute()) / Self::SLOT_MINUTES;
+        self.0 & (1 << slot) != 0
+    }
+
+    fn has_slot(self, slot: u32) -> bool {
+        self.0 & (1 << (slot % Self::SLOTS)) != 0
+    }
+}
+
+impl std::fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let time = |slot: u32| {
+            let minutes = slot % Self::SLOTS * Self::SLOT_MINUTES;
+            format!("{:02}:{:02}", minutes / 60, minutes % 60)
+        };
+        // Ranges are read from a quiet-free slot on, so one running past
+        // midnight stays in one piece
+        let Some(first_free) = (0..Self::SLOTS).find(|slot| !self.has_slot(*slot)) else {
+            return f.write_str("00:00-24:00");
+        };
+        let mut ranges = Vec::new();
+        let mut start = None;
+        for slot in first_free..=first_free + Self::SLOTS {
+            match (start, self.has_slot(slot)) {
+                (None, true) => start = Some(slot),
+                (Some(from), false) => {
+                    ranges.push(format!("{}-{}", time(from), time(slot)));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        f.write_str(&ranges.join(", "))
+    }
+}
+
+// Durations as typed in settings: whole minutes ("25") or MM:SS ("0:45").
+pub fn parse_duration(input: &str) -> Option<u32> {
+    let (minutes, seconds) = match input.trim().split_once(':') {
+        Some((minutes, seconds)) => (minutes.trim(), seconds.trim().parse::<u32>().ok()?),
+        None => (input.trim(), 0),
+    };
+    if seconds >= 60 {
+        return None;
+    }
+    minutes
+        .parse::<u32>()
+        .ok()?
+        .checked_mul(60)?
+        .checked_add(seconds)
+}
+
+pub fn format_duration(seconds: u32) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -10,10 +654,132 @@ pub struct Settings {
     pub short_break_seconds: u32,
     pub long_break_seconds: u32,
     pub long_break_every: u32,
+    pub long_break_pattern: CadencePattern,
+    pub alarm_volume: u32,
+    pub alarm_pattern: AlarmPattern,
+    pub overlay_corner: OverlayCorner,
+    pub overlay_font_size: u32,
+    pub low_power_threshold: u32,
+    pub alarm_repeat_seconds: u32,
+    pub day_start_hour: u32,
+    pub completion_criteria: CompletionCriteria,
+    pub tick_volume: u32,
+    pub ambient_sound: AmbientSound,
+    pub ambient_volume: u32,
+    pub break_screen: bool,
+    pub strict_mode: bool,
+    pub screen_lock_action: ScreenLockAction,
+    pub always_on_top: bool,
+    pub start_minimized: bool,
+    pub close_to_tray: bool,
+    pub minimize_to_tray: bool,
+    pub api_port: u32,
+    pub language: Language,
+    pub announce_transitions: bool,
+    pub palette: PaletteKind,
+    // Percent, applied to everything in the timer window
+    pub ui_scale: u32,
+    // How long snoozing a finished pomodoro keeps working, or silences the
+    // alarm after a break
+    pub snooze_minutes: u32,
+    // 20-20-20 reminders: every 20 minutes of work, look 20 feet away for
+    // 20 seconds
+    pub eye_breaks: bool,
+    // Ask for a short focus intention when a pomodoro starts
+    pub ask_intention: bool,
+    // A second work length to alternate with day by day, 0 for none
+    pub experiment_work_seconds: u32,
+    pub long_break_skip: LongBreakSkip,
+    // How much the break button lengthens a running break, 0 for no button
+    pub break_extension_minutes: u32,
+    // Added to a break for each pomodoro beyond the first finished without
+    // one, 0 to keep breaks their usual length
+    pub adaptive_break_minutes: u32,
+    pub quiet_hours: QuietHours,
+    pub shortcuts: Shortcuts,
+    pub timer_face: TimerFace,
+    // How much a background image or gradient is dimmed, in percent
+    pub background_dim: u32,
+    pub break_screen_placement: BreakScreenPlacement,
+    // Hold off screen blanking and locking during work periods
+    pub keep_screen_on: bool,
+    // Silence the desktop's notifications during work periods
+    pub do_not_disturb: bool,
+    pub focus_guard: FocusGuard,
+    // Sample the front window's title during work, kept on this machine
+    pub track_window_titles: bool,
+    // Start work when an editor reports activity, and mark pomodoros
+    // without any as idle
+    pub editor_heartbeats: bool,
 }
 
 impl Settings {
+    pub const DEFAULT_AMBIENT_VOLUME: u32 = 50;
+
     pub const DEFAULT_LONG_BREAK_EVERY: u32 = 4;
+    pub const DEFAULT_ALARM_VOLUME: u32 = 100;
+    pub const DEFAULT_OVERLAY_FONT_SIZE: u32 = 32;
+
+    // During a length experiment, alternate days use the second work length
+    pub fn work_seconds_on(&self, day: NaiveDate) -> u32 {
+        crate::experiment::work_seconds(self.work_seconds, self.experiment_work_seconds, day)
+    }
+
+    // A pattern, when set, replaces the plain "every N pomodoros" cadence.
+    // A long break cadence of 0 turns long breaks off, pattern and all
+    pub fn is_long_break_after(&self, work_periods: u32) -> bool {
+        if self.long_break_every == 0 {
+            return false;
+        }
+        if self.long_break_pattern.is_empty() {
+            return work_periods.is_multiple_of(self.long_break_every);
+        }
+        if work_periods == 0 {
+            return false;
+        }
+        let cycle = self.long_break_pattern.cycle_length();
+        self.long_break_pattern
+            .contains((work_periods - 1) % cycle + 1)
+    }
+
+    pub fn long_breaks_preview(&self, count: usize) -> Vec<u32> {
+        (1..=CadencePattern::MAX_POSITION * 2)
+            .filter(|work_periods| self.is_long_break_after(*work_periods))
+            .take(count)
+            .collect()
+    }
+
+    pub fn alarm_volume_level(&self) -> f32 {
+        self.alarm_volume.min(100) as f32 / 100.0
+    }
+
+    pub fn tick_volume_level(&self) -> f32 {
+        self.tick_volume.min(100) as f32 / 100.0
+    }
+
+    pub fn ambient_volume_level(&self) -> f32 {
+        self.ambient_volume.min(100) as f32 / 100.0
+    }
+
+    // Settings for a brand-new database. Older builds had no settings screen
+    // and only the WORK_LENGTH/BREAK_LENGTH/LONG_BREAK_LENGTH constants, so
+    // environment overrides of those (in seconds) are honoured on first run.
+    pub fn first_run() -> Self {
+        let seconds = |name: &str| {
+            std::env::var(format!("POMODORO_{name}"))
+                .ok()
+                .and_then(|value| value.trim().parse::<u32>().ok())
+                .filter(|value| *value > 0)
+        };
+        let defaults = Self::default();
+
+        Self {
+            work_seconds: seconds("WORK_LENGTH").unwrap_or(defaults.work_seconds),
+            short_break_seconds: seconds("BREAK_LENGTH").unwrap_or(defaults.short_break_seconds),
+            long_break_seconds: seconds("LONG_BREAK_LENGTH").unwrap_or(defaults.long_break_seconds),
+            ..defaults
+        }
+    }
 }
 
 impl Default for Settings {
@@ -23,47 +789,467 @@ impl Default for Settings {
             short_break_seconds: super::BREAK_LENGTH,
             long_break_seconds: super::LONG_BREAK_LENGTH,
             long_break_every: Self::DEFAULT_LONG_BREAK_EVERY,
+            long_break_pattern: CadencePattern::default(),
+            alarm_volume: Self::DEFAULT_ALARM_VOLUME,
+            alarm_pattern: AlarmPattern::default(),
+            overlay_corner: OverlayCorner::TopRight,
+            overlay_font_size: Self::DEFAULT_OVERLAY_FONT_SIZE,
+            low_power_threshold: 0,
+            alarm_repeat_seconds: 0,
+            day_start_hour: 0,
+            completion_criteria: CompletionCriteria::Elapsed,
+            tick_volume: 0,
+            ambient_sound: AmbientSound::Off,
+            ambient_volume: Self::DEFAULT_AMBIENT_VOLUME,
+            break_screen: false,
+            strict_mode: false,
+            screen_lock_action: ScreenLockAction::Pause,
+            always_on_top: false,
+            start_minimized: false,
+            close_to_tray: false,
+            minimize_to_tray: false,
+            api_port: 0,
+            language: Language::System,
+            announce_transitions: false,
+            palette: PaletteKind::Standard,
+            ui_scale: 100,
+            snooze_minutes: 5,
+            eye_breaks: false,
+            ask_intention: false,
+            experiment_work_seconds: 0,
+            long_break_skip: LongBreakSkip::Forfeit,
+            break_extension_minutes: 0,
+            adaptive_break_minutes: 0,
+            quiet_hours: QuietHours::default(),
+            shortcuts: Shortcuts::default(),
+            timer_face: TimerFace::Digits,
+            background_dim: 60,
+            break_screen_placement: BreakScreenPlacement::Current,
+            keep_screen_on: true,
+            do_not_disturb: false,
+            focus_guard: FocusGuard::Off,
+            track_window_titles: false,
+            editor_heartbeats: false,
+        }
+    }
+}
+
+// Where the main window was last left. The position is unknown on Wayland,
+// which doesn't report it, and the compositor places the window instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub position: Option<Point>,
+    pub size: Size,
+}
+
+impl WindowGeometry {
+    pub const DEFAULT_SIZE: Size = Size::new(600.0, 500.0);
+    pub const MIN_SIZE: Size = Size::new(320.0, 240.0);
+    // Where Windows parks a minimized window
+    const PARKED: f32 = -32000.0;
+    // How much of the title bar must be on a monitor to drag the window back
+    const GRIP: f32 = 40.0;
+
+    pub fn is_parked(position: Point) -> bool {
+        position.x <= Self::PARKED || position.y <= Self::PARKED
+    }
+
+    // Forgets a position that would open the window off screen, e.g. on a
+    // monitor that has since been unplugged, so the system places it
+    // instead. With no monitors to go by, only a parked position is.
+    pub fn on_screen(self, monitors: &[Monitor]) -> Self {
+        let Some(position) = self.position else {
+            return self;
+        };
+        let title_bar = position.y + Self::GRIP / 2.0;
+        let grabbable = |monitor: &Monitor| {
+            let left = position.x.max(monitor.position.x);
+            let right = (position.x + self.size.width).min(monitor.position.x + monitor.size.width);
+            right - left >= Self::GRIP
+                && title_bar >= monitor.position.y
+                && title_bar < monitor.position.y + monitor.size.height
+        };
+        let visible =
+            !Self::is_parked(position) && (monitors.is_empty() || monitors.iter().any(grabbable));
+        if visible {
+            self
+        } else {
+            Self {
+                position: None,
+                ..self
+            }
+        }
+    }
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            position: None,
+            size: Self::DEFAULT_SIZE,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct SettingsDraft {
-    pub work_minutes: String,
-    pub short_break_minutes: String,
-    pub long_break_minutes: String,
-    pub long_break_every: String,
+    pub work_duration: String,
+    pub experiment: bool,
+    pub experiment_duration: String,
+    pub short_break_duration: String,
+    pub long_break_duration: String,
+    pub long_break_every: u32,
+    pub long_break_pattern: String,
+    pub adaptive_break_minutes: u32,
+    pub alarm_volume: String,
+    pub alarm_tones: String,
+    pub alarm_beeps: u32,
+    pub alarm_beep_ms: String,
+    pub alarm_gap_ms: String,
+    pub alarm_length_ms: String,
+    pub tick_volume: String,
+    pub ambient_sound: AmbientSound,
+    pub ambient_volume: String,
+    pub ambient_path: String,
+    pub countdown_font: String,
+    pub integrations: Integrations,
+    pub break_screen: bool,
+    pub strict_mode: bool,
+    pub screen_lock_action: ScreenLockAction,
+    pub long_break_skip: LongBreakSkip,
+    pub quiet_hours: String,
+    pub shortcuts: Shortcuts,
+    pub timer_face: TimerFace,
+    // Kept with the focus messages rather than in `Settings`, and filled in
+    // by the caller
+    pub backgrounds: Backgrounds,
+    pub background_dim: u32,
+    pub break_screen_placement: BreakScreenPlacement,
+    pub keep_screen_on: bool,
+    pub do_not_disturb: bool,
+    pub focus_guard: FocusGuard,
+    // Kept in their own table, and filled in by the caller
+    pub guarded_apps: Vec<GuardedApp>,
+    pub new_guarded_app: String,
+    pub track_window_titles: bool,
+    pub editor_heartbeats: bool,
+    // Also kept in their own table, "; " between patterns
+    pub distracting_titles: String,
+    pub always_on_top: bool,
+    pub start_minimized: bool,
+    pub close_to_tray: bool,
+    pub minimize_to_tray: bool,
+    pub api_port: String,
+    pub language: Language,
+    pub announce_transitions: bool,
+    pub palette: PaletteKind,
+    pub ui_scale: String,
+    pub snooze_minutes: u32,
+    pub break_extension_minutes: u32,
+    pub eye_breaks: bool,
+    pub ask_intention: bool,
+    pub overlay_corner: OverlayCorner,
+    pub overlay_font_size: String,
+    pub low_power_threshold: String,
+    pub alarm_repeat_seconds: String,
+    pub day_start_hour: String,
+    pub completion_criteria: CompletionCriteria,
+    pub focus_messages: FocusMessages,
+    pub remote_sources: String,
+    pub holidays: String,
 }
 
 impl SettingsDraft {
-    pub fn from_settings(settings: Settings) -> Self {
+    pub fn from_settings(
+        settings: Settings,
+        focus_messages: &FocusMessages,
+        remote_sources: &[String],
+        holidays: &[NaiveDate],
+        ambient_path: &str,
+        countdown_font: &str,
+        integrations: &Integrations,
+    ) -> Self {
         Self {
-            work_minutes: (settings.work_seconds / 60).to_string(),
-            short_break_minutes: (settings.short_break_seconds / 60).to_string(),
-            long_break_minutes: (settings.long_break_seconds / 60).to_string(),
-            long_break_every: settings.long_break_every.to_string(),
+            work_duration: format_duration(settings.work_seconds),
+            experiment: settings.experiment_work_seconds > 0,
+            experiment_duration: format_duration(match settings.experiment_work_seconds {
+                0 => settings.work_seconds,
+                seconds => seconds,
+            }),
+            short_break_duration: format_duration(settings.short_break_seconds),
+            long_break_duration: format_duration(settings.long_break_seconds),
+            long_break_every: settings.long_break_every,
+            adaptive_break_minutes: settings.adaptive_break_minutes,
+            long_break_pattern: settings.long_break_pattern.to_string(),
+            alarm_volume: settings.alarm_volume.to_string(),
+            alarm_tones: settings
+                .alarm_pattern
+                .tones()
+                .iter()
+                .map(u16::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            alarm_beeps: settings.alarm_pattern.beeps,
+            alarm_beep_ms: settings.alarm_pattern.beep_ms.to_string(),
+            alarm_gap_ms: settings.alarm_pattern.gap_ms.to_string(),
+            alarm_length_ms: settings.alarm_pattern.length_ms.to_string(),
+            tick_volume: settings.tick_volume.to_string(),
+            ambient_sound: settings.ambient_sound,
+            ambient_volume: settings.ambient_volume.to_string(),
+            ambient_path: ambient_path.to_string(),
+            countdown_font: countdown_font.to_string(),
+            integrations: integrations.clone(),
+            break_screen: settings.break_screen,
+            strict_mode: settings.strict_mode,
+            screen_lock_action: settings.screen_lock_action,
+            long_break_skip: settings.long_break_skip,
+            quiet_hours: settings.quiet_hours.to_string(),
+            shortcuts: settings.shortcuts,
+            timer_face: settings.timer_face,
+            backgrounds: Backgrounds::default(),
+            background_dim: settings.background_dim,
+            break_screen_placement: settings.break_screen_placement,
+            keep_screen_on: settings.keep_screen_on,
+            do_not_disturb: settings.do_not_disturb,
+            focus_guard: settings.focus_guard,
+            guarded_apps: Vec::new(),
+            new_guarded_app: String::new(),
+            track_window_titles: settings.track_window_titles,
+            editor_heartbeats: settings.editor_heartbeats,
+            distracting_titles: String::new(),
+            always_on_top: settings.always_on_top,
+            start_minimized: settings.start_minimized,
+            close_to_tray: settings.close_to_tray,
+            minimize_to_tray: settings.minimize_to_tray,
+            api_port: settings.api_port.to_string(),
+            language: settings.language,
+            announce_transitions: settings.announce_transitions,
+            palette: settings.palette,
+            ui_scale: settings.ui_scale.to_string(),
+            snooze_minutes: settings.snooze_minutes,
+            break_extension_minutes: settings.break_extension_minutes,
+            eye_breaks: settings.eye_breaks,
+            ask_intention: settings.ask_intention,
+            overlay_corner: settings.overlay_corner,
+            overlay_font_size: settings.overlay_font_size.to_string(),
+            low_power_threshold: settings.low_power_threshold.to_string(),
+            alarm_repeat_seconds: settings.alarm_repeat_seconds.to_string(),
+            day_start_hour: settings.day_start_hour.to_string(),
+            completion_criteria: settings.completion_criteria,
+            focus_messages: focus_messages.clone(),
+            remote_sources: remote_sources.join("; "),
+            holidays: holidays
+                .iter()
+                .map(|day| day.format("%Y-%m-%d").to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
         }
     }
 
     pub fn parse(&self) -> Option<Settings> {
-        let work_minutes: u32 = self.work_minutes.trim().parse().ok()?;
-        let short_break_minutes: u32 = self.short_break_minutes.trim().parse().ok()?;
-        let long_break_minutes: u32 = self.long_break_minutes.trim().parse().ok()?;
-        let long_break_every: u32 = self.long_break_every.trim().parse().ok()?;
-
-        if work_minutes == 0
-            || short_break_minutes == 0
-            || long_break_minutes == 0
-            || long_break_every == 0
+        let work_seconds = parse_duration(&self.work_duration)?;
+        let short_break_seconds = parse_duration(&self.short_break_duration)?;
+        let long_break_seconds = parse_duration(&self.long_break_duration)?;
+        let long_break_pattern = CadencePattern::parse(&self.long_break_pattern)?;
+        let quiet_hours = QuietHours::parse(&self.quiet_hours)?;
+        self.backgrounds.is_valid().then_some(())?;
+        let alarm_volume: u32 = self.alarm_volume.trim().parse().ok()?;
+        let alarm_pattern = self.parse_alarm_pattern()?;
+        let tick_volume: u32 = self.tick_volume.trim().parse().ok()?;
+        let ambient_volume: u32 = self.ambient_volume.trim().parse().ok()?;
+        let overlay_font_size: u32 = self.overlay_font_size.trim().parse().ok()?;
+        let low_power_threshold: u32 = self.low_power_threshold.trim().parse().ok()?;
+        let alarm_repeat_seconds: u32 = self.alarm_repeat_seconds.trim().parse().ok()?;
+        let day_start_hour: u32 = self.day_start_hour.trim().parse().ok()?;
+        let api_port: u32 = self.api_port.trim().parse().ok()?;
+        let ui_scale: u32 = self.ui_scale.trim().parse().ok()?;
+        let experiment_work_seconds = match self.experiment {
+            true => parse_duration(&self.experiment_duration).filter(|seconds| *seconds > 0)?,
+            false => 0,
+        };
+
+        if work_seconds == 0
+            || short_break_seconds == 0
+            || long_break_seconds == 0
+            || alarm_volume > 100
+            || tick_volume > 100
+            || ambient_volume > 100
+            || (self.ambient_sound == AmbientSound::File && self.ambient_path.trim().is_empty())
+            || low_power_threshold > 100
+            || day_start_hour > 23
+            || api_port > u16::MAX as u32
+            || !(8..=200).contains(&overlay_font_size)
+            || !(80..=200).contains(&ui_scale)
         {
             return None;
         }
 
         Some(Settings {
-            work_seconds: work_minutes.saturating_mul(60),
-            short_break_seconds: short_break_minutes.saturating_mul(60),
-            long_break_seconds: long_break_minutes.saturating_mul(60),
-            long_break_every,
+            work_seconds,
+            short_break_seconds,
+            long_break_seconds,
+            long_break_every: self.long_break_every,
+            long_break_pattern,
+            alarm_volume,
+            alarm_pattern,
+            overlay_corner: self.overlay_corner,
+            overlay_font_size,
+            low_power_threshold,
+            alarm_repeat_seconds,
+            day_start_hour,
+            completion_criteria: self.completion_criteria,
+            tick_volume,
+            ambient_sound: self.ambient_sound,
+            ambient_volume,
+            break_screen: self.break_screen,
+            strict_mode: self.strict_mode,
+            screen_lock_action: self.screen_lock_action,
+            always_on_top: self.always_on_top,
+            start_minimized: self.start_minimized,
+            close_to_tray: self.close_to_tray,
+            minimize_to_tray: self.minimize_to_tray,
+            api_port,
+            language: self.language,
+            announce_transitions: self.announce_transitions,
+            palette: self.palette,
+            ui_scale,
+            snooze_minutes: self.snooze_minutes,
+            eye_breaks: self.eye_breaks,
+            ask_intention: self.ask_intention,
+            experiment_work_seconds,
+            long_break_skip: self.long_break_skip,
+            break_extension_minutes: self.break_extension_minutes,
+            adaptive_break_minutes: self.adaptive_break_minutes,
+            quiet_hours,
+            shortcuts: self.shortcuts,
+            timer_face: self.timer_face,
+            background_dim: self.background_dim,
+            break_screen_placement: self.break_screen_placement,
+            keep_screen_on: self.keep_screen_on,
+            do_not_disturb: self.do_not_disturb,
+            focus_guard: self.focus_guard,
+            track_window_titles: self.track_window_titles,
+            editor_heartbeats: self.editor_heartbeats,
         })
     }
+
+    pub fn parse_alarm_pattern(&self) -> Option<AlarmPattern> {
+        AlarmPattern::new(
+            &AlarmPattern::parse_tones(&self.alarm_tones)?,
+            self.alarm_beeps,
+            self.alarm_beep_ms.trim().parse().ok()?,
+            self.alarm_gap_ms.trim().parse().ok()?,
+            self.alarm_length_ms.trim().parse().ok()?,
+        )
+    }
+
+    pub fn parse_holidays(&self) -> Option<Vec<NaiveDate>> {
+        self.holidays
+            .split(';')
+            .map(str::trim)
+            .filter(|day| !day.is_empty())
+            .map(crate::streak::parse_day)
+            .collect()
+    }
+
+    pub fn parse_remote_sources(&self) -> Vec<String> {
+        self.remote_sources
+            .split(';')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn parse_distracting_titles(&self) -> Vec<String> {
+        self.distracting_titles
+            .split(';')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::{
+        CadencePattern, QuietHours, Settings, WindowGeometry, format_duration, parse_duration,
+    };
+    use crate::platform::monitors::Monitor;
+    use chrono::NaiveTime;
+    use iced::{Point, Size};
+
+    #[test]
+    fn windows_reopen_on_a_connected_monitor() {
+        let monitors = [Monitor {
+            position: Point::ORIGIN,
+            size: Size::new(1920.0, 1080.0),
+            primary: true,
+        }];
+        let at = |x, y| WindowGeometry {
+            position: Some(Point::new(x, y)),
+            size: WindowGeometry::DEFAULT_SIZE,
+        };
+
+        assert_eq!(at(100.0, 100.0).on_screen(&monitors), at(100.0, 100.0));
+        // Mostly off the left edge, but the title bar can still be grabbed
+        assert_eq!(at(-500.0, 10.0).on_screen(&monitors), at(-500.0, 10.0));
+        // Left on a second monitor that is gone
+        assert_eq!(at(2500.0, 200.0).on_screen(&monitors).position, None);
+        // Closed while minimized on Windows
+        assert_eq!(at(-32000.0, -32000.0).on_screen(&monitors).position, None);
+        assert_eq!(at(-32000.0, -32000.0).on_screen(&[]).position, None);
+        assert_eq!(at(2500.0, 200.0).on_screen(&[]), at(2500.0, 200.0));
+    }
+
+    #[test]
+    fn cadence_pattern_repeats_after_its_last_position() {
+        let settings = Settings {
+            long_break_pattern: CadencePattern::parse("2, 5").unwrap(),
+            ..Settings::default()
+        };
+        assert_eq!(settings.long_breaks_preview(4), vec![2, 5, 7, 10]);
+        assert_eq!(settings.long_break_pattern.to_string(), "2,5");
+
+        assert!(CadencePattern::parse("").unwrap().is_empty());
+        assert!(CadencePattern::parse("0").is_none());
+        assert!(CadencePattern::parse("2,x").is_none());
+
+        let never = Settings {
+            long_break_every: 0,
+            ..settings
+        };
+        assert!(never.long_breaks_preview(4).is_empty());
+    }
+
+    #[test]
+    fn quiet_hours_cover_their_ranges_and_run_past_midnight() {
+        let quiet = QuietHours::parse("12:00-13:00, 22:00-7").unwrap();
+        let at = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        assert!(quiet.contains(at(12, 30)));
+        assert!(!quiet.contains(at(13, 0)));
+        assert!(quiet.contains(at(23, 59)));
+        assert!(quiet.contains(at(6, 45)));
+        assert!(!quiet.contains(at(7, 0)));
+        assert_eq!(quiet.to_string(), "12:00-13:00, 22:00-07:00");
+
+        assert!(QuietHours::parse("").unwrap().is_empty());
+        assert_eq!(
+            QuietHours::parse("0-24").unwrap().to_string(),
+            "00:00-24:00"
+        );
+        assert!(QuietHours::parse("7:10-8").is_none());
+        assert!(QuietHours::parse("lunch").is_none());
+    }
+
+    #[test]
+    fn durations_take_minutes_or_minutes_and_seconds() {
+        assert_eq!(parse_duration("25"), Some(1500));
+        assert_eq!(parse_duration(" 0:45 "), Some(45));
+        assert_eq!(parse_duration("52:30"), Some(3150));
+        assert_eq!(parse_duration("5:60"), None);
+        assert_eq!(parse_duration("five"), None);
+        assert_eq!(format_duration(3150), "52:30");
+    }
 }
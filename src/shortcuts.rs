@@ -0,0 +1,320 @@
+use crate::i18n::t;
+use iced::keyboard::{self, Key, Modifiers, key::Named};
+
+// Keys that reach the timer when no text input has taken them, so the
+// main actions work without a mouse. Buttons can't take keyboard focus in
+// iced, so Tab only moves between text inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    FocusNext,
+    FocusPrevious,
+    StartStop,
+    Reset,
+    Skip,
+    // Stops a ringing alarm, or leaves the current screen
+    Back,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 6] = [
+        Shortcut::StartStop,
+        Shortcut::Reset,
+        Shortcut::Skip,
+        Shortcut::Back,
+        Shortcut::FocusNext,
+        Shortcut::FocusPrevious,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Shortcut::FocusNext => "focus_next",
+            Shortcut::FocusPrevious => "focus_previous",
+            Shortcut::StartStop => "start_stop",
+            Shortcut::Reset => "reset",
+            Shortcut::Skip => "skip",
+            Shortcut::Back => "back",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|shortcut| shortcut.key() == key)
+    }
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|shortcut| *shortcut == self)
+            .unwrap_or_default()
+    }
+
+    pub fn default_binding(self) -> Binding {
+        match self {
+            Shortcut::FocusNext => Binding::named(Named::Tab, Modifiers::empty()),
+            Shortcut::FocusPrevious => Binding::named(Named::Tab, Modifiers::SHIFT),
+            Shortcut::StartStop => Binding::named(Named::Space, Modifiers::empty()),
+            Shortcut::Reset => Binding::character('r'),
+            Shortcut::Skip => Binding::character('s'),
+            Shortcut::Back => Binding::named(Named::Escape, Modifiers::empty()),
+        }
+    }
+}
+
+impl std::fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shortcut::FocusNext => t("shortcut-focus-next"),
+            Shortcut::FocusPrevious => t("shortcut-focus-previous"),
+            Shortcut::StartStop => t("shortcut-start-stop"),
+            Shortcut::Reset => t("shortcut-reset"),
+            Shortcut::Skip => t("shortcut-skip"),
+            Shortcut::Back => t("shortcut-back"),
+        })
+    }
+}
+
+// The named keys that can be bound, with the names they are saved and
+// shown under
+const NAMED_KEYS: [(Named, &str); 27] = [
+    (Named::Tab, "Tab"),
+    (Named::Space, "Space"),
+    (Named::Escape, "Esc"),
+    (Named::Enter, "Enter"),
+    (Named::Backspace, "Backspace"),
+    (Named::Delete, "Delete"),
+    (Named::Insert, "Insert"),
+    (Named::Home, "Home"),
+    (Named::End, "End"),
+    (Named::PageUp, "PageUp"),
+    (Named::PageDown, "PageDown"),
+    (Named::ArrowUp, "Up"),
+    (Named::ArrowDown, "Down"),
+    (Named::ArrowLeft, "Left"),
+    (Named::ArrowRight, "Right"),
+    (Named::F1, "F1"),
+    (Named::F2, "F2"),
+    (Named::F3, "F3"),
+    (Named::F4, "F4"),
+    (Named::F5, "F5"),
+    (Named::F6, "F6"),
+    (Named::F7, "F7"),
+    (Named::F8, "F8"),
+    (Named::F9, "F9"),
+    (Named::F10, "F10"),
+    (Named::F11, "F11"),
+    (Named::F12, "F12"),
+];
+
+const MODIFIER_NAMES: [(Modifiers, &str); 4] = [
+    (Modifiers::CTRL, "Ctrl"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::LOGO, "Super"),
+    (Modifiers::SHIFT, "Shift"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BoundKey {
+    Named(Named),
+    // Always lowercase, with Shift kept in the modifiers
+    Character(char),
+}
+
+// A key with the modifiers held down for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    key: BoundKey,
+    modifiers: Modifiers,
+}
+
+impl Binding {
+    fn named(key: Named, modifiers: Modifiers) -> Self {
+        Self {
+            key: BoundKey::Named(key),
+            modifiers,
+        }
+    }
+
+    fn character(key: char) -> Self {
+        Self {
+            key: BoundKey::Character(key),
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    // Key presses that could be bound. Modifier keys on their own and
+    // named keys outside the list above give nothing.
+    pub fn from_event(event: keyboard::Event) -> Option<Binding> {
+        let keyboard::Event::KeyPressed { key, modifiers, .. } = event else {
+            return None;
+        };
+        let key = match key.as_ref() {
+            Key::Named(named) => NAMED_KEYS
+                .iter()
+                .any(|(known, _)| *known == named)
+                .then_some(BoundKey::Named(named))?,
+            Key::Character(text) => {
+                let mut chars = text.chars().flat_map(char::to_lowercase);
+                match (chars.next(), chars.next()) {
+                    (Some(key), None) => BoundKey::Character(key),
+                    _ => return None,
+                }
+            }
+            Key::Unidentified => return None,
+        };
+        let modifiers = MODIFIER_NAMES
+            .iter()
+            .filter(|(modifier, _)| modifiers.contains(*modifier))
+            .fold(Modifiers::empty(), |all, (modifier, _)| all | *modifier);
+        Some(Self { key, modifiers })
+    }
+
+    // The form Display writes, in any case: "Space", "Ctrl+Shift+R"
+    pub fn parse(text: &str) -> Option<Binding> {
+        let mut rest = text.trim();
+        let mut modifiers = Modifiers::empty();
+        'modifiers: loop {
+            for (modifier, name) in MODIFIER_NAMES {
+                let prefix = rest.get(..name.len() + 1);
+                if prefix.is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{name}+")))
+                    && rest.len() > name.len() + 1
+                {
+                    modifiers |= modifier;
+                    rest = &rest[name.len() + 1..];
+                    continue 'modifiers;
+                }
+            }
+            break;
+        }
+        let mut chars = rest.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(key), None) => BoundKey::Character(key.to_lowercase().next()?),
+            _ => NAMED_KEYS
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(rest))
+                .map(|(named, _)| BoundKey::Named(*named))?,
+        };
+        Some(Self { key, modifiers })
+    }
+}
+
+impl std::fmt::Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match self.key {
+            BoundKey::Named(named) => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|(known, _)| *known == named)
+                    .map(|(_, name)| *name)
+                    .unwrap_or_default();
+                f.write_str(name)
+            }
+            BoundKey::Character(key) => write!(f, "{}", key.to_uppercase()),
+        }
+    }
+}
+
+// The binding for each shortcut, in the order of Shortcut::ALL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcuts([Binding; 6]);
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self(Shortcut::ALL.map(Shortcut::default_binding))
+    }
+}
+
+impl Shortcuts {
+    pub fn get(&self, shortcut: Shortcut) -> Binding {
+        self.0[shortcut.index()]
+    }
+
+    pub fn find(&self, binding: Binding) -> Option<Shortcut> {
+        Shortcut::ALL
+            .into_iter()
+            .find(|shortcut| self.get(*shortcut) == binding)
+    }
+
+    // Gives the shortcut already bound to the key instead of binding it twice
+    pub fn set(&mut self, shortcut: Shortcut, binding: Binding) -> Result<(), Shortcut> {
+        match self.find(binding) {
+            Some(other) if other != shortcut => Err(other),
+            _ => {
+                self.0[shortcut.index()] = binding;
+                Ok(())
+            }
+        }
+    }
+
+    // One "shortcut=binding" per line. Shortcuts left out, unreadable, or
+    // bound to a key another line already took keep their default.
+    pub fn parse(text: &str) -> Self {
+        let mut shortcuts = Self::default();
+        let mut bound = Vec::new();
+        for line in text.lines() {
+            let Some((shortcut, binding)) = line.split_once('=') else {
+                continue;
+            };
+            if let (Some(shortcut), Some(binding)) =
+                (Shortcut::from_key(shortcut.trim()), Binding::parse(binding))
+                && !bound.contains(&binding)
+            {
+                shortcuts.0[shortcut.index()] = binding;
+                bound.push(binding);
+            }
+        }
+        // A default still in place may clash with a key taken by another
+        // shortcut; put that one back to its default too
+        if Shortcut::ALL
+            .iter()
+            .any(|shortcut| shortcuts.find(shortcuts.get(*shortcut)) != Some(*shortcut))
+        {
+            return Self::default();
+        }
+        shortcuts
+    }
+}
+
+impl std::fmt::Display for Shortcuts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for shortcut in Shortcut::ALL {
+            writeln!(f, "{}={}", shortcut.key(), self.get(shortcut))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod shortcuts_tests {
+    use super::*;
+
+    #[test]
+    fn bindings_round_trip_and_refuse_keys_already_taken() {
+        assert_eq!(
+            Binding::parse("ctrl+shift+R").map(|binding| binding.to_string()),
+            Some("Ctrl+Shift+R".to_string())
+        );
+        assert_eq!(
+            Binding::parse("Ctrl++").map(|binding| binding.to_string()),
+            Some("Ctrl++".to_string())
+        );
+        assert_eq!(Binding::parse("Hyper+R"), None);
+
+        let mut shortcuts = Shortcuts::default();
+        let enter = Binding::parse("Enter").unwrap();
+        assert_eq!(shortcuts.set(Shortcut::StartStop, enter), Ok(()));
+        assert_eq!(
+            shortcuts.set(Shortcut::Reset, enter),
+            Err(Shortcut::StartStop)
+        );
+        assert_eq!(shortcuts.find(enter), Some(Shortcut::StartStop));
+        assert_eq!(Shortcuts::parse(&shortcuts.to_string()), shortcuts);
+
+        // A line that would bind one key twice is dropped
+        let clashing = Shortcuts::parse("reset=Space");
+        assert_eq!(clashing, Shortcuts::default());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::i18n::t;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+
+// Longer ranges are charted by week
+const MAX_DAILY_BARS: i64 = 31;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayTotals {
+    pub day: NaiveDate,
+    pub pomodoros: u32,
+    pub focus_seconds: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagFilter {
+    All,
+    Untagged,
+    Tag(String),
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagFilter::All => write!(f, "{}", t("stats-all-tags")),
+            TagFilter::Untagged => write!(f, "{}", t("stats-untagged")),
+            TagFilter::Tag(tag) => write!(f, "{}", tag),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsDraft {
+    pub from: String,
+    pub to: String,
+    pub tag: TagFilter,
+}
+
+impl StatsDraft {
+    // The `days` days up to and including `today`
+    pub fn last_days(days: i64, today: NaiveDate, tag: TagFilter) -> Self {
+        Self {
+            from: (today - Duration::days(days - 1)).to_string(),
+            to: today.to_string(),
+            tag,
+        }
+    }
+
+    pub fn parse(&self) -> Option<(NaiveDate, NaiveDate, TagFilter)> {
+        let from = crate::streak::parse_day(&self.from)?;
+        let to = crate::streak::parse_day(&self.to)?;
+        (from <= to).then_some((from, to, self.tag.clone()))
+    }
+}
+
+impl Default for StatsDraft {
+    fn default() -> Self {
+        Self {
+            from: String::new(),
+            to: String::new(),
+            tag: TagFilter::All,
+        }
+    }
+}
+
+// Chart bars from `from` to `to`: one per day, or one per week (labelled
+// by its Monday) when the range is too long for daily bars.
+pub fn bars(days: &[DayTotals], from: NaiveDate, to: NaiveDate) -> Vec<(NaiveDate, u32)> {
+    let weekly = (to - from).num_days() >= MAX_DAILY_BARS;
+    let bucket = |day: NaiveDate| {
+        if weekly {
+            day - Duration::days(day.weekday().num_days_from_monday() as i64)
+        } else {
+            day
+        }
+    };
+
+    let mut bars: Vec<(NaiveDate, u32)> = Vec::new();
+    for day in from.iter_days().take_while(|day| *day <= to) {
+        let start = bucket(day);
+        if bars.last().map(|(last, _)| *last) != Some(start) {
+            bars.push((start, 0));
+        }
+    }
+    for totals in days {
+        let start = bucket(totals.day);
+        if let Some((_, pomodoros)) = bars.iter_mut().find(|(day, _)| *day == start) {
+            *pomodoros += totals.pomodoros;
+        }
+    }
+    bars
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    #[test]
+    fn long_ranges_are_charted_by_week() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        let totals = |d: u32, pomodoros: u32| DayTotals {
+            day: day(d),
+            pomodoros,
+            focus_seconds: pomodoros * 1500,
+        };
+        let days = [totals(27, 3), totals(29, 2), totals(31, 4)];
+
+        assert_eq!(
+            bars(&days, day(27), day(31)),
+            [
+                (day(27), 3),
+                (day(28), 0),
+                (day(29), 2),
+                (day(30), 0),
+                (day(31), 4)
+            ]
+        );
+        // Wed 24 April to Fri 31 May, in weeks starting on Mondays
+        let april = NaiveDate::from_ymd_opt(2024, 4, 24).unwrap();
+        let weekly = bars(&days, april, day(31));
+        assert_eq!(weekly.len(), 6);
+        assert_eq!(
+            weekly[0],
+            (NaiveDate::from_ymd_opt(2024, 4, 22).unwrap(), 0)
+        );
+        assert_eq!(weekly[5], (day(27), 9));
+    }
+}
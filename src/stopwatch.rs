@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+// Free focus: counts up from zero until the session is finished by hand.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stopwatch {
+    // Time banked before the last pause
+    banked: Duration,
+    running_since: Option<Instant>,
+}
+
+impl Stopwatch {
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        self.banked
+            + self
+                .running_since
+                .map(|since| now.saturating_duration_since(since))
+                .unwrap_or_default()
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        self.running_since.get_or_insert(now);
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        self.banked = self.elapsed(now);
+        self.running_since = None;
+    }
+}
+
+// Sessions shorter than this aren't worth keeping
+pub const MIN_SECONDS: u32 = 60;
+
+// How many whole pomodoros of the usual work length the session was worth
+pub fn equivalent_pomodoros(elapsed_seconds: u32, work_seconds: u32) -> u32 {
+    elapsed_seconds.checked_div(work_seconds).unwrap_or(0)
+}
+
+pub fn format_elapsed(seconds: u32) -> String {
+    match seconds / 3600 {
+        0 => format!("{:02}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+#[cfg(test)]
+mod stopwatch_tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_time_survives_pauses_and_counts_whole_pomodoros() {
+        let start = Instant::now();
+        let mut stopwatch = Stopwatch::default();
+        stopwatch.start(start);
+        stopwatch.pause(start + Duration::from_secs(600));
+        // Paused time doesn't count
+        stopwatch.start(start + Duration::from_secs(900));
+        let now = start + Duration::from_secs(4200);
+        assert_eq!(stopwatch.elapsed(now), Duration::from_secs(3900));
+
+        assert_eq!(equivalent_pomodoros(3900, 1500), 2);
+        assert_eq!(equivalent_pomodoros(1499, 1500), 0);
+        assert_eq!(format_elapsed(3900), "1:05:00");
+        assert_eq!(format_elapsed(599), "09:59");
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use std::collections::HashSet;
+
+// Days start at the configured hour, so a session at 1am can still count
+// towards the previous day for night owls.
+pub fn today(day_start_hour: u32) -> NaiveDate {
+    day_of(Local::now().naive_local(), day_start_hour)
+}
+
+pub fn day_of(time: NaiveDateTime, day_start_hour: u32) -> NaiveDate {
+    (time - Duration::hours(day_start_hour as i64)).date()
+}
+
+pub fn parse_day(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+}
+
+// Consecutive days with at least one pomodoro, counting back from today.
+// Today only extends the streak once it has a session, and holidays are
+// skipped without breaking it.
+pub fn current_streak(
+    active_days: &HashSet<NaiveDate>,
+    holidays: &HashSet<NaiveDate>,
+    today: NaiveDate,
+) -> u32 {
+    let mut streak = 0;
+    let mut day = today;
+    if !active_days.contains(&day) {
+        day -= Duration::days(1);
+    }
+
+    loop {
+        if active_days.contains(&day) {
+            streak += 1;
+        } else if !holidays.contains(&day) {
+            return streak;
+        }
+        match day.pred_opt() {
+            Some(previous) => day = previous,
+            None => return streak,
+        }
+    }
+}
+
+#[cfg(test)]
+mod streak_tests {
+    use super::{current_streak, parse_day};
+    use std::collections::HashSet;
+
+    fn days(values: &[&str]) -> HashSet<chrono::NaiveDate> {
+        values.iter().filter_map(|value| parse_day(value)).collect()
+    }
+
+    #[test]
+    fn holidays_do_not_break_the_streak() {
+        let active = days(&["2026-03-02", "2026-03-04", "2026-03-05"]);
+        let today = parse_day("2026-03-06").unwrap();
+
+        assert_eq!(current_streak(&active, &HashSet::new(), today), 2);
+        assert_eq!(current_streak(&active, &days(&["2026-03-03"]), today), 3);
+    }
+}
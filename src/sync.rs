@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+};
+
+// In a sync folder (Dropbox, Syncthing, ...) every device appends its
+// sessions to its own `<device id>.jsonl`, so no file ever has two writers.
+// A later line for the same session replaces the earlier one: corrections
+// are written again and deletions as `deleted`. Conflicted copies made by
+// the sync tool are read like any other journal and merged the same way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub device_id: String,
+    pub ended_at: i64,
+    pub utc_offset_seconds: i32,
+    pub duration_seconds: u32,
+    #[serde(default)]
+    pub tag: String,
+    // When the line was written, to order copies of a journal
+    pub written_at: i64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+}
+
+impl JournalEntry {
+    fn key(&self) -> (&str, i64) {
+        (&self.device_id, self.ended_at)
+    }
+
+    fn same_session(&self, other: &JournalEntry) -> bool {
+        self.utc_offset_seconds == other.utc_offset_seconds
+            && self.duration_seconds == other.duration_seconds
+            && self.tag == other.tag
+    }
+}
+
+// Lines that don't parse, e.g. one cut short by a sync in progress, are
+// skipped.
+fn parse(contents: &str) -> impl Iterator<Item = JournalEntry> + '_ {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+}
+
+// The newest line for each session, deletions included.
+fn latest(entries: Vec<JournalEntry>) -> HashMap<(String, i64), JournalEntry> {
+    let mut latest: HashMap<(String, i64), JournalEntry> = HashMap::new();
+    for entry in entries {
+        let key = (entry.device_id.clone(), entry.ended_at);
+        match latest.get(&key) {
+            Some(current) if current.written_at > entry.written_at => {}
+            _ => {
+                latest.insert(key, entry);
+            }
+        }
+    }
+    latest
+}
+
+pub fn merge(entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+    let mut sessions: Vec<JournalEntry> = latest(entries)
+        .into_values()
+        .filter(|entry| !entry.deleted)
+        .collect();
+    sessions.sort_by(|a, b| a.key().cmp(&b.key()));
+    sessions
+}
+
+fn journal_files(dir: &Path) -> Vec<PathBuf> {
+    match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+            .collect(),
+        Err(err) => {
+            println!("Error reading sync folder {}: {}", dir.display(), err);
+            Vec::new()
+        }
+    }
+}
+
+fn read_entries(dir: &Path) -> Vec<JournalEntry> {
+    journal_files(dir)
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .flat_map(|contents| parse(&contents).collect::<Vec<_>>())
+        .collect()
+}
+
+// Every device's sessions in the sync folder.
+pub fn read(dir: &str) -> Vec<JournalEntry> {
+    merge(read_entries(&crate::daily_log::expand_home(dir)))
+}
+
+// The lines that bring this device's journal in line with its database:
+// sessions it lacks or has in an older form, and deletions for sessions
+// that are gone.
+pub fn pending(
+    journal: Vec<JournalEntry>,
+    local: &[JournalEntry],
+    device_id: &str,
+    now: i64,
+) -> Vec<JournalEntry> {
+    let journal = latest(
+        journal
+            .into_iter()
+            .filter(|entry| entry.device_id == device_id)
+            .collect(),
+    );
+    let mut lines = Vec::new();
+    for session in local {
+        let written = journal.get(&(session.device_id.clone(), session.ended_at));
+        if !written.is_some_and(|entry| !entry.deleted && entry.same_session(session)) {
+            lines.push(JournalEntry {
+                written_at: now,
+                ..session.clone()
+            });
+        }
+    }
+
+    let kept: HashSet<i64> = local.iter().map(|session| session.ended_at).collect();
+    let mut deleted: Vec<&JournalEntry> = journal
+        .values()
+        .filter(|entry| !entry.deleted && !kept.contains(&entry.ended_at))
+        .collect();
+    deleted.sort_by_key(|entry| entry.ended_at);
+    lines.extend(deleted.into_iter().map(|entry| JournalEntry {
+        written_at: now,
+        deleted: true,
+        ..entry.clone()
+    }));
+    lines
+}
+
+fn catch_up(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    crate::db::flush();
+    let (device_id, local) = crate::db::load_journal_sessions();
+    let lines = pending(read_entries(dir), &local, &device_id, crate::db::unix_now());
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let mut journal = String::new();
+    for line in lines {
+        journal.push_str(&serde_json::to_string(&line)?);
+        journal.push('\n');
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(format!("{}.jsonl", device_id)))?
+        .write_all(journal.as_bytes())
+}
+
+static WRITER: OnceLock<Sender<PathBuf>> = OnceLock::new();
+
+fn spawn_writer() -> Sender<PathBuf> {
+    let (sender, receiver) = mpsc::channel::<PathBuf>();
+    thread::spawn(move || {
+        for dir in receiver {
+            if let Err(err) = catch_up(&dir) {
+                println!("Error writing sync journal in {}: {}", dir.display(), err);
+            }
+        }
+    });
+
+    sender
+}
+
+// Brings this device's journal up to date in the background.
+pub fn write_journal(dir: &str) {
+    if dir.is_empty() {
+        return;
+    }
+    let _ = WRITER
+        .get_or_init(spawn_writer)
+        .send(crate::daily_log::expand_home(dir));
+}
+
+#[cfg(test)]
+mod sync_tests {
+    use super::*;
+
+    fn entry(device_id: &str, ended_at: i64, tag: &str, written_at: i64) -> JournalEntry {
+        JournalEntry {
+            device_id: device_id.to_string(),
+            ended_at,
+            utc_offset_seconds: 0,
+            duration_seconds: 1500,
+            tag: tag.to_string(),
+            written_at,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn journals_merge_and_catch_up() {
+        // A conflicted copy of the laptop's journal holds an older version
+        // of a session, and a half-synced line
+        let journal = "{\"device_id\":\"laptop\",\"ended_at\":100,\"utc_offset_seconds\":0,\
+                       \"duration_seconds\":1500,\"tag\":\"Report\",\"written_at\":20}\n";
+        let conflicted = "{\"device_id\":\"laptop\",\"ended_at\":100,\"utc_offset_seconds\":0,\
+                          \"duration_seconds\":1500,\"tag\":\"\",\"written_at\":10}\n\
+                          {\"device_id\":\"laptop\",\"ended_at\":";
+        let mut entries: Vec<JournalEntry> = parse(conflicted).chain(parse(journal)).collect();
+        entries.push(JournalEntry {
+            deleted: true,
+            ..entry("desktop", 50, "", 30)
+        });
+        entries.push(entry("desktop", 50, "", 25));
+        entries.push(entry("desktop", 200, "", 25));
+
+        assert_eq!(
+            merge(entries.clone()),
+            [
+                entry("desktop", 200, "", 25),
+                entry("laptop", 100, "Report", 20)
+            ]
+        );
+
+        // The desktop corrected one session and deleted the other since
+        let local = [entry("desktop", 50, "Email", 0)];
+        assert_eq!(
+            pending(entries, &local, "desktop", 40),
+            [
+                entry("desktop", 50, "Email", 40),
+                JournalEntry {
+                    deleted: true,
+                    ..entry("desktop", 200, "", 40)
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::sync::JournalEntry;
+use iced::futures::{SinkExt, Stream};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::Notify;
+use toml::Table;
+
+// A self-hosted server keeps every device's sessions and the shared
+// settings. Requests carry `Authorization: Bearer <token>`:
+//
+//   GET  /sessions?since=<cursor>  -> {"entries": [entry, ...], "cursor": "..."}
+//   POST /sessions                 <- {"entries": [entry, ...]}
+//   GET  /settings                 -> {"updated_at": 1717164000, "settings": {...}}
+//   PUT  /settings                 <- the same
+//
+// Entries are the sync folder's journal lines. The server stores the one
+// with the latest `written_at` for each device and end time, and hands out
+// an opaque cursor so later pulls only return what changed. Settings use
+// the config.toml keys and the last write wins; `GET /settings` answers 404
+// until a device has sent some.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const SYNC_EVERY: Duration = Duration::from_secs(300);
+// Name of the access token in the system keychain
+pub const TOKEN_NAME: &str = "sync-server-token";
+
+static NUDGE: Notify = Notify::const_new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SyncServerConfig {
+    pub url: String,
+    pub token: String,
+}
+
+impl SyncServerConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.url.is_empty() && !self.token.is_empty()
+    }
+}
+
+#[derive(Deserialize)]
+struct Pulled {
+    entries: Vec<JournalEntry>,
+    cursor: String,
+}
+
+#[derive(Serialize)]
+struct Pushed<'a> {
+    entries: &'a [JournalEntry],
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharedSettings {
+    updated_at: i64,
+    settings: Table,
+}
+
+// What to do with the shared settings, going by which side changed last
+#[derive(Debug, PartialEq)]
+enum SettingsSync {
+    // The server's are newer and replace ours
+    Take(i64, Table),
+    // Both sides already agree
+    Keep,
+    // Ours are newer, or the server has none yet
+    Send,
+}
+
+fn compare_settings(updated_at: i64, shared: Option<SharedSettings>) -> SettingsSync {
+    match shared {
+        Some(shared) if shared.updated_at > updated_at => {
+            SettingsSync::Take(shared.updated_at, shared.settings)
+        }
+        Some(shared) if shared.updated_at == updated_at => SettingsSync::Keep,
+        _ => SettingsSync::Send,
+    }
+}
+
+// This device's sessions the server doesn't have yet, or has an older
+// version of, going by what it sent us before and just now
+fn to_upload(
+    mut known: Vec<JournalEntry>,
+    pulled: &[JournalEntry],
+    local: &[JournalEntry],
+    device_id: &str,
+    now: i64,
+) -> Vec<JournalEntry> {
+    known.extend(pulled.iter().cloned());
+    crate::sync::pending(known, local, device_id, now)
+}
+
+fn describe(err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, response) => format!(
+            "HTTP {} {}",
+            code,
+            response.into_string().unwrap_or_default()
+        ),
+        err => err.kind().to_string(),
+    }
+}
+
+// Pulls and pushes sessions, then settings. Returns the server's settings
+// when they are newer than ours.
+fn sync(config: &SyncServerConfig) -> Result<Option<(i64, Table)>, String> {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let base = config.url.trim_end_matches('/');
+    let auth = format!("Bearer {}", config.token);
+    crate::db::flush();
+
+    let pulled: Pulled = agent
+        .get(&format!("{}/sessions", base))
+        .query("since", &crate::db::load_server_cursor())
+        .set("Authorization", &auth)
+        .call()
+        .map_err(describe)?
+        .into_json()
+        .map_err(|err| err.to_string())?;
+    let (device_id, local) = crate::db::load_journal_sessions();
+    let entries = to_upload(
+        crate::db::load_server_sessions(),
+        &pulled.entries,
+        &local,
+        &device_id,
+        crate::db::unix_now(),
+    );
+    crate::db::save_server_sessions(Some(pulled.cursor), pulled.entries);
+    if !entries.is_empty() {
+        agent
+            .post(&format!("{}/sessions", base))
+            .set("Authorization", &auth)
+            .send_json(Pushed { entries: &entries })
+            .map_err(describe)?;
+        crate::db::save_server_sessions(None, entries);
+    }
+
+    let updated_at = crate::db::load_settings_updated_at();
+    let shared = match agent
+        .get(&format!("{}/settings", base))
+        .set("Authorization", &auth)
+        .call()
+    {
+        Ok(response) => Some(
+            response
+                .into_json::<SharedSettings>()
+                .map_err(|err| err.to_string())?,
+        ),
+        Err(ureq::Error::Status(404, _)) => None,
+        Err(err) => return Err(describe(err)),
+    };
+    match compare_settings(updated_at, shared) {
+        SettingsSync::Take(updated_at, settings) => return Ok(Some((updated_at, settings))),
+        SettingsSync::Keep => {}
+        SettingsSync::Send => {
+            let mut settings = Table::new();
+            crate::config::insert(crate::db::load_settings(), &mut settings);
+            agent
+                .put(&format!("{}/settings", base))
+                .set("Authorization", &auth)
+                .send_json(SharedSettings {
+                    updated_at,
+                    settings,
+                })
+                .map_err(describe)?;
+        }
+    }
+    Ok(None)
+}
+
+// Asks for a sync now rather than at the next interval, e.g. after a
+// pomodoro or a settings change.
+pub fn nudge() {
+    NUDGE.notify_one();
+}
+
+// Syncs every few minutes and when nudged, yielding the server's settings
+// whenever they replace ours.
+pub fn run(config: &SyncServerConfig) -> impl Stream<Item = (i64, Table)> + use<> {
+    let config = config.clone();
+    iced::stream::channel(1, async move |mut output| {
+        loop {
+            let synced = {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || sync(&config)).await
+            };
+            match synced {
+                Ok(Ok(Some(settings))) => {
+                    let _ = output.send(settings).await;
+                }
+                Ok(Ok(None)) => {}
+                Ok(Err(err)) => println!("Error syncing with {}: {}", config.url, err),
+                Err(_) => return,
+            }
+            let _ = tokio::time::timeout(SYNC_EVERY, NUDGE.notified()).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod sync_server_tests {
+    use super::*;
+
+    fn entry(device_id: &str, ended_at: i64, tag: &str, written_at: i64) -> JournalEntry {
+        JournalEntry {
+            device_id: device_id.to_string(),
+            ended_at,
+            utc_offset_seconds: 0,
+            duration_seconds: 1500,
+            tag: tag.to_string(),
+            written_at,
+            deleted: false,
+        }
+    }
+
+    fn shared(updated_at: i64) -> Option<SharedSettings> {
+        let mut settings = Table::new();
+        settings.insert("work_minutes".to_string(), 50.into());
+        Some(SharedSettings {
+            updated_at,
+            settings,
+        })
+    }
+
+    #[test]
+    fn the_latest_settings_win() {
+        match compare_settings(100, shared(200)) {
+            SettingsSync::Take(updated_at, settings) => {
+                assert_eq!(updated_at, 200);
+                assert_eq!(settings["work_minutes"].as_integer(), Some(50));
+            }
+            other => panic!("expected the server's settings, got {:?}", other),
+        }
+        assert_eq!(compare_settings(200, shared(200)), SettingsSync::Keep);
+        assert_eq!(compare_settings(300, shared(200)), SettingsSync::Send);
+        assert_eq!(compare_settings(0, None), SettingsSync::Send);
+    }
+
+    #[test]
+    fn only_sessions_the_server_lacks_are_uploaded() {
+        let local = [
+            entry("laptop", 100, "Report", 1),
+            entry("laptop", 200, "", 1),
+            entry("laptop", 300, "Review", 1),
+        ];
+        // Sent earlier, then retagged here since
+        let known = vec![entry("laptop", 100, "", 10), entry("desktop", 150, "", 10)];
+        // Already uploaded as it is now
+        let pulled = [entry("laptop", 200, "", 20)];
+
+        assert_eq!(
+            to_upload(known, &pulled, &local, "laptop", 30),
+            [
+                entry("laptop", 100, "Report", 30),
+                entry("laptop", 300, "Review", 30)
+            ]
+        );
+    }
+}
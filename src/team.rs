@@ -0,0 +1,210 @@
+use iced::futures::{SinkExt, Stream};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream, UdpSocket},
+};
+
+// A host announces its session by UDP broadcast on the local network and
+// streams its timer to whoever connects over TCP: a greeting line, then the
+// API status as one JSON line per change.
+const GREETING: &str = "roth-pomodoro-team";
+const DISCOVERY_PORT: u16 = 47615;
+const ANNOUNCE_EVERY: Duration = Duration::from_secs(2);
+// Hosts that stop announcing drop off the list
+const HOST_EXPIRY: Duration = Duration::from_secs(7);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TeamHost {
+    pub name: String,
+    pub address: SocketAddr,
+}
+
+// The part of the host's status a follower mirrors.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TeamStatus {
+    pub state: String,
+    pub period: String,
+    pub remaining_seconds: u32,
+}
+
+#[derive(Debug, Clone)]
+pub enum TeamEvent {
+    Status(TeamStatus),
+    Disconnected,
+}
+
+fn announcement(port: u16, name: &str) -> String {
+    format!("{GREETING}\n{port}\n{name}")
+}
+
+fn parse_announcement(message: &str) -> Option<(u16, String)> {
+    let mut lines = message.splitn(3, '\n');
+    if lines.next()? != GREETING {
+        return None;
+    }
+    let port = lines.next()?.parse().ok()?;
+    let name = lines.next()?.trim();
+    Some((port, name.to_string()))
+}
+
+async fn serve(stream: TcpStream) {
+    let mut updates = crate::api::subscribe();
+    let (_, mut writer) = stream.into_split();
+    if writer
+        .write_all(format!("{GREETING}\n").as_bytes())
+        .await
+        .is_err()
+    {
+        return;
+    }
+    updates.mark_changed();
+    while updates.changed().await.is_ok() {
+        let line = match &*updates.borrow_and_update() {
+            Some(status) => serde_json::to_string(status).unwrap_or_default(),
+            None => continue,
+        };
+        if writer
+            .write_all(format!("{line}\n").as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+// Hosts a session named `name` for as long as the subscription lives,
+// yielding how many followers are connected.
+pub fn host(name: &str) -> impl Stream<Item = usize> + use<> {
+    let name = name.to_string();
+    iced::stream::channel(1, async move |mut output| {
+        let listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                println!("Error hosting team session: {}", err);
+                return;
+            }
+        };
+        let port = listener.local_addr().map(|a| a.port()).unwrap_or(0);
+        let announcer = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await {
+            Ok(socket) if socket.set_broadcast(true).is_ok() => Some(socket),
+            Ok(_) => None,
+            Err(err) => {
+                println!("Error announcing team session: {}", err);
+                None
+            }
+        };
+        let message = announcement(port, &name);
+        let (left, mut gone) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let mut followers = 0;
+        let mut announce = tokio::time::interval(ANNOUNCE_EVERY);
+
+        loop {
+            tokio::select! {
+                _ = announce.tick() => {
+                    if let Some(socket) = &announcer {
+                        let _ = socket
+                            .send_to(message.as_bytes(), (Ipv4Addr::BROADCAST, DISCOVERY_PORT))
+                            .await;
+                    }
+                }
+                accepted = listener.accept() => {
+                    let Ok((stream, _)) = accepted else {
+                        continue;
+                    };
+                    let left = left.clone();
+                    tokio::spawn(async move {
+                        serve(stream).await;
+                        let _ = left.send(());
+                    });
+                    followers += 1;
+                    let _ = output.send(followers).await;
+                }
+                Some(()) = gone.recv() => {
+                    followers -= 1;
+                    let _ = output.send(followers).await;
+                }
+            }
+        }
+    })
+}
+
+// Sessions hosted on the local network, refreshed as announcements arrive.
+pub fn discover() -> impl Stream<Item = Vec<TeamHost>> {
+    iced::stream::channel(1, async |mut output| {
+        let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT)).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                println!("Error looking for team sessions: {}", err);
+                return;
+            }
+        };
+        let mut seen: HashMap<TeamHost, Instant> = HashMap::new();
+        let mut buffer = [0; 512];
+
+        loop {
+            let received =
+                tokio::time::timeout(ANNOUNCE_EVERY, socket.recv_from(&mut buffer)).await;
+            if let Ok(Ok((length, from))) = received
+                && let Ok(message) = std::str::from_utf8(&buffer[..length])
+                && let Some((port, name)) = parse_announcement(message)
+            {
+                let host = TeamHost {
+                    name,
+                    address: SocketAddr::new(from.ip(), port),
+                };
+                seen.insert(host, Instant::now());
+            }
+            seen.retain(|_, at| at.elapsed() < HOST_EXPIRY);
+
+            let mut hosts: Vec<TeamHost> = seen.keys().cloned().collect();
+            hosts.sort_by(|a, b| a.name.cmp(&b.name).then(a.address.cmp(&b.address)));
+            let _ = output.send(hosts).await;
+        }
+    })
+}
+
+// Follows the host at `address` until it goes away.
+pub fn join(address: &SocketAddr) -> impl Stream<Item = TeamEvent> + use<> {
+    let address = *address;
+    iced::stream::channel(1, async move |mut output| {
+        let stream = match TcpStream::connect(address).await {
+            Ok(stream) => stream,
+            Err(err) => {
+                println!("Error joining team session at {}: {}", address, err);
+                let _ = output.send(TeamEvent::Disconnected).await;
+                return;
+            }
+        };
+        let mut lines = BufReader::new(stream).lines();
+        if matches!(lines.next_line().await, Ok(Some(line)) if line == GREETING) {
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Ok(status) = serde_json::from_str::<TeamStatus>(&line) {
+                    let _ = output.send(TeamEvent::Status(status)).await;
+                }
+            }
+        }
+        let _ = output.send(TeamEvent::Disconnected).await;
+    })
+}
+
+#[cfg(test)]
+mod team_tests {
+    use super::*;
+
+    #[test]
+    fn announcements_round_trip() {
+        let message = announcement(51234, "Mob on Ada's laptop\n");
+        assert_eq!(
+            parse_announcement(&message),
+            Some((51234, "Mob on Ada's laptop".to_string()))
+        );
+        assert_eq!(parse_announcement("roth-pomodoro\n51234\nOther"), None);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::api::Status;
+use crate::i18n::{t, tf};
+use crate::instance::Command;
+use iced::futures::{SinkExt, Stream};
+use serde::Deserialize;
+use std::{
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+const API_URL: &str = "https://api.telegram.org";
+// getUpdates holds the request open this long when there is nothing new
+const LONG_POLL_SECONDS: u64 = 30;
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+// Gives the timer a moment to act on a command before reporting the result
+const REPLY_DELAY: Duration = Duration::from_millis(300);
+// Name of the bot token in the system keychain
+pub const TOKEN_NAME: &str = "telegram-token";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TelegramConfig {
+    pub token: String,
+    // Only messages from this chat are obeyed, and replies go there
+    pub chat_id: String,
+}
+
+impl TelegramConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.token.is_empty() && self.chat_id.parse::<i64>().is_ok()
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!("{}/bot{}/{}", API_URL, self.token, method)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BotCommand {
+    Remote(Command),
+    Status,
+}
+
+// Accepts `/pause` as well as `/pause@SomeBot`, which is what Telegram sends
+// when a command is picked from the menu in a group.
+fn parse_command(text: &str) -> Option<BotCommand> {
+    let word = text.split_whitespace().next()?.strip_prefix('/')?;
+    let name = word.split('@').next()?.to_lowercase();
+    match name.as_str() {
+        "status" => Some(BotCommand::Status),
+        name => Command::from_key(name).map(BotCommand::Remote),
+    }
+}
+
+fn describe(status: Option<&Status>) -> String {
+    let Some(status) = status else {
+        return t("telegram-not-running").to_string();
+    };
+    let period = match status.period {
+        "work" => format!("🍅 {}", t("period-work")),
+        "short_break" => format!("☕ {}", t("period-short-break")),
+        _ => format!("☕ {}", t("period-long-break")),
+    };
+    let state = match status.state {
+        "running" => t("telegram-state-running"),
+        "paused" => t("telegram-state-paused"),
+        "ringing" => t("telegram-state-ringing"),
+        _ => t("telegram-state-idle"),
+    };
+    tf(
+        "telegram-status",
+        &[
+            ("period", &period),
+            (
+                "remaining",
+                &format!(
+                    "{:02}:{:02}",
+                    status.remaining_seconds / 60,
+                    status.remaining_seconds % 60
+                ),
+            ),
+            ("state", &state),
+            ("count", &status.completed_pomodoros),
+        ],
+    )
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(LONG_POLL_SECONDS + 15))
+        .build()
+}
+
+// ureq errors include the request URL, which carries the bot token
+fn error_text(err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, _) => format!("HTTP {}", code),
+        ureq::Error::Transport(transport) => transport.kind().to_string(),
+    }
+}
+
+fn send_message(agent: &ureq::Agent, config: &TelegramConfig, text: &str) {
+    let result = agent
+        .post(&config.url("sendMessage"))
+        .send_form(&[("chat_id", config.chat_id.as_str()), ("text", text)]);
+    if let Err(err) = result {
+        println!("Error sending Telegram message: {}", error_text(err));
+    }
+}
+
+struct Outgoing {
+    config: TelegramConfig,
+    text: String,
+}
+
+static SENDER: OnceLock<Sender<Outgoing>> = OnceLock::new();
+
+fn spawn_sender() -> Sender<Outgoing> {
+    let (sender, receiver) = mpsc::channel::<Outgoing>();
+    thread::spawn(move || {
+        let agent = agent();
+        for outgoing in receiver {
+            send_message(&agent, &outgoing.config, &outgoing.text);
+        }
+    });
+
+    sender
+}
+
+pub fn send(config: &TelegramConfig, text: String) {
+    if !config.is_enabled() {
+        return;
+    }
+    let _ = SENDER.get_or_init(spawn_sender).send(Outgoing {
+        config: config.clone(),
+        text,
+    });
+}
+
+#[derive(Deserialize)]
+struct Updates {
+    result: Vec<Update>,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    update_id: i64,
+    message: Option<IncomingMessage>,
+}
+
+#[derive(Deserialize)]
+struct IncomingMessage {
+    chat: Chat,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+fn get_updates(
+    agent: &ureq::Agent,
+    config: &TelegramConfig,
+    offset: i64,
+    timeout: u64,
+) -> Result<Updates, String> {
+    agent
+        .get(&config.url("getUpdates"))
+        .query("offset", &offset.to_string())
+        .query("timeout", &timeout.to_string())
+        .query("allowed_updates", "[\"message\"]")
+        .call()
+        .map_err(error_text)?
+        .into_json()
+        .map_err(|err| err.to_string())
+}
+
+// Where to resume after dropping what was sent while the app wasn't
+// running. Asking for offset -1 makes Telegram forget all but the latest
+// update, which is then skipped as well.
+fn skip_backlog(agent: &ureq::Agent, config: &TelegramConfig) -> Result<i64, String> {
+    let updates = get_updates(agent, config, -1, 0)?;
+    Ok(next_offset(0, &updates.result))
+}
+
+fn next_offset(offset: i64, updates: &[Update]) -> i64 {
+    updates
+        .iter()
+        .map(|update| update.update_id + 1)
+        .fold(offset, i64::max)
+}
+
+// Long-polls the bot for commands from the configured chat and answers
+// `/status` from the same feed as the HTTP API. Commands sent before the
+// app started are ignored, so an old `/skip` isn't replayed on launch.
+pub fn commands(config: &TelegramConfig) -> impl Stream<Item = Command> + use<> {
+    let config = config.clone();
+    iced::stream::channel(16, async move |mut output| {
+        let Ok(chat_id) = config.chat_id.parse::<i64>() else {
+            return;
+        };
+        let agent = agent();
+        let mut offset = None;
+
+        loop {
+            let Some(current) = offset else {
+                let skipped = {
+                    let (agent, config) = (agent.clone(), config.clone());
+                    tokio::task::spawn_blocking(move || skip_backlog(&agent, &config)).await
+                };
+                match skipped {
+                    Ok(Ok(next)) => offset = Some(next),
+                    Ok(Err(err)) => {
+                        println!("Error polling Telegram: {}", err);
+                        tokio::time::sleep(RETRY_DELAY).await;
+                    }
+                    Err(_) => return,
+                }
+                continue;
+            };
+            let updates = {
+                let (agent, config) = (agent.clone(), config.clone());
+                tokio::task::spawn_blocking(move || {
+                    get_updates(&agent, &config, current, LONG_POLL_SECONDS)
+                })
+                .await
+            };
+            let updates = match updates {
+                Ok(Ok(updates)) => updates,
+                Ok(Err(err)) => {
+                    println!("Error polling Telegram: {}", err);
+                    tokio::time::sleep(RETRY_DELAY).await;
+                    continue;
+                }
+                Err(_) => return,
+            };
+
+            offset = Some(next_offset(current, &updates.result));
+            for update in updates.result {
+                let Some(message) = update.message.filter(|m| m.chat.id == chat_id) else {
+                    continue;
+                };
+                let Some(command) = message.text.as_deref().and_then(parse_command) else {
+                    continue;
+                };
+                if let BotCommand::Remote(command) = command {
+                    let _ = output.send(command).await;
+                    tokio::time::sleep(REPLY_DELAY).await;
+                }
+                let reply = describe(crate::api::subscribe().borrow().as_ref());
+                send(&config, reply);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod telegram_tests {
+    use super::*;
+
+    #[test]
+    fn bot_commands_are_parsed() {
+        assert_eq!(
+            parse_command("/pause"),
+            Some(BotCommand::Remote(Command::Pause))
+        );
+        assert_eq!(
+            parse_command("/Skip@roth_pomodoro_bot now"),
+            Some(BotCommand::Remote(Command::Skip))
+        );
+        assert_eq!(parse_command("/status"), Some(BotCommand::Status));
+        assert_eq!(parse_command("pause"), None);
+        assert_eq!(parse_command("/unknown"), None);
+    }
+
+    #[test]
+    fn polling_resumes_after_the_latest_update() {
+        let updates: Updates = serde_json::from_str(
+            r#"{"ok":true,"result":[
+                {"update_id":41,"message":{"chat":{"id":7},"text":"/skip"}},
+                {"update_id":43}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(next_offset(0, &updates.result), 44);
+        assert_eq!(next_offset(50, &updates.result), 50);
+        assert_eq!(next_offset(12, &[]), 12);
+    }
+}
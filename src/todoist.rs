@@ -0,0 +1,178 @@
+use crate::i18n::t;
+use serde::Deserialize;
+use std::{
+    fmt,
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+const API_URL: &str = "https://api.todoist.com/api/v1";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// Name of the API token in the system keychain
+pub const TOKEN_NAME: &str = "todoist-token";
+pub const EVERY_CHOICES: [u32; 8] = [1, 2, 3, 4, 5, 6, 8, 10];
+
+// What happens to the active task after every N pomodoros spent on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskAction {
+    #[default]
+    Comment,
+    Complete,
+}
+
+impl TaskAction {
+    pub const ALL: [TaskAction; 2] = [TaskAction::Comment, TaskAction::Complete];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            TaskAction::Comment => "comment",
+            TaskAction::Complete => "complete",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.key() == key)
+    }
+}
+
+impl fmt::Display for TaskAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskAction::Comment => write!(f, "{}", t("todoist-comment")),
+            TaskAction::Complete => write!(f, "{}", t("todoist-complete")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoistConfig {
+    pub token: String,
+    pub action: TaskAction,
+    pub every: u32,
+}
+
+impl Default for TodoistConfig {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            action: TaskAction::default(),
+            every: 1,
+        }
+    }
+}
+
+impl TodoistConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.token.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TodoistTask {
+    pub id: String,
+    pub content: String,
+}
+
+#[derive(Deserialize)]
+struct TaskPage {
+    results: Vec<TodoistTask>,
+    next_cursor: Option<String>,
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build()
+}
+
+fn bearer(token: &str) -> String {
+    format!("Bearer {}", token)
+}
+
+fn fetch_today_blocking(token: &str) -> Result<Vec<TodoistTask>, String> {
+    let agent = agent();
+    let mut tasks = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut request = agent
+            .get(&format!("{}/tasks/filter", API_URL))
+            .set("Authorization", &bearer(token))
+            .query("query", "today | overdue");
+        if let Some(cursor) = &cursor {
+            request = request.query("cursor", cursor);
+        }
+        let page: TaskPage = request
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(401 | 403, _) => t("todoist-token-rejected").to_string(),
+                err => err.to_string(),
+            })?
+            .into_json()
+            .map_err(|err| err.to_string())?;
+        tasks.extend(page.results);
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return Ok(tasks),
+        }
+    }
+}
+
+// Today's and overdue tasks, in Todoist's order.
+pub async fn fetch_today(token: String) -> Result<Vec<TodoistTask>, String> {
+    tokio::task::spawn_blocking(move || fetch_today_blocking(&token))
+        .await
+        .map_err(|err| err.to_string())?
+}
+
+struct Report {
+    token: String,
+    task: TodoistTask,
+    action: TaskAction,
+    pomodoros: u32,
+}
+
+static SENDER: OnceLock<Sender<Report>> = OnceLock::new();
+
+fn spawn_sender() -> Sender<Report> {
+    let (sender, receiver) = mpsc::channel::<Report>();
+    thread::spawn(move || {
+        let agent = agent();
+        for report in receiver {
+            let result = match report.action {
+                TaskAction::Comment => agent
+                    .post(&format!("{}/comments", API_URL))
+                    .set("Authorization", &bearer(&report.token))
+                    .send_json(serde_json::json!({
+                        "task_id": report.task.id,
+                        "content": format!("🍅 {} pomodoros of focus so far", report.pomodoros),
+                    })),
+                TaskAction::Complete => agent
+                    .post(&format!("{}/tasks/{}/close", API_URL, report.task.id))
+                    .set("Authorization", &bearer(&report.token))
+                    .call(),
+            };
+            if let Err(err) = result {
+                println!(
+                    "Error updating Todoist task {}: {}",
+                    report.task.content, err
+                );
+            }
+        }
+    });
+
+    sender
+}
+
+pub fn report(config: &TodoistConfig, task: &TodoistTask, pomodoros: u32) {
+    if !config.is_enabled() {
+        return;
+    }
+    let _ = SENDER.get_or_init(spawn_sender).send(Report {
+        token: config.token.clone(),
+        task: task.clone(),
+        action: config.action,
+        pomodoros,
+    });
+}
@@ -0,0 +1,140 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::DateTime;
+use std::{
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+const API_URL: &str = "https://api.track.toggl.com/api/v9";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// Name of the API token in the system keychain
+pub const TOKEN_NAME: &str = "toggl-token";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TogglConfig {
+    pub api_token: String,
+    pub workspace_id: String,
+    // Optional; entries without a project land in the workspace
+    pub project_id: String,
+    // Used when the session has nothing more specific to say
+    pub description: String,
+}
+
+impl TogglConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.api_token.is_empty() && self.workspace_id.parse::<u64>().is_ok()
+    }
+}
+
+struct TimeEntry {
+    config: TogglConfig,
+    started_at: i64,
+    duration_seconds: u32,
+    description: String,
+}
+
+impl TimeEntry {
+    fn new(config: &TogglConfig, ended_at: i64, duration_seconds: u32, description: &str) -> Self {
+        let description = if description.is_empty() {
+            &config.description
+        } else {
+            description
+        };
+        Self {
+            config: config.clone(),
+            started_at: ended_at - duration_seconds as i64,
+            duration_seconds,
+            description: description.to_string(),
+        }
+    }
+
+    fn body(&self) -> serde_json::Value {
+        let start = DateTime::from_timestamp(self.started_at, 0)
+            .unwrap_or_default()
+            .to_rfc3339();
+        let workspace_id = self.config.workspace_id.parse::<u64>().unwrap_or_default();
+        serde_json::json!({
+            "created_with": "roth-pomodoro",
+            "description": self.description,
+            "workspace_id": workspace_id,
+            "project_id": self.config.project_id.parse::<u64>().ok(),
+            "start": start,
+            "duration": self.duration_seconds,
+            "tags": ["pomodoro"],
+        })
+    }
+}
+
+static SENDER: OnceLock<Sender<TimeEntry>> = OnceLock::new();
+
+fn spawn_sender() -> Sender<TimeEntry> {
+    let (sender, receiver) = mpsc::channel::<TimeEntry>();
+    thread::spawn(move || {
+        let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+        for entry in receiver {
+            let auth = STANDARD.encode(format!("{}:api_token", entry.config.api_token));
+            let result = agent
+                .post(&format!(
+                    "{}/workspaces/{}/time_entries",
+                    API_URL, entry.config.workspace_id
+                ))
+                .set("Authorization", &format!("Basic {}", auth))
+                .send_json(entry.body());
+            match result {
+                Ok(_) => {}
+                Err(ureq::Error::Status(code, response)) => println!(
+                    "Error creating Toggl time entry: HTTP {} {}",
+                    code,
+                    response.into_string().unwrap_or_default()
+                ),
+                Err(err) => println!("Error creating Toggl time entry: {}", err),
+            }
+        }
+    });
+
+    sender
+}
+
+// Records a finished work period as a Toggl time entry. It is sent once;
+// an entry Toggl doesn't accept, e.g. while offline, is not retried.
+pub fn record(config: &TogglConfig, ended_at: i64, duration_seconds: u32, description: &str) {
+    if !config.is_enabled() {
+        return;
+    }
+    let _ = SENDER.get_or_init(spawn_sender).send(TimeEntry::new(
+        config,
+        ended_at,
+        duration_seconds,
+        description,
+    ));
+}
+
+#[cfg(test)]
+mod toggl_tests {
+    use super::*;
+
+    #[test]
+    fn time_entries_start_when_the_period_did() {
+        let mut config = TogglConfig {
+            api_token: "token".to_string(),
+            workspace_id: "123".to_string(),
+            project_id: String::new(),
+            description: "Deep work".to_string(),
+        };
+        let body = TimeEntry::new(&config, 1_714_559_100, 1500, "").body();
+        assert_eq!(body["start"], "2024-05-01T10:00:00+00:00");
+        assert_eq!(body["duration"], 1500);
+        assert_eq!(body["workspace_id"], 123);
+        assert_eq!(body["project_id"], serde_json::Value::Null);
+        assert_eq!(body["description"], "Deep work");
+
+        config.project_id = "456".to_string();
+        let body = TimeEntry::new(&config, 1_714_559_100, 1500, "Write the parser").body();
+        assert_eq!(body["project_id"], 456);
+        assert_eq!(body["description"], "Write the parser");
+    }
+}
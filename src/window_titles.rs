@@ -0,0 +1,61 @@
+use crate::platform::active_window;
+
+// Work time split by what the window in front was showing, as sampled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TitleTally {
+    pub focused_seconds: u32,
+    pub distracted_seconds: u32,
+}
+
+impl TitleTally {
+    pub fn add(&mut self, title: &str, patterns: &[String], seconds: u32) {
+        if is_distracting(title, patterns) {
+            self.distracted_seconds += seconds;
+        } else {
+            self.focused_seconds += seconds;
+        }
+    }
+
+    pub fn tracked_seconds(&self) -> u32 {
+        self.focused_seconds + self.distracted_seconds
+    }
+}
+
+// A title is distracting when it contains any of the patterns, whatever
+// the case. Browsers put the page title in theirs, so "youtube" catches
+// the site.
+pub fn is_distracting(title: &str, patterns: &[String]) -> bool {
+    let title = title.to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| title.contains(&pattern.to_lowercase()))
+}
+
+pub async fn active_title() -> Option<String> {
+    tokio::task::spawn_blocking(active_window::title)
+        .await
+        .ok()
+        .flatten()
+        .filter(|title| !title.is_empty())
+}
+
+#[cfg(test)]
+mod window_titles_tests {
+    use super::*;
+
+    #[test]
+    fn titles_matching_a_pattern_count_as_distracted() {
+        let patterns = vec!["YouTube".to_string(), "reddit".to_string()];
+        let mut tally = TitleTally::default();
+        tally.add("Cat videos - youtube — Mozilla Firefox", &patterns, 15);
+        tally.add(
+            "main.rs - roth-pomodoro - Visual Studio Code",
+            &patterns,
+            15,
+        );
+        tally.add("r/rust - Reddit", &patterns, 15);
+        assert_eq!(tally.distracted_seconds, 30);
+        assert_eq!(tally.focused_seconds, 15);
+        assert_eq!(tally.tracked_seconds(), 45);
+    }
+}
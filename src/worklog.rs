@@ -0,0 +1,329 @@
+use crate::i18n::t;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::DateTime;
+use iced::futures::{SinkExt, Stream};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+const CLOCKIFY_URL: &str = "https://api.clockify.me/api/v1";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+// How long queued worklogs wait after a failed attempt, e.g. while offline.
+// The wait doubles with each failure in a row, up to RETRY_AT_MOST.
+const RETRY_EVERY: Duration = Duration::from_secs(300);
+const RETRY_AT_MOST: Duration = Duration::from_secs(3600);
+// Longer answers are cut short for the settings screen
+const ERROR_CHARS: usize = 200;
+// Name of the API token in the system keychain
+pub const TOKEN_NAME: &str = "worklog-token";
+
+static NUDGE: Notify = Notify::const_new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WorklogService {
+    #[default]
+    Off,
+    Clockify,
+    Jira,
+}
+
+impl WorklogService {
+    pub const ALL: [WorklogService; 3] = [
+        WorklogService::Off,
+        WorklogService::Clockify,
+        WorklogService::Jira,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            WorklogService::Off => "off",
+            WorklogService::Clockify => "clockify",
+            WorklogService::Jira => "jira",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|service| service.key() == key)
+    }
+}
+
+impl std::fmt::Display for WorklogService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WorklogService::Off => t("worklog-off"),
+            WorklogService::Clockify => "Clockify",
+            WorklogService::Jira => "Jira",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WorklogConfig {
+    pub service: WorklogService,
+    // The Jira site, e.g. https://example.atlassian.net
+    pub jira_url: String,
+    pub jira_email: String,
+    pub clockify_workspace_id: String,
+    // The Jira issue key, or the Clockify project id (optional there)
+    pub task_key: String,
+    pub token: String,
+}
+
+impl WorklogConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.token.is_empty()
+            && match self.service {
+                WorklogService::Off => false,
+                WorklogService::Clockify => !self.clockify_workspace_id.is_empty(),
+                WorklogService::Jira => {
+                    !self.jira_url.is_empty()
+                        && !self.jira_email.is_empty()
+                        && !self.task_key.is_empty()
+                }
+            }
+    }
+}
+
+// A finished pomodoro waiting in the outbox to be posted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorklogEntry {
+    pub service: WorklogService,
+    pub started_at: i64,
+    pub duration_seconds: u32,
+    pub task_key: String,
+    pub comment: String,
+}
+
+impl WorklogEntry {
+    fn clockify_body(&self) -> serde_json::Value {
+        let at = |seconds| {
+            DateTime::from_timestamp(seconds, 0)
+                .unwrap_or_default()
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string()
+        };
+        serde_json::json!({
+            "start": at(self.started_at),
+            "end": at(self.started_at + self.duration_seconds as i64),
+            "description": self.comment,
+            "projectId": (!self.task_key.is_empty()).then_some(&self.task_key),
+        })
+    }
+
+    // Jira counts worklogs in whole minutes, and wants its comment in its
+    // document format
+    fn jira_body(&self) -> serde_json::Value {
+        let started = DateTime::from_timestamp(self.started_at, 0)
+            .unwrap_or_default()
+            .format("%Y-%m-%dT%H:%M:%S%.3f%z")
+            .to_string();
+        let mut body = serde_json::json!({
+            "started": started,
+            "timeSpentSeconds": self.duration_seconds.max(60),
+        });
+        if !self.comment.is_empty() {
+            body["comment"] = serde_json::json!({
+                "type": "doc",
+                "version": 1,
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": self.comment }],
+                }],
+            });
+        }
+        body
+    }
+}
+
+// What's left in the outbox after a pass, for the settings screen
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WorklogStatus {
+    pub waiting: usize,
+    // Why the oldest waiting worklog couldn't be posted, if it was tried
+    pub last_error: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    // Kept for later: offline, rate limited, or a token or issue key that
+    // needs fixing in settings
+    Retry(String),
+    // The service found the worklog itself invalid and would again
+    Rejected(String),
+}
+
+impl Failure {
+    fn from_status(code: u16, body: &str) -> Self {
+        let message = format!("HTTP {} {}", code, body.trim())
+            .chars()
+            .take(ERROR_CHARS)
+            .collect();
+        match code {
+            400 | 422 => Failure::Rejected(message),
+            _ => Failure::Retry(message),
+        }
+    }
+}
+
+fn retry_after(attempts: u32) -> Duration {
+    RETRY_EVERY
+        .saturating_mul(1 << attempts.saturating_sub(1).min(4))
+        .min(RETRY_AT_MOST)
+}
+
+fn post(agent: &ureq::Agent, config: &WorklogConfig, entry: &WorklogEntry) -> Result<(), Failure> {
+    let result = match entry.service {
+        WorklogService::Clockify => agent
+            .post(&format!(
+                "{}/workspaces/{}/time-entries",
+                CLOCKIFY_URL, config.clockify_workspace_id
+            ))
+            .set("X-Api-Key", &config.token)
+            .send_json(entry.clockify_body()),
+        WorklogService::Jira => {
+            let auth = STANDARD.encode(format!("{}:{}", config.jira_email, config.token));
+            agent
+                .post(&format!(
+                    "{}/rest/api/3/issue/{}/worklog",
+                    config.jira_url.trim_end_matches('/'),
+                    entry.task_key
+                ))
+                .set("Authorization", &format!("Basic {}", auth))
+                .send_json(entry.jira_body())
+        }
+        WorklogService::Off => return Ok(()),
+    };
+    match result {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, response)) => Err(Failure::from_status(
+            code,
+            &response.into_string().unwrap_or_default(),
+        )),
+        Err(err) => Err(Failure::Retry(err.kind().to_string())),
+    }
+}
+
+// Posts what's in the outbox, oldest first, stopping at the first failure
+// worth retrying. Entries for a service that's no longer set up stay queued
+// until it is again. Returns how long to wait before the next pass.
+fn drain(config: &WorklogConfig) -> (WorklogStatus, Duration) {
+    let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+    let mut wait = RETRY_EVERY;
+    crate::db::flush();
+    for (id, entry, attempts) in crate::db::load_worklog_outbox() {
+        if entry.service != config.service {
+            continue;
+        }
+        match post(&agent, config, &entry) {
+            Ok(()) => crate::db::remove_worklog(id),
+            Err(Failure::Rejected(err)) => {
+                println!(
+                    "{} rejected the worklog of {}s started at {} for {:?}, dropping it: {}",
+                    entry.service, entry.duration_seconds, entry.started_at, entry.task_key, err
+                );
+                crate::db::remove_worklog(id);
+            }
+            Err(Failure::Retry(err)) => {
+                println!("Error posting worklog, will retry: {}", err);
+                crate::db::worklog_failed(id, &err);
+                wait = retry_after(attempts + 1);
+                break;
+            }
+        }
+    }
+    crate::db::flush();
+    (crate::db::load_worklog_status(), wait)
+}
+
+// Queues a finished work period and asks for it to be posted right away.
+pub fn record(config: &WorklogConfig, ended_at: i64, duration_seconds: u32, description: &str) {
+    if !config.is_enabled() {
+        return;
+    }
+    crate::db::queue_worklog(WorklogEntry {
+        service: config.service,
+        started_at: ended_at - duration_seconds as i64,
+        duration_seconds,
+        task_key: config.task_key.clone(),
+        comment: description.to_string(),
+    });
+    NUDGE.notify_one();
+}
+
+// Works through the outbox when nudged and every few minutes, backing off
+// while attempts keep failing, and yields what's left after each pass.
+pub fn run(config: &WorklogConfig) -> impl Stream<Item = WorklogStatus> + use<> {
+    let config = config.clone();
+    iced::stream::channel(1, async move |mut output| {
+        loop {
+            let drained = {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || drain(&config)).await
+            };
+            let wait = match drained {
+                Ok((status, wait)) => {
+                    let _ = output.send(status).await;
+                    wait
+                }
+                Err(_) => return,
+            };
+            let _ = tokio::time::timeout(wait, NUDGE.notified()).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod worklog_tests {
+    use super::*;
+
+    #[test]
+    fn worklogs_are_dated_as_each_service_expects() {
+        let entry = WorklogEntry {
+            service: WorklogService::Jira,
+            started_at: 1_714_557_600,
+            duration_seconds: 1500,
+            task_key: "POM-42".to_string(),
+            comment: "Write the parser".to_string(),
+        };
+        let jira = entry.jira_body();
+        assert_eq!(jira["started"], "2024-05-01T10:00:00.000+0000");
+        assert_eq!(jira["timeSpentSeconds"], 1500);
+        assert_eq!(
+            jira["comment"]["content"][0]["content"][0]["text"],
+            "Write the parser"
+        );
+
+        let clockify = entry.clockify_body();
+        assert_eq!(clockify["start"], "2024-05-01T10:00:00Z");
+        assert_eq!(clockify["end"], "2024-05-01T10:25:00Z");
+        assert_eq!(clockify["projectId"], "POM-42");
+    }
+
+    #[test]
+    fn only_invalid_worklogs_are_dropped() {
+        assert_eq!(
+            Failure::from_status(400, "{\"message\":\"Invalid time\"}\n"),
+            Failure::Rejected("HTTP 400 {\"message\":\"Invalid time\"}".to_string())
+        );
+        assert!(matches!(
+            Failure::from_status(422, ""),
+            Failure::Rejected(_)
+        ));
+        // A bad token or issue key is fixed in settings, then posted
+        for code in [401, 403, 404, 408, 429, 503] {
+            assert!(matches!(Failure::from_status(code, ""), Failure::Retry(_)));
+        }
+        let Failure::Retry(long) = Failure::from_status(500, &"x".repeat(1000)) else {
+            unreachable!();
+        };
+        assert_eq!(long.chars().count(), ERROR_CHARS);
+    }
+
+    #[test]
+    fn retries_back_off() {
+        assert_eq!(retry_after(1), RETRY_EVERY);
+        assert_eq!(retry_after(2), RETRY_EVERY * 2);
+        assert_eq!(retry_after(4), RETRY_EVERY * 8);
+        assert_eq!(retry_after(5), RETRY_AT_MOST);
+        assert_eq!(retry_after(40), RETRY_AT_MOST);
+    }
+}